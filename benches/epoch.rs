@@ -0,0 +1,56 @@
+//! Per-epoch parsing benchmark.
+//! Each file is parsed once beforehand, to determine how many epochs it contains.
+//! Criterion then reports the throughput in Epoch/s, which makes results
+//! comparable across formats, revisions and file sizes.
+extern crate criterion;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion, Throughput};
+
+use criterion::measurement::WallTime;
+
+use rinex::prelude::Rinex;
+
+fn rinex_parsing(path: &str) -> Rinex {
+    Rinex::from_file(path).unwrap()
+}
+
+fn bench_epochs(grp: &mut BenchmarkGroup<'_, WallTime>, name: &str, path: &str) {
+    let epochs = rinex_parsing(path).epoch_iter().count();
+    grp.throughput(Throughput::Elements(epochs as u64));
+
+    grp.bench_function(name, |b| {
+        b.iter(|| {
+            rinex_parsing(path);
+        })
+    });
+}
+
+fn benchmark(c: &mut Criterion) {
+    // Observation epochs
+    let mut obs_grp = c.benchmark_group("epoch/OBS");
+    bench_epochs(&mut obs_grp, "V2", "data/OBS/V2/AJAC3550.21O");
+    bench_epochs(&mut obs_grp, "V3", "data/OBS/V3/DUTH0630.22O");
+    obs_grp.finish();
+
+    // Navigation frames
+    let mut nav_grp = c.benchmark_group("epoch/NAV");
+    bench_epochs(&mut nav_grp, "V2", "data/NAV/V2/amel0010.21g");
+    bench_epochs(
+        &mut nav_grp,
+        "V3",
+        "data/NAV/V3/AMEL00NLD_R_20210010000_01D_MN.rnx",
+    );
+    nav_grp.finish();
+
+    // Hatanaka decoding + Observation epochs
+    let mut crinex_grp = c.benchmark_group("epoch/CRINEX");
+    bench_epochs(&mut crinex_grp, "V3", "data/CRNX/V3/VLNS0630.22D");
+    bench_epochs(
+        &mut crinex_grp,
+        "V3/1H",
+        "data/CRNX/V3/KMS300DNK_R_20221591000_01H_30S_MO.crx",
+    );
+    crinex_grp.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
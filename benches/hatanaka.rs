@@ -1,8 +1,56 @@
 //! Benchmarking of the low level CRINEX API & associated objects
-use rinex::hatanaka::{NumDiff, TextDiff};
+use rinex::{
+    hatanaka::{Decompressor, NumDiff, TextDiff},
+    prelude::{Constellation, Observable},
+};
+
+use std::collections::HashMap;
+use std::str::FromStr;
 
 extern crate criterion;
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+/// Generates a CRINEX (V3) record of 60 epochs (1' sampling), 4 satellites and 2 observables.
+fn crinex_v3_record() -> Vec<String> {
+    let mut lines = vec![
+        "> 2020 01 01 00 00  0.0000000  0  4      G01G07G08G10".to_string(),
+        "".to_string(),
+    ];
+
+    for _ in 0..4 {
+        lines.push("3&20832393682 3&109474992854".to_string());
+    }
+
+    for minute in 1..60 {
+        lines.push(format!("{:16}{:02} 0", "", minute));
+        lines.push("".to_string());
+
+        for _ in 0..4 {
+            lines.push("1000 1000".to_string());
+        }
+    }
+
+    lines
+}
+
+/// Decompresses the complete record, returns the recovered size.
+fn crinex_decompression(
+    lines: &[String],
+    observables: &HashMap<Constellation, Vec<Observable>>,
+) -> usize {
+    let mut buf = [0; 1024];
+    let mut decompressor = Decompressor::new(true, Constellation::GPS, observables.clone());
+
+    lines
+        .iter()
+        .map(|line| {
+            let line = format!("{}\n", line);
+            decompressor
+                .decompress(&line, line.len(), &mut buf, 1024)
+                .unwrap()
+        })
+        .sum()
+}
 
 fn textdiff_decompression(textdiff: &mut TextDiff, data: &[&str]) {
     for data in data {
@@ -185,6 +233,29 @@ fn benchmark(c: &mut Criterion) {
     });
 
     numdiff_group.finish();
+
+    // complete record decompression, without file I/O nor RINEX parsing
+    let mut decompression_grp = c.benchmark_group("decompressor");
+
+    let observables = HashMap::from([(
+        Constellation::GPS,
+        vec![
+            Observable::from_str("C1C").unwrap(),
+            Observable::from_str("L1C").unwrap(),
+        ],
+    )]);
+
+    let lines = crinex_v3_record();
+    let size = crinex_decompression(&lines, &observables);
+    decompression_grp.throughput(Throughput::Bytes(size as u64));
+
+    decompression_grp.bench_function("decompress/V3", |b| {
+        b.iter(|| {
+            black_box(crinex_decompression(&lines, &observables));
+        })
+    });
+
+    decompression_grp.finish();
 }
 
 criterion_group!(benches, benchmark);
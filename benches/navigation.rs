@@ -0,0 +1,108 @@
+//! Isolated Navigation parsing benchmark.
+//! Navigation frames are generated in memory, which removes file I/O
+//! from the measurement. Criterion reports the throughput in frame/s.
+//! On unix, `--profile-time <seconds>` also renders a flamegraph of each benchmark.
+extern crate criterion;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion, Throughput};
+
+use criterion::measurement::WallTime;
+
+use rinex::prelude::Rinex;
+
+use std::io::BufReader;
+
+/// Number of satellites and hourly frames per satellite
+const NUM_SV: usize = 32;
+const NUM_FRAMES: usize = 24;
+
+fn nav_v2() -> String {
+    let mut content = String::from(
+        "     2.11           N: GPS NAV DATA                         RINEX VERSION / TYPE
+                                                            END OF HEADER
+",
+    );
+    for prn in 1..=NUM_SV {
+        for hh in 0..NUM_FRAMES {
+            content.push_str(&format!(
+                "{:2} 20  6 25 {:2}  0  0.0-3.240541554987D-04-6.252776074689D-12 0.000000000000D+00
+    5.900000000000D+01-1.184375000000D+02 4.022667267167D-09-2.101453627455D+00
+   -6.023049354553D-06 8.965924079530D-03 5.858391523361D-06 5.153708061218D+03
+    3.456000000000D+05 2.048909664154D-08-7.727970385488D-01-1.676380634308D-07
+    9.777128542054D-01 2.564062500000D+02 7.028787287921D-01-8.216413896601D-09
+    4.142707975542D-11 1.000000000000D+00 2.111000000000D+03 0.000000000000D+00
+    2.000000000000D+00 0.000000000000D+00 5.122274160385D-09 5.900000000000D+01
+    3.384180000000D+05 4.000000000000D+00
+",
+                prn, hh
+            ));
+        }
+    }
+    content
+}
+
+fn nav_v3() -> String {
+    let mut content = String::from(
+        "     3.04           N: GNSS NAV DATA    G: GPS              RINEX VERSION / TYPE
+                                                            END OF HEADER
+",
+    );
+    for prn in 1..=NUM_SV {
+        for hh in 0..NUM_FRAMES {
+            content.push_str(&format!(
+                "G{:02} 2020 06 25 {:02} 00 00-3.240541554987E-04-6.252776074689E-12 0.000000000000E+00
+     5.900000000000E+01-1.184375000000E+02 4.022667267167E-09-2.101453627455E+00
+    -6.023049354553E-06 8.965924079530E-03 5.858391523361E-06 5.153708061218E+03
+     3.456000000000E+05 2.048909664154E-08-7.727970385488E-01-1.676380634308E-07
+     9.777128542054E-01 2.564062500000E+02 7.028787287921E-01-8.216413896601E-09
+     4.142707975542E-11 1.000000000000E+00 2.111000000000E+03 0.000000000000E+00
+     2.000000000000E+00 0.000000000000E+00 5.122274160385E-09 5.900000000000E+01
+     3.384180000000E+05 4.000000000000E+00
+",
+                prn, hh
+            ));
+        }
+    }
+    content
+}
+
+fn rinex_parsing(content: &str) -> Rinex {
+    Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap()
+}
+
+fn bench_frames(grp: &mut BenchmarkGroup<'_, WallTime>, name: &str, content: &str) {
+    let frames = rinex_parsing(content).nav_ephemeris_frames_iter().count();
+    assert_eq!(frames, NUM_SV * NUM_FRAMES, "invalid {} content", name);
+
+    grp.throughput(Throughput::Elements(frames as u64));
+
+    grp.bench_function(name, |b| {
+        b.iter(|| {
+            rinex_parsing(content);
+        })
+    });
+}
+
+fn benchmark(c: &mut Criterion) {
+    let mut nav_grp = c.benchmark_group("navigation");
+    bench_frames(&mut nav_grp, "V2", &nav_v2());
+    bench_frames(&mut nav_grp, "V3", &nav_v3());
+    nav_grp.finish();
+}
+
+#[cfg(unix)]
+fn config() -> Criterion {
+    use pprof::criterion::{Output, PProfProfiler};
+    Criterion::default().with_profiler(PProfProfiler::new(1000, Output::Flamegraph(None)))
+}
+
+#[cfg(not(unix))]
+fn config() -> Criterion {
+    Criterion::default()
+}
+
+criterion_group! {
+    name = benches;
+    config = config();
+    targets = benchmark
+}
+criterion_main!(benches);
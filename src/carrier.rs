@@ -124,7 +124,7 @@ impl std::str::FromStr for Carrier {
             Ok(Self::E5a)
         } else if content.eq("E5B") {
             Ok(Self::E5b)
-        } else if content.eq("E5A5B") {
+        } else if content.eq("E5A5B") || content.eq("E5") {
             Ok(Self::E5a5b)
         } else if content.eq("E6") {
             Ok(Self::E6)
@@ -190,8 +190,8 @@ impl Carrier {
             Self::G1(None) => 1602.000_f64,
             Self::G1(Some(c)) => 1602.000_f64 + (*c as f64 * 9.0 / 16.0),
             Self::G2a => 1248.060_f64,
-            Self::G2(None) => 1246.060_f64,
-            Self::G2(Some(c)) => 1246.060_f64 + (*c as f64 * 7.0 / 16.0),
+            Self::G2(None) => 1246.000_f64,
+            Self::G2(Some(c)) => 1246.000_f64 + (*c as f64 * 7.0 / 16.0),
             Self::G3 => 1202.025_f64,
             Self::S1 => 2036.250,
             Self::U2 => 401.250,
@@ -243,7 +243,8 @@ impl Carrier {
         match self {
             Self::L1 | Self::G1(_) | Self::G1a | Self::E1 => 15.345_f64,
             Self::L2 | Self::G2(_) | Self::G2a => 11.0_f64,
-            Self::L5 | Self::E5a | Self::E5b | Self::E5a5b => 12.5_f64,
+            Self::L5 | Self::E5a | Self::E5b => 12.5_f64,
+            Self::E5a5b => 51.150_f64,
             Self::G3 => todo!("G3 bandwidth is not known to this day"),
             Self::E6 => todo!("E6 bandwidth is not known to this day"),
             Self::L6 => todo!("L6 bandwidth is not known to this day"),
@@ -327,15 +328,25 @@ impl Carrier {
     //    }
     //}
 
-    /// Converts to exact Glonass carrier
+    /// Converts to exact Glonass carrier, from the frequency channel number (FDMA)
     pub fn with_glonass_offset(&self, offset: i8) -> Self {
         match self {
-            Self::L1 => Self::G1(Some(offset)),
-            Self::L2 => Self::G2(Some(offset)),
+            Self::L1 | Self::G1(_) => Self::G1(Some(offset)),
+            Self::L2 | Self::G2(_) => Self::G2(Some(offset)),
             other => *other,
         }
     }
 
+    /// Converts a phase measurement in carrier cycles to meters
+    pub fn cycles_to_meters(&self, cycles: f64) -> f64 {
+        cycles * self.wavelength()
+    }
+
+    /// Converts a phase measurement in meters to carrier cycles
+    pub fn meters_to_cycles(&self, meters: f64) -> f64 {
+        meters / self.wavelength()
+    }
+
     pub(crate) fn gpsl1_codes() -> [&'static str; 40] {
         [
             "C1", "L1", "D1", "S1", "P1", "C1C", "L1C", "D1C", "S1C", "C1S", "L1S", "D1S", "S1S",
@@ -445,21 +456,25 @@ impl Carrier {
         ]
     }
 
-    pub(crate) fn e5a5b_codes() -> [&'static str; 12] {
+    /// E5 AltBOC (E5a + E5b) codes
+    pub(crate) fn e5a5b_codes() -> [&'static str; 16] {
         [
-            "C8I", "L8I", "D8I", "S8I", "C8Q", "L8Q", "D8Q", "S8Q", "C8X", "L8X", "D8X", "S8X",
+            "C8", "L8", "D8", "S8", "C8I", "L8I", "D8I", "S8I", "C8Q", "L8Q", "D8Q", "S8Q", "C8X",
+            "L8X", "D8X", "S8X",
         ]
     }
 
-    pub(crate) fn e5a_codes() -> [&'static str; 12] {
+    pub(crate) fn e5a_codes() -> [&'static str; 16] {
         [
-            "C5I", "L5I", "D5I", "S5I", "C5Q", "L5Q", "D5Q", "S5Q", "C5X", "L5X", "D5X", "S5X",
+            "C5", "L5", "D5", "S5", "C5I", "L5I", "D5I", "S5I", "C5Q", "L5Q", "D5Q", "S5Q", "C5X",
+            "L5X", "D5X", "S5X",
         ]
     }
 
-    pub(crate) fn e5b_codes() -> [&'static str; 12] {
+    pub(crate) fn e5b_codes() -> [&'static str; 16] {
         [
-            "C7I", "L7I", "D7I", "S7I", "C7Q", "L7Q", "D7Q", "S7Q", "C7X", "L7X", "D7X", "S7X",
+            "C7", "L7", "D7", "S7", "C7I", "L7I", "D7I", "S7I", "C7Q", "L7Q", "D7Q", "S7Q", "C7X",
+            "L7X", "D7X", "S7X",
         ]
     }
 
@@ -551,7 +566,7 @@ impl Carrier {
     pub(crate) fn qzl6_codes() -> [&'static str; 24] {
         [
             "C6", "L6", "D6", "S6", "C6S", "L6S", "D6S", "S6S", "C6L", "L6L", "D6L", "S6L", "C6X",
-            "L6X", "D6X", "S6X", "C6E", "L6E", "D6E", "S6E", "C6S", "L6S", "D6S", "S6Z",
+            "L6X", "D6X", "S6X", "C6E", "L6E", "D6E", "S6E", "C6Z", "L6Z", "D6Z", "S6Z",
         ]
     }
 
@@ -590,10 +605,10 @@ impl Carrier {
         ]
     }
 
-    pub(crate) fn b1_codes() -> [&'static str; 16] {
+    pub(crate) fn b1_codes() -> [&'static str; 24] {
         [
-            "C1", "L1", "D1", "S1", "C2I", "L2I", "D2I", "S2I", "C2Q", "L2Q", "D2Q", "S2Q", "C2X",
-            "L2X", "D2X", "S2X",
+            "C1", "L1", "D1", "S1", "C1I", "L1I", "D1I", "S1I", "C1Q", "L1Q", "D1Q", "S1Q", "C2I",
+            "L2I", "D2I", "S2I", "C2Q", "L2Q", "D2Q", "S2Q", "C2X", "L2X", "D2X", "S2X",
         ]
     }
 
@@ -674,6 +689,12 @@ impl Carrier {
         ]
     }
 
+    pub(crate) fn irnl1_codes() -> [&'static str; 12] {
+        [
+            "C1D", "L1D", "D1D", "S1D", "C1P", "L1P", "D1P", "S1P", "C1X", "L1X", "D1X", "S1X",
+        ]
+    }
+
     pub(crate) fn irn_s_codes() -> [&'static str; 16] {
         [
             "C9A", "L9A", "D9A", "S9A", "C9B", "L9B", "D9B", "S9B", "C9C", "L9C", "D9C", "S9C",
@@ -690,6 +711,8 @@ impl Carrier {
                 let code = code.as_str();
                 if Self::irnl5_codes().contains(&code) {
                     Ok(Self::L5)
+                } else if Self::irnl1_codes().contains(&code) {
+                    Ok(Self::L1)
                 } else if Self::irn_s_codes().contains(&code) {
                     Ok(Self::S)
                 } else {
@@ -744,6 +767,7 @@ impl Carrier {
                 5 => Ok(Self::E5a),
                 6 => Ok(Self::E6),
                 7 => Ok(Self::E5b),
+                8 => Ok(Self::E5a5b),
                 _ => Ok(Self::E1),
             },
             Constellation::SBAS => match sv.prn {
@@ -910,7 +934,7 @@ mod test {
              * BeiDou
              */
             } else if constell == Constellation::BeiDou {
-                let codes = vec!["L1", "L2I", "D2X", "D2Q", "S1", "S2I"];
+                let codes = vec!["L1", "L2I", "D2X", "D2Q", "S1", "S2I", "C1I", "L1Q"];
                 for code in codes {
                     let obs = Observable::from_str(code).unwrap();
                     assert_eq!(
@@ -942,6 +966,22 @@ mod test {
                         Carrier::B2
                     );
                 }
+                let codes = vec!["C7D", "L7P", "D7Z", "S7Z"];
+                for code in codes {
+                    let obs = Observable::from_str(code).unwrap();
+                    assert_eq!(
+                        Carrier::from_observable(constell, &obs).unwrap(),
+                        Carrier::B2b
+                    );
+                }
+                let codes = vec!["C8D", "L8P", "D8X", "S8X"];
+                for code in codes {
+                    let obs = Observable::from_str(code).unwrap();
+                    assert_eq!(
+                        Carrier::from_observable(constell, &obs).unwrap(),
+                        Carrier::B2a2b
+                    );
+                }
                 let codes = vec!["C6I", "L6I", "L6X", "C6X", "S6I", "S6Q", "D6Q"];
                 for code in codes {
                     let obs = Observable::from_str(code).unwrap();
@@ -986,6 +1026,14 @@ mod test {
                         Carrier::E6
                     );
                 }
+                let codes = vec!["C8", "L8", "C8Q", "L8X", "D8I", "S8X"];
+                for code in codes {
+                    let obs = Observable::from_str(code).unwrap();
+                    assert_eq!(
+                        Carrier::from_observable(constell, &obs).unwrap(),
+                        Carrier::E5a5b
+                    );
+                }
             /*
              * IRNSS
              */
@@ -1000,7 +1048,9 @@ mod test {
                         Carrier::L5
                     );
                 }
-                let codes = vec!["C9A", "L9B", "L9X", "C9X", "S9B", "D9B", "C9B"];
+                let codes = vec![
+                    "C9A", "L9B", "L9X", "C9X", "S9B", "D9B", "C9B", "S9C", "L9C",
+                ];
                 for code in codes {
                     let obs = Observable::from_str(code).unwrap();
                     assert_eq!(
@@ -1008,6 +1058,14 @@ mod test {
                         Carrier::S
                     );
                 }
+                let codes = vec!["C1D", "L1P", "D1X", "S1X", "C1X"];
+                for code in codes {
+                    let obs = Observable::from_str(code).unwrap();
+                    assert_eq!(
+                        Carrier::from_observable(constell, &obs).unwrap(),
+                        Carrier::L1
+                    );
+                }
             /*
              * QZSS
              */
@@ -1037,7 +1095,9 @@ mod test {
                     );
                 }
 
-                let codes = vec!["C6", "L6", "L6S", "C6L", "S6S", "S6L", "L6X", "D6E"];
+                let codes = vec![
+                    "C6", "L6", "L6S", "C6L", "S6S", "S6L", "L6X", "D6E", "C6Z", "L6Z", "D6Z",
+                ];
                 for code in codes {
                     let obs = Observable::from_str(code).unwrap();
                     assert_eq!(
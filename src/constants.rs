@@ -1,5 +1,8 @@
 //! const value of Gnss
 
+/// Speed of light in vacuum, in m/s
+pub(crate) const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
+
 #[cfg(feature = "nav")]
 use crate::prelude::{Constellation, SV};
 
@@ -2,6 +2,7 @@ use crate::{
     doris::{ClockObservation, DorisKey, Record},
     epoch::epoch_decompose as epoch_decomposition,
     prelude::{FormattingError, Header},
+    rounding::{FixedFormatter, RoundingMode},
 };
 
 use std::io::{BufWriter, Write};
@@ -36,6 +37,7 @@ pub fn format<W: Write>(
     w: &mut BufWriter<W>,
     record: &Record,
     header: &Header,
+    rounding: RoundingMode,
 ) -> Result<(), FormattingError> {
     const NUM_OBS_PER_LINE: usize = 5;
     const OBSERVATIONS_BLANK: &str = "              ";
@@ -65,7 +67,11 @@ pub fn format<W: Write>(
                     .filter(|(k, _)| &k.station == station && &k.observable == obs)
                     .reduce(|k, _| k)
                 {
-                    write!(w, "{:14.3}", signal.value)?;
+                    write!(
+                        w,
+                        "{}",
+                        FixedFormatter::new_observation(signal.value, rounding)
+                    )?;
                     if let Some(flag) = signal.m1 {
                         write!(w, "{}", flag)?;
                     } else {
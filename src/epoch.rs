@@ -1,7 +1,7 @@
 //! Epoch parsing helper
 
 use crate::{
-    prelude::{Epoch, ParsingError, TimeScale},
+    prelude::{Duration, Epoch, ParsingError, TimeScale},
     types::Type,
 };
 
@@ -299,6 +299,133 @@ pub(crate) fn epoch_decompose(e: Epoch) -> (i32, u8, u8, u8, u8, u8, u32) {
     )
 }
 
+/// Number of seconds in one week
+const SECONDS_PER_WEEK: f64 = 604_800.0;
+
+/// Number of seconds in one day
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// [WeekTow] describes a GNSS time as a week counter and a time of week.
+/// It converts to [Epoch] with `Epoch::try_from`.
+/// ```
+/// use rinex::prelude::{Epoch, TimeScale, WeekTow};
+///
+/// let t = Epoch::try_from(WeekTow::new(2190, 345_600.0, TimeScale::GPST)).unwrap();
+/// assert_eq!(t.to_time_of_week(), (2190, 345_600_000_000_000));
+///
+/// // time of week must be within one week
+/// assert!(Epoch::try_from(WeekTow::new(2190, 604_800.0, TimeScale::GPST)).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WeekTow {
+    /// Week counter, since the origin of the [TimeScale]
+    pub week: u32,
+    /// Time of week, in seconds
+    pub tow_s: f64,
+    /// GNSS [TimeScale]
+    pub timescale: TimeScale,
+}
+
+impl WeekTow {
+    /// Builds new [WeekTow]
+    pub fn new(week: u32, tow_s: f64, timescale: TimeScale) -> Self {
+        Self {
+            week,
+            tow_s,
+            timescale,
+        }
+    }
+}
+
+impl TryFrom<WeekTow> for Epoch {
+    type Error = ParsingError;
+    /// Converts [WeekTow] to [Epoch]. Fails if time of week is not within
+    /// [0, 604800[ s, or if the [TimeScale] is not a GNSS [TimeScale].
+    fn try_from(value: WeekTow) -> Result<Self, Self::Error> {
+        if !value.timescale.is_gnss() || !(0.0..SECONDS_PER_WEEK).contains(&value.tow_s) {
+            return Err(ParsingError::EpochConversion);
+        }
+        let nanos = (value.tow_s * 1.0E9).round() as u64;
+        Ok(Epoch::from_time_of_week(value.week, nanos, value.timescale))
+    }
+}
+
+/// [MjdSeconds] describes a time as a Modified Julian Day and seconds of day.
+/// It converts to [Epoch] with `Epoch::try_from`.
+/// ```
+/// use rinex::prelude::{Epoch, MjdSeconds, TimeScale};
+/// use std::str::FromStr;
+///
+/// let t = Epoch::try_from(MjdSeconds::new(58849, 43_200.0, TimeScale::UTC)).unwrap();
+/// assert_eq!(t, Epoch::from_str("2020-01-01T12:00:00 UTC").unwrap());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MjdSeconds {
+    /// Modified Julian Day
+    pub mjd: u32,
+    /// Seconds of day
+    pub seconds: f64,
+    /// [TimeScale]
+    pub timescale: TimeScale,
+}
+
+impl MjdSeconds {
+    /// Builds new [MjdSeconds]
+    pub fn new(mjd: u32, seconds: f64, timescale: TimeScale) -> Self {
+        Self {
+            mjd,
+            seconds,
+            timescale,
+        }
+    }
+}
+
+impl TryFrom<MjdSeconds> for Epoch {
+    type Error = ParsingError;
+    /// Converts [MjdSeconds] to [Epoch]. Fails if seconds of day are not within
+    /// [0, 86400[ s ([0, 86400] in UTC, to account for leap seconds).
+    fn try_from(value: MjdSeconds) -> Result<Self, Self::Error> {
+        let valid = if value.timescale == TimeScale::UTC {
+            (0.0..=SECONDS_PER_DAY).contains(&value.seconds)
+        } else {
+            (0.0..SECONDS_PER_DAY).contains(&value.seconds)
+        };
+        if !valid {
+            return Err(ParsingError::EpochConversion);
+        }
+        // calendar date of this MJD, expressed in the desired timescale
+        let (y, m, d, _, _, _, _) = Epoch::from_mjd_utc(value.mjd as f64).to_gregorian_utc();
+        Ok(Epoch::from_gregorian_at_midnight(y, m, d, value.timescale)
+            + Duration::from_seconds(value.seconds))
+    }
+}
+
+/// [GpstSeconds] describes a time as the number of seconds
+/// elapsed since the GPST origin (1980-01-06T00:00:00 UTC).
+/// It converts to [Epoch] with `Epoch::try_from`.
+/// ```
+/// use rinex::prelude::{Epoch, GpstSeconds, TimeScale};
+///
+/// let t = Epoch::try_from(GpstSeconds(604_800.0)).unwrap();
+/// assert_eq!(t.time_scale, TimeScale::GPST);
+/// assert_eq!(t.to_time_of_week(), (1, 0));
+///
+/// assert!(Epoch::try_from(GpstSeconds(-1.0)).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GpstSeconds(pub f64);
+
+impl TryFrom<GpstSeconds> for Epoch {
+    type Error = ParsingError;
+    /// Converts [GpstSeconds] to [Epoch]. Fails on negative or invalid number of seconds.
+    fn try_from(value: GpstSeconds) -> Result<Self, Self::Error> {
+        if !value.0.is_finite() || value.0 < 0.0 {
+            return Err(ParsingError::EpochConversion);
+        }
+        Ok(Epoch::from_gpst_seconds(value.0))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -640,4 +767,31 @@ mod test {
 
         assert!(parse_formatted_month("Ced").is_err());
     }
+
+    #[test]
+    fn epoch_conversions() {
+        let t = Epoch::from_str("2022-01-01T00:00:00 GST").unwrap();
+        let (week, nanos) = t.to_time_of_week();
+
+        let converted =
+            Epoch::try_from(WeekTow::new(week, nanos as f64 * 1.0E-9, TimeScale::GST)).unwrap();
+        assert_eq!(converted, t);
+
+        // not a GNSS timescale
+        assert!(Epoch::try_from(WeekTow::new(week, 0.0, TimeScale::TAI)).is_err());
+        assert!(Epoch::try_from(WeekTow::new(week, -1.0, TimeScale::GPST)).is_err());
+        assert!(Epoch::try_from(WeekTow::new(week, f64::NAN, TimeScale::GPST)).is_err());
+
+        let t = Epoch::from_str("2020-01-01T00:00:30 GPST").unwrap();
+        let converted = Epoch::try_from(MjdSeconds::new(58849, 30.0, TimeScale::GPST)).unwrap();
+        assert_eq!(converted, t);
+
+        // leap second
+        assert!(Epoch::try_from(MjdSeconds::new(58849, 86_400.0, TimeScale::UTC)).is_ok());
+        assert!(Epoch::try_from(MjdSeconds::new(58849, 86_400.0, TimeScale::GPST)).is_err());
+
+        let converted = Epoch::try_from(GpstSeconds(t.to_gpst_seconds())).unwrap();
+        assert_eq!(converted, t);
+        assert!(Epoch::try_from(GpstSeconds(f64::INFINITY)).is_err());
+    }
 }
@@ -1,15 +1,17 @@
 use thiserror::Error;
 
 use gnss_rs::{
-    constellation::ParsingError as ConstellationParsingError, cospar::Error as CosparParsingError,
-    domes::Error as DOMESParsingError, sv::ParsingError as SVParsingError,
+    constellation::ParsingError as ConstellationParsingError,
+    cospar::Error as CosparParsingError,
+    domes::Error as DOMESParsingError,
+    sv::{ParsingError as SVParsingError, SV},
 };
 
 use hifitime::{HifitimeError, ParsingError as HifitimeParsingError};
 
 use std::io::Error as IoError;
 
-use crate::hatanaka::Error as HatanakaError;
+use crate::{hatanaka::Error as HatanakaError, production::Error as ProductionError};
 
 /// Errors that may rise in Parsing process
 #[derive(Debug, Error)]
@@ -28,6 +30,8 @@ pub enum ParsingError {
     ClockParsing,
     #[error("invalid epoch format")]
     EpochFormat,
+    #[error("invalid epoch conversion")]
+    EpochConversion,
     #[error("epoch parsing")]
     EpochParsing,
     #[error("datime parsing")]
@@ -48,6 +52,14 @@ pub enum ParsingError {
     UndefinedConstellation,
     #[error("sv parsing")]
     SVParsing(#[from] SVParsingError),
+    #[error("invalid sv format")]
+    SVFormat,
+    #[error("{0}: PRN out of range for this constellation")]
+    InvalidPRN(SV),
+    #[error("site log parsing")]
+    SiteLog,
+    #[error("unknown satellite block")]
+    SatelliteBlock,
     #[error("cospar parsing")]
     COSPAR(#[from] CosparParsingError),
     #[error("nav: eop missing line")]
@@ -58,8 +70,16 @@ pub enum ParsingError {
     BadObsBadTimescaleDefinition,
     #[error("bad RINEX: missing timescale specs")]
     NoTimescaleDefinition,
+    #[error("checkpoint does not describe the CRINEX decompressor state")]
+    CheckpointDecompressor,
+    #[error("SYS / PHASE SHIFT parsing")]
+    PhaseShift,
+    #[error("GLONASS COD/PHS/BIS parsing")]
+    GlonassCodePhaseBias,
     #[error("SYS / SCALE FACTOR parsing")]
     SystemScalingFactor,
+    #[error("WAVELENGTH FACT L1/2 parsing")]
+    WavelengthFactors,
     #[error("REF CLOCK OFFS parsing")]
     RcvClockOffsApplied,
     #[error("header coordinates parsing")]
@@ -108,6 +128,8 @@ pub enum ParsingError {
     DorisStation,
     #[error("obs/doris: missing observable specs")]
     MissingObservableDefinition,
+    #[error("P1-C1 DCB parsing")]
+    DCBParsing,
     #[error("clock profile type parsing")]
     ClockProfileType,
     #[error("clock profile parsing")]
@@ -176,6 +198,8 @@ pub enum ParsingError {
     DorisClockParsing,
     #[error("ionex scaling exponent")]
     IonexScalingExponent,
+    #[error("i/o: input error")]
+    InputError(#[from] IoError),
 }
 
 /// Errors that may rise in Formatting process
@@ -197,63 +221,439 @@ pub enum FormattingError {
     NoGridDefinition,
 }
 
-/// General error (processing, analysis..)
-#[derive(Debug)]
+/// [ErrorCategory] sorts all [Error]s, so they can be reliably mapped
+/// by other layers (FFI, CLI exit codes..).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Input / output error
+    Io,
+    /// Invalid or non supported content
+    Format,
+    /// Invalid or non supported revision
+    Version,
+    /// (De)compression error
+    Compression,
+    /// Processing / analysis error
+    Processing,
+}
+
+impl std::fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Io => write!(f, "i/o"),
+            Self::Format => write!(f, "format"),
+            Self::Version => write!(f, "version"),
+            Self::Compression => write!(f, "compression"),
+            Self::Processing => write!(f, "processing"),
+        }
+    }
+}
+
+/// Numeric code of this [HatanakaError]: CRINEX errors share the same codes,
+/// whether they were raised by the parser or by a direct (de)compression.
+fn compression_code(error: &HatanakaError) -> u16 {
+    match error {
+        HatanakaError::BufferOverflow => 4000,
+        HatanakaError::EpochFormat => 4001,
+        HatanakaError::BadV1Format => 4002,
+        HatanakaError::BadV3Format => 4003,
+        HatanakaError::SVParsing => 4004,
+    }
+}
+
+/// Numeric code of this [ProductionError]
+fn production_code(error: &ProductionError) -> u16 {
+    match error {
+        ProductionError::NonStandardFileName => 2200,
+        ProductionError::InvalidFileSequence => 2201,
+        ProductionError::InvalidFFU => 2202,
+    }
+}
+
+impl ParsingError {
+    /// Returns [ErrorCategory] of this [ParsingError]
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::InputError(_) => ErrorCategory::Io,
+            Self::VersionFormat
+            | Self::VersionParsing
+            | Self::AntexVersion
+            | Self::IonexVersion
+            | Self::NonSupportedVersion => ErrorCategory::Version,
+            Self::CRINEX(_) | Self::BadUtf8Crinex | Self::CheckpointDecompressor => {
+                ErrorCategory::Compression
+            },
+            _ => ErrorCategory::Format,
+        }
+    }
+
+    /// Returns the stable numeric code of this [ParsingError], see [Error::code].
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::HeaderLineTooShort => 2000,
+            Self::EmptyEpoch => 2001,
+            Self::EpochFlag => 2002,
+            Self::NumSat => 2003,
+            Self::MarkerType => 2004,
+            Self::ClockParsing => 2005,
+            Self::EpochFormat => 2006,
+            Self::EpochConversion => 2007,
+            Self::EpochParsing => 2008,
+            Self::DatetimeParsing => 2009,
+            Self::DatetimeFormat => 2010,
+            Self::VersionFormat => 3000,
+            Self::VersionParsing => 3001,
+            Self::TypeParsing => 2011,
+            Self::ObservableParsing => 2012,
+            Self::ConstellationParsing(_) => 2013,
+            Self::UndefinedConstellation => 2014,
+            Self::SVParsing(_) => 2015,
+            Self::SVFormat => 2016,
+            Self::InvalidPRN(_) => 2017,
+            Self::SiteLog => 2018,
+            Self::SatelliteBlock => 2019,
+            Self::COSPAR(_) => 2020,
+            Self::EopMissingData => 2021,
+            Self::ClockTypeofData => 2022,
+            Self::BadObsBadTimescaleDefinition => 2023,
+            Self::NoTimescaleDefinition => 2024,
+            Self::CheckpointDecompressor => 4011,
+            Self::PhaseShift => 2025,
+            Self::GlonassCodePhaseBias => 2026,
+            Self::SystemScalingFactor => 2027,
+            Self::WavelengthFactors => 2028,
+            Self::RcvClockOffsApplied => 2029,
+            Self::Coordinates => 2030,
+            Self::AntennaCoordinates => 2031,
+            Self::SensorCoordinates => 2032,
+            Self::AntexVersion => 3002,
+            Self::IonexVersion => 3003,
+            Self::IonexReferenceSystem => 2033,
+            Self::NonSupportedVersion => 3004,
+            Self::IonexMappingFunction => 2034,
+            Self::UnknownObservable => 2035,
+            Self::BadObservable => 2036,
+            Self::LeapFormat => 2037,
+            Self::LeapParsing => 2038,
+            Self::HifitimeParsing(_) => 2039,
+            Self::Hifitime(_) => 2040,
+            Self::DorisL1L2DateOffset => 2041,
+            Self::NumberOfCalibratedAntennasParsing => 2042,
+            Self::AntexAntennaCalibrationNumber => 2043,
+            Self::AntexAPCCoordinates => 2044,
+            Self::AntexZenithGrid => 2045,
+            Self::AntexFrequency => 2046,
+            Self::DorisStationFormat => 2047,
+            Self::DorisStation => 2048,
+            Self::MissingObservableDefinition => 2049,
+            Self::DCBParsing => 2050,
+            Self::ClockProfileType => 2051,
+            Self::ClockProfile => 2052,
+            Self::DOMES(_) => 2053,
+            Self::IonexMapIndex => 2054,
+            Self::IonexGridSpecs => 2055,
+            Self::BadIonexGridSpecs => 2056,
+            Self::IonexGridCoordinates => 2057,
+            Self::NavFrameClass => 2058,
+            Self::NavInvalidTimescale => 2059,
+            Self::NavMsgType => 2060,
+            Self::NavEpochWeekCounter => 2061,
+            Self::NavTimeOffsetParinsg => 2062,
+            Self::NoNavigationDefinition => 2063,
+            Self::NavHealthFlagDefinition => 2064,
+            Self::NavFlagsMapping => 2065,
+            Self::NavDataSourceDefinition => 2066,
+            Self::NavUnknownComplexType => 2067,
+            Self::NavFlagsDefinition => 2068,
+            Self::NavNullOrbit => 2069,
+            Self::KlobucharData => 2070,
+            Self::NequickGData => 2071,
+            Self::BdgimData => 2072,
+            Self::SystemTimeData => 2073,
+            Self::IonexEarthObservationSat => 2074,
+            Self::IonexModel => 2075,
+            Self::AntexCalibrationMethod => 2076,
+            Self::ObsHardwareEvent => 2077,
+            Self::BadV2SatellitesDescription => 2078,
+            Self::NumSatParsing => 2079,
+            Self::CRINEX(e) => compression_code(e),
+            Self::BadUtf8Crinex => 4010,
+            Self::DorisStationIdentification => 2080,
+            Self::DorisClockParsing => 2081,
+            Self::IonexScalingExponent => 2082,
+            Self::InputError(_) => 1000,
+        }
+    }
+}
+
+impl FormattingError {
+    /// Returns [ErrorCategory] of this [FormattingError]
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::OutputError(_) => ErrorCategory::Io,
+            _ => ErrorCategory::Format,
+        }
+    }
+
+    /// Returns the stable numeric code of this [FormattingError], see [Error::code].
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::OutputError(_) => 1001,
+            Self::NoConstellationDefinition => 2100,
+            Self::MissingNavigationStandards => 2101,
+            Self::UndefinedObservables => 2102,
+            Self::MissingObservableDefinition => 2103,
+            Self::NoNavigationDefinition => 2104,
+            Self::NoGridDefinition => 2105,
+        }
+    }
+}
+
+/// Top level error, that aggregates all errors of this library.
+/// Each [Error] belongs to an [ErrorCategory] and has a stable numeric code (see [Error::code]).
+/// Underlying errors are exposed by [std::error::Error::source].
+#[derive(Debug, Error)]
 pub enum Error {
-    /// Invalid frequency
+    /// [ParsingError] (codes: 1000 for i/o, 20xx for format, 30xx for version, 40xx for compression)
+    #[error("parsing error: {0}")]
+    Parsing(#[from] ParsingError),
+
+    /// [FormattingError] (codes: 1001 for i/o, 21xx for format)
+    #[error("formatting error: {0}")]
+    Formatting(#[from] FormattingError),
+
+    /// I/O error (code: 1002)
+    #[error("i/o error: {0}")]
+    Io(#[from] IoError),
+
+    /// CRINEX (de)compression error (codes: 40xx, identical to [ParsingError::CRINEX])
+    #[error("crinex error: {0}")]
+    Compression(#[from] HatanakaError),
+
+    /// File production / naming error (codes: 22xx)
+    #[error("production error: {0}")]
+    Production(#[from] ProductionError),
+
+    /// Invalid frequency (code: 5000)
+    #[error("invalid frequency")]
     InvalidFrequency,
 
-    /// Sampling Period is not determined
+    /// Sampling Period is not determined (code: 5001)
+    #[error("undetermined sampling period")]
     UndeterminedSamplingPeriod,
 
-    /// Unknown frequency
+    /// Unknown frequency (code: 5002)
+    #[error("unknown frequency")]
     UnknownFrequency,
 
-    /// Non supported GPS [Observable]
+    /// Non supported GPS [Observable] (code: 5003)
+    #[error("unknown gps observable")]
     UnknownGPSObservable,
 
-    /// Non supported Galileo [Observable]
+    /// Non supported Galileo [Observable] (code: 5004)
+    #[error("unknown galileo observable")]
     UnknownGalieoObservable,
 
-    /// Non supported Glonass [Observable]
+    /// Non supported Glonass [Observable] (code: 5005)
+    #[error("unknown glonass observable")]
     UnknownGlonassObservable,
 
-    /// Non supported QZSS [Observable]
+    /// Non supported QZSS [Observable] (code: 5006)
+    #[error("unknown qzss observable")]
     UnknownQZSSObservable,
 
-    /// Non supported BDS [Observable]
+    /// Non supported BDS [Observable] (code: 5007)
+    #[error("unknown bds observable")]
     UnknownBeiDouObservable,
 
-    /// Non supported IRNSS [Observable]
+    /// Non supported IRNSS [Observable] (code: 5008)
+    #[error("unknown irnss observable")]
     UnknownIRNSSObservable,
 
-    /// Non supported SBAS [Observable]
+    /// Non supported SBAS [Observable] (code: 5009)
+    #[error("unknown sbas observable")]
     UnknownSBASObservable,
 
-    /// Non supported DORIS [Observable]
+    /// Non supported DORIS [Observable] (code: 5010)
+    #[error("unknown doris observable")]
     UnknownDORISObservable,
 
-    /// Unknown GPS Frequency
+    /// Unknown GPS Frequency (code: 5011)
+    #[error("unknown gps frequency")]
     UnknownGPSFrequency,
 
-    /// Unknown Galileo Frequency
+    /// Unknown Galileo Frequency (code: 5012)
+    #[error("unknown galileo frequency")]
     UnknownGalileoFrequency,
 
-    /// Unknown QZSS Frequency
+    /// Unknown QZSS Frequency (code: 5013)
+    #[error("unknown qzss frequency")]
     UnknownQzssFrequency,
 
-    /// Unknown Glonass Frequency
+    /// Unknown Glonass Frequency (code: 5014)
+    #[error("unknown glonass frequency")]
     UnknownGlonassFrequency,
 
-    /// Unknown BDS Frequency
+    /// Unknown BDS Frequency (code: 5015)
+    #[error("unknown bds frequency")]
     UnknownBDSFrequency,
 
-    /// Unknown IRNSS Frequency
+    /// Unknown IRNSS Frequency (code: 5016)
+    #[error("unknown irnss frequency")]
     UnknownIRNSSFrequency,
 
-    /// Unknown SBAS Frequency
+    /// Unknown SBAS Frequency (code: 5017)
+    #[error("unknown sbas frequency")]
     UnknownSBASFrequency,
 
-    /// Unknown DORIS Frequency
+    /// Unknown DORIS Frequency (code: 5018)
+    #[error("unknown doris frequency")]
     UnknownDORISFrequency,
 }
+
+impl Error {
+    /// Returns [ErrorCategory] of this [Error]
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Parsing(e) => e.category(),
+            Self::Formatting(e) => e.category(),
+            Self::Io(_) => ErrorCategory::Io,
+            Self::Compression(_) => ErrorCategory::Compression,
+            Self::Production(_) => ErrorCategory::Format,
+            _ => ErrorCategory::Processing,
+        }
+    }
+
+    /// Returns the stable numeric code of this [Error].
+    /// Codes are grouped by [ErrorCategory]: 1xxx (i/o), 2xxx (format),
+    /// 3xxx (version), 4xxx (compression) and 5xxx (processing).
+    /// Format codes are further grouped by origin: 20xx (parsing),
+    /// 21xx (formatting) and 22xx (production).
+    /// Each error has its own code, which remains the same whether it is
+    /// wrapped in [Error] or not.
+    /// ```
+    /// use rinex::prelude::{Error, ErrorCategory, ParsingError};
+    ///
+    /// let error = Error::from(ParsingError::NonSupportedVersion);
+    /// assert_eq!(error.category(), ErrorCategory::Version);
+    /// assert_eq!(error.code(), 3004);
+    ///
+    /// assert_eq!(Error::UnknownFrequency.code(), 5002);
+    /// ```
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::Parsing(e) => e.code(),
+            Self::Formatting(e) => e.code(),
+            Self::Io(_) => 1002,
+            Self::Compression(e) => compression_code(e),
+            Self::Production(e) => production_code(e),
+            Self::InvalidFrequency => 5000,
+            Self::UndeterminedSamplingPeriod => 5001,
+            Self::UnknownFrequency => 5002,
+            Self::UnknownGPSObservable => 5003,
+            Self::UnknownGalieoObservable => 5004,
+            Self::UnknownGlonassObservable => 5005,
+            Self::UnknownQZSSObservable => 5006,
+            Self::UnknownBeiDouObservable => 5007,
+            Self::UnknownIRNSSObservable => 5008,
+            Self::UnknownSBASObservable => 5009,
+            Self::UnknownDORISObservable => 5010,
+            Self::UnknownGPSFrequency => 5011,
+            Self::UnknownGalileoFrequency => 5012,
+            Self::UnknownQzssFrequency => 5013,
+            Self::UnknownGlonassFrequency => 5014,
+            Self::UnknownBDSFrequency => 5015,
+            Self::UnknownIRNSSFrequency => 5016,
+            Self::UnknownSBASFrequency => 5017,
+            Self::UnknownDORISFrequency => 5018,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Error, ErrorCategory, FormattingError, ParsingError};
+    use crate::{hatanaka::Error as HatanakaError, production::Error as ProductionError};
+    use std::error::Error as _;
+    use std::io::{Error as IoError, ErrorKind};
+
+    #[test]
+    fn error_codes() {
+        let io = || IoError::new(ErrorKind::UnexpectedEof, "eof");
+
+        for (error, category, code) in [
+            (Error::from(io()), ErrorCategory::Io, 1002),
+            (
+                Error::from(ParsingError::InputError(io())),
+                ErrorCategory::Io,
+                1000,
+            ),
+            (
+                Error::from(FormattingError::OutputError(io())),
+                ErrorCategory::Io,
+                1001,
+            ),
+            (
+                Error::from(ParsingError::EpochFlag),
+                ErrorCategory::Format,
+                2002,
+            ),
+            (
+                Error::from(FormattingError::UndefinedObservables),
+                ErrorCategory::Format,
+                2102,
+            ),
+            (
+                Error::from(ParsingError::VersionParsing),
+                ErrorCategory::Version,
+                3001,
+            ),
+            (
+                Error::from(ParsingError::BadUtf8Crinex),
+                ErrorCategory::Compression,
+                4010,
+            ),
+            (Error::InvalidFrequency, ErrorCategory::Processing, 5000),
+            (
+                Error::UnknownDORISFrequency,
+                ErrorCategory::Processing,
+                5018,
+            ),
+        ] {
+            assert_eq!(error.category(), category, "{}", error);
+            assert_eq!(error.code(), code, "{}", error);
+        }
+
+        // each error has its own code
+        let codes = [
+            Error::from(ParsingError::EmptyEpoch).code(),
+            Error::from(ParsingError::EpochFlag).code(),
+            Error::from(ParsingError::NumSat).code(),
+            Error::from(FormattingError::NoConstellationDefinition).code(),
+            Error::from(FormattingError::NoGridDefinition).code(),
+            Error::from(ProductionError::InvalidFFU).code(),
+        ];
+        for (i, code) in codes.iter().enumerate() {
+            assert!(!codes[..i].contains(code), "duplicated code {}", code);
+        }
+
+        // CRINEX errors: same code, whatever the path
+        let parsed = Error::from(ParsingError::CRINEX(HatanakaError::BadV3Format));
+        let direct = Error::from(HatanakaError::BadV3Format);
+        assert_eq!(parsed.category(), ErrorCategory::Compression);
+        assert_eq!(parsed.code(), direct.code());
+
+        // inner message is preserved
+        assert_eq!(
+            Error::from(ParsingError::EpochFlag).to_string(),
+            "parsing error: invalid epoch flag"
+        );
+        assert_eq!(direct.to_string(), "crinex error: invalid v3 format");
+
+        // source chain
+        let error = Error::from(ParsingError::InputError(io()));
+        let source = error.source().unwrap();
+        assert_eq!(source.to_string(), "i/o: input error");
+        assert_eq!(source.source().unwrap().to_string(), "eof");
+    }
+}
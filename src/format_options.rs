@@ -0,0 +1,65 @@
+//! [Rinex] formatting options
+use crate::prelude::RoundingMode;
+
+#[cfg(doc)]
+use crate::prelude::Rinex;
+
+/// [FormattingOptions] customize how a [Rinex] is produced, see [Rinex::format_with_options].
+/// By default, "PGM / RUN BY / DATE" is stamped with the name and version of this library
+/// and the current date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormattingOptions {
+    /// [RoundingMode] of fixed width numeric fields
+    pub rounding: RoundingMode,
+    /// Stamp "PGM / RUN BY / DATE" when producing the file.
+    /// When false, the original stamp is preserved.
+    pub stamp: bool,
+    /// Agency ("RUN BY") to stamp. When not specified, the original agency is preserved.
+    pub agency: Option<String>,
+    /// Emit the optional "PRN / # OF OBS" header field (Observation RINEX),
+    /// deduced from the record.
+    pub prn_obs_counts: bool,
+}
+
+impl Default for FormattingOptions {
+    fn default() -> Self {
+        Self {
+            rounding: RoundingMode::default(),
+            stamp: true,
+            agency: None,
+            prn_obs_counts: false,
+        }
+    }
+}
+
+impl FormattingOptions {
+    /// Copies and returns [FormattingOptions] with desired [RoundingMode]
+    pub fn with_rounding(&self, rounding: RoundingMode) -> Self {
+        let mut s = self.clone();
+        s.rounding = rounding;
+        s
+    }
+
+    /// Copies and returns [FormattingOptions] with desired agency ("RUN BY")
+    pub fn with_agency(&self, agency: &str) -> Self {
+        let mut s = self.clone();
+        s.agency = Some(agency.to_string());
+        s
+    }
+
+    /// Copies and returns [FormattingOptions] that emit "PRN / # OF OBS",
+    /// which some archive ingest systems require.
+    pub fn with_prn_obs_counts(&self) -> Self {
+        let mut s = self.clone();
+        s.prn_obs_counts = true;
+        s
+    }
+
+    /// Copies and returns [FormattingOptions] that preserve the original
+    /// "PGM / RUN BY / DATE" stamp.
+    pub fn without_stamp(&self) -> Self {
+        let mut s = self.clone();
+        s.stamp = false;
+        s
+    }
+}
@@ -1,12 +1,33 @@
-use dms_coordinates::DMS;
-use map_3d::{deg2rad, ecef2geodetic, geodetic2ecef, rad2deg, Ellipsoid};
-
+//! Ground position (static reference point) definition
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "qc")]
-use maud::{html, Markup, Render};
+use qc_traits::{html, Markup, QcHtmlReporting};
+
+/// WGS84 semi major axis, in meters
+const WGS84_SEMI_MAJOR_AXIS_M: f64 = 6_378_137.0;
+
+/// WGS84 flattening
+const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
 
+/// Maximal number of iterations of the geodetic latitude solver
+const GEODETIC_MAX_ITER: usize = 10;
+
+/// [GroundPosition] describes a static position on ground,
+/// like the "APPROX POSITION XYZ" of a RINEX header.
+/// It is stored as ECEF WGS84 coordinates, in meters.
+/// ```
+/// use rinex::prelude::GroundPosition;
+///
+/// // geodetic coordinates in decimal degrees and meters
+/// let pos = GroundPosition::from_geodetic((45.0, 2.0, 100.0));
+///
+/// let (lat_ddeg, lon_ddeg, alt_m) = pos.to_geodetic();
+/// assert!((lat_ddeg - 45.0).abs() < 1.0E-9);
+/// assert!((lon_ddeg - 2.0).abs() < 1.0E-9);
+/// assert!((alt_m - 100.0).abs() < 1.0E-6);
+/// ```
 #[derive(Default, Copy, Debug, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GroundPosition(f64, f64, f64);
@@ -24,27 +45,73 @@ impl From<GroundPosition> for (f64, f64, f64) {
 }
 
 impl GroundPosition {
-    /// Builds Self from ECEF WGS84 coordinates
+    /// Builds Self from ECEF WGS84 coordinates, in meters
     pub fn from_ecef_wgs84(pos: (f64, f64, f64)) -> Self {
         Self(pos.0, pos.1, pos.2)
     }
-    /// Builds Self from Geodetic coordinates in ddeg
+
+    /// Builds Self from WGS84 geodetic coordinates:
+    /// latitude and longitude in decimal degrees, ellipsoidal height in meters.
     pub fn from_geodetic(pos: (f64, f64, f64)) -> Self {
-        let (x, y, z) = pos;
-        let (x, y, z) = geodetic2ecef(deg2rad(x), deg2rad(y), deg2rad(z), Ellipsoid::WGS84);
-        Self(x, y, z)
+        let (lat_ddeg, lon_ddeg, alt_m) = pos;
+        let (lat_rad, lon_rad) = (lat_ddeg.to_radians(), lon_ddeg.to_radians());
+
+        let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+        let (sin_lat, cos_lat) = lat_rad.sin_cos();
+        let n = WGS84_SEMI_MAJOR_AXIS_M / (1.0 - e2 * sin_lat.powi(2)).sqrt();
+
+        Self(
+            (n + alt_m) * cos_lat * lon_rad.cos(),
+            (n + alt_m) * cos_lat * lon_rad.sin(),
+            (n * (1.0 - e2) + alt_m) * sin_lat,
+        )
     }
-    /// Converts Self to ECEF WGS84
+
+    /// Converts Self to ECEF WGS84 coordinates, in meters
     pub fn to_ecef_wgs84(&self) -> (f64, f64, f64) {
         (self.0, self.1, self.2)
     }
-    /// Converts Self to geodetic coordinates in ddeg
+
+    /// Converts Self to WGS84 geodetic coordinates:
+    /// latitude and longitude in decimal degrees, ellipsoidal height in meters.
     pub fn to_geodetic(&self) -> (f64, f64, f64) {
         let (x, y, z) = (self.0, self.1, self.2);
-        let (lat, lon, alt) = ecef2geodetic(x, y, z, Ellipsoid::WGS84);
-        (rad2deg(lat), rad2deg(lon), alt)
+
+        let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+        let p = (x.powi(2) + y.powi(2)).sqrt();
+        let lon_rad = y.atan2(x);
+
+        // polar axis: latitude is not defined by the iteration below
+        if p < 1.0E-9 {
+            let b = WGS84_SEMI_MAJOR_AXIS_M * (1.0 - WGS84_FLATTENING);
+            let lat_ddeg = 90.0_f64.copysign(z);
+            return (lat_ddeg, lon_rad.to_degrees(), z.abs() - b);
+        }
+
+        let mut lat_rad = z.atan2(p * (1.0 - e2));
+
+        for _ in 0..GEODETIC_MAX_ITER {
+            let sin_lat = lat_rad.sin();
+            let n = WGS84_SEMI_MAJOR_AXIS_M / (1.0 - e2 * sin_lat.powi(2)).sqrt();
+
+            let next = (z + e2 * n * sin_lat).atan2(p);
+            let converged = (next - lat_rad).abs() < 1.0E-14;
+            lat_rad = next;
+
+            if converged {
+                break;
+            }
+        }
+
+        // well conditioned at all latitudes
+        let (sin_lat, cos_lat) = lat_rad.sin_cos();
+        let alt_m = p * cos_lat + z * sin_lat
+            - WGS84_SEMI_MAJOR_AXIS_M * (1.0 - e2 * sin_lat.powi(2)).sqrt();
+
+        (lat_rad.to_degrees(), lon_rad.to_degrees(), alt_m)
     }
-    /// Returns position altitude
+
+    /// Returns position altitude (ellipsoidal height), in meters
     pub fn altitude(&self) -> f64 {
         self.to_geodetic().2
     }
@@ -66,77 +133,66 @@ impl std::fmt::UpperHex for GroundPosition {
 }
 
 #[cfg(feature = "qc")]
-impl Render for GroundPosition {
+impl QcHtmlReporting for GroundPosition {
     fn render(&self) -> Markup {
-        let ecef = (self.0, self.1, self.2);
         let geo = self.to_geodetic();
         html! {
-            table {
+            table class="table is-bordered" {
                 tr {
-                    th {
-                        "ECEF (WGS84)"
-                    }
+                    th { "ECEF (WGS84)" }
+                    td { (format!("x={:.3} m", self.0)) }
+                    td { (format!("y={:.3} m", self.1)) }
+                    td { (format!("z={:.3} m", self.2)) }
                 }
                 tr {
-                    th {
-                        "X"
-                    }
-                    td {
-                        (format!("{:.3} m", ecef.0))
-                    }
-                    th {
-                        "Y"
-                    }
-                    td {
-                        (format!("{:.3} m", ecef.1))
-                    }
-                    th {
-                       "Z"
-                    }
-                    td {
-                        (format!("{:.3} m", ecef.2))
-                    }
-                }
-                tr {
-                    th {
-                        "GEO"
-                    }
-                }
-                tr {
-                    th {
-                        "Latitude"
-                    }
-                    td {
-                        (format!("{:.6}°", geo.0))
-                    }
-                    th {
-                        "Longitude"
-                    }
-                    td {
-                        (format!("{:.6}°", geo.1))
-                    }
-                    th {
-                        "Altitude"
-                    }
-                    td {
-                        (format!("{:.3} m", geo.2))
-                    }
-                }
-                tr {
-                    th {
-                        "DMS"
-                    }
-                    td {
-                        (DMS::from_ddeg_latitude(geo.0).to_string())
-                    }
-                    th {
-                        "DMS"
-                    }
-                    td {
-                        (DMS::from_ddeg_longitude(geo.1).to_string())
-                    }
+                    th { "Geodetic" }
+                    td { (format!("lat={:.6}°", geo.0)) }
+                    td { (format!("lon={:.6}°", geo.1)) }
+                    td { (format!("alt={:.3} m", geo.2)) }
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::GroundPosition;
+
+    #[test]
+    fn geodetic_conversion() {
+        // equator & polar axis
+        for (ecef, geo) in [
+            ((6_378_137.0, 0.0, 0.0), (0.0, 0.0, 0.0)),
+            ((0.0, 6_378_237.0, 0.0), (0.0, 90.0, 100.0)),
+            ((-6_378_137.0, 0.0, 0.0), (0.0, 180.0, 0.0)),
+            ((0.0, 0.0, 6_356_752.314_245), (90.0, 0.0, 0.0)),
+        ] {
+            let (lat, lon, alt) = GroundPosition::from_ecef_wgs84(ecef).to_geodetic();
+            assert!((lat - geo.0).abs() < 1.0E-9, "bad latitude {}", lat);
+            assert!((lon - geo.1).abs() < 1.0E-9, "bad longitude {}", lon);
+            assert!((alt - geo.2).abs() < 1.0E-4, "bad altitude {}", alt);
+        }
+
+        for (lat_ddeg, lon_ddeg, alt_m) in [
+            (0.0, 0.0, 0.0),
+            (45.0, -120.0, 1500.0),
+            (-33.5, 151.2, -20.0),
+            (89.999, 10.0, 3000.0),
+            (90.0, 0.0, 10.0),
+            (-90.0, 0.0, 10.0),
+        ] {
+            let pos = GroundPosition::from_geodetic((lat_ddeg, lon_ddeg, alt_m));
+            let (lat, lon, alt) = pos.to_geodetic();
+
+            assert!((lat - lat_ddeg).abs() < 1.0E-9, "bad latitude {}", lat);
+            if lat_ddeg.abs() < 90.0 {
+                assert!((lon - lon_ddeg).abs() < 1.0E-9, "bad longitude {}", lon);
+            }
+            assert!((alt - alt_m).abs() < 1.0E-4, "bad altitude {}", alt);
+
+            let ecef = pos.to_ecef_wgs84();
+            assert_eq!(GroundPosition::from(ecef), pos);
+        }
+    }
+}
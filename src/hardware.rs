@@ -1,7 +1,7 @@
 //! Receiver and antenna
 use crate::{
     fmt_rinex,
-    prelude::{FormattingError, COSPAR, SV},
+    prelude::{FormattingError, ParsingError, COSPAR, SV},
 };
 
 use std::{
@@ -34,7 +34,10 @@ impl Receiver {
             w,
             "{}",
             fmt_rinex(
-                &format!("{:<20}{:<20}{}", self.sn, self.model, self.firmware),
+                &format!(
+                    "{:<20.20}{:<20.20}{:<20.20}",
+                    self.sn, self.model, self.firmware
+                ),
                 "REC # / TYPE / VERS"
             )
         )?;
@@ -74,6 +77,113 @@ impl FromStr for Receiver {
     }
 }
 
+/// Parses 3 consecutive F14.4 fields, tolerating
+/// values that do not respect the column alignment.
+fn parse_3f14(content: &str) -> Option<(f64, f64, f64)> {
+    let field = |nth: usize| {
+        content
+            .get(nth * 14..((nth + 1) * 14).min(content.len()))?
+            .trim()
+            .parse::<f64>()
+            .ok()
+    };
+
+    if let (Some(a), Some(b), Some(c)) = (field(0), field(1), field(2)) {
+        return Some((a, b, c));
+    }
+
+    let mut items = content
+        .split_ascii_whitespace()
+        .map(|item| item.parse::<f64>().ok());
+
+    Some((items.next()??, items.next()??, items.next()??))
+}
+
+/// [DeltaHEN] describes the antenna reference point eccentricities,
+/// relative to the marker, in the local frame ("ANTENNA: DELTA H/E/N").
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeltaHEN {
+    /// Height of the antenna reference point above the marker, in meters
+    pub height_m: f64,
+    /// Eastern eccentricity, in meters
+    pub eastern_m: f64,
+    /// Northern eccentricity, in meters
+    pub northern_m: f64,
+}
+
+impl DeltaHEN {
+    /// Builds new [DeltaHEN] from height, eastern and northern components, in meters
+    pub fn new(height_m: f64, eastern_m: f64, northern_m: f64) -> Self {
+        Self {
+            height_m,
+            eastern_m,
+            northern_m,
+        }
+    }
+
+    /// Parses [DeltaHEN] from "ANTENNA: DELTA H/E/N" content (3F14.4)
+    pub(crate) fn parse(content: &str) -> Result<Self, ParsingError> {
+        let (h, e, n) = parse_3f14(content).ok_or(ParsingError::AntennaCoordinates)?;
+        Ok(Self::new(h, e, n))
+    }
+
+    /// Formats [DeltaHEN] into [BufWriter]
+    pub(crate) fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+        writeln!(
+            w,
+            "{}",
+            fmt_rinex(
+                &format!(
+                    "{:14.4}{:14.4}{:14.4}",
+                    self.height_m, self.eastern_m, self.northern_m
+                ),
+                "ANTENNA: DELTA H/E/N"
+            )
+        )?;
+        Ok(())
+    }
+}
+
+/// [DeltaXYZ] describes the position of the antenna reference point
+/// in the body-fixed coordinate system of a vehicle ("ANTENNA: DELTA X/Y/Z").
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeltaXYZ {
+    /// X component, in meters
+    pub x_m: f64,
+    /// Y component, in meters
+    pub y_m: f64,
+    /// Z component, in meters
+    pub z_m: f64,
+}
+
+impl DeltaXYZ {
+    /// Builds new [DeltaXYZ] from its components, in meters
+    pub fn new(x_m: f64, y_m: f64, z_m: f64) -> Self {
+        Self { x_m, y_m, z_m }
+    }
+
+    /// Parses [DeltaXYZ] from "ANTENNA: DELTA X/Y/Z" content (3F14.4)
+    pub(crate) fn parse(content: &str) -> Result<Self, ParsingError> {
+        let (x, y, z) = parse_3f14(content).ok_or(ParsingError::AntennaCoordinates)?;
+        Ok(Self::new(x, y, z))
+    }
+
+    /// Formats [DeltaXYZ] into [BufWriter]
+    pub(crate) fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+        writeln!(
+            w,
+            "{}",
+            fmt_rinex(
+                &format!("{:14.4}{:14.4}{:14.4}", self.x_m, self.y_m, self.z_m),
+                "ANTENNA: DELTA X/Y/Z"
+            )
+        )?;
+        Ok(())
+    }
+}
+
 /// Antenna description
 #[derive(Default, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -82,53 +192,46 @@ pub struct Antenna {
     pub model: String,
     /// Serial number / identification number
     pub sn: String,
-    /// Base / reference point coordinates
-    pub coords: Option<(f64, f64, f64)>,
-    /// Optionnal `h` eccentricity (height component),
-    /// referenced to base/reference point, in meter
-    pub height: Option<f64>,
-    /// Optionnal `eastern` eccentricity (eastern component),
-    /// referenced to base/reference point, in meter
-    pub eastern: Option<f64>,
-    /// Optionnal `northern` eccentricity (northern component),
-    /// referenced to base/reference point, in meter
-    pub northern: Option<f64>,
+    /// Possible [DeltaXYZ] antenna position, on vehicles
+    pub delta_xyz: Option<DeltaXYZ>,
+    /// Possible [DeltaHEN] eccentricities, relative to the marker
+    pub delta_hen: Option<DeltaHEN>,
 }
 
 impl Antenna {
-    /// Formats [Antenna] into [BufWriter]
+    /// Formats [Antenna] into [BufWriter].
+    /// "ANTENNA: DELTA H/E/N" is mandatory and always produced (null when not known).
     pub(crate) fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        writeln!(
-            w,
-            "{}",
-            fmt_rinex(&format!("{:<20}{}", self.sn, self.model), "ANT # / TYPE")
-        )?;
-        if let Some(coords) = &self.coords {
-            writeln!(
-                w,
-                "{}",
-                fmt_rinex(
-                    &format!("{:14.4}{:14.4}{:14.4}", coords.0, coords.1, coords.2),
-                    "APPROX POSITION XYZ"
-                )
-            )?;
-        }
         writeln!(
             w,
             "{}",
             fmt_rinex(
-                &format!(
-                    "{:14.4}{:14.4}{:14.4}",
-                    self.height.unwrap_or(0.0),
-                    self.eastern.unwrap_or(0.0),
-                    self.northern.unwrap_or(0.0)
-                ),
-                "ANTENNA: DELTA H/E/N"
+                &format!("{:<20.20}{:<20.20}", self.sn, self.model),
+                "ANT # / TYPE"
             )
         )?;
+        if let Some(delta) = &self.delta_xyz {
+            delta.format(w)?;
+        }
+        self.delta_hen.unwrap_or_default().format(w)?;
         Ok(())
     }
 
+    /// Returns antenna height above the marker, in meters
+    pub fn height_m(&self) -> Option<f64> {
+        self.delta_hen.map(|delta| delta.height_m)
+    }
+
+    /// Returns antenna eastern eccentricity, in meters
+    pub fn eastern_m(&self) -> Option<f64> {
+        self.delta_hen.map(|delta| delta.eastern_m)
+    }
+
+    /// Returns antenna northern eccentricity, in meters
+    pub fn northern_m(&self) -> Option<f64> {
+        self.delta_hen.map(|delta| delta.northern_m)
+    }
+
     /// Sets desired model
     pub fn with_model(&self, m: &str) -> Self {
         let mut s = self.clone();
@@ -143,31 +246,38 @@ impl Antenna {
         s
     }
 
-    /// Sets reference/base coordinates (3D)
-    pub fn with_base_coordinates(&self, coords: (f64, f64, f64)) -> Self {
+    /// Sets [DeltaXYZ] antenna position
+    pub fn with_delta_xyz(&self, delta: DeltaXYZ) -> Self {
+        let mut s = self.clone();
+        s.delta_xyz = Some(delta);
+        s
+    }
+
+    /// Sets [DeltaHEN] eccentricities
+    pub fn with_delta_hen(&self, delta: DeltaHEN) -> Self {
         let mut s = self.clone();
-        s.coords = Some(coords);
+        s.delta_hen = Some(delta);
         s
     }
 
     /// Sets antenna `h` eccentricity component
     pub fn with_height(&self, h: f64) -> Self {
         let mut s = self.clone();
-        s.height = Some(h);
+        s.delta_hen.get_or_insert_with(Default::default).height_m = h;
         s
     }
 
     /// Sets antenna `eastern` coordinates component
     pub fn with_eastern_component(&self, e: f64) -> Self {
         let mut s = self.clone();
-        s.eastern = Some(e);
+        s.delta_hen.get_or_insert_with(Default::default).eastern_m = e;
         s
     }
 
     /// Sets antenna `northern` coordiantes component
     pub fn with_northern_component(&self, n: f64) -> Self {
         let mut s = self.clone();
-        s.northern = Some(n);
+        s.delta_hen.get_or_insert_with(Default::default).northern_m = n;
         s
     }
 }
@@ -196,12 +306,12 @@ impl QcHtmlReporting for Antenna {
                     }
                     tr {
                         th {
-                            "Base Coordinates"
+                            "Delta X/Y/Z"
                         }
                         td {
-                            @if let Some(coords) = self.coords {
-                                (format!("({}m, {}m, {}m) (ECEF)",
-                                    coords.0, coords.1, coords.2))
+                            @if let Some(delta) = self.delta_xyz {
+                                (format!("({}m, {}m, {}m)",
+                                    delta.x_m, delta.y_m, delta.z_m))
                             } @else {
                                 "Unknown"
                             }
@@ -211,8 +321,8 @@ impl QcHtmlReporting for Antenna {
                         "Height"
                     }
                     td {
-                        @if let Some(h) = self.height {
-                            (format!("{} m", h))
+                        @if let Some(delta) = self.delta_hen {
+                            (format!("{} m", delta.height_m))
                         } @else {
                             "Unknown"
                         }
@@ -221,12 +331,8 @@ impl QcHtmlReporting for Antenna {
                         "Eccentricity"
                     }
                     td {
-                        @if let Some(north) = self.northern {
-                            @if let Some(east) = self.eastern {
-                                (format!("{}m N, {}m E", north, east))
-                            } @else {
-                                "Unknown"
-                            }
+                        @if let Some(delta) = self.delta_hen {
+                            (format!("{}m N, {}m E", delta.northern_m, delta.eastern_m))
                         } @else {
                             "Unknown"
                         }
@@ -322,4 +428,38 @@ mod test {
         assert_eq!(rcvr.sn, "2090088");
         assert_eq!(rcvr.firmware, "4.51");
     }
+
+    #[test]
+    fn antenna_deltas() {
+        let delta = DeltaHEN::parse("        1.2340        0.0000       -0.0120").unwrap();
+        assert_eq!(delta, DeltaHEN::new(1.234, 0.0, -0.012));
+
+        // misaligned
+        let delta = DeltaXYZ::parse("  0.1 -0.2   3.0").unwrap();
+        assert_eq!(delta, DeltaXYZ::new(0.1, -0.2, 3.0));
+
+        assert!(DeltaHEN::parse("        1.2340").is_err());
+
+        let antenna = Antenna::default()
+            .with_model("TRM59800.00")
+            .with_delta_xyz(delta)
+            .with_height(1.234);
+
+        assert_eq!(antenna.height_m(), Some(1.234));
+        assert_eq!(antenna.eastern_m(), Some(0.0));
+
+        let mut buf = BufWriter::new(Vec::new());
+        antenna.format(&mut buf).unwrap();
+        let formatted = String::from_utf8(buf.into_inner().unwrap()).unwrap();
+
+        let lines = formatted.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[1],
+            "        0.1000       -0.2000        3.0000                  ANTENNA: DELTA X/Y/Z"
+        );
+        assert_eq!(
+            lines[2],
+            "        1.2340        0.0000        0.0000                  ANTENNA: DELTA H/E/N"
+        );
+    }
 }
@@ -2,10 +2,14 @@
 use crate::{
     hatanaka::{Error, NumDiff, TextDiff},
     prelude::{Constellation, Observable, SV},
+    sv::parse_lenient as parse_sv_lenient,
 };
 
 use std::{collections::HashMap, str::FromStr};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 pub mod io;
 
 use num_integer::div_ceil;
@@ -30,6 +34,7 @@ use crate::hatanaka::Compressor;
 pub type Decompressor = DecompressorExpert<5>;
 
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum State {
     #[default]
     /// Gathering Epoch descriptor.
@@ -98,6 +103,8 @@ impl State {
 /// With [DecompressorExpert] you can support any value.
 /// Keep in mind that CRINEX is not a lossless compression for signal observations.
 /// The higher the compression order, the larger the error over the signal observations.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DecompressorExpert<const M: usize> {
     /// Whether this is a V3 parser or not
     v3: bool,
@@ -124,12 +131,15 @@ pub struct DecompressorExpert<const M: usize> {
     /// Cleaned up flags buffer (single malloc)
     flags_buf: String,
     /// [TextDiff] for observation flags
+    #[cfg_attr(feature = "serde", serde(with = "crate::hatanaka::serde_entries"))]
     flags_diff: HashMap<SV, TextDiff>,
     /// Clock offset differentiator
     clock_diff: NumDiff<M>,
     /// Observation differentiators
+    #[cfg_attr(feature = "serde", serde(with = "crate::hatanaka::serde_entries"))]
     obs_diff: HashMap<(SV, usize), NumDiff<M>>,
     /// [Observable]s specs for each [Constellation]
+    #[cfg_attr(feature = "serde", serde(with = "crate::hatanaka::serde_entries"))]
     gnss_observables: HashMap<Constellation, Vec<Observable>>,
 }
 
@@ -184,31 +194,14 @@ impl<const M: usize> DecompressorExpert<M> {
         let start = Self::sv_slice_start(self.v3, self.sv_ptr);
         let end = (start + 3).min(self.epoch_desc_len);
 
-        if let Ok(sv) = SV::from_str(&self.epoch_descriptor[start..end].trim()) {
-            Some(sv)
+        let descriptor = &self.epoch_descriptor[start..end];
+
+        if self.v3 {
+            SV::from_str(descriptor.trim()).ok()
         } else {
-            // May fail on old revisions that have a mono GNSS system
-            // that have tendency to omit the constellation description (leaving only the PRN#)
-            if !self.v3 {
-                match self.constellation {
-                    Constellation::Mixed => {
-                        None // incorrect description, will rapidly panic
-                    },
-                    constellation => {
-                        // PRN# parsing attempt
-                        if let Ok(prn) = &self.epoch_descriptor[start..end].trim().parse::<u8>() {
-                            Some(SV {
-                                prn: *prn,
-                                constellation,
-                            })
-                        } else {
-                            None // incorrect description, will rapidly panic
-                        }
-                    },
-                }
-            } else {
-                None
-            }
+            // Old revisions that have a mono GNSS system
+            // have tendency to omit the constellation description (leaving only the PRN#)
+            parse_sv_lenient(descriptor, Some(self.constellation)).ok()
         }
     }
 
@@ -1203,4 +1196,72 @@ mod test {
             assert_eq!(output, expected);
         }
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialized_state() {
+        use crate::prelude::{Constellation, Observable};
+        use std::collections::HashMap;
+
+        let observables = HashMap::from([(
+            Constellation::GPS,
+            vec![
+                Observable::from_str("C1C").unwrap(),
+                Observable::from_str("L1C").unwrap(),
+            ],
+        )]);
+
+        let content = "> 2020 01 01 00 00  0.0000000  0  2      G01G07
+
+3&20832393682 3&109474992854
+3&20832393682 3&109474998854
+                 1 3
+
+1000 1000
+1000 1000
+                 2 0
+
+0 0
+0 0
+";
+        let lines = content.lines().collect::<Vec<_>>();
+        let (first, remaining) = lines.split_at(4);
+
+        let mut buf = [0; 1024];
+        let mut decompressor = Decompressor::new(true, Constellation::GPS, observables);
+
+        for line in first {
+            let line = format!("{}\n", line);
+            decompressor
+                .decompress(&line, line.len(), &mut buf, 1024)
+                .unwrap();
+        }
+
+        // serialized state, resumed in another process
+        let json = serde_json::to_string(&decompressor).unwrap();
+        let mut resumed: Decompressor = serde_json::from_str(&json).unwrap();
+
+        let mut output = String::new();
+
+        for line in remaining {
+            let line = format!("{}\n", line);
+
+            let size = decompressor
+                .decompress(&line, line.len(), &mut buf, 1024)
+                .unwrap();
+            let expected = from_utf8(&buf[..size]).unwrap().to_string();
+
+            let size = resumed
+                .decompress(&line, line.len(), &mut buf, 1024)
+                .unwrap();
+            let recovered = from_utf8(&buf[..size]).unwrap();
+
+            assert_eq!(recovered, expected, "\"{}\"", line.trim_end());
+            output.push_str(recovered);
+        }
+
+        // differential state was preserved
+        assert!(output.contains("> 2020 01 01 00 02 00.0000000  0  2"));
+        assert!(output.contains("G07  20832395.682   109475000.854"));
+    }
 }
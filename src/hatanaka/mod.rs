@@ -25,6 +25,31 @@ pub use textdiff::TextDiff;
 
 use thiserror::Error as ErrorTrait;
 
+/// Serializes maps whose keys are not strings (not supported by most formats),
+/// as a sequence of (key, value) entries.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_entries {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::{collections::HashMap, hash::Hash};
+
+    pub fn serialize<K: Serialize, V: Serialize, S: Serializer>(
+        map: &HashMap<K, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
 /// Hatanaka dedicated Errors
 #[derive(Debug, ErrorTrait)]
 pub enum Error {
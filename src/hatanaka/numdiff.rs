@@ -1,5 +1,8 @@
 //! Y. Hatanaka lossy Numerical compression algorithm
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 /// [NumDiff] is dedicated to numerical (de-)compression, following
 /// the algorithm developped by Y. Hatanaka. This compression
 /// is not lossless: the more efficient the data compression, the bigger the error.
@@ -13,15 +16,41 @@
 /// Note that we support m<=(M=6), therefore if you remain within our application,
 /// you can use higher compression order.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NumDiff<const M: usize> {
     /// iteration counter
     m: usize,
     /// compression level, within M maximal range
     level: usize,
     /// internal data history
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "serialize_history"),
+        serde(deserialize_with = "deserialize_history")
+    )]
     buf: [i64; M],
 }
 
+/// Data history is serialized as a sequence (serde does not support arrays of generic size)
+#[cfg(feature = "serde")]
+fn serialize_history<const M: usize, S: Serializer>(
+    buf: &[i64; M],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(buf.iter())
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_history<'de, const M: usize, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[i64; M], D::Error> {
+    let history = Vec::<i64>::deserialize(deserializer)?;
+    let len = history.len();
+    history
+        .try_into()
+        .map_err(|_| serde::de::Error::invalid_length(len, &"compression order"))
+}
+
 impl<const M: usize> NumDiff<M> {
     /// Builds a [NumDiff] structure dedicated to numerical (de-)compression.
     /// Level must not exceed 6 otherwise this will panic.
@@ -1,12 +1,16 @@
 //! Y. Hatanaka lossless TextDiff algorithm
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// [TextDiff] is a structure that implements the Text diff. algorithm
 /// designed by Y. Hatanaka, which is a lossless text compression algorithm.
 /// [TextDiff] in its current form does not allow compressing & decompressing (back & forth)
 /// at the same time: you need two dedicated objects.
 /// This does not bother our application because it only operates in one way,
 /// but it is one aspect to keep in mind.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TextDiff {
     buffer: String,
     compressed: String,
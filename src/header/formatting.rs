@@ -7,13 +7,12 @@ use crate::{
     types::Type,
 };
 
+use itertools::Itertools;
 use std::io::{BufWriter, Write};
 
 impl Header {
     /// Formats [Header] into [Write]able interface, using efficient buffering.
     pub fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        const NUM_GLO_CHANNELS_PER_LINE: usize = 8;
-
         let major = self.version.major;
 
         if let Some(obs) = &self.obs {
@@ -32,6 +31,10 @@ impl Header {
             self.format_observer_agency(w)?;
         }
 
+        if major > 3 {
+            self.format_v4_information(w)?;
+        }
+
         self.format_comments(w)?;
         self.format_rinex_dependent(w)?;
 
@@ -58,44 +61,42 @@ impl Header {
             )?;
         }
 
-        self.format_sampling_interval(w)?;
-
-        if let Some(leap) = self.leap {
-            leap.format(w)?;
+        if major < 3 && self.obs.is_some() {
+            if let Some((l1, l2)) = self.wavelengths {
+                writeln!(
+                    w,
+                    "{}",
+                    fmt_rinex(&format!("{:6}{:6}", l1, l2), "WAVELENGTH FACT L1/2")
+                )?;
+            }
         }
 
-        let num_channels = self.glo_channels.len();
+        self.format_sampling_interval(w)?;
 
-        if num_channels > 0 {
-            write!(w, "{:3} ", num_channels,)?;
+        if major > 2 {
+            for dcb in self.dcb_compensations.iter() {
+                writeln!(w, "{}", dcb.format())?;
+            }
+            for pcv in self.pcv_compensations.iter() {
+                writeln!(w, "{}", pcv.format())?;
+            }
         }
 
-        let mut modulo = 0;
-        for (nth, (sv, channel)) in self.glo_channels.iter().enumerate() {
-            write!(w, "{:x} {:2} ", sv, channel,)?;
+        if let Some(leap) = self.leap {
+            leap.format(w)?;
+        }
 
-            if nth == NUM_GLO_CHANNELS_PER_LINE - 1 {
-                write!(w, "GLONASS SLOT / FRQ #\n    ")?;
-            } else {
-                if (nth % NUM_GLO_CHANNELS_PER_LINE) == NUM_GLO_CHANNELS_PER_LINE - 1 {
-                    write!(w, "GLONASS SLOT / FRQ #\n    ")?;
-                }
-            }
+        self.format_glonass_channels(w)?;
 
-            modulo = nth % NUM_GLO_CHANNELS_PER_LINE;
+        if major > 2 {
+            self.format_glonass_biases(w)?;
         }
 
-        if modulo > 0 && modulo != NUM_GLO_CHANNELS_PER_LINE - 1 {
-            writeln!(
-                w,
-                "{:>width$}",
-                "GLONASS SLOT / FRQ #",
-                width = 79 - 9 - (modulo + 1) * 6
-            )?;
-        }
+        // KB model, in a reproducible order
+        let mut ionod_corrections = self.ionod_corrections.iter().collect::<Vec<_>>();
+        ionod_corrections.sort_by_key(|(constellation, _)| **constellation);
 
-        // KB model
-        for (index, (constellation, model)) in self.ionod_corrections.iter().enumerate() {
+        for (index, (constellation, model)) in ionod_corrections.into_iter().enumerate() {
             if let Some(kb) = model.as_klobuchar() {
                 if major == 2 && index == 0 {
                     kb.format_v2_header(w)?;
@@ -114,6 +115,11 @@ impl Header {
         // [+] SBAS detail (detailed vehicle identity)
         // [+] RINEX 3 -> 2 observables conversion (see OBS/V2/rovn as an example)
 
+        // lines we could not interpret, as is
+        for line in self.unparsed.iter() {
+            writeln!(w, "{}", line)?;
+        }
+
         // Conclusion
         writeln!(w, "{}", fmt_rinex("", "END OF HEADER"))?;
         Ok(())
@@ -154,7 +160,7 @@ impl Header {
                         "{}",
                         fmt_rinex(
                             &format!(
-                                "{:6}.{:02}           NAVIGATION DATA     {:X<20}",
+                                "{:6}.{:02}           NAVIGATION DATA     {:x}",
                                 major, minor, c
                             ),
                             "RINEX VERSION / TYPE"
@@ -183,7 +189,7 @@ impl Header {
                         "{}",
                         fmt_rinex(
                             &format!(
-                                "{:6}.{:02}           OBSERVATION DATA    {:x<20}",
+                                "{:6}.{:02}           OBSERVATION DATA    {:x}",
                                 major, minor, c
                             ),
                             "RINEX VERSION / TYPE"
@@ -246,24 +252,12 @@ impl Header {
 
     /// Formats "PGM / RUN BY / DATE"
     fn format_prog_runby<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        let mut string = if let Some(program) = &self.program {
-            format!("{:<20}", program)
-        } else {
-            "                    ".to_string()
-        };
-
-        if let Some(runby) = &self.run_by {
-            let formatted = format!("{:<20}", runby);
-            string.push_str(&formatted);
-        } else {
-            string.push_str("                    ");
-        };
-
-        if let Some(date) = &self.date {
-            string.push_str(date);
-        } else {
-            string.push_str("                    ");
-        };
+        let string = format!(
+            "{:<20.20}{:<20.20}{:<20.20}",
+            self.program.as_deref().unwrap_or_default(),
+            self.run_by.as_deref().unwrap_or_default(),
+            self.date.as_deref().unwrap_or_default(),
+        );
 
         // PGM / RUN BY / DATE
         writeln!(w, "{}", fmt_rinex(&string, "PGM / RUN BY / DATE"),)?;
@@ -271,22 +265,53 @@ impl Header {
         Ok(())
     }
 
+    /// Formats "DOI", "LICENSE OF USE" and "STATION INFORMATION" (V4)
+    fn format_v4_information<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+        if let Some(doi) = &self.doi {
+            writeln!(w, "{}", fmt_rinex(doi, "DOI"))?;
+        }
+        if let Some(license) = &self.license {
+            Self::format_multiline(w, license, "LICENSE OF USE")?;
+        }
+        if let Some(url) = &self.station_url {
+            Self::format_multiline(w, url, "STATION INFORMATION")?;
+        }
+        Ok(())
+    }
+
+    /// Formats content that may span several lines, wrapped on word boundaries.
+    fn format_multiline<W: Write>(
+        w: &mut BufWriter<W>,
+        content: &str,
+        marker: &str,
+    ) -> Result<(), FormattingError> {
+        let mut line = String::new();
+        for word in content.split_ascii_whitespace() {
+            if !line.is_empty() && line.len() + 1 + word.len() > 60 {
+                writeln!(w, "{}", fmt_rinex(&line, marker))?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        if !line.is_empty() {
+            writeln!(w, "{}", fmt_rinex(&line, marker))?;
+        }
+        Ok(())
+    }
+
     /// Formats "OBSERVER / AGENCY"
     fn format_observer_agency<W: Write>(
         &self,
         w: &mut BufWriter<W>,
     ) -> Result<(), FormattingError> {
-        let mut string = if let Some(observer) = &self.observer {
-            format!("{:<20}", observer)
-        } else {
-            "                    ".to_string()
-        };
-
-        if let Some(agency) = &self.agency {
-            string.push_str(agency);
-        } else {
-            string.push_str("                    ");
-        };
+        let string = format!(
+            "{:<20.20}{:<40.40}",
+            self.observer.as_deref().unwrap_or_default(),
+            self.agency.as_deref().unwrap_or_default(),
+        );
 
         writeln!(w, "{}", fmt_rinex(&string, "OBSERVER / AGENCY"),)?;
 
@@ -302,12 +327,58 @@ impl Header {
             writeln!(
                 w,
                 "{}",
-                fmt_rinex(&format!("{:6}", interval.to_seconds()), "INTERVAL")
+                fmt_rinex(&format!("{:10.3}", interval.to_seconds()), "INTERVAL")
             )?;
         }
         Ok(())
     }
 
+    /// Formats "GLONASS SLOT / FRQ #", 8 channels per line
+    fn format_glonass_channels<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+    ) -> Result<(), FormattingError> {
+        const NUM_GLO_CHANNELS_PER_LINE: usize = 8;
+
+        let channels = self.glo_channels.iter().sorted().collect::<Vec<_>>();
+
+        for (nth, chunk) in channels.chunks(NUM_GLO_CHANNELS_PER_LINE).enumerate() {
+            let mut string = if nth == 0 {
+                format!("{:3} ", channels.len())
+            } else {
+                "    ".to_string()
+            };
+
+            for (sv, channel) in chunk.iter() {
+                string.push_str(&format!("{} {:2} ", sv, channel));
+            }
+
+            writeln!(w, "{}", fmt_rinex(&string, "GLONASS SLOT / FRQ #"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats "GLONASS COD/PHS/BIS", 4 biases per line
+    fn format_glonass_biases<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+        let biases = self
+            .glo_biases
+            .iter()
+            .map(|(observable, bias)| (observable.to_string(), bias))
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .collect::<Vec<_>>();
+
+        for chunk in biases.chunks(4) {
+            let mut string = String::new();
+            for (observable, bias) in chunk.iter() {
+                string.push_str(&format!(" {:<3} {:8.3}", observable, bias));
+            }
+            writeln!(w, "{}", fmt_rinex(&string, "GLONASS COD/PHS/BIS"))?;
+        }
+
+        Ok(())
+    }
+
     /// Formats all comments
     fn format_comments<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
         for comment in self.comments.iter() {
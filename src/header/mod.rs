@@ -4,25 +4,33 @@ use crate::{
     antex::HeaderFields as AntexHeader,
     clock::HeaderFields as ClockHeader,
     doris::HeaderFields as DorisHeader,
-    hardware::{Antenna, Receiver, SvAntenna},
+    fmt_rinex,
+    ground_position::GroundPosition,
+    hardware::{Antenna, DeltaHEN, DeltaXYZ, Receiver, SvAntenna},
     hatanaka::CRINEX,
     ionex::HeaderFields as IonexHeader,
     leap::Leap,
-    marker::GeodeticMarker,
+    marker::{GeodeticMarker, MarkerType},
     meteo::HeaderFields as MeteoHeader,
     navigation::HeaderFields as NavigationHeader,
-    navigation::IonosphereModel,
+    navigation::{IonosphereModel, KbModel, NgModel},
     observation::HeaderFields as ObservationHeader,
-    prelude::{Constellation, Duration, Epoch, COSPAR, SV},
+    prelude::{
+        Carrier, Constellation, Duration, Epoch, Error, Observable, ParsingError, TimeScale,
+        COSPAR, SV,
+    },
     types::Type,
     version::Version,
 };
 
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 mod formatting;
 mod parsing;
+mod validation;
+
+pub use validation::HeaderIssue;
 
 #[cfg(feature = "qc")]
 mod qc;
@@ -42,28 +50,118 @@ use serde::Serialize;
 #[cfg(feature = "processing")]
 pub(crate) mod processing;
 
-/// DCB compensation description
+/// DCB compensation description ("SYS / DCBS APPLIED")
 #[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DcbCompensation {
-    /// Program used for DCBs evaluation and compensation
-    pub program: String,
+    /// Program used for DCBs evaluation and compensation, if specified
+    pub program: Option<String>,
     /// Constellation to which this compensation applies to
     pub constellation: Constellation,
-    /// URL: source of corrections
-    pub url: String,
+    /// URL: source of corrections, if specified
+    pub url: Option<String>,
 }
 
-/// PCV compensation description
+/// PCV compensation description ("SYS / PCVS APPLIED")
 #[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PcvCompensation {
-    /// Program used for PCVs evaluation and compensation
-    pub program: String,
+    /// Program used for PCVs evaluation and compensation, if specified
+    pub program: Option<String>,
     /// Constellation to which this compensation applies to
     pub constellation: Constellation,
-    /// URL: source of corrections
-    pub url: String,
+    /// URL: source of corrections, if specified
+    pub url: Option<String>,
+}
+
+/// Parses a "SYS / DCBS APPLIED" or "SYS / PCVS APPLIED" description:
+/// (constellation, program, url). Returns None when no corrections were applied
+/// (blank program and url).
+fn parse_applied_corrections(
+    content: &str,
+) -> Result<Option<(Constellation, Option<String>, Option<String>)>, ParsingError> {
+    let field = |range: std::ops::Range<usize>| {
+        let (start, end) = (range.start.min(content.len()), range.end.min(content.len()));
+        let field = content.get(start..end).unwrap_or_default().trim();
+        if field.is_empty() {
+            None
+        } else {
+            Some(field.to_string())
+        }
+    };
+
+    let (program, url) = (field(2..19), field(20..60));
+
+    if program.is_none() && url.is_none() {
+        return Ok(None);
+    }
+
+    let constellation = Constellation::from_str(content.get(..1).unwrap_or_default())?;
+    Ok(Some((constellation, program, url)))
+}
+
+/// Formats a "SYS / DCBS APPLIED" or "SYS / PCVS APPLIED" description
+fn format_applied_corrections(
+    constellation: Constellation,
+    program: &Option<String>,
+    url: &Option<String>,
+    marker: &str,
+) -> String {
+    let content = format!(
+        "{:x} {:<17.17} {:<40.40}",
+        constellation,
+        program.as_deref().unwrap_or_default(),
+        url.as_deref().unwrap_or_default()
+    );
+    fmt_rinex(&content, marker)
+}
+
+impl DcbCompensation {
+    /// Parses [DcbCompensation] from "SYS / DCBS APPLIED" content.
+    /// Returns None when no corrections were applied.
+    pub(crate) fn parse(content: &str) -> Result<Option<Self>, ParsingError> {
+        Ok(
+            parse_applied_corrections(content)?.map(|(constellation, program, url)| Self {
+                program,
+                constellation,
+                url,
+            }),
+        )
+    }
+
+    /// Formats [DcbCompensation] as "SYS / DCBS APPLIED"
+    pub(crate) fn format(&self) -> String {
+        format_applied_corrections(
+            self.constellation,
+            &self.program,
+            &self.url,
+            "SYS / DCBS APPLIED",
+        )
+    }
+}
+
+impl PcvCompensation {
+    /// Parses [PcvCompensation] from "SYS / PCVS APPLIED" content.
+    /// Returns None when no corrections were applied.
+    pub(crate) fn parse(content: &str) -> Result<Option<Self>, ParsingError> {
+        Ok(
+            parse_applied_corrections(content)?.map(|(constellation, program, url)| Self {
+                program,
+                constellation,
+                url,
+            }),
+        )
+    }
+
+    /// Formats [PcvCompensation] as "SYS / PCVS APPLIED"
+    pub(crate) fn format(&self) -> String {
+        format_applied_corrections(
+            self.constellation,
+            &self.program,
+            &self.url,
+            "SYS / PCVS APPLIED",
+        )
+    }
 }
 
 /// Describes `RINEX` file header
@@ -78,6 +176,10 @@ pub struct Header {
     pub constellation: Option<Constellation>,
     /// Comments from this section
     pub comments: Vec<String>,
+    /// Header lines that were not interpreted (unknown or malformed labels),
+    /// stored as is. They are written back when formatting, so vendor
+    /// specific extensions survive a round trip.
+    pub unparsed: Vec<String>,
     /// Possible software name (publishing software)
     pub program: Option<String>,
     /// Possible software operator
@@ -92,8 +194,11 @@ pub struct Header {
     pub agency: Option<String>,
     /// Possible [GeodeticMarker]
     pub geodetic_marker: Option<GeodeticMarker>,
-    /// Glonass FDMA channels
+    /// Glonass FDMA channels, as described by "GLONASS SLOT / FRQ #"
     pub glo_channels: HashMap<SV, i8>,
+    /// Glonass code-phase biases (in meters) per [Observable],
+    /// as described by "GLONASS COD/PHS/BIS" (V3.02+)
+    pub glo_biases: HashMap<Observable, f64>,
     /// Possible COSPAR number (launch information)
     pub cospar: Option<COSPAR>,
     /// Possible [Leap] seconds counter
@@ -163,12 +268,14 @@ impl Default for Header {
             dcb_compensations: Default::default(),
             license: Default::default(),
             comments: Default::default(),
+            unparsed: Default::default(),
             run_by: Default::default(),
             station_url: Default::default(),
             observer: Default::default(),
             agency: Default::default(),
             geodetic_marker: Default::default(),
             glo_channels: Default::default(),
+            glo_biases: Default::default(),
             sampling_interval: None,
             leap: None,
             rx_position: None,
@@ -257,24 +364,176 @@ impl Header {
             .join(".")
     }
 
-    /// Generates the special "FILE MERGE" comment
-    pub(crate) fn merge_comment(pkg_version: &str, timestamp: Epoch) -> String {
+    /// Generates a time-tagged processing comment, formatted like
+    /// the "PGM / RUN BY / DATE" field: program, operation and date of processing.
+    /// The date is omitted when unknown.
+    pub(crate) fn processing_comment(
+        pkg_version: &str,
+        operation: &str,
+        timestamp: Option<Epoch>,
+    ) -> String {
         let formatted_version = Self::format_pkg_version(pkg_version);
+        let program = format!("rs-rinex v{}", formatted_version);
 
-        let (y, m, d, hh, mm, ss, _) = timestamp.to_gregorian_utc();
-        format!(
-            "rs-rinex v{} {:>width$}          {}{:02}{:02} {:02}{:02}{:02} {:x}",
-            formatted_version,
-            "FILE MERGE",
-            y,
-            m,
-            d,
-            hh,
-            mm,
-            ss,
-            timestamp.time_scale,
-            width = 19 - formatted_version.len(),
-        )
+        match timestamp {
+            Some(t) => {
+                let (y, m, d, hh, mm, ss, _) = t.to_gregorian_utc();
+                format!(
+                    "{:<20}{:<20}{}{:02}{:02} {:02}{:02}{:02} {:x}",
+                    program, operation, y, m, d, hh, mm, ss, t.time_scale,
+                )
+            },
+            None => format!("{:<20}{}", program, operation)
+                .trim_end()
+                .to_string(),
+        }
+    }
+
+    /// Parses the date of "PGM / RUN BY / DATE", either formatted
+    /// "yyyymmdd hhmmss zone" (RINEX3+) or "dd-mmm-yy hh:mm" (RINEX2).
+    /// The time zone is not interpreted and the date is considered UTC.
+    pub(crate) fn parse_production_date(date: &str) -> Option<Epoch> {
+        const MONTHS: [&str; 12] = [
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ];
+
+        let date = date.trim();
+        let mut items = date.split_whitespace();
+        let (day, time) = (items.next()?, items.next()?);
+
+        let (y, m, d) = if day.len() == 8 {
+            (
+                day[0..4].parse::<i32>().ok()?,
+                day[4..6].parse::<u8>().ok()?,
+                day[6..8].parse::<u8>().ok()?,
+            )
+        } else {
+            let mut items = day.split('-');
+            let d = items.next()?.parse::<u8>().ok()?;
+            let month = items.next()?.to_uppercase();
+            let m = MONTHS.iter().position(|name| *name == month)? as u8 + 1;
+            let y = items.next()?.parse::<i32>().ok()?;
+            let y = if y < 80 { y + 2000 } else { y + 1900 };
+            (y, m, d)
+        };
+
+        let (hh, mm, ss) = if time.contains(':') {
+            let mut items = time.split(':');
+            let hh = items.next()?.parse::<u8>().ok()?;
+            let mm = items.next()?.parse::<u8>().ok()?;
+            let ss = items.next().map_or(Some(0), |ss| ss.parse::<u8>().ok())?;
+            (hh, mm, ss)
+        } else if time.len() == 6 {
+            (
+                time[0..2].parse::<u8>().ok()?,
+                time[2..4].parse::<u8>().ok()?,
+                time[4..6].parse::<u8>().ok()?,
+            )
+        } else {
+            return None;
+        };
+
+        Epoch::maybe_from_gregorian_utc(y, m, d, hh, mm, ss, 0).ok()
+    }
+
+    /// Returns the production [Epoch] ("PGM / RUN BY / DATE"), if it is known and
+    /// could be interpreted.
+    pub fn production_epoch(&self) -> Option<Epoch> {
+        Self::parse_production_date(self.date.as_ref()?)
+    }
+
+    /// Formats this [Epoch] as the date of "PGM / RUN BY / DATE", for this major revision:
+    /// "yyyymmdd hhmmss UTC" in RINEX3+, "dd-mmm-yy hh:mm" in RINEX2.
+    pub(crate) fn format_production_date(major: u8, t: Epoch) -> String {
+        const MONTHS: [&str; 12] = [
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ];
+
+        let (y, m, d, hh, mm, ss, _) = t.to_gregorian_utc();
+
+        if major < 3 {
+            format!(
+                "{:02}-{}-{:02} {:02}:{:02}",
+                d,
+                MONTHS[(m as usize).clamp(1, 12) - 1],
+                y.rem_euclid(100),
+                hh,
+                mm
+            )
+        } else {
+            format!("{}{:02}{:02} {:02}{:02}{:02} UTC", y, m, d, hh, mm, ss)
+        }
+    }
+
+    /// Copies and returns [Header] stamped ("PGM / RUN BY / DATE") as produced
+    /// by this library at this [Epoch]. The agency ("RUN BY") is preserved when not specified.
+    pub(crate) fn stamped(&self, agency: Option<&str>, t: Epoch) -> Self {
+        let mut s = self.clone();
+        s.program = Some(format!(
+            "rs-rinex v{}",
+            Self::format_pkg_version(env!("CARGO_PKG_VERSION"))
+        ));
+        if let Some(agency) = agency {
+            s.run_by = Some(agency.to_string());
+        }
+        s.date = Some(Self::format_production_date(self.version.major, t));
+        s
+    }
+
+    /// Appends one entry to the processing log, embedded in the COMMENT section.
+    /// The first comment describes the operation (up to 19 characters) and is
+    /// time-tagged with this timestamp, or with the current system time when not specified.
+    /// The following comments describe its parameters, if any, wrapped at 60 columns.
+    /// This gives provenance information to downstream users.
+    /// It is automatically called by our processing pipelines
+    /// (merge, masking, decimation, smoothing..), you may use it to document your own operations.
+    pub fn push_processing_log(
+        &mut self,
+        timestamp: Option<Epoch>,
+        operation: &str,
+        parameters: &str,
+    ) {
+        let timestamp = timestamp.or_else(|| Epoch::now().ok());
+        let operation = operation.chars().take(19).collect::<String>();
+
+        self.comments.push(Self::processing_comment(
+            env!("CARGO_PKG_VERSION"),
+            &operation.to_uppercase(),
+            timestamp,
+        ));
+
+        let mut line = String::new();
+
+        for word in parameters.split_whitespace() {
+            let word = word.chars().collect::<Vec<_>>();
+
+            for piece in word.chunks(60) {
+                if !line.is_empty() && line.chars().count() + 1 + piece.len() > 60 {
+                    self.comments.push(std::mem::take(&mut line));
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.extend(piece.iter());
+            }
+        }
+
+        if !line.is_empty() {
+            self.comments.push(line);
+        }
+    }
+
+    /// Copies and returns [Header] with one more processing log entry.
+    /// See [Self::push_processing_log].
+    pub fn with_processing_log(
+        &self,
+        timestamp: Option<Epoch>,
+        operation: &str,
+        parameters: &str,
+    ) -> Self {
+        let mut s = self.clone();
+        s.push_processing_log(timestamp, operation, parameters);
+        s
     }
 
     /// Copies and returns [Header] with specific RINEX [Version]
@@ -300,6 +559,34 @@ impl Header {
         s
     }
 
+    /// Copies and returns [Header] with publishing software name ("PGM / RUN BY / DATE")
+    pub fn with_program(&self, program: &str) -> Self {
+        let mut s = self.clone();
+        s.program = Some(program.to_string());
+        s
+    }
+
+    /// Copies and returns [Header] with operator that produced this file ("PGM / RUN BY / DATE")
+    pub fn with_operator(&self, run_by: &str) -> Self {
+        let mut s = self.clone();
+        s.run_by = Some(run_by.to_string());
+        s
+    }
+
+    /// Copies and returns [Header] with observer ("OBSERVER / AGENCY")
+    pub fn with_observer(&self, observer: &str) -> Self {
+        let mut s = self.clone();
+        s.observer = Some(observer.to_string());
+        s
+    }
+
+    /// Copies and returns [Header] with agency ("OBSERVER / AGENCY")
+    pub fn with_agency(&self, agency: &str) -> Self {
+        let mut s = self.clone();
+        s.agency = Some(agency.to_string());
+        s
+    }
+
     /// Copies and returns [Header] with special [CRINEX] fields
     pub(crate) fn with_crinex(&self, c: CRINEX) -> Self {
         let mut s = self.clone();
@@ -325,6 +612,188 @@ impl Header {
         s
     }
 
+    /// Copies and returns [Header] without [Receiver] nor [Antenna] information,
+    /// typically to anonymize a file.
+    pub fn without_hardware(&self) -> Self {
+        let mut s = self.clone();
+        s.rcvr = None;
+        s.rcvr_antenna = None;
+        s
+    }
+
+    /// Copies and returns [Header] with approximate receiver [GroundPosition],
+    /// stored as ECEF WGS84 coordinates ("APPROX POSITION XYZ").
+    pub fn with_ground_position(&self, position: GroundPosition) -> Self {
+        self.with_ground_position_ecef(position.to_ecef_wgs84())
+    }
+
+    /// Copies and returns [Header] with approximate receiver position,
+    /// as ECEF (x, y, z) coordinates in meters ("APPROX POSITION XYZ").
+    pub fn with_ground_position_ecef(&self, ecef_m: (f64, f64, f64)) -> Self {
+        let mut s = self.clone();
+        s.rx_position = Some(ecef_m);
+        s
+    }
+
+    /// Returns approximate receiver [GroundPosition] ("APPROX POSITION XYZ"), if known.
+    /// Use [GroundPosition::to_geodetic] to obtain WGS84 latitude, longitude and height.
+    pub fn ground_position(&self) -> Option<GroundPosition> {
+        self.rx_position.map(GroundPosition::from_ecef_wgs84)
+    }
+
+    /// Converts this [Epoch] to UTC, using the "LEAP SECONDS" description
+    /// when it exists (see [Leap::to_utc]), the leap second table of the library otherwise.
+    pub fn epoch_to_utc(&self, t: Epoch) -> Epoch {
+        match &self.leap {
+            Some(leap) => leap.to_utc(t),
+            None => t.to_time_scale(TimeScale::UTC),
+        }
+    }
+
+    /// Returns receiver antenna [DeltaHEN] eccentricities, if known
+    pub fn antenna_delta_hen(&self) -> Option<DeltaHEN> {
+        self.rcvr_antenna.as_ref()?.delta_hen
+    }
+
+    /// Returns receiver antenna [DeltaXYZ] position, if known
+    pub fn antenna_delta_xyz(&self) -> Option<DeltaXYZ> {
+        self.rcvr_antenna.as_ref()?.delta_xyz
+    }
+
+    /// Copies and returns [Header] with Digital Object Identifier ("DOI", V4)
+    pub fn with_doi(&self, doi: &str) -> Self {
+        let mut s = self.clone();
+        s.doi = Some(doi.to_string());
+        s
+    }
+
+    /// Copies and returns [Header] with file license ("LICENSE OF USE", V4)
+    pub fn with_license(&self, license: &str) -> Self {
+        let mut s = self.clone();
+        s.license = Some(license.to_string());
+        s
+    }
+
+    /// Copies and returns [Header] with station information URL ("STATION INFORMATION", V4)
+    pub fn with_station_information(&self, url: &str) -> Self {
+        let mut s = self.clone();
+        s.station_url = Some(url.to_string());
+        s
+    }
+
+    /// Copies and returns [Header] with specific [GeodeticMarker]
+    pub fn with_geodetic_marker(&self, marker: GeodeticMarker) -> Self {
+        let mut s = self.clone();
+        s.geodetic_marker = Some(marker);
+        s
+    }
+
+    /// Copies and returns [Header] with this marker name ("MARKER NAME").
+    /// Other [GeodeticMarker] fields are preserved when they exist.
+    pub fn with_marker_name(&self, name: &str) -> Self {
+        let mut s = self.clone();
+        let marker = s.geodetic_marker.get_or_insert_with(Default::default);
+        marker.name = name.to_string();
+        s
+    }
+
+    /// Returns [MarkerType] ("MARKER TYPE"), if defined.
+    pub fn marker_type(&self) -> Option<MarkerType> {
+        self.geodetic_marker.as_ref()?.marker_type
+    }
+
+    /// Returns true if signals received by this marker crossed the troposphere,
+    /// meaning a troposphere model should apply: false for
+    /// [MarkerType::Spaceborne] receivers. Markers of undefined type
+    /// are considered Earth bound.
+    pub fn requires_troposphere_model(&self) -> bool {
+        self.marker_type()
+            .map(|marker| marker.requires_troposphere_model())
+            .unwrap_or(true)
+    }
+
+    /// Copies and returns [Header] with observer and agency ("OBSERVER / AGENCY")
+    pub fn with_observer_agency(&self, observer: &str, agency: &str) -> Self {
+        let mut s = self.clone();
+        s.observer = Some(observer.to_string());
+        s.agency = Some(agency.to_string());
+        s
+    }
+
+    /// Copies and returns [Header] with specific sampling interval
+    pub fn with_sampling_interval(&self, interval: Duration) -> Self {
+        let mut s = self.clone();
+        s.sampling_interval = Some(interval);
+        s
+    }
+
+    /// Copies and returns [Header] with specific [Leap] seconds counter
+    pub fn with_leap(&self, leap: Leap) -> Self {
+        let mut s = self.clone();
+        s.leap = Some(leap);
+        s
+    }
+
+    /// Copies and returns [Header] with observables declared for this [Constellation]
+    /// ("SYS / # / OBS TYPES" or "# / TYPES OF OBSERV").
+    /// Observation fields are created if need be.
+    pub fn with_observables(&self, c: Constellation, observables: &[Observable]) -> Self {
+        let mut s = self.clone();
+        let obs = s.obs.get_or_insert_with(Default::default);
+        obs.codes.insert(c, observables.to_vec());
+        s
+    }
+
+    /// Copies and returns [Header] without these observables, for all constellations,
+    /// along with their scaling factors. Constellations that no longer have observables
+    /// are removed. This is typically followed by the same operation on the record.
+    /// ```
+    /// use rinex::prelude::{Constellation, Header, Observable};
+    /// use std::str::FromStr;
+    ///
+    /// let c1c = Observable::from_str("C1C").unwrap();
+    /// let l1c = Observable::from_str("L1C").unwrap();
+    ///
+    /// let header = Header::basic_obs()
+    ///     .with_observables(Constellation::GPS, &[c1c.clone(), l1c.clone()])
+    ///     .with_observables(Constellation::Galileo, &[c1c.clone()])
+    ///     .without_observables(&[c1c]);
+    ///
+    /// let codes = &header.obs.as_ref().unwrap().codes;
+    /// assert_eq!(codes.get(&Constellation::GPS), Some(&vec![l1c]));
+    /// assert!(codes.get(&Constellation::Galileo).is_none());
+    /// ```
+    pub fn without_observables(&self, observables: &[Observable]) -> Self {
+        let mut s = self.clone();
+        if let Some(obs) = &mut s.obs {
+            obs.codes.retain(|_, codes| {
+                codes.retain(|code| !observables.contains(code));
+                !codes.is_empty()
+            });
+            obs.scaling
+                .retain(|(_, code), _| !observables.contains(code));
+        }
+        s
+    }
+
+    /// Copies and returns [Header] with [Epoch] of first observation ("TIME OF FIRST OBS").
+    /// Observation fields are created if need be.
+    pub fn with_timeof_first_obs(&self, t: Epoch) -> Self {
+        let mut s = self.clone();
+        let obs = s.obs.get_or_insert_with(Default::default);
+        obs.timeof_first_obs = Some(t);
+        s
+    }
+
+    /// Copies and returns [Header] with [Epoch] of last observation ("TIME OF LAST OBS").
+    /// Observation fields are created if need be.
+    pub fn with_timeof_last_obs(&self, t: Epoch) -> Self {
+        let mut s = self.clone();
+        let obs = s.obs.get_or_insert_with(Default::default);
+        obs.timeof_last_obs = Some(t);
+        s
+    }
+
     /// Copies and returns [Header] modified to [Constellation]
     pub fn with_constellation(&self, c: Constellation) -> Self {
         let mut s = self.clone();
@@ -332,10 +801,22 @@ impl Header {
         s
     }
 
+    /// Returns all comments, in order of appearance.
+    /// Comments are emitted in this very order when formatting.
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Appends a new comment, emitted after all existing comments.
+    /// Comments wider than 60 characters are wrapped over several lines.
+    pub fn push_comment(&mut self, c: &str) {
+        self.comments.push(c.to_string());
+    }
+
     /// Copies and returns [Header] with a new comment
     pub fn with_comment(&self, c: &str) -> Self {
         let mut s = self.clone();
-        s.comments.push(c.to_string());
+        s.push_comment(c);
         s
     }
 
@@ -360,6 +841,72 @@ impl Header {
         s
     }
 
+    /// Copies and returns [Header] with Glonass FDMA channel definition for this [SV]
+    pub fn with_glonass_channel(&self, sv: SV, channel: i8) -> Self {
+        let mut s = self.clone();
+        s.glo_channels.insert(sv, channel);
+        s
+    }
+
+    /// Returns Glonass FDMA channel number of this [SV],
+    /// as described by "GLONASS SLOT / FRQ #"
+    pub fn glonass_channel(&self, sv: SV) -> Option<i8> {
+        self.glo_channels.get(&sv).copied()
+    }
+
+    /// Copies and returns [Header] with Glonass code-phase bias (in meters)
+    /// for this [Observable]
+    pub fn with_glonass_code_phase_bias(&self, observable: Observable, bias_m: f64) -> Self {
+        let mut s = self.clone();
+        s.glo_biases.insert(observable, bias_m);
+        s
+    }
+
+    /// Returns Glonass code-phase bias (in meters) of this [Observable],
+    /// as described by "GLONASS COD/PHS/BIS". This is the bias between code
+    /// and phase observations of this signal, that was not compensated
+    /// by the receiver, and is required for Glonass ambiguity resolution.
+    pub fn glonass_code_phase_bias(&self, observable: &Observable) -> Option<f64> {
+        self.glo_biases.get(observable).copied()
+    }
+
+    /// Returns the [IonosphereModel] described in this [Header] for this [Constellation],
+    /// either from "ION ALPHA" / "ION BETA" (RINEX2) or "IONOSPHERIC CORR" (RINEX3).
+    /// These models apply to the entire day course, see [crate::Rinex::ionosphere_models_iter]
+    /// for a time aware iterator that also includes RINEX4 models.
+    pub fn ionosphere_model(&self, constellation: Constellation) -> Option<&IonosphereModel> {
+        if constellation.is_sbas() {
+            self.ionod_corrections.get(&Constellation::SBAS)
+        } else {
+            self.ionod_corrections.get(&constellation)
+        }
+    }
+
+    /// Returns the Klobuchar [KbModel] described in this [Header] for this [Constellation].
+    /// See [Self::ionosphere_model].
+    pub fn klobuchar_model(&self, constellation: Constellation) -> Option<&KbModel> {
+        self.ionosphere_model(constellation)?.as_klobuchar()
+    }
+
+    /// Returns the Nequick-G [NgModel] described in this [Header] for this [Constellation]
+    /// (Galileo). See [Self::ionosphere_model].
+    pub fn nequick_g_model(&self, constellation: Constellation) -> Option<&NgModel> {
+        self.ionosphere_model(constellation)?.as_nequick_g()
+    }
+
+    /// Returns the exact [Carrier] this [SV] emits this [Observable] on.
+    /// Glonass FDMA carriers are resolved from "GLONASS SLOT / FRQ #",
+    /// when described in this [Header].
+    pub fn sv_carrier(&self, sv: SV, observable: &Observable) -> Result<Carrier, Error> {
+        let carrier = observable.to_carrier(sv.constellation)?;
+        match self.glonass_channel(sv) {
+            Some(channel) if sv.constellation == Constellation::Glonass => {
+                Ok(carrier.with_glonass_offset(channel))
+            },
+            _ => Ok(carrier),
+        }
+    }
+
     /// Converts approximate coordinates to an [Orbit]
     /// at given point in spacetime and using [Frame] model
     #[cfg(feature = "nav")]
@@ -393,9 +940,32 @@ impl Header {
 
 #[cfg(test)]
 mod test {
-    use crate::prelude::{Epoch, Header};
+    use super::{DcbCompensation, PcvCompensation};
+    use crate::prelude::{Constellation, Epoch, Header};
     use std::str::FromStr;
 
+    #[test]
+    fn applied_corrections() {
+        let content = format!("{:<60}", "G CC2NONCC          ftp://igs.org/pub/dcb");
+        let dcb = DcbCompensation::parse(&content).unwrap().unwrap();
+        assert_eq!(dcb.constellation, Constellation::GPS);
+        assert_eq!(dcb.program.as_deref(), Some("CC2NONCC"));
+        assert_eq!(dcb.url.as_deref(), Some("ftp://igs.org/pub/dcb"));
+        assert_eq!(&dcb.format()[..60], content);
+
+        // program only
+        let pcv = PcvCompensation::parse("E PCV_PGM").unwrap().unwrap();
+        assert_eq!(pcv.constellation, Constellation::Galileo);
+        assert_eq!(pcv.program.as_deref(), Some("PCV_PGM"));
+        assert!(pcv.url.is_none());
+
+        // blank: not applied
+        assert!(DcbCompensation::parse(&format!("{:<60}", "R"))
+            .unwrap()
+            .is_none());
+        assert!(PcvCompensation::parse("").unwrap().is_none());
+    }
+
     #[test]
     fn test_merge_comment() {
         let j2000 = Epoch::from_str("2000-01-01T00:00:00 UTC").unwrap();
@@ -439,8 +1009,314 @@ mod test {
         ] {
             assert_eq!(Header::format_pkg_version(pkg_version), formatted);
 
-            let generated = Header::merge_comment(pkg_version, j2000);
+            let generated = Header::processing_comment(pkg_version, "FILE MERGE", Some(j2000));
             assert_eq!(generated, comment,);
         }
     }
+
+    #[test]
+    fn test_glonass_carriers() {
+        use crate::prelude::{Carrier, Observable, SV};
+
+        let r01 = SV::from_str("R01").unwrap();
+        let r02 = SV::from_str("R02").unwrap();
+        let g01 = SV::from_str("G01").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let c2p = Observable::from_str("C2P").unwrap();
+
+        let header = Header::basic_obs().with_glonass_channel(r01, -4);
+
+        assert_eq!(header.glonass_channel(r01), Some(-4));
+        assert_eq!(header.glonass_channel(r02), None);
+
+        assert_eq!(header.sv_carrier(r01, &l1c).unwrap(), Carrier::G1(Some(-4)));
+        assert_eq!(header.sv_carrier(r01, &c2p).unwrap(), Carrier::G2(Some(-4)));
+        assert_eq!(header.sv_carrier(r02, &l1c).unwrap(), Carrier::G1(None));
+        assert_eq!(header.sv_carrier(g01, &l1c).unwrap(), Carrier::L1);
+    }
+
+    #[test]
+    fn test_processing_comment() {
+        let j2000 = Epoch::from_str("2000-01-01T00:00:00 UTC").unwrap();
+
+        for (pkg_version, operation, comment) in [
+            (
+                "1.0.0",
+                "DECIMATION",
+                "rs-rinex v1.0.0     DECIMATION          20000101 000000 UTC",
+            ),
+            (
+                "0.17.1-rc-1",
+                "MASK",
+                "rs-rinex v0.17.1rc1 MASK                20000101 000000 UTC",
+            ),
+        ] {
+            let generated = Header::processing_comment(pkg_version, operation, Some(j2000));
+            assert_eq!(generated, comment);
+        }
+
+        assert_eq!(
+            Header::processing_comment("1.0.0", "MASK", None),
+            "rs-rinex v1.0.0     MASK"
+        );
+    }
+
+    #[test]
+    fn test_header_builder() {
+        use crate::{
+            hardware::{Antenna, Receiver},
+            marker::GeodeticMarker,
+            prelude::{Constellation, Duration, Observable},
+        };
+        use std::io::BufWriter;
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+
+        let header = Header::basic_obs()
+            .with_general_information("RX2RNX", "Operator", "Agency")
+            .with_observer_agency("Observer", "Agency")
+            .with_receiver(
+                Receiver::default()
+                    .with_model("RX")
+                    .with_serial_number("1234"),
+            )
+            .with_receiver_antenna(Antenna::default().with_model("ANT").with_height(0.1))
+            .with_geodetic_marker(GeodeticMarker::default().with_name("SITE"))
+            .with_ground_position_ecef((4696989.6880, 723994.2090, 4239678.3040))
+            .with_sampling_interval(Duration::from_seconds(30.0))
+            .with_timeof_first_obs(t0)
+            .with_observables(Constellation::GPS, &[c1c.clone(), l1c.clone()]);
+
+        assert_eq!(
+            header.rx_position,
+            Some((4696989.6880, 723994.2090, 4239678.3040))
+        );
+        let (lat_ddeg, lon_ddeg, _) = header.ground_position().unwrap().to_geodetic();
+        assert!(lat_ddeg > 41.0 && lat_ddeg < 42.0);
+        assert!(lon_ddeg > 8.0 && lon_ddeg < 9.0);
+        assert_eq!(header.observer.as_deref(), Some("Observer"));
+
+        let obs = header.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, Some(t0));
+        assert_eq!(obs.codes.get(&Constellation::GPS), Some(&vec![c1c, l1c]));
+
+        let mut w = BufWriter::new(Vec::new());
+        header.format(&mut w).unwrap();
+        let formatted = String::from_utf8(w.into_inner().unwrap()).unwrap();
+
+        for field in [
+            "OBSERVER / AGENCY",
+            "REC # / TYPE / VERS",
+            "ANT # / TYPE",
+            "MARKER NAME",
+            "APPROX POSITION XYZ",
+            "INTERVAL",
+            "TIME OF FIRST OBS",
+            "SYS / # / OBS TYPES",
+            "END OF HEADER",
+        ] {
+            assert!(formatted.contains(field), "missing {}", field);
+        }
+    }
+
+    #[test]
+    fn test_header_comments() {
+        use std::io::{BufReader, BufWriter};
+
+        let content =
+            "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+first comment                                               COMMENT
+  2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+  indented comment                                          COMMENT
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+last comment                                                COMMENT
+                                                            END OF HEADER
+";
+
+        let mut reader = BufReader::new(content.as_bytes());
+        let mut header = Header::parse(&mut reader).unwrap();
+
+        assert_eq!(
+            header.comments(),
+            &["first comment", "  indented comment", "last comment"]
+        );
+
+        header.push_comment("pushed comment");
+
+        assert_eq!(
+            header.comments(),
+            &[
+                "first comment",
+                "  indented comment",
+                "last comment",
+                "pushed comment"
+            ]
+        );
+
+        let mut w = BufWriter::new(Vec::new());
+        header.format(&mut w).unwrap();
+        let formatted = String::from_utf8(w.into_inner().unwrap()).unwrap();
+
+        let comments = formatted
+            .lines()
+            .filter(|line| line.ends_with("COMMENT"))
+            .map(|line| line[..60].trim_end())
+            .collect::<Vec<_>>();
+
+        assert_eq!(comments, header.comments());
+
+        // comments are emitted prior data fields
+        let first_obs = formatted.find("TIME OF FIRST OBS").unwrap();
+        let last_comment = formatted.rfind("COMMENT").unwrap();
+        assert!(last_comment < first_obs);
+    }
+
+    #[test]
+    fn test_marker_type() {
+        use crate::marker::MarkerType;
+        use std::io::BufReader;
+
+        let content =
+            "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+LEO                                                         MARKER NAME
+SPACEBORNE                                                  MARKER TYPE
+                                                            END OF HEADER
+";
+
+        let mut reader = BufReader::new(content.as_bytes());
+        let header = Header::parse(&mut reader).unwrap();
+
+        assert_eq!(header.marker_type(), Some(MarkerType::Spaceborne));
+        assert!(!header.requires_troposphere_model());
+
+        assert!(Header::basic_obs().requires_troposphere_model());
+    }
+
+    #[test]
+    fn test_processing_log() {
+        let t = Epoch::from_str("2021-01-02T00:01:14 UTC").unwrap();
+
+        let header = Header::default()
+            .with_processing_log(Some(t), "decimation", "Modulo(2)")
+            .with_processing_log(None, "repair", "");
+
+        assert_eq!(header.comments.len(), 3);
+
+        let pgm = format!(
+            "rs-rinex v{}",
+            Header::format_pkg_version(env!("CARGO_PKG_VERSION"))
+        );
+
+        assert!(header.comments[0].starts_with(&pgm));
+        assert_eq!(&header.comments[0][20..40], "DECIMATION          ");
+        assert_eq!(&header.comments[0][40..], "20210102 000114 UTC");
+        assert_eq!(header.comments[1], "Modulo(2)");
+        assert!(header.comments[2].starts_with(&pgm));
+        assert_eq!(&header.comments[2][20..40], "REPAIR              ");
+        assert!(header.comments[2][40..].ends_with(" UTC"));
+
+        // operation is separated from the date
+        let header = Header::default().with_processing_log(Some(t), "a very long operation", "");
+        assert_eq!(
+            &header.comments[0][20..],
+            "A VERY LONG OPERATI 20210102 000114 UTC"
+        );
+
+        // parameters are wrapped at 60 columns
+        let parameters = ["parameter"; 12].join(" ");
+        let header = Header::default().with_processing_log(Some(t), "mask", &parameters);
+        assert_eq!(header.comments.len(), 3);
+        assert_eq!(header.comments[1], ["parameter"; 6].join(" "));
+        assert_eq!(header.comments[2], ["parameter"; 6].join(" "));
+        assert!(header.comments.iter().all(|c| c.len() <= 60));
+
+        let header = Header::default().with_processing_log(Some(t), "mask", &"x".repeat(70));
+        assert_eq!(header.comments[1], "x".repeat(60));
+        assert_eq!(header.comments[2], "x".repeat(10));
+    }
+
+    #[test]
+    fn production_date_parsing() {
+        let t = Epoch::from_str("2021-01-02T00:01:14 UTC").unwrap();
+        let t_min = Epoch::from_str("2021-01-02T00:01:00 UTC").unwrap();
+
+        for (date, expected) in [
+            ("20210102 000114 UTC", Some(t)),
+            ("20210102 000114 LCL", Some(t)),
+            ("02-JAN-21 00:01", Some(t_min)),
+            ("02-Jan-21 00:01:14", Some(t)),
+            ("", None),
+            ("yesterday", None),
+        ] {
+            assert_eq!(Header::parse_production_date(date), expected, "{}", date);
+        }
+
+        for major in [2, 3] {
+            let formatted = Header::format_production_date(major, t);
+            assert!(Header::parse_production_date(&formatted).is_some());
+        }
+    }
+
+    #[test]
+    fn header_customization() {
+        use crate::hardware::{Antenna, Receiver};
+        use crate::prelude::GeodeticMarker;
+
+        let header = Header::basic_obs()
+            .with_receiver(Receiver::default().with_model("SEPT POLARX5"))
+            .with_receiver_antenna(Antenna::default().with_model("LEIAR20"))
+            .with_geodetic_marker(GeodeticMarker::default().with_name("ESBC"))
+            .with_program("teqc")
+            .with_operator("operator")
+            .with_observer("observer")
+            .with_agency("agency");
+
+        assert_eq!(header.rcvr.as_ref().unwrap().model, "SEPT POLARX5");
+        assert_eq!(header.rcvr_antenna.as_ref().unwrap().model, "LEIAR20");
+        assert_eq!(header.program.as_deref(), Some("teqc"));
+        assert_eq!(header.run_by.as_deref(), Some("operator"));
+        assert_eq!(header.observer.as_deref(), Some("observer"));
+        assert_eq!(header.agency.as_deref(), Some("agency"));
+
+        // anonymization
+        let anonymized = header
+            .without_hardware()
+            .with_marker_name("XXXX")
+            .with_observer("")
+            .with_agency("");
+
+        assert!(anonymized.rcvr.is_none());
+        assert!(anonymized.rcvr_antenna.is_none());
+        assert_eq!(anonymized.geodetic_marker.as_ref().unwrap().name, "XXXX");
+        assert_eq!(anonymized.program, header.program);
+
+        // original is not modified
+        assert_eq!(header.geodetic_marker.as_ref().unwrap().name, "ESBC");
+        assert!(Header::default()
+            .with_marker_name("ESBC")
+            .geodetic_marker
+            .is_some());
+    }
+
+    #[test]
+    fn production_stamp() {
+        let t = Epoch::from_str("2021-01-02T00:01:14 UTC").unwrap();
+
+        assert_eq!(Header::format_production_date(3, t), "20210102 000114 UTC");
+        assert_eq!(Header::format_production_date(2, t), "02-JAN-21 00:01");
+
+        let header = Header::basic_obs()
+            .with_version(crate::prelude::Version::new(3, 5))
+            .with_operator("IGS");
+
+        let stamped = header.stamped(None, t);
+        assert!(stamped.program.as_ref().unwrap().starts_with("rs-rinex v"));
+        assert_eq!(stamped.run_by.as_deref(), Some("IGS"));
+        assert_eq!(stamped.date.as_deref(), Some("20210102 000114 UTC"));
+
+        let stamped = header.stamped(Some("ESA"), t);
+        assert_eq!(stamped.run_by.as_deref(), Some("ESA"));
+    }
 }
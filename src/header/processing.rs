@@ -16,6 +16,7 @@ fn header_mask_eq(hd: &mut Header, item: &FilterItem) {
             if !constellations.contains(&Constellation::Glonass) {
                 // remove glonass specs in case it is no longer contained
                 hd.glo_channels.clear();
+                hd.glo_biases.clear();
             }
         },
         _ => {},
@@ -0,0 +1,347 @@
+//! Header completeness validation
+use crate::prelude::{Constellation, Header, RinexType, Version};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [HeaderIssue] is one problem reported by [Header::validate]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum HeaderIssue {
+    /// This mandatory label is missing
+    Missing(&'static str),
+    /// This field is present but cannot be formatted as is
+    Malformed {
+        /// Header label
+        label: &'static str,
+        /// Readable description
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for HeaderIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Missing(label) => write!(f, "missing \"{}\"", label),
+            Self::Malformed { label, reason } => write!(f, "malformed \"{}\": {}", label, reason),
+        }
+    }
+}
+
+/// Reports a [HeaderIssue::Malformed] when `value` exceeds its column width
+fn check_width(
+    issues: &mut Vec<HeaderIssue>,
+    label: &'static str,
+    name: &str,
+    value: &str,
+    width: usize,
+) {
+    if value.len() > width {
+        issues.push(HeaderIssue::Malformed {
+            label,
+            reason: format!("{} exceeds {} characters", name, width),
+        });
+    }
+}
+
+impl Header {
+    /// Verifies this [Header] contains all mandatory labels of this RINEX [Version]
+    /// and [RinexType], and that all fields can be formatted without loss.
+    /// Returns the list of [HeaderIssue]s, which is empty when this [Header] is complete.
+    /// This allows producers to verify a file is acceptable (IGS) before writing it.
+    /// ```
+    /// use rinex::header::HeaderIssue;
+    /// use rinex::prelude::{GeodeticMarker, Header, RinexType, Version};
+    ///
+    /// let header = Header::basic_obs()
+    ///     .with_geodetic_marker(GeodeticMarker::default().with_name("ESBC00DNK"));
+    ///
+    /// let issues = header.validate(Version::new(3, 5), RinexType::ObservationData);
+    /// assert!(issues.contains(&HeaderIssue::Missing("APPROX POSITION XYZ")));
+    /// assert!(!issues.contains(&HeaderIssue::Missing("MARKER NAME")));
+    /// ```
+    pub fn validate(&self, version: Version, rinex_type: RinexType) -> Vec<HeaderIssue> {
+        let mut issues = Vec::new();
+
+        if version.major == 0 || version.major > 4 {
+            issues.push(HeaderIssue::Malformed {
+                label: "RINEX VERSION / TYPE",
+                reason: format!("non supported revision {}", version),
+            });
+        }
+
+        if self.rinex_type != rinex_type {
+            issues.push(HeaderIssue::Malformed {
+                label: "RINEX VERSION / TYPE",
+                reason: format!("expecting {}, got {}", rinex_type, self.rinex_type),
+            });
+        }
+
+        if self.program.is_none() && self.run_by.is_none() && self.date.is_none() {
+            issues.push(HeaderIssue::Missing("PGM / RUN BY / DATE"));
+        }
+
+        for (name, value, width) in [
+            ("program", &self.program, 20),
+            ("run by", &self.run_by, 20),
+            ("date", &self.date, 20),
+        ] {
+            if let Some(value) = value {
+                check_width(&mut issues, "PGM / RUN BY / DATE", name, value, width);
+            }
+        }
+
+        match rinex_type {
+            RinexType::ObservationData => self.validate_observation(version, &mut issues),
+            RinexType::MeteoData => self.validate_meteo(&mut issues),
+            RinexType::NavigationData if version.major < 3 && self.constellation.is_none() => {
+                issues.push(HeaderIssue::Missing("RINEX VERSION / TYPE"));
+            },
+            RinexType::ClockData
+                if self
+                    .clock
+                    .as_ref()
+                    .map(|clk| clk.codes.is_empty())
+                    .unwrap_or(true) =>
+            {
+                issues.push(HeaderIssue::Missing("# / TYPES OF DATA"));
+            },
+            _ => {},
+        }
+
+        issues
+    }
+
+    fn validate_marker(&self, issues: &mut Vec<HeaderIssue>) {
+        match &self.geodetic_marker {
+            Some(marker) => {
+                if marker.name.trim().is_empty() {
+                    issues.push(HeaderIssue::Missing("MARKER NAME"));
+                }
+                check_width(issues, "MARKER NAME", "name", &marker.name, 60);
+            },
+            None => issues.push(HeaderIssue::Missing("MARKER NAME")),
+        }
+    }
+
+    fn validate_observation(&self, version: Version, issues: &mut Vec<HeaderIssue>) {
+        self.validate_marker(issues);
+
+        if self.observer.is_none() && self.agency.is_none() {
+            issues.push(HeaderIssue::Missing("OBSERVER / AGENCY"));
+        }
+        if let Some(observer) = &self.observer {
+            check_width(issues, "OBSERVER / AGENCY", "observer", observer, 20);
+        }
+        if let Some(agency) = &self.agency {
+            check_width(issues, "OBSERVER / AGENCY", "agency", agency, 40);
+        }
+
+        match &self.rcvr {
+            Some(rcvr) => {
+                check_width(issues, "REC # / TYPE / VERS", "serial number", &rcvr.sn, 20);
+                check_width(issues, "REC # / TYPE / VERS", "model", &rcvr.model, 20);
+                check_width(
+                    issues,
+                    "REC # / TYPE / VERS",
+                    "firmware",
+                    &rcvr.firmware,
+                    20,
+                );
+            },
+            None => issues.push(HeaderIssue::Missing("REC # / TYPE / VERS")),
+        }
+
+        match &self.rcvr_antenna {
+            Some(antenna) => {
+                check_width(issues, "ANT # / TYPE", "serial number", &antenna.sn, 20);
+                check_width(issues, "ANT # / TYPE", "model", &antenna.model, 20);
+                if antenna.delta_hen.is_none() {
+                    issues.push(HeaderIssue::Missing("ANTENNA: DELTA H/E/N"));
+                }
+            },
+            None => {
+                issues.push(HeaderIssue::Missing("ANT # / TYPE"));
+                issues.push(HeaderIssue::Missing("ANTENNA: DELTA H/E/N"));
+            },
+        }
+
+        // optional for moving platforms in RINEX4
+        if self.rx_position.is_none() && version.major < 4 {
+            issues.push(HeaderIssue::Missing("APPROX POSITION XYZ"));
+        }
+
+        if version.major < 3 && self.wavelengths.is_none() {
+            issues.push(HeaderIssue::Missing("WAVELENGTH FACT L1/2"));
+        }
+
+        let obs = match &self.obs {
+            Some(obs) => obs,
+            None => {
+                issues.push(if version.major < 3 {
+                    HeaderIssue::Missing("# / TYPES OF OBSERV")
+                } else {
+                    HeaderIssue::Missing("SYS / # / OBS TYPES")
+                });
+                issues.push(HeaderIssue::Missing("TIME OF FIRST OBS"));
+                return;
+            },
+        };
+
+        if obs.codes.values().all(|codes| codes.is_empty()) {
+            issues.push(if version.major < 3 {
+                HeaderIssue::Missing("# / TYPES OF OBSERV")
+            } else {
+                HeaderIssue::Missing("SYS / # / OBS TYPES")
+            });
+        }
+
+        match (obs.timeof_first_obs, obs.timeof_last_obs) {
+            (None, _) => issues.push(HeaderIssue::Missing("TIME OF FIRST OBS")),
+            (Some(first), Some(last)) if last < first => {
+                issues.push(HeaderIssue::Malformed {
+                    label: "TIME OF LAST OBS",
+                    reason: "prior TIME OF FIRST OBS".to_string(),
+                });
+            },
+            _ => {},
+        }
+
+        if let Some(dt) = self.sampling_interval {
+            if dt.to_seconds() <= 0.0 {
+                issues.push(HeaderIssue::Malformed {
+                    label: "INTERVAL",
+                    reason: "null or negative interval".to_string(),
+                });
+            }
+        }
+
+        // V3.01+ phase alignment description
+        if version >= Version::new(3, 1) && obs.phase_shifts.is_empty() && version.major < 4 {
+            issues.push(HeaderIssue::Missing("SYS / PHASE SHIFT"));
+        }
+
+        // V3.02+ Glonass specific fields
+        let has_glonass = obs.codes.contains_key(&Constellation::Glonass)
+            || self.constellation == Some(Constellation::Glonass);
+
+        if has_glonass && version >= Version::new(3, 2) {
+            if self.glo_channels.is_empty() {
+                issues.push(HeaderIssue::Missing("GLONASS SLOT / FRQ #"));
+            }
+            if self.glo_biases.is_empty() {
+                issues.push(HeaderIssue::Missing("GLONASS COD/PHS/BIS"));
+            }
+        }
+    }
+
+    fn validate_meteo(&self, issues: &mut Vec<HeaderIssue>) {
+        self.validate_marker(issues);
+
+        let meteo = match &self.meteo {
+            Some(meteo) => meteo,
+            None => {
+                issues.push(HeaderIssue::Missing("# / TYPES OF OBSERV"));
+                issues.push(HeaderIssue::Missing("SENSOR MOD/TYPE/ACC"));
+                issues.push(HeaderIssue::Missing("SENSOR POS XYZ/H"));
+                return;
+            },
+        };
+
+        if meteo.codes.is_empty() {
+            issues.push(HeaderIssue::Missing("# / TYPES OF OBSERV"));
+        }
+
+        // each observable must be described by a sensor
+        if meteo
+            .codes
+            .iter()
+            .any(|code| !meteo.sensors.iter().any(|s| s.observable == *code))
+        {
+            issues.push(HeaderIssue::Missing("SENSOR MOD/TYPE/ACC"));
+        }
+
+        if !meteo.sensors.iter().any(|s| s.position.is_some()) {
+            issues.push(HeaderIssue::Missing("SENSOR POS XYZ/H"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HeaderIssue;
+    use crate::{
+        hardware::{Antenna, DeltaHEN, Receiver},
+        prelude::{Epoch, GeodeticMarker, Header, Observable, RinexType, Version},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn obs_header_validation() {
+        let header = Header::basic_obs();
+        let issues = header.validate(Version::new(2, 11), RinexType::ObservationData);
+
+        for label in [
+            "MARKER NAME",
+            "OBSERVER / AGENCY",
+            "REC # / TYPE / VERS",
+            "ANT # / TYPE",
+            "ANTENNA: DELTA H/E/N",
+            "APPROX POSITION XYZ",
+            "WAVELENGTH FACT L1/2",
+            "# / TYPES OF OBSERV",
+            "TIME OF FIRST OBS",
+        ] {
+            assert!(
+                issues.contains(&HeaderIssue::Missing(label)),
+                "\"{}\" should be missing",
+                label
+            );
+        }
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let mut header = header
+            .with_geodetic_marker(GeodeticMarker::default().with_name("ESBC00DNK"))
+            .with_receiver(Receiver::default().with_model("SEPT POLARX5"))
+            .with_receiver_antenna(
+                Antenna::default()
+                    .with_model("LEIAR20")
+                    .with_delta_hen(DeltaHEN::new(0.1, 0.0, 0.0)),
+            )
+            .with_observer_agency("observer", "agency")
+            .with_ground_position_ecef((3582105.291, 532589.7313, 5232754.8054));
+
+        let obs = header.obs.as_mut().unwrap();
+        obs.timeof_first_obs = Some(t0);
+        obs.codes.insert(
+            crate::prelude::Constellation::GPS,
+            vec![Observable::from_str("C1C").unwrap()],
+        );
+
+        // V4: complete
+        assert_eq!(
+            header.validate(Version::new(4, 0), RinexType::ObservationData),
+            vec![]
+        );
+
+        // V3.05: phase shifts are mandatory
+        assert_eq!(
+            header.validate(Version::new(3, 5), RinexType::ObservationData),
+            vec![HeaderIssue::Missing("SYS / PHASE SHIFT")]
+        );
+
+        // wrong type & field too wide
+        let header = header.with_observer_agency("a very long observer name", "agency");
+        let issues = header.validate(Version::new(4, 0), RinexType::NavigationData);
+        assert_eq!(issues.len(), 1);
+
+        let issues = header.validate(Version::new(4, 0), RinexType::ObservationData);
+        assert_eq!(
+            issues,
+            vec![HeaderIssue::Malformed {
+                label: "OBSERVER / AGENCY",
+                reason: "observer exceeds 20 characters".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Helmert transformations between terrestrial reference frames
+use crate::prelude::{Epoch, GroundPosition, Rinex};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Milliarcseconds to radians
+const MAS_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0 * 1000.0);
+
+/// Julian year, in days
+const JULIAN_YEAR_DAYS: f64 = 365.25;
+
+/// [RotationConvention] defines the sign of the rotation parameters
+/// of a [HelmertTransform]. Both conventions are used by geodetic agencies,
+/// rotation parameters being opposite from one another.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RotationConvention {
+    /// Position vector rotation (IERS, EUREF)
+    #[default]
+    PositionVector,
+    /// Coordinate frame rotation (NGS)
+    CoordinateFrame,
+}
+
+/// [HelmertTransform] describes a 7 parameter (similarity) transformation
+/// between two terrestrial reference frames, or a 14 parameter (time dependent)
+/// transformation when rates are specified. Parameters are evaluated at the
+/// epoch of the coordinates, which is mandatory between a global frame (ITRF) and
+/// a regional frame attached to a tectonic plate (ETRS89, NAD83).
+/// ```
+/// use rinex::prelude::{Epoch, GroundPosition, HelmertTransform};
+/// use std::str::FromStr;
+///
+/// // ESBC00DNK (ITRF2014)
+/// let itrf = GroundPosition::from_ecef_wgs84((3582105.291, 532589.7313, 5232754.8054));
+/// let t = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+///
+/// let etrf = itrf.transform(&HelmertTransform::itrf2014_to_etrf2014(), t);
+///
+/// // the eurasian plate drifted by about 2.5 cm/year since 1989
+/// let (x, y, z) = etrf.to_ecef_wgs84();
+/// let (dx, dy, dz) = (x - 3582105.291, y - 532589.7313, z - 5232754.8054);
+/// let drift_m = (dx.powi(2) + dy.powi(2) + dz.powi(2)).sqrt();
+/// assert!(drift_m > 0.6 && drift_m < 0.9);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HelmertTransform {
+    /// Translation (x, y, z), in meters
+    pub translation_m: (f64, f64, f64),
+    /// Scale factor, in parts per billion
+    pub scale_ppb: f64,
+    /// Rotation (x, y, z), in milliarcseconds
+    pub rotation_mas: (f64, f64, f64),
+    /// Translation rates (x, y, z), in meters per year
+    pub translation_rate_m_yr: (f64, f64, f64),
+    /// Scale factor rate, in parts per billion per year
+    pub scale_rate_ppb_yr: f64,
+    /// Rotation rates (x, y, z), in milliarcseconds per year
+    pub rotation_rate_mas_yr: (f64, f64, f64),
+    /// Reference epoch of the parameters, in decimal years.
+    /// Only significant when rates are specified.
+    pub reference_epoch_yr: f64,
+    /// [RotationConvention] of the rotation parameters
+    pub convention: RotationConvention,
+}
+
+impl HelmertTransform {
+    /// Builds a 7 parameter [HelmertTransform]
+    pub fn new(
+        translation_m: (f64, f64, f64),
+        scale_ppb: f64,
+        rotation_mas: (f64, f64, f64),
+    ) -> Self {
+        Self {
+            translation_m,
+            scale_ppb,
+            rotation_mas,
+            ..Default::default()
+        }
+    }
+
+    /// Copies and returns [HelmertTransform] with these rates (14 parameter
+    /// transformation), defined at this reference epoch, in decimal years.
+    pub fn with_rates(
+        &self,
+        translation_rate_m_yr: (f64, f64, f64),
+        scale_rate_ppb_yr: f64,
+        rotation_rate_mas_yr: (f64, f64, f64),
+        reference_epoch_yr: f64,
+    ) -> Self {
+        let mut s = *self;
+        s.translation_rate_m_yr = translation_rate_m_yr;
+        s.scale_rate_ppb_yr = scale_rate_ppb_yr;
+        s.rotation_rate_mas_yr = rotation_rate_mas_yr;
+        s.reference_epoch_yr = reference_epoch_yr;
+        s
+    }
+
+    /// Copies and returns [HelmertTransform] with desired [RotationConvention]
+    pub fn with_convention(&self, convention: RotationConvention) -> Self {
+        let mut s = *self;
+        s.convention = convention;
+        s
+    }
+
+    /// ITRF2014 to ETRF2014 (ETRS89 realization), as published by EUREF.
+    pub fn itrf2014_to_etrf2014() -> Self {
+        Self::default().with_rates((0.0, 0.0, 0.0), 0.0, (0.085, 0.531, -0.770), 1989.0)
+    }
+
+    /// ITRF2008 to NAD83(2011), as published by the NGS.
+    pub fn itrf2008_to_nad83_2011() -> Self {
+        Self::new(
+            (0.99343, -1.90331, -0.52655),
+            1.71504,
+            (25.91467, 9.42645, 11.59935),
+        )
+        .with_rates(
+            (0.00079, -0.00060, -0.00134),
+            -0.10201,
+            (0.06667, -0.75744, -0.05133),
+            1997.0,
+        )
+        .with_convention(RotationConvention::CoordinateFrame)
+    }
+
+    /// Returns the inverse [HelmertTransform] (to first order)
+    pub fn inverse(&self) -> Self {
+        let neg = |(x, y, z): (f64, f64, f64)| (-x, -y, -z);
+        Self {
+            translation_m: neg(self.translation_m),
+            scale_ppb: -self.scale_ppb,
+            rotation_mas: neg(self.rotation_mas),
+            translation_rate_m_yr: neg(self.translation_rate_m_yr),
+            scale_rate_ppb_yr: -self.scale_rate_ppb_yr,
+            rotation_rate_mas_yr: neg(self.rotation_rate_mas_yr),
+            reference_epoch_yr: self.reference_epoch_yr,
+            convention: self.convention,
+        }
+    }
+
+    /// Returns the (translation (m), scale factor (ppb), rotation (mas)) parameters
+    /// of this [HelmertTransform], evaluated at this [Epoch].
+    pub fn parameters(&self, t: Epoch) -> ((f64, f64, f64), f64, (f64, f64, f64)) {
+        let dt_yr = (t.to_jde_tt_days() - 2_451_545.0) / JULIAN_YEAR_DAYS + 2000.0
+            - self.reference_epoch_yr;
+
+        let at = |(x, y, z): (f64, f64, f64), (dx, dy, dz): (f64, f64, f64)| {
+            (x + dx * dt_yr, y + dy * dt_yr, z + dz * dt_yr)
+        };
+
+        (
+            at(self.translation_m, self.translation_rate_m_yr),
+            self.scale_ppb + self.scale_rate_ppb_yr * dt_yr,
+            at(self.rotation_mas, self.rotation_rate_mas_yr),
+        )
+    }
+
+    /// Applies this [HelmertTransform] to ECEF coordinates (in meters), at this [Epoch].
+    pub fn apply(&self, ecef_m: (f64, f64, f64), t: Epoch) -> (f64, f64, f64) {
+        let ((tx, ty, tz), scale_ppb, (rx, ry, rz)) = self.parameters(t);
+        let d = scale_ppb * 1.0E-9;
+
+        let sign = match self.convention {
+            RotationConvention::PositionVector => 1.0,
+            RotationConvention::CoordinateFrame => -1.0,
+        };
+
+        let (rx, ry, rz) = (
+            sign * rx * MAS_TO_RAD,
+            sign * ry * MAS_TO_RAD,
+            sign * rz * MAS_TO_RAD,
+        );
+
+        let (x, y, z) = ecef_m;
+
+        (
+            x + tx + d * x - rz * y + ry * z,
+            y + ty + rz * x + d * y - rx * z,
+            z + tz - ry * x + rx * y + d * z,
+        )
+    }
+}
+
+impl GroundPosition {
+    /// Transforms this [GroundPosition] into another reference frame,
+    /// by applying this [HelmertTransform] at this [Epoch].
+    pub fn transform(&self, transform: &HelmertTransform, t: Epoch) -> Self {
+        Self::from_ecef_wgs84(transform.apply(self.to_ecef_wgs84(), t))
+    }
+}
+
+impl Rinex {
+    /// Transforms the "APPROX POSITION XYZ" of this [Rinex] into another reference frame,
+    /// by applying this [HelmertTransform], whose parameters are evaluated at this [Epoch]
+    /// (usually the first [Epoch] of the record). The processing log keeps track of this
+    /// transformation. Returns false when this [Rinex] does not describe a ground position.
+    pub fn transform_ground_position_mut(
+        &mut self,
+        transform: &HelmertTransform,
+        t: Epoch,
+    ) -> bool {
+        let position = match self.header.ground_position() {
+            Some(position) => position.transform(transform, t),
+            None => return false,
+        };
+
+        self.header.rx_position = Some(position.to_ecef_wgs84());
+
+        let ((tx, ty, tz), scale_ppb, (rx, ry, rz)) = transform.parameters(t);
+
+        let convention = match transform.convention {
+            RotationConvention::PositionVector => "position-vector",
+            RotationConvention::CoordinateFrame => "coordinate-frame",
+        };
+
+        self.header.push_processing_log(
+            None,
+            "HELMERT",
+            &format!(
+                "epoch={} translation_m={:.5},{:.5},{:.5} scale_ppb={:.5} rotation_mas={:.5},{:.5},{:.5} convention={}",
+                t, tx, ty, tz, scale_ppb, rx, ry, rz, convention
+            ),
+        );
+
+        true
+    }
+
+    /// Copies and returns [Rinex] with "APPROX POSITION XYZ" transformed
+    /// into another reference frame. See [Self::transform_ground_position_mut].
+    pub fn transform_ground_position(&self, transform: &HelmertTransform, t: Epoch) -> Self {
+        let mut s = self.clone();
+        s.transform_ground_position_mut(transform, t);
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HelmertTransform, RotationConvention};
+    use crate::prelude::{Epoch, Rinex};
+    use std::str::FromStr;
+
+    #[test]
+    fn helmert_transform() {
+        let t = Epoch::from_str("2020-01-01T12:00:00 GPST").unwrap();
+        let pos = (4_000_000.0, 1_000_000.0, 4_800_000.0);
+
+        // identity
+        assert_eq!(HelmertTransform::default().apply(pos, t), pos);
+
+        // pure translation & scale
+        let transform = HelmertTransform::new((1.0, -2.0, 3.0), 1000.0, (0.0, 0.0, 0.0));
+        let (x, y, z) = transform.apply(pos, t);
+        assert!((x - 4_000_005.0).abs() < 1.0E-6);
+        assert!((y - 999_999.0).abs() < 1.0E-6);
+        assert!((z - 4_800_007.8).abs() < 1.0E-6);
+
+        // rotation about z axis: both conventions are opposite
+        let transform = HelmertTransform::new((0.0, 0.0, 0.0), 0.0, (0.0, 0.0, 1000.0));
+        let (x, y, _) = transform.apply((6_378_137.0, 0.0, 0.0), t);
+        assert!(x > 6_378_136.9);
+        assert!((y - 30.922).abs() < 1.0E-3, "bad rotation {}", y);
+
+        let (_, y, _) = transform
+            .with_convention(RotationConvention::CoordinateFrame)
+            .apply((6_378_137.0, 0.0, 0.0), t);
+        assert!((y + 30.922).abs() < 1.0E-3, "bad rotation {}", y);
+
+        // time dependency: parameters are null at reference epoch
+        let transform =
+            HelmertTransform::default().with_rates((0.01, 0.0, 0.0), 0.0, (0.0, 0.0, 0.0), 2010.0);
+
+        let t_ref = Epoch::from_str("2010-01-01T00:00:00 TT").unwrap();
+        let (x, _, _) = transform.apply(pos, t_ref);
+        assert!((x - pos.0).abs() < 1.0E-4);
+
+        let (x, _, _) = transform.apply(pos, t);
+        assert!((x - pos.0 - 0.1).abs() < 1.0E-4);
+
+        // round trip
+        let transform = HelmertTransform::itrf2008_to_nad83_2011();
+        let nad83 = transform.apply(pos, t);
+        assert!((nad83.0 - pos.0).abs() > 0.5);
+
+        let (x, y, z) = transform.inverse().apply(nad83, t);
+        assert!((x - pos.0).abs() < 1.0E-3);
+        assert!((y - pos.1).abs() < 1.0E-3);
+        assert!((z - pos.2).abs() < 1.0E-3);
+    }
+
+    #[test]
+    fn helmert_ground_position() {
+        let t = Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap();
+        let transform = HelmertTransform::itrf2014_to_etrf2014();
+
+        let mut rinex = Rinex::basic_obs();
+        assert!(!rinex.transform_ground_position_mut(&transform, t));
+        assert!(rinex.header.comments.is_empty());
+
+        let itrf = (3582105.291, 532589.7313, 5232754.8054);
+        rinex.header = rinex.header.with_ground_position_ecef(itrf);
+
+        let etrf = rinex.transform_ground_position(&transform, t);
+        let (x, _, _) = etrf.header.rx_position.unwrap();
+        assert!((x - itrf.0).abs() > 0.1);
+
+        assert!(etrf.header.comments[0][20..].starts_with("HELMERT"));
+        assert!(etrf.header.comments[1].starts_with("epoch=2020-06-25T00:00:00 GPST"));
+        assert!(etrf
+            .header
+            .comments
+            .iter()
+            .all(|comment| comment.chars().count() <= 60));
+    }
+}
@@ -1,6 +1,7 @@
 //! Leap second counter
 use crate::{
-    prelude::{ParsingError, TimeScale},
+    fmt_rinex,
+    prelude::{Duration, Epoch, ParsingError, TimeScale},
     FormattingError,
 };
 
@@ -29,26 +30,24 @@ pub struct Leap {
 impl Leap {
     // Format [Leap] into [BufWriter]
     pub fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        if let Some(delta) = &self.delta_tls {
-            write!(
-                w,
-                "{:6}{:6}{:6}{:6} ",
+        // 4I6,A3
+        let mut content = if let Some(delta) = &self.delta_tls {
+            format!(
+                "{:6}{:6}{:6}{:6}",
                 self.leap,
                 delta,
                 self.week.unwrap_or(0),
                 self.day.unwrap_or(0),
-            )?;
+            )
         } else {
-            write!(w, "{:6}                   ", self.leap,)?;
-        }
+            format!("{:6}", self.leap)
+        };
 
         if let Some(ts) = self.timescale {
-            write!(w, "{:x}", ts)?;
-        } else {
-            write!(w, "   ")?;
+            content = format!("{:<24}{:x}", content, ts);
         }
 
-        writeln!(w, "                                LEAP SECONDS")?;
+        writeln!(w, "{}", fmt_rinex(&content, "LEAP SECONDS"))?;
 
         Ok(())
     }
@@ -81,66 +80,105 @@ impl Leap {
 
 impl std::str::FromStr for Leap {
     type Err = ParsingError;
+    /// Parses [Leap] from "LEAP SECONDS" content (4I6,A3).
+    /// Only the current number of leap seconds is mandatory.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // two formats exist
-        let mut ls = Leap::default();
-        let items = s.split_ascii_whitespace().collect::<Vec<_>>();
-        let count = items.len();
-
-        if count < 3 {
-            // Simple / basic format
-            ls.leap = items[0].parse::<u32>().or(Err(ParsingError::LeapParsing))?;
-        } else {
-            // Complex / complete format
-            let (leap, rem) = s.split_at(5);
-            let (tls, rem) = rem.split_at(5);
-            let (week, rem) = rem.split_at(5);
-            let (day, rem) = rem.split_at(5);
-            let system = rem.trim();
-
-            ls.leap = leap
-                .trim()
-                .parse::<u32>()
-                .or(Err(ParsingError::LeapParsing))?;
-
-            let tls = tls
-                .trim()
-                .parse::<u32>()
-                .or(Err(ParsingError::LeapParsing))?;
-
-            ls.delta_tls = Some(tls);
-
-            let week = week
-                .trim()
-                .parse::<u32>()
-                .or(Err(ParsingError::LeapParsing))?;
-
-            ls.week = Some(week);
-
-            let day = day
-                .trim()
-                .parse::<u32>()
-                .or(Err(ParsingError::LeapParsing))?;
-
-            ls.day = Some(day);
-
-            if system.eq("") {
-                ls.timescale = None;
-            } else {
-                let ts = TimeScale::from_str(system)?;
-                ls.timescale = Some(ts);
-            }
+        let s = s.trim_end();
+
+        // A3 timescale follows the numeric fields
+        let (counters, system) = match s.find(|c: char| c.is_ascii_alphabetic()) {
+            Some(offset) => s.split_at(offset),
+            None => (s, ""),
+        };
+
+        let mut items = counters
+            .split_ascii_whitespace()
+            .map(|item| item.parse::<u32>().or(Err(ParsingError::LeapParsing)));
+
+        let mut ls = Leap {
+            leap: items.next().ok_or(ParsingError::LeapParsing)??,
+            ..Default::default()
+        };
+
+        ls.delta_tls = items.next().transpose()?;
+        ls.week = items.next().transpose()?;
+        ls.day = items.next().transpose()?;
+
+        let system = system.trim();
+        if !system.is_empty() {
+            ls.timescale = Some(TimeScale::from_str(system)?);
         }
 
         Ok(ls)
     }
 }
 
+impl Leap {
+    /// Returns the [Epoch] of the leap second event described by the week
+    /// and day counters, in the [TimeScale] of this [Leap] (GPST when not specified).
+    /// Days are counted from 1 in GPS (and GPS like) weeks, and from 0 in BeiDou weeks.
+    pub fn event_epoch(&self) -> Option<Epoch> {
+        let (week, day) = (self.week?, self.day?);
+        let timescale = self.timescale.unwrap_or(TimeScale::GPST);
+
+        let day = if timescale == TimeScale::BDT {
+            day
+        } else {
+            day.checked_sub(1)?
+        };
+
+        Some(Epoch::from_time_of_week(
+            week,
+            day as u64 * 86_400 * 1_000_000_000,
+            timescale,
+        ))
+    }
+
+    /// Returns the number of leap seconds that applies at this [Epoch]:
+    /// ΔtLS after the leap second event (when described), the current number otherwise.
+    pub fn leap_seconds_at(&self, t: Epoch) -> u32 {
+        match (self.delta_tls, self.event_epoch()) {
+            (Some(delta_tls), Some(event)) if t >= event => delta_tls,
+            _ => self.leap,
+        }
+    }
+
+    /// Converts this [Epoch] to UTC, using the leap seconds described by this [Leap]
+    /// rather than the leap second table of the library, which may be outdated.
+    /// The number of leap seconds is the difference between the [TimeScale] of this [Leap]
+    /// (GPST when not specified) and UTC.
+    /// ```
+    /// use rinex::prelude::{Epoch, Leap, TimeScale};
+    /// use std::str::FromStr;
+    ///
+    /// let leap = Leap::from_str("    18    18  2185     7GPS").unwrap();
+    /// let t = Epoch::from_str("2022-01-01T00:00:18 GPST").unwrap();
+    ///
+    /// let utc = leap.to_utc(t);
+    /// assert_eq!(utc.time_scale, TimeScale::UTC);
+    /// assert_eq!(utc, Epoch::from_str("2022-01-01T00:00:00 UTC").unwrap());
+    /// ```
+    pub fn to_utc(&self, t: Epoch) -> Epoch {
+        let timescale = self.timescale.unwrap_or(TimeScale::GPST);
+        if timescale == TimeScale::UTC {
+            return t.to_time_scale(TimeScale::UTC);
+        }
+
+        let t = t.to_time_scale(timescale);
+        let leap = self.leap_seconds_at(t);
+
+        // express the UTC calendar description in this timescale
+        let shifted = t - Duration::from_seconds(leap as f64);
+        let (y, m, d, hh, mm, ss, ns) = shifted.to_gregorian(timescale);
+        Epoch::from_gregorian_utc(y, m, d, hh, mm, ss, ns)
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use crate::{
-        prelude::{Leap, TimeScale},
+        prelude::{Epoch, Leap, TimeScale},
         tests::formatting::Utf8Buffer,
     };
 
@@ -211,4 +249,47 @@ mod test {
             "    18    18  2185     7                                    LEAP SECONDS\n"
         );
     }
+
+    #[test]
+    fn leap_second_header_content() {
+        // untrimmed header content
+        let leap = Leap::from_str("    18    18  2185     7BDS                 ").unwrap();
+        assert_eq!(leap.leap, 18);
+        assert_eq!(leap.delta_tls, Some(18));
+        assert_eq!(leap.timescale, Some(TimeScale::BDT));
+
+        // partial description
+        let leap = Leap::from_str("    17    18").unwrap();
+        assert_eq!(leap.delta_tls, Some(18));
+        assert!(leap.week.is_none());
+        assert!(leap.event_epoch().is_none());
+
+        assert!(Leap::from_str("").is_err());
+        assert!(Leap::from_str("    1a").is_err());
+    }
+
+    #[test]
+    fn leap_second_event() {
+        // 2017-01-01 leap second: GPS week 1930, day 1 (sunday)
+        let leap = Leap::from_str("    17    18  1930     1GPS").unwrap();
+        let event = leap.event_epoch().unwrap();
+        assert_eq!(event, Epoch::from_str("2017-01-01T00:00:00 GPST").unwrap());
+
+        let before = Epoch::from_str("2016-12-31T12:00:00 GPST").unwrap();
+        let after = Epoch::from_str("2017-01-01T12:00:00 GPST").unwrap();
+        assert_eq!(leap.leap_seconds_at(before), 17);
+        assert_eq!(leap.leap_seconds_at(after), 18);
+
+        assert_eq!(
+            leap.to_utc(after),
+            Epoch::from_str("2017-01-01T11:59:42 UTC").unwrap()
+        );
+
+        // deliberately outdated: the header prevails
+        let outdated = Leap::from_str("    16").unwrap();
+        assert_eq!(
+            outdated.to_utc(after),
+            Epoch::from_str("2017-01-01T11:59:44 UTC").unwrap()
+        );
+    }
 }
@@ -42,17 +42,28 @@ pub mod navigation;
 pub mod observation;
 pub mod production;
 pub mod record;
+pub mod sitelog;
+pub mod sv;
 pub mod types;
 pub mod version;
 
 mod bibliography;
 mod constants;
 mod epoch;
+mod format_options;
+mod ground_position;
+mod helmert;
 mod iterators;
 mod leap;
 mod linspace;
 mod observable;
+mod parse_options;
+mod retain;
+mod rounding;
 mod sampling;
+mod summary;
+mod time_window;
+mod whitespace;
 
 #[cfg(feature = "qc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "qc")))]
@@ -70,6 +81,10 @@ mod binex;
 #[cfg_attr(docsrs, doc(cfg(feature = "rtcm")))]
 mod rtcm;
 
+#[cfg(feature = "slr")]
+#[cfg_attr(docsrs, doc(cfg(feature = "slr")))]
+pub mod slr;
+
 #[cfg(test)]
 mod tests;
 
@@ -99,6 +114,7 @@ use crate::{
     hatanaka::CRINEX,
     observable::Observable,
     production::{DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU},
+    whitespace::WhitespaceReport,
 };
 
 /// Package to include all basic structures
@@ -107,15 +123,26 @@ pub mod prelude {
     pub use crate::{
         carrier::Carrier,
         doris::Station,
-        error::{Error, FormattingError, ParsingError},
+        epoch::{GpstSeconds, MjdSeconds, WeekTow},
+        error::{Error, ErrorCategory, FormattingError, ParsingError},
+        format_options::FormattingOptions,
+        ground_position::GroundPosition,
         hatanaka::{
             Decompressor, DecompressorExpert, DecompressorExpertIO, DecompressorIO, CRINEX,
         },
-        header::Header,
+        header::{Header, HeaderIssue},
+        helmert::{HelmertTransform, RotationConvention},
         leap::Leap,
         observable::Observable,
+        parse_options::ParsingOptions,
+        record::ParsingCheckpoint,
+        rounding::RoundingMode,
+        sampling::SamplingReport,
+        summary::ParsingSummary,
+        time_window::TimeWindow,
         types::Type as RinexType,
         version::Version,
+        whitespace::WhitespaceReport,
         Rinex,
     };
 
@@ -147,7 +174,7 @@ pub mod prelude {
 
         pub use crate::observation::{
             ClockObservation, Combination, CombinationKey, EpochFlag, LliFlags, ObsKey,
-            Observations, SignalObservation, SNR,
+            Observations, P1C1Biases, SignalObservation, SignalPriority, SNR,
         };
     }
 
@@ -183,6 +210,7 @@ pub mod prelude {
     #[cfg(feature = "qc")]
     #[cfg_attr(docsrs, doc(cfg(feature = "qc")))]
     pub mod qc {
+        pub use crate::qc::merge::obs::{MergePolicy, ObsMergeOptions};
         pub use qc_traits::{Merge, MergeError};
     }
 
@@ -193,6 +221,16 @@ pub mod prelude {
             Decimate, DecimationFilter, Filter, GnssAbsoluteTime, MaskFilter, Masking,
             Preprocessing, Split, Timeshift,
         };
+
+        #[cfg(feature = "obs")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+        pub use crate::processing::cycle_slips::{CycleSlip, CycleSlipDetector, CycleSlipOptions};
+        #[cfg(feature = "obs")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+        pub use crate::processing::differencing::{double_difference, ReferenceStrategy};
+        #[cfg(feature = "obs")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+        pub use crate::processing::smoothing::HatchOptions;
     }
 
     #[cfg(feature = "binex")]
@@ -207,7 +245,8 @@ pub mod prelude {
 /// Package dedicated to file production.
 pub mod prod {
     pub use crate::production::{
-        DataSource, DetailedProductionAttributes, ProductionAttributes, FFU, PPU,
+        DataSource, DetailedProductionAttributes, LatencyMonitor, LatencyStatistics,
+        ProductionAttributes, FFU, PPU,
     };
 }
 
@@ -239,15 +278,16 @@ pub(crate) fn is_rinex_comment(content: &str) -> bool {
  * macro to format one header line or a comment
  */
 pub(crate) fn fmt_rinex(content: &str, marker: &str) -> String {
+    // wrapped on characters (not bytes) boundaries
+    let content = content.chars().collect::<Vec<_>>();
     if content.len() < 60 {
+        let content = content.iter().collect::<String>();
         format!("{:<padding$}{}", content, marker, padding = 60)
     } else {
         let mut string = String::new();
         let nb_lines = num_integer::div_ceil(content.len(), 60);
-        for i in 0..nb_lines {
-            let start_off = i * 60;
-            let end_off = std::cmp::min(start_off + 60, content.len());
-            let chunk = &content[start_off..end_off];
+        for (i, chunk) in content.chunks(60).enumerate() {
+            let chunk = chunk.iter().collect::<String>();
             string.push_str(&format!("{:<padding$}{}", chunk, marker, padding = 60));
             if i < nb_lines - 1 {
                 string.push('\n');
@@ -304,6 +344,8 @@ pub struct Rinex {
     pub record: Record,
     /// [ProductionAttributes] filled
     pub production: ProductionAttributes,
+    /// [ParsingSummary] filled when parsing
+    summary: ParsingSummary,
 }
 
 impl Rinex {
@@ -314,6 +356,7 @@ impl Rinex {
             record,
             comments: Comments::new(),
             production: ProductionAttributes::default(),
+            summary: Default::default(),
         }
     }
 
@@ -323,6 +366,7 @@ impl Rinex {
             header: Header::basic_nav(),
             comments: Default::default(),
             production: ProductionAttributes::default(),
+            summary: Default::default(),
             record: Record::NavRecord(Default::default()),
         }
     }
@@ -333,6 +377,7 @@ impl Rinex {
             header: Header::basic_obs(),
             comments: Default::default(),
             production: ProductionAttributes::default(),
+            summary: Default::default(),
             record: Record::ObsRecord(Default::default()),
         }
     }
@@ -343,6 +388,7 @@ impl Rinex {
             comments: Default::default(),
             header: Header::basic_crinex(),
             production: ProductionAttributes::default(),
+            summary: Default::default(),
             record: Record::ObsRecord(Default::default()),
         }
     }
@@ -354,6 +400,7 @@ impl Rinex {
             record: self.record.clone(),
             comments: self.comments.clone(),
             production: self.production.clone(),
+            summary: self.summary.clone(),
         }
     }
 
@@ -369,6 +416,7 @@ impl Rinex {
             header: self.header.clone(),
             comments: self.comments.clone(),
             production: self.production.clone(),
+            summary: self.summary.clone(),
         }
     }
 
@@ -435,6 +483,9 @@ impl Rinex {
                     scaling: params.scaling.clone(),
                     timeof_first_obs: params.timeof_first_obs,
                     timeof_last_obs: params.timeof_last_obs,
+                    phase_shifts: params.phase_shifts.clone(),
+                    num_sv: params.num_sv,
+                    prn_obs_counts: params.prn_obs_counts.clone(),
                 });
 
             self.header.program = Some(format!(
@@ -825,27 +876,166 @@ impl Rinex {
     /// Attributes potentially described by a file name need to be provided either
     /// manually / externally, or guessed when parsing has been completed.
     pub fn parse<R: Read>(reader: &mut BufReader<R>) -> Result<Self, ParsingError> {
+        Self::parse_with_options(reader, &ParsingOptions::default())
+    }
+
+    /// Parse [RINEX] content by consuming [BufReader], like [Self::parse], with custom [ParsingOptions].
+    /// ```
+    /// use rinex::prelude::{ParsingError, ParsingOptions, Rinex};
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682
+    /// G45  20832395.682
+    /// ";
+    ///
+    /// // lenient (default): G45 is dropped and reported
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// assert_eq!(rinex.signal_observations_iter().count(), 1);
+    /// assert_eq!(rinex.parse_summary().dropped_sv.len(), 1);
+    ///
+    /// // strict
+    /// let opts = ParsingOptions::default().with_strict_prn();
+    /// let result = Rinex::parse_with_options(&mut BufReader::new(content.as_bytes()), &opts);
+    /// assert!(matches!(result, Err(ParsingError::InvalidPRN(_))));
+    /// ```
+    pub fn parse_with_options<R: Read>(
+        reader: &mut BufReader<R>,
+        opts: &ParsingOptions,
+    ) -> Result<Self, ParsingError> {
+        let mut summary = ParsingSummary::default();
+
         // Parses Header section (=consumes header until this point)
-        let mut header = Header::parse(reader)?;
+        let mut header = Header::parse_summarized(reader, &mut summary)?;
 
         // Parse record (=consumes rest of this resource)
         // Comments are preserved and store "as is"
-        let (record, comments) = Record::parse(&mut header, reader)?;
+        let (record, comments, _) =
+            Record::parse_chunk(&mut header, reader, 0, None, None, opts, &mut summary)?;
 
         Ok(Self {
             header,
             comments,
             record,
             production: Default::default(),
+            summary,
         })
     }
 
+    /// Parse [RINEX] content by consuming [BufReader], like [Self::parse], in tolerant mode:
+    /// whitespaces are normalized prior parsing. Tabulations, that would otherwise shift all
+    /// following fixed-column fields, are expanded to spaces (up to next tabulation stop).
+    /// The returned [WhitespaceReport] lists all lines that were repaired.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// \tEND OF HEADER";
+    ///
+    /// let mut reader = BufReader::new(content.as_bytes());
+    /// let (rinex, report) = Rinex::parse_tolerant(&mut reader)
+    ///     .unwrap();
+    ///
+    /// assert!(rinex.is_observation_rinex());
+    /// assert_eq!(report.repaired_lines, vec![2]);
+    /// ```
+    pub fn parse_tolerant<R: Read>(
+        reader: &mut BufReader<R>,
+    ) -> Result<(Self, WhitespaceReport), ParsingError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let content = String::from_utf8_lossy(&bytes);
+        let (repaired, report) = whitespace::repair(&content);
+
+        let mut reader = BufReader::new(repaired.as_bytes());
+        let rinex = Self::parse(&mut reader)?;
+        Ok((rinex, report))
+    }
+
     /// Format [RINEX] into writable I/O using efficient buffered writer
     /// and following standard specifications. The revision to be followed is defined
     /// in [Header] section. This is the mirror operation of [Self::parse].
+    /// "PGM / RUN BY / DATE" is stamped by this library, use [Self::format_with_options]
+    /// to preserve the original stamp.
     pub fn format<W: Write>(&self, writer: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        self.header.format(writer)?;
-        self.record.format(writer, &self.header)?;
+        self.format_with_options(writer, &FormattingOptions::default())
+    }
+
+    /// Format [RINEX] into writable I/O, like [Self::format], selecting the [RoundingMode]
+    /// of fixed width numeric fields (observations). This is typically used
+    /// to match the output of reference tools exactly, in validation pipelines.
+    /// Navigation fields, expressed in scientific notation, are not affected.
+    pub fn format_with_rounding<W: Write>(
+        &self,
+        writer: &mut BufWriter<W>,
+        rounding: RoundingMode,
+    ) -> Result<(), FormattingError> {
+        self.format_with_options(
+            writer,
+            &FormattingOptions::default().with_rounding(rounding),
+        )
+    }
+
+    /// Format [RINEX] into writable I/O, like [Self::format], with custom [FormattingOptions].
+    /// ```
+    /// use rinex::prelude::{FormattingOptions, Rinex};
+    /// use std::io::{BufReader, BufWriter};
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// teqc  2019Feb25     IGS                 20210102 000114 UTC PGM / RUN BY / DATE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let opts = FormattingOptions::default().with_agency("ESA");
+    /// let mut w = BufWriter::new(Vec::new());
+    /// rinex.format_with_options(&mut w, &opts).unwrap();
+    ///
+    /// let formatted = String::from_utf8(w.into_inner().unwrap()).unwrap();
+    /// let pgm = formatted.lines().nth(1).unwrap();
+    /// assert!(pgm.starts_with("rs-rinex"));
+    /// assert_eq!(&pgm[20..40], "ESA                 ");
+    ///
+    /// // preserve original stamp
+    /// let mut w = BufWriter::new(Vec::new());
+    /// rinex.format_with_options(&mut w, &opts.without_stamp()).unwrap();
+    ///
+    /// let formatted = String::from_utf8(w.into_inner().unwrap()).unwrap();
+    /// assert!(formatted.lines().nth(1).unwrap().starts_with("teqc  2019Feb25     IGS"));
+    /// ```
+    pub fn format_with_options<W: Write>(
+        &self,
+        writer: &mut BufWriter<W>,
+        opts: &FormattingOptions,
+    ) -> Result<(), FormattingError> {
+        // complete TIME OF FIRST / LAST OBS from the record, when missing
+        let mut completed = self.header_with_timeof_obs();
+
+        if opts.stamp {
+            let header = completed.as_ref().unwrap_or(&self.header);
+            completed = Some(header.stamped(opts.agency.as_deref(), epoch::now()));
+        }
+
+        // # OF SATELLITES (and PRN / # OF OBS) always describe the actual record
+        if self.is_observation_rinex() {
+            let header = completed.as_ref().unwrap_or(&self.header);
+            completed = Some(self.header_with_sv_counts(header, opts.prn_obs_counts));
+        }
+
+        let header = completed.as_ref().unwrap_or(&self.header);
+
+        header.format(writer)?;
+        self.record
+            .format_with_rounding(writer, header, opts.rounding)?;
         writer.flush()?;
         Ok(())
     }
@@ -894,7 +1084,7 @@ impl Rinex {
     /// This is the mirror operation of [Self::from_file].
     /// Returns total amount of bytes that was generated.
     /// ```
-    /// // Read a RINEX and dump it without any modifications
+    /// // Read a RINEX and dump it, with our own PGM / RUN BY / DATE stamp
     /// use rinex::prelude::*;
     /// let rnx = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
     ///   .unwrap();
@@ -906,12 +1096,47 @@ impl Rinex {
     ///   * [Self::guess_production_attributes] helps generate standardized filenames for
     ///     files that do not follow naming conventions
     pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), FormattingError> {
+        self.to_file_with_options(path, &FormattingOptions::default())
+    }
+
+    /// Dumps [Rinex] into writable local file, like [Self::to_file],
+    /// with custom [FormattingOptions].
+    pub fn to_file_with_options<P: AsRef<Path>>(
+        &self,
+        path: P,
+        opts: &FormattingOptions,
+    ) -> Result<(), FormattingError> {
         let fd = File::create(path)?;
         let mut writer = BufWriter::new(fd);
-        self.format(&mut writer)?;
+        self.format_with_options(&mut writer, opts)?;
         Ok(())
     }
 
+    /// Dumps [Rinex] into this writable I/O, see [Self::format].
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    ///      2    C1    L1                                          # / TYPES OF OBSERV
+    ///                                                             END OF HEADER
+    ///  20  1  1  0  0  0.0000000  0  1G01
+    ///   20832393.682   109474991.854
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let mut output = Vec::<u8>::new();
+    /// rinex.to_writer(&mut output).unwrap();
+    ///
+    /// let output = String::from_utf8(output).unwrap();
+    /// assert!(output.contains(" 20  1  1  0  0  0.0000000  0  1G01\n"));
+    /// ```
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), FormattingError> {
+        let mut writer = BufWriter::new(writer);
+        self.format(&mut writer)
+    }
+
     /// Parses [Rinex] from local gzip compressed file.
     /// Will panic if provided file does not exist or is not readable.
     /// Refer to [Self::from_file] for more information.
@@ -979,7 +1204,7 @@ impl Rinex {
     /// This is the mirror operation of [Self::from_gzip_file].
     /// Returns total amount of bytes that was generated.
     /// ```
-    /// // Read a RINEX and dump it without any modifications
+    /// // Read a RINEX and dump it, with our own PGM / RUN BY / DATE stamp
     /// use rinex::prelude::*;
     /// let rnx = Rinex::from_file("data/OBS/V3/DUTH0630.22O")
     ///   .unwrap();
@@ -1105,6 +1330,29 @@ impl Rinex {
         }
     }
 
+    /// Returns the number of satellites encountered in this [Rinex],
+    /// whatever the header declares. This is the "# OF SATELLITES"
+    /// written when producing Observation RINEX.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    ///     12                                                      # OF SATELLITES
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682
+    /// G07  20832394.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// assert_eq!(rinex.sv_count(), 2);
+    /// ```
+    pub fn sv_count(&self) -> usize {
+        self.sv_iter().count()
+    }
+
     // /// List all [`SV`] per epoch of appearance.
     // /// ```
     // /// use rinex::prelude::*;
@@ -1201,6 +1449,14 @@ impl Rinex {
         Box::new(self.sv_iter().map(|sv| sv.constellation).unique().sorted())
     }
 
+    /// Returns the list of [Constellation]s actually present in this record,
+    /// whatever the header declares. This is particularly useful when the header
+    /// describes [Constellation::Mixed] content, or when header and record disagree,
+    /// which is frequent in real files. See [Self::constellations_iter].
+    pub fn constellations(&self) -> Vec<Constellation> {
+        self.constellations_iter().collect()
+    }
+
     // /// Returns an Iterator over Unique Constellations, per Epoch
     // pub fn constellation_epoch(
     //     &self,
@@ -1336,6 +1592,8 @@ impl Masking for Rinex {
     }
     fn mask_mut(&mut self, f: &MaskFilter) {
         header_mask_mut(&mut self.header, f);
+        self.header
+            .push_processing_log(None, "MASK", &processing::mask_filter_log(f));
         if let Some(rec) = self.record.as_mut_obs() {
             observation_mask_mut(rec, f);
         } else if let Some(rec) = self.record.as_mut_nav() {
@@ -1523,6 +1781,27 @@ mod test {
         }
     }
     #[test]
+    fn fmt_wrapped_non_ascii_comments() {
+        let desc =
+            "Station installée près de Besançon, antenne réinstallée après les orages de l'été";
+        assert!(desc.len() > desc.chars().count());
+
+        let comments = fmt_comment(desc);
+        assert_eq!(comments.lines().count(), 2);
+
+        for line in comments.lines() {
+            assert_eq!(line.chars().count(), 67);
+            assert!(line.ends_with("COMMENT"));
+        }
+
+        let content = comments
+            .lines()
+            .map(|line| line.chars().take(60).collect::<String>())
+            .collect::<String>();
+
+        assert_eq!(content.trim_end(), desc);
+    }
+    #[test]
     fn fmt_observables_v3() {
         for (desc, expected) in [
 ("R    9 C1C L1C S1C C2C C2P L2C L2P S2C S2P",
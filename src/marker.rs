@@ -58,10 +58,30 @@ pub enum MarkerType {
     Human,
 }
 
+impl MarkerType {
+    /// Returns true if this marker is fixed to the Earth surface
+    pub fn is_earth_fixed(&self) -> bool {
+        matches!(self, Self::Geodetic | Self::NonGeodetic | Self::FixedBuoy)
+    }
+
+    /// Returns true if this marker is an orbiting space vehicle
+    pub fn is_spaceborne(&self) -> bool {
+        *self == Self::Spaceborne
+    }
+
+    /// Returns true if signals received by this marker crossed the troposphere,
+    /// meaning a troposphere model should apply. This is not the case
+    /// of [MarkerType::Spaceborne] receivers.
+    pub fn requires_troposphere_model(&self) -> bool {
+        !self.is_spaceborne()
+    }
+}
+
 impl std::str::FromStr for MarkerType {
     type Err = ParsingError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        // both "NON_GEODETIC" and "NON GEODETIC" are encountered
+        match s.trim().to_lowercase().replace('_', " ").as_str() {
             "geodetic" => Ok(Self::Geodetic),
             "non geodetic" => Ok(Self::NonGeodetic),
             "ground craft" => Ok(Self::Groundcraft),
@@ -69,6 +89,7 @@ impl std::str::FromStr for MarkerType {
             "airborne" => Ok(Self::Airborne),
             "non physical" => Ok(Self::NonPhysical),
             "spaceborne" => Ok(Self::Spaceborne),
+            "fixed buoy" => Ok(Self::FixedBuoy),
             "floating ice" => Ok(Self::FloatingIce),
             "floating buoy" => Ok(Self::FloatingBuoy),
             "glacier" => Ok(Self::Glacier),
@@ -84,7 +105,19 @@ impl std::fmt::Display for MarkerType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Self::Geodetic => write!(f, "GEODETIC"),
-            _ => write!(f, "HUMAN"),
+            Self::NonGeodetic => write!(f, "NON_GEODETIC"),
+            Self::NonPhysical => write!(f, "NON_PHYSICAL"),
+            Self::Spaceborne => write!(f, "SPACEBORNE"),
+            Self::Airborne => write!(f, "AIRBORNE"),
+            Self::Watercraft => write!(f, "WATER_CRAFT"),
+            Self::Groundcraft => write!(f, "GROUND_CRAFT"),
+            Self::FixedBuoy => write!(f, "FIXED_BUOY"),
+            Self::FloatingBuoy => write!(f, "FLOATING_BUOY"),
+            Self::FloatingIce => write!(f, "FLOATING_ICE"),
+            Self::Glacier => write!(f, "GLACIER"),
+            Self::Ballistic => write!(f, "BALLISTIC"),
+            Self::Animal => write!(f, "ANIMAL"),
+            Self::Human => write!(f, "HUMAN"),
         }
     }
 }
@@ -92,7 +125,11 @@ impl std::fmt::Display for MarkerType {
 impl GeodeticMarker {
     /// Formats [GeodeticMarker] into [BufWriter]
     pub(crate) fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        writeln!(w, "{}", fmt_rinex(&self.name, "MARKER NAME"))?;
+        writeln!(
+            w,
+            "{}",
+            fmt_rinex(&format!("{:<60.60}", self.name), "MARKER NAME")
+        )?;
         if let Some(number) = self.number() {
             writeln!(w, "{}", fmt_rinex(&number, "MARKER NUMBER"))?;
         }
@@ -131,7 +168,46 @@ impl GeodeticMarker {
 
 #[cfg(test)]
 mod test {
-    use super::GeodeticMarker;
+    use super::{GeodeticMarker, MarkerType};
+    use std::str::FromStr;
+
+    #[test]
+    fn marker_type() {
+        for (content, expected) in [
+            ("GEODETIC", MarkerType::Geodetic),
+            ("NON_GEODETIC", MarkerType::NonGeodetic),
+            ("NON GEODETIC", MarkerType::NonGeodetic),
+            ("NON_PHYSICAL", MarkerType::NonPhysical),
+            ("SPACEBORNE", MarkerType::Spaceborne),
+            ("GROUND_CRAFT", MarkerType::Groundcraft),
+            ("WATER_CRAFT", MarkerType::Watercraft),
+            ("AIRBORNE", MarkerType::Airborne),
+            ("FIXED_BUOY", MarkerType::FixedBuoy),
+            ("FLOATING_BUOY", MarkerType::FloatingBuoy),
+            ("FLOATING_ICE", MarkerType::FloatingIce),
+            ("GLACIER", MarkerType::Glacier),
+            ("BALLISTIC", MarkerType::Ballistic),
+            ("ANIMAL", MarkerType::Animal),
+            ("HUMAN", MarkerType::Human),
+        ] {
+            let marker = MarkerType::from_str(content).unwrap();
+            assert_eq!(marker, expected);
+            assert_eq!(
+                MarkerType::from_str(&marker.to_string()).unwrap(),
+                marker,
+                "reciprocal"
+            );
+        }
+
+        assert!(MarkerType::from_str("SUBMARINE").is_err());
+
+        assert!(MarkerType::Spaceborne.is_spaceborne());
+        assert!(!MarkerType::Spaceborne.requires_troposphere_model());
+        assert!(!MarkerType::Spaceborne.is_earth_fixed());
+        assert!(MarkerType::Geodetic.requires_troposphere_model());
+        assert!(MarkerType::Geodetic.is_earth_fixed());
+        assert!(!MarkerType::Airborne.is_earth_fixed());
+    }
     #[test]
     fn marker_number() {
         let marker = GeodeticMarker::default();
@@ -4,15 +4,18 @@ use crate::{
     epoch::format as format_epoch,
     meteo::{MeteoKey, Record},
     prelude::{FormattingError, Header, RinexType},
+    rounding::{FixedFormatter, RoundingMode},
 };
 
 use itertools::Itertools;
 
-/// Formats Meteo epoch into [BufWriter]
+/// Formats Meteo epoch into [BufWriter], observations
+/// being rounded with desired [RoundingMode]
 pub fn format<W: Write>(
     w: &mut BufWriter<W>,
     record: &Record,
     header: &Header,
+    rounding: RoundingMode,
 ) -> Result<(), FormattingError> {
     let observables = &header
         .meteo
@@ -34,7 +37,7 @@ pub fn format<W: Write>(
             };
 
             if let Some(observation) = record.get(&key) {
-                write!(w, "{:7.1}", observation)?;
+                write!(w, "{}", FixedFormatter::new(*observation, 7, 1, rounding))?;
             } else {
                 write!(w, "           ")?;
             }
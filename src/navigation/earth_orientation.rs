@@ -10,6 +10,19 @@ use serde::Serialize;
 
 use std::str::FromStr;
 
+/// [EarthOrientationParameters] evaluated at a specific [Epoch],
+/// from one or several [EarthOrientation] messages.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct EarthOrientationParameters {
+    /// Pole X coordinate (arc-sec)
+    pub x_arcsec: f64,
+    /// Pole Y coordinate (arc-sec)
+    pub y_arcsec: f64,
+    /// UT1 - UTC (in seconds)
+    pub delta_ut1_s: f64,
+}
+
 /// Earth Orientation Message
 #[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
@@ -25,6 +38,41 @@ pub struct EarthOrientation {
 }
 
 impl EarthOrientation {
+    /// Evaluates this [EarthOrientation] message at t [Epoch], with t_ref the
+    /// [Epoch] of publication of this message (NAV record [Epoch]). Each parameter
+    /// is expanded to second order: p(t) = p + ṗ dt + ½ p̈ dt², dt in days.
+    /// ```
+    /// use rinex::navigation::EarthOrientation;
+    /// use rinex::prelude::{Duration, Epoch};
+    /// use std::str::FromStr;
+    ///
+    /// let eop = EarthOrientation {
+    ///     x: (0.1, 0.01, 0.0),
+    ///     y: (0.3, -0.02, 0.0),
+    ///     t_tm: 0,
+    ///     delta_ut1: (-0.02, -1.0E-4, 0.0),
+    /// };
+    ///
+    /// let t_ref = Epoch::from_str("2023-03-14T00:00:00 UTC").unwrap();
+    /// let t = t_ref + Duration::from_days(0.5);
+    ///
+    /// let params = eop.evaluate(t_ref, t);
+    /// assert!((params.x_arcsec - 0.105).abs() < 1.0E-12);
+    /// assert!((params.y_arcsec - 0.29).abs() < 1.0E-12);
+    /// assert!((params.delta_ut1_s + 0.02005).abs() < 1.0E-12);
+    /// ```
+    pub fn evaluate(&self, t_ref: Epoch, t: Epoch) -> EarthOrientationParameters {
+        let dt_days = (t - t_ref).to_unit(hifitime::Unit::Day);
+
+        let expand = |(p, dp, ddp): (f64, f64, f64)| p + dp * dt_days + 0.5 * ddp * dt_days.powi(2);
+
+        EarthOrientationParameters {
+            x_arcsec: expand(self.x),
+            y_arcsec: expand(self.y),
+            delta_ut1_s: expand(self.delta_ut1),
+        }
+    }
+
     pub(crate) fn parse(
         line_1: &str,
         line_2: &str,
@@ -80,7 +128,7 @@ impl EarthOrientation {
 #[cfg(test)]
 mod test {
     use super::EarthOrientation;
-    use crate::prelude::{Epoch, TimeScale};
+    use crate::prelude::{Duration, Epoch, TimeScale};
     use std::str::FromStr;
     #[test]
     fn earth_orientations_parsing() {
@@ -138,4 +186,25 @@ mod test {
             assert_eq!(eop.delta_ut1.2, dddut1);
         }
     }
+
+    #[test]
+    fn earth_orientation_evaluation() {
+        let (t_ref, eop) = EarthOrientation::parse(
+            "    2023 03 14 16 51 12-4.024982452393e-02 3.957748413086e-05 2.000000000000e-06",
+            "                        3.562908172607e-01 2.602100372314e-03 0.000000000000e+00",
+            "     4.392000000000e+03-1.940387487411e-02-1.411736011505e-04 0.000000000000e+00",
+            TimeScale::UTC,
+        )
+        .unwrap();
+
+        let params = eop.evaluate(t_ref, t_ref);
+        assert_eq!(params.x_arcsec, eop.x.0);
+        assert_eq!(params.y_arcsec, eop.y.0);
+        assert_eq!(params.delta_ut1_s, eop.delta_ut1.0);
+
+        let params = eop.evaluate(t_ref, t_ref - Duration::from_days(2.0));
+        assert!((params.x_arcsec - (eop.x.0 - 2.0 * eop.x.1 + 2.0 * eop.x.2)).abs() < 1.0E-15);
+        assert!((params.y_arcsec - (eop.y.0 - 2.0 * eop.y.1)).abs() < 1.0E-15);
+        assert!((params.delta_ut1_s - (eop.delta_ut1.0 - 2.0 * eop.delta_ut1.1)).abs() < 1.0E-15);
+    }
 }
@@ -0,0 +1,282 @@
+//! Satellite eclipse seasons and attitude maneuvers
+use crate::{
+    constants::Omega,
+    prelude::{Epoch, Rinex, SV},
+};
+
+use nalgebra::Vector3;
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Astronomical unit, in km
+const AU_KM: f64 = 149_597_870.7;
+
+/// Earth equatorial radius, in km
+const EARTH_RADIUS_KM: f64 = 6378.137;
+
+/// Returns the Sun position in ECEF frame (km) at desired [Epoch],
+/// using the low precision analytic model of the Astronomical Almanac.
+/// Accuracy is about 0.01°, which is enough to predict eclipses and attitude maneuvers,
+/// but not to model solar radiation pressure or phase wind-up precisely.
+/// Earth rotation is described by the Greenwich Mean Sidereal Time (UT1 ~ UTC).
+/// ```
+/// use rinex::navigation::sun_position_ecef_km;
+/// use rinex::prelude::Epoch;
+/// use std::str::FromStr;
+///
+/// // June solstice
+/// let t = Epoch::from_str("2020-06-20T21:44:00 UTC").unwrap();
+/// let (x_km, y_km, z_km) = sun_position_ecef_km(t);
+///
+/// let r_km = (x_km.powi(2) + y_km.powi(2) + z_km.powi(2)).sqrt();
+/// let declination_deg = (z_km / r_km).asin().to_degrees();
+/// assert!((declination_deg - 23.44).abs() < 0.05);
+/// ```
+pub fn sun_position_ecef_km(t: Epoch) -> (f64, f64, f64) {
+    let n = t.to_jde_tt_days() - 2_451_545.0;
+
+    let mean_longitude_deg = 280.460 + 0.985_647_4 * n;
+    let mean_anomaly_rad = (357.528 + 0.985_600_3 * n).to_radians();
+
+    let ecliptic_longitude_rad = (mean_longitude_deg
+        + 1.915 * mean_anomaly_rad.sin()
+        + 0.020 * (2.0 * mean_anomaly_rad).sin())
+    .to_radians();
+
+    let obliquity_rad = (23.439 - 0.000_000_4 * n).to_radians();
+
+    let distance_km = AU_KM
+        * (1.000_14
+            - 0.016_71 * mean_anomaly_rad.cos()
+            - 0.000_14 * (2.0 * mean_anomaly_rad).cos());
+
+    // inertial (true of date)
+    let x = distance_km * ecliptic_longitude_rad.cos();
+    let y = distance_km * obliquity_rad.cos() * ecliptic_longitude_rad.sin();
+    let z = distance_km * obliquity_rad.sin() * ecliptic_longitude_rad.sin();
+
+    // earth fixed
+    let d_ut = t.to_jde_utc_days() - 2_451_545.0;
+    let gmst_rad = (280.460_618_37 + 360.985_647_366_29 * d_ut)
+        .rem_euclid(360.0)
+        .to_radians();
+
+    let (sin_gmst, cos_gmst) = gmst_rad.sin_cos();
+    (cos_gmst * x + sin_gmst * y, -sin_gmst * x + cos_gmst * y, z)
+}
+
+/// [AttitudeManeuver] performed by a satellite during its eclipse season,
+/// when the nominal yaw attitude can no longer be maintained.
+/// Phase observations (wind-up) and clock estimates are degraded during these
+/// maneuvers, which is why they are usually excluded from precise analyses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum AttitudeManeuver {
+    /// Satellite is in the shadow of the Earth (midnight turn)
+    Eclipse,
+    /// Satellite is close to the Sun direction (noon turn)
+    NoonTurn,
+}
+
+impl std::fmt::Display for AttitudeManeuver {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Eclipse => write!(f, "Eclipse"),
+            Self::NoonTurn => write!(f, "Noon-Turn"),
+        }
+    }
+}
+
+/// [EclipseOptions] to customize [AttitudeManeuver]s detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EclipseOptions {
+    /// Satellites are in eclipse season when the Sun elevation
+    /// above the orbital plane (beta angle) is lower than this value, in degrees
+    pub beta_threshold_deg: f64,
+    /// Noon turns are flagged when the satellite is within this angle
+    /// of the projection of the Sun in the orbital plane, in degrees
+    pub noon_turn_window_deg: f64,
+}
+
+impl Default for EclipseOptions {
+    /// Builds default [EclipseOptions], suited for MEO constellations
+    fn default() -> Self {
+        Self {
+            beta_threshold_deg: 14.0,
+            noon_turn_window_deg: 10.0,
+        }
+    }
+}
+
+impl EclipseOptions {
+    /// Copies and returns [EclipseOptions] with desired beta angle threshold, in degrees
+    pub fn with_beta_threshold_deg(&self, beta_deg: f64) -> Self {
+        let mut s = *self;
+        s.beta_threshold_deg = beta_deg;
+        s
+    }
+
+    /// Copies and returns [EclipseOptions] with desired noon turn window, in degrees
+    pub fn with_noon_turn_window_deg(&self, window_deg: f64) -> Self {
+        let mut s = *self;
+        s.noon_turn_window_deg = window_deg;
+        s
+    }
+}
+
+/// Returns the beta angle (in degrees) from inertial satellite position and velocity,
+/// and Sun position, all expressed in the same frame.
+fn beta_angle_deg(r_km: &Vector3<f64>, v_km_s: &Vector3<f64>, sun_km: &Vector3<f64>) -> f64 {
+    let normal = r_km.cross(v_km_s).normalize();
+    normal.dot(&sun_km.normalize()).asin().to_degrees()
+}
+
+/// Returns [AttitudeManeuver] being performed, if any.
+fn attitude_maneuver(
+    r_km: &Vector3<f64>,
+    v_km_s: &Vector3<f64>,
+    sun_km: &Vector3<f64>,
+    opts: &EclipseOptions,
+) -> Option<AttitudeManeuver> {
+    let beta_deg = beta_angle_deg(r_km, v_km_s, sun_km);
+    if beta_deg.abs() > opts.beta_threshold_deg {
+        return None;
+    }
+
+    let sun = sun_km.normalize();
+    let along_sun = r_km.dot(&sun);
+
+    // cylindrical shadow model
+    if along_sun < 0.0 && (r_km - along_sun * sun).norm() < EARTH_RADIUS_KM {
+        return Some(AttitudeManeuver::Eclipse);
+    }
+
+    // angle to the projection of the Sun in the orbital plane
+    let normal = r_km.cross(v_km_s).normalize();
+    let sun_in_plane = (sun - sun.dot(&normal) * normal).normalize();
+    let noon_angle_deg = r_km
+        .normalize()
+        .dot(&sun_in_plane)
+        .clamp(-1.0, 1.0)
+        .acos()
+        .to_degrees();
+
+    if noon_angle_deg < opts.noon_turn_window_deg {
+        Some(AttitudeManeuver::NoonTurn)
+    } else {
+        None
+    }
+}
+
+impl Rinex {
+    /// Returns the beta angle of this [SV] (Sun elevation above the orbital plane),
+    /// in degrees, at desired [Epoch]. This only applies to Navigation [Rinex].
+    pub fn sv_beta_angle_deg(&self, sv: SV, t: Epoch) -> Option<f64> {
+        let (r_km, v_km_s) = self.sv_inertial_state(sv, t)?;
+        let sun_km = Vector3::from(<[f64; 3]>::from(sun_position_ecef_km(t)));
+        Some(beta_angle_deg(&r_km, &v_km_s, &sun_km))
+    }
+
+    /// Flags the [AttitudeManeuver]s performed by each [SV] during its eclipse season,
+    /// for each [Epoch] of interest (typically, Observation time tags).
+    /// This only applies to Navigation [Rinex]: orbits are resolved from the
+    /// radio messages. Epochs that are not flagged correspond to nominal attitude.
+    pub fn nav_attitude_maneuvers<I: IntoIterator<Item = Epoch>>(
+        &self,
+        epochs: I,
+        opts: &EclipseOptions,
+    ) -> BTreeMap<SV, BTreeMap<Epoch, AttitudeManeuver>> {
+        let mut flags = BTreeMap::<SV, BTreeMap<Epoch, AttitudeManeuver>>::new();
+        let satellites = self.sv_iter().collect::<Vec<_>>();
+
+        for t in epochs {
+            let sun_km = Vector3::from(<[f64; 3]>::from(sun_position_ecef_km(t)));
+
+            for sv in satellites.iter() {
+                let (r_km, v_km_s) = match self.sv_inertial_state(*sv, t) {
+                    Some(state) => state,
+                    None => continue,
+                };
+
+                if let Some(maneuver) = attitude_maneuver(&r_km, &v_km_s, &sun_km, opts) {
+                    flags.entry(*sv).or_default().insert(t, maneuver);
+                }
+            }
+        }
+
+        flags
+    }
+
+    /// Returns ECEF position and velocity (km, km/s) of this [SV],
+    /// velocity being corrected for the Earth rotation (inertial velocity),
+    /// so it defines the actual orbital plane.
+    fn sv_inertial_state(&self, sv: SV, t: Epoch) -> Option<(Vector3<f64>, Vector3<f64>)> {
+        let orbit = self.sv_orbit(sv, t)?;
+        let r_km = orbit.radius_km;
+        let omega = Vector3::new(0.0, 0.0, Omega::GPS);
+        let v_km_s = orbit.velocity_km_s + omega.cross(&r_km);
+        Some((r_km, v_km_s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn sun_position() {
+        // equinox: subsolar point is close to the equator,
+        // and to the Greenwich meridian at noon
+        let t = Epoch::from_str("2020-03-20T12:00:00 UTC").unwrap();
+        let (x, y, z) = sun_position_ecef_km(t);
+        let r = (x.powi(2) + y.powi(2) + z.powi(2)).sqrt();
+
+        assert!(
+            (r / AU_KM - 0.996).abs() < 0.002,
+            "bad distance {}",
+            r / AU_KM
+        );
+        assert!((z / r).asin().to_degrees().abs() < 0.5);
+        assert!(y.atan2(x).to_degrees().abs() < 3.0);
+    }
+
+    #[test]
+    fn maneuver_detection() {
+        let opts = EclipseOptions::default();
+        let sun_km = Vector3::new(AU_KM, 0.0, 0.0);
+        let r = 26_560.0;
+        let v = 3.87;
+
+        // orbital plane contains the Sun (beta = 0)
+        let (r_midnight, v_midnight) = (Vector3::new(-r, 0.0, 0.0), Vector3::new(0.0, -v, 0.0));
+        assert!(beta_angle_deg(&r_midnight, &v_midnight, &sun_km).abs() < 1.0E-9);
+        assert_eq!(
+            attitude_maneuver(&r_midnight, &v_midnight, &sun_km, &opts),
+            Some(AttitudeManeuver::Eclipse)
+        );
+
+        let (r_noon, v_noon) = (Vector3::new(r, 0.0, 0.0), Vector3::new(0.0, v, 0.0));
+        assert_eq!(
+            attitude_maneuver(&r_noon, &v_noon, &sun_km, &opts),
+            Some(AttitudeManeuver::NoonTurn)
+        );
+
+        // quadrature: nominal attitude
+        let (r_quad, v_quad) = (Vector3::new(0.0, r, 0.0), Vector3::new(-v, 0.0, 0.0));
+        assert_eq!(attitude_maneuver(&r_quad, &v_quad, &sun_km, &opts), None);
+
+        // out of eclipse season (beta = 30°): never flagged
+        let beta = 30.0_f64.to_radians();
+        let w = Vector3::new(beta.cos(), 0.0, -beta.sin());
+        let y = Vector3::new(0.0, v, 0.0);
+        for (r_km, v_km_s) in [(r * w, y), (-r * w, -y)] {
+            let beta_deg = beta_angle_deg(&r_km, &v_km_s, &sun_km);
+            assert!((beta_deg - 30.0).abs() < 1.0E-9, "bad beta {}", beta_deg);
+            assert_eq!(attitude_maneuver(&r_km, &v_km_s, &sun_km, &opts), None);
+        }
+    }
+}
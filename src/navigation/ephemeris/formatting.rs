@@ -33,32 +33,28 @@ impl Ephemeris {
             },
         };
 
+        // RINEX2: 3X,4D19.12, RINEX3: 4X,4D19.12
+        let v2 = version.major < 3;
+        let indent = if v2 { "   " } else { "    " };
+
         // starts with (clock_bias, drift, rate)
         // epoch has already been buffered
         write!(
             w,
             "{}{}{}",
-            NavFormatter::new(self.clock_bias),
-            NavFormatter::new(self.clock_drift),
-            NavFormatter::new(self.clock_drift_rate),
+            NavFormatter::new_v2v3(self.clock_bias, v2),
+            NavFormatter::new_v2v3(self.clock_drift, v2),
+            NavFormatter::new_v2v3(self.clock_drift_rate, v2),
         )?;
 
         // following standard specs
         let data_fields = &standard_specs.items;
-        for i in 0..data_fields.len() {
-            if let Some(value) = self.get_orbit_f64(data_fields[i].0) {
-                if i % 4 == 0 {
-                    write!(w, "\n   {}", NavFormatter::new(value))?;
-                } else {
-                    write!(w, "{}", NavFormatter::new(value))?;
-                }
-            } else {
-                if i % 4 == 0 {
-                    write!(w, "\n   {}", NavFormatter::new(0.0))?;
-                } else {
-                    write!(w, "{}", NavFormatter::new(0.0))?;
-                }
+        for (i, (field, _)) in data_fields.iter().enumerate() {
+            let value = self.get_orbit_f64(field).unwrap_or(0.0);
+            if i % 4 == 0 {
+                write!(w, "\n{}", indent)?;
             }
+            write!(w, "{}", NavFormatter::new_v2v3(value, v2))?;
         }
 
         write!(w, "\n")?;
@@ -114,14 +110,14 @@ mod test {
 
         assert_eq!(
             utf8,
-            "-1.000000000000E-04-2.000000000000E-11 0.000000000000E+00
-    1.000000000000E+00 2.000000000000E+00 3.000000000000E+00 0.000000000000E+00
-    5.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00 0.000000000000E+00 0.000000000000E+00
-    0.000000000000E+00 0.000000000000E+00\n"
+            "-1.000000000000D-04-2.000000000000D-11 0.000000000000D+00
+    1.000000000000D+00 2.000000000000D+00 3.000000000000D+00 0.000000000000D+00
+    5.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    0.000000000000D+00 0.000000000000D+00\n"
         );
     }
 }
@@ -0,0 +1,209 @@
+//! Glonass state vector propagation
+use crate::{
+    navigation::Ephemeris,
+    prelude::{Constellation, Epoch, SV},
+};
+
+/// PZ-90 earth gravitational constant (in km³.s⁻²)
+const PZ90_GM_KM3_S2: f64 = 398_600.441_8;
+
+/// PZ-90 earth equatorial radius (in km)
+const PZ90_EARTH_RADIUS_KM: f64 = 6_378.136;
+
+/// Second zonal harmonic of the PZ-90 geopotential
+const PZ90_J2: f64 = 1.08262575E-3;
+
+/// PZ-90 earth rotation rate (in rad.s⁻¹)
+const PZ90_EARTH_ROTATION_RAD_S: f64 = 7.292115E-5;
+
+/// Maximal Runge-Kutta integration step (in seconds)
+const RK4_MAX_STEP_S: f64 = 30.0;
+
+/// ECEF (position, velocity) state, in km and km.s⁻¹
+type State = [f64; 6];
+
+/// Returns the state derivative, as per the Glonass ICD equations of motion,
+/// expressed in the rotating PZ-90 frame: central term, J2 zonal harmonic,
+/// centrifugal and Coriolis accelerations, plus the lunisolar accelerations
+/// (which are broadcast, and considered constant over the integration period).
+fn derivative(state: &State, lunisolar: (f64, f64, f64)) -> State {
+    let [x, y, z, vx, vy, vz] = *state;
+
+    let r = (x.powi(2) + y.powi(2) + z.powi(2)).sqrt();
+
+    let gm_r3 = PZ90_GM_KM3_S2 / r.powi(3);
+    let j2 = 1.5 * PZ90_J2 * PZ90_GM_KM3_S2 * PZ90_EARTH_RADIUS_KM.powi(2) / r.powi(5);
+    let z2_r2 = 5.0 * z.powi(2) / r.powi(2);
+    let we = PZ90_EARTH_ROTATION_RAD_S;
+
+    [
+        vx,
+        vy,
+        vz,
+        -gm_r3 * x - j2 * x * (1.0 - z2_r2) + we.powi(2) * x + 2.0 * we * vy + lunisolar.0,
+        -gm_r3 * y - j2 * y * (1.0 - z2_r2) + we.powi(2) * y - 2.0 * we * vx + lunisolar.1,
+        -gm_r3 * z - j2 * z * (3.0 - z2_r2) + lunisolar.2,
+    ]
+}
+
+/// Integrates the equations of motion from initial state, over dt (in seconds),
+/// with the 4th order Runge-Kutta method. dt may be negative.
+fn rk4(initial: State, lunisolar: (f64, f64, f64), dt: f64) -> State {
+    let steps = (dt.abs() / RK4_MAX_STEP_S).ceil().max(1.0);
+    let h = dt / steps;
+
+    let shifted = |state: &State, k: &State, h: f64| {
+        let mut shifted = *state;
+        for (value, k) in shifted.iter_mut().zip(k.iter()) {
+            *value += h * k;
+        }
+        shifted
+    };
+
+    let mut state = initial;
+
+    for _ in 0..steps as usize {
+        let k1 = derivative(&state, lunisolar);
+        let k2 = derivative(&shifted(&state, &k1, h / 2.0), lunisolar);
+        let k3 = derivative(&shifted(&state, &k2, h / 2.0), lunisolar);
+        let k4 = derivative(&shifted(&state, &k3, h), lunisolar);
+
+        for i in 0..6 {
+            state[i] += h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+        }
+    }
+
+    state
+}
+
+impl Ephemeris {
+    /// Propagates the broadcast Glonass state vector, published at toc, to t.
+    fn glonass_state(&self, sv: SV, toc: Epoch, t: Epoch) -> Option<State> {
+        if sv.constellation != Constellation::Glonass {
+            return None;
+        }
+
+        let dt = t - toc;
+
+        if dt.abs() > Self::validity_duration(sv.constellation)? {
+            return None;
+        }
+
+        let typed = self.to_typed(sv)?;
+        let eph = typed.as_state_vector()?;
+
+        let (x, y, z) = eph.position_km;
+        let (vx, vy, vz) = eph.velocity_km_s;
+
+        Some(rk4(
+            [x, y, z, vx, vy, vz],
+            eph.acceleration_km_s2,
+            dt.to_seconds(),
+        ))
+    }
+
+    /// Returns ECEF (PZ-90) position (in km) of this Glonass [SV] at t [Epoch], obtained from
+    /// this broadcast [Ephemeris] published at toc (the state vector reference epoch tb).
+    /// The state vector is propagated by numerical integration (4th order Runge-Kutta) of
+    /// the equations of motion described in the Glonass ICD, which account for the J2 zonal
+    /// harmonic, the earth rotation and the broadcast lunisolar accelerations.
+    /// Returns None for other constellations, or when t lies outside the validity window
+    /// ([Self::validity_duration]) of this [Ephemeris].
+    /// ```
+    /// use rinex::navigation::{Ephemeris, OrbitItem};
+    /// use rinex::prelude::{Duration, Epoch, SV};
+    /// use std::str::FromStr;
+    ///
+    /// let r01 = SV::from_str("R01").unwrap();
+    /// let toc = Epoch::from_str("2021-01-01T00:15:00 UTC").unwrap();
+    ///
+    /// let eph = Ephemeris::default()
+    ///     .with_orbit("satPosX", OrbitItem::from(7003.008789))
+    ///     .with_orbit("satPosY", OrbitItem::from(-12206.626953))
+    ///     .with_orbit("satPosZ", OrbitItem::from(21280.765625))
+    ///     .with_orbit("velX", OrbitItem::from(0.7835417))
+    ///     .with_orbit("velY", OrbitItem::from(2.8042530))
+    ///     .with_orbit("velZ", OrbitItem::from(1.3525150));
+    ///
+    /// // reference epoch
+    /// let (x_km, y_km, z_km) = eph.glonass_sv_position(r01, toc, toc).unwrap();
+    /// assert_eq!((x_km, y_km, z_km), (7003.008789, -12206.626953, 21280.765625));
+    ///
+    /// let t = toc + Duration::from_seconds(900.0);
+    /// let (x_km, y_km, z_km) = eph.glonass_sv_position(r01, toc, t).unwrap();
+    /// let r_km = (x_km.powi(2) + y_km.powi(2) + z_km.powi(2)).sqrt();
+    /// assert!(r_km > 25_000.0 && r_km < 26_000.0);
+    ///
+    /// // outside validity window
+    /// let t = toc + Duration::from_seconds(3600.0);
+    /// assert!(eph.glonass_sv_position(r01, toc, t).is_none());
+    /// ```
+    pub fn glonass_sv_position(&self, sv: SV, toc: Epoch, t: Epoch) -> Option<(f64, f64, f64)> {
+        let [x, y, z, _, _, _] = self.glonass_state(sv, toc, t)?;
+        Some((x, y, z))
+    }
+
+    /// Returns ECEF (PZ-90) velocity (in km.s⁻¹) of this Glonass [SV] at t [Epoch],
+    /// obtained from this broadcast [Ephemeris] published at toc.
+    /// See [Self::glonass_sv_position].
+    pub fn glonass_sv_velocity(&self, sv: SV, toc: Epoch, t: Epoch) -> Option<(f64, f64, f64)> {
+        let [_, _, _, vx, vy, vz] = self.glonass_state(sv, toc, t)?;
+        Some((vx, vy, vz))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        navigation::{Ephemeris, OrbitItem},
+        prelude::{Duration, Epoch, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn glonass_rk4_propagation() {
+        let r01 = SV::from_str("R01").unwrap();
+        let toc = Epoch::from_str("2021-01-01T00:15:00 UTC").unwrap();
+
+        let eph = Ephemeris::default()
+            .with_orbit("satPosX", OrbitItem::from(7003.008789))
+            .with_orbit("satPosY", OrbitItem::from(-12206.626953))
+            .with_orbit("satPosZ", OrbitItem::from(21280.765625))
+            .with_orbit("velX", OrbitItem::from(0.7835417))
+            .with_orbit("velY", OrbitItem::from(2.8042530))
+            .with_orbit("velZ", OrbitItem::from(1.3525150))
+            .with_orbit("accelY", OrbitItem::from(1.7E-9))
+            .with_orbit("accelZ", OrbitItem::from(-5.41E-9));
+
+        // independent integration, with 1s steps
+        let t = toc + Duration::from_seconds(900.0);
+        let expected_km = (7820.583625, -9648.082032, 22287.577353);
+        let expected_km_s = (1.032417340, 2.868774523, 0.881233929);
+
+        let (x, y, z) = eph.glonass_sv_position(r01, toc, t).unwrap();
+        assert!((x - expected_km.0).abs() < 1.0E-3, "x={}", x);
+        assert!((y - expected_km.1).abs() < 1.0E-3, "y={}", y);
+        assert!((z - expected_km.2).abs() < 1.0E-3, "z={}", z);
+
+        let (vx, vy, vz) = eph.glonass_sv_velocity(r01, toc, t).unwrap();
+        assert!((vx - expected_km_s.0).abs() < 1.0E-6, "vx={}", vx);
+        assert!((vy - expected_km_s.1).abs() < 1.0E-6, "vy={}", vy);
+        assert!((vz - expected_km_s.2).abs() < 1.0E-6, "vz={}", vz);
+
+        // backward propagation
+        let t = toc - Duration::from_seconds(900.0);
+        let (x, y, z) = eph.glonass_sv_position(r01, toc, t).unwrap();
+        let expected_km = (6409.728916, -14673.178737, 19860.903071);
+        assert!((x - expected_km.0).abs() < 1.0E-3, "x={}", x);
+        assert!((y - expected_km.1).abs() < 1.0E-3, "y={}", y);
+        assert!((z - expected_km.2).abs() < 1.0E-3, "z={}", z);
+
+        // validity window
+        let t = toc + Duration::from_seconds(1801.0);
+        assert!(eph.glonass_sv_position(r01, toc, t).is_none());
+
+        // not a Glonass vehicle
+        let g01 = SV::from_str("G01").unwrap();
+        assert!(eph.glonass_sv_position(g01, toc, toc).is_none());
+    }
+}
@@ -0,0 +1,366 @@
+//! Typed satellite health
+use crate::navigation::{
+    bds::{BdsHealth, BdsSatH1},
+    gal::GalHealth,
+    geo::GeoHealth,
+    glonass::{GlonassHealth, GlonassHealth2},
+    gps::GpsQzssl1cHealth,
+    Ephemeris,
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Galileo Signal Health Status (HS)
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum GalSignalStatus {
+    /// Signal OK
+    #[default]
+    Ok = 0,
+    /// Signal out of service
+    OutOfService = 1,
+    /// Signal will be out of service
+    ExtendedOperations = 2,
+    /// Signal component currently in test
+    InTest = 3,
+}
+
+impl From<u32> for GalSignalStatus {
+    fn from(val: u32) -> Self {
+        match val & 0x03 {
+            1 => Self::OutOfService,
+            2 => Self::ExtendedOperations,
+            3 => Self::InTest,
+            _ => Self::Ok,
+        }
+    }
+}
+
+/// Galileo [GalSignalHealth], for one signal component
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GalSignalHealth {
+    /// Signal Health Status (HS)
+    pub status: GalSignalStatus,
+    /// Data Validity Status (DVS): true when the navigation data
+    /// is broadcast without guarantee
+    pub without_guarantee: bool,
+}
+
+impl GalSignalHealth {
+    /// Returns true if this signal may be used for navigation
+    pub fn is_usable(&self) -> bool {
+        self.status == GalSignalStatus::Ok && !self.without_guarantee
+    }
+}
+
+/// Satellite [Health], decoded from the constellation dependent health words.
+/// Obtain it with [Ephemeris::health].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Health {
+    /// GPS and QZSS LNAV or CNAV health word
+    GpsQzss {
+        /// LNAV health summary (MSB): some or all navigation data are bad
+        nav_data_bad: bool,
+        /// Signal component health (LNAV 5 LSB, CNAV L1/L2/L5 bits): 0 when all signals are OK
+        signals: u8,
+    },
+    /// GPS and QZSS CNAV-2 health
+    GpsQzssL1c {
+        /// L1C signal is unhealthy
+        l1c_unhealthy: bool,
+    },
+    /// Galileo health
+    Galileo {
+        /// E1-B signal health
+        e1b: GalSignalHealth,
+        /// E5a signal health
+        e5a: GalSignalHealth,
+        /// E5b signal health
+        e5b: GalSignalHealth,
+        /// Signal In Space Accuracy (in meters), None when
+        /// No Accuracy Prediction is Available (NAPA)
+        sisa_m: Option<f64>,
+    },
+    /// Glonass health
+    Glonass {
+        /// Bn: unhealthy satellite, as per the immediate data
+        bn: bool,
+        /// ln: unhealthy satellite (GLO-M/K only, modern frames)
+        ln: bool,
+        /// Almanac health (Cn), when reported in modern frames
+        almanac_healthy: Option<bool>,
+    },
+    /// BeiDou D1/D2 health
+    BeiDou {
+        /// SatH1: unhealthy satellite
+        sat_h1: bool,
+    },
+    /// Modern BeiDou (CNAV1/2/3) [BdsHealth]
+    BeiDouCnav(BdsHealth),
+    /// SBAS health
+    Sbas(GeoHealth),
+    /// IRNSS health
+    Irnss {
+        /// Unhealthy satellite
+        unhealthy: bool,
+    },
+}
+
+impl Health {
+    /// Returns true if this satellite may be used for navigation:
+    /// - GPS / QZSS: all bits of the health word are cleared
+    /// - Galileo: all signals are OK (HS), with guaranteed data (DVS), and SISA is available
+    /// - Glonass: neither Bn nor ln is asserted
+    /// - BeiDou: SatH1 is cleared, or modern health is [BdsHealth::Healthy]
+    /// - SBAS: ranging is on and URA index is not 15
+    /// - IRNSS: health flag is cleared
+    pub fn is_usable(&self) -> bool {
+        match self {
+            Self::GpsQzss {
+                nav_data_bad,
+                signals,
+            } => !nav_data_bad && *signals == 0,
+            Self::GpsQzssL1c { l1c_unhealthy } => !l1c_unhealthy,
+            Self::Galileo {
+                e1b,
+                e5a,
+                e5b,
+                sisa_m,
+            } => e1b.is_usable() && e5a.is_usable() && e5b.is_usable() && sisa_m.is_some(),
+            Self::Glonass { bn, ln, .. } => !bn && !ln,
+            Self::BeiDou { sat_h1 } => !sat_h1,
+            Self::BeiDouCnav(health) => *health == BdsHealth::Healthy,
+            Self::Sbas(health) => {
+                !health.intersects(GeoHealth::GEO_HEALTH_MT17_BIT0 | GeoHealth::GEO_URA_INDEX_IS_15)
+            },
+            Self::Irnss { unhealthy } => !unhealthy,
+        }
+    }
+}
+
+impl Ephemeris {
+    /// Decodes the health words of this [Ephemeris] as [Health].
+    /// Returns None when the health field is missing.
+    /// ```
+    /// use rinex::navigation::{gal::GalHealth, Ephemeris, Health, OrbitItem};
+    ///
+    /// let eph = Ephemeris::default()
+    ///     .with_orbit("health", OrbitItem::GalHealth(GalHealth::E5B_HS_BIT0))
+    ///     .with_orbit("sisa", OrbitItem::from(3.12));
+    ///
+    /// let health = eph.health().unwrap();
+    /// assert!(!health.is_usable());
+    ///
+    /// match health {
+    ///     Health::Galileo { e1b, e5b, sisa_m, .. } => {
+    ///         assert!(e1b.is_usable());
+    ///         assert!(!e5b.is_usable());
+    ///         assert_eq!(sisa_m, Some(3.12));
+    ///     },
+    ///     _ => unreachable!("bad interpretation"),
+    /// }
+    /// ```
+    pub fn health(&self) -> Option<Health> {
+        let health = self.orbits.get("health")?;
+
+        if let Some(flag) = health.as_gps_qzss_l1l2l5_health_flag() {
+            let word: u32 = flag.into();
+            Some(Health::GpsQzss {
+                nav_data_bad: word & 0x20 > 0,
+                signals: (word & 0x1f) as u8,
+            })
+        } else if let Some(flag) = health.as_gps_qzss_l1c_health_flag() {
+            Some(Health::GpsQzssL1c {
+                l1c_unhealthy: flag.intersects(GpsQzssl1cHealth::UNHEALTHY),
+            })
+        } else if let Some(flag) = health.as_galileo_health_flag() {
+            let bits = flag.bits();
+
+            let signal = |dvs: GalHealth, hs_shift: u32| GalSignalHealth {
+                status: GalSignalStatus::from(bits >> hs_shift),
+                without_guarantee: flag.intersects(dvs),
+            };
+
+            // null SISA is not stored, NAPA is encoded as negative value
+            let sisa_m = self.get_orbit_f64("sisa").unwrap_or(0.0);
+
+            Some(Health::Galileo {
+                e1b: signal(GalHealth::E1B_DVS, 1),
+                e5a: signal(GalHealth::E5A_DVS, 4),
+                e5b: signal(GalHealth::E5B_DVS, 7),
+                sisa_m: if sisa_m < 0.0 { None } else { Some(sisa_m) },
+            })
+        } else if let Some(flag) = health.as_glonass_health_flag() {
+            let health2 = self
+                .orbits
+                .get("health2")
+                .and_then(|item| item.as_glonass_health2_flag());
+
+            Some(Health::Glonass {
+                bn: flag.intersects(GlonassHealth::UNHEALTHY),
+                ln: health2
+                    .as_ref()
+                    .map(|flag| flag.intersects(GlonassHealth2::M_K_ONLY_L3_BIT))
+                    .unwrap_or(false),
+                almanac_healthy: health2.and_then(|flag| {
+                    if flag.intersects(GlonassHealth2::ALAMANAC_IS_REPORTED) {
+                        Some(flag.intersects(GlonassHealth2::HEALTHY_ALMANAC))
+                    } else {
+                        None
+                    }
+                }),
+            })
+        } else if let Some(flag) = health.as_bds_sat_h1_flag() {
+            Some(Health::BeiDou {
+                sat_h1: flag.intersects(BdsSatH1::UNHEALTHY),
+            })
+        } else if let Some(flag) = health.as_bds_health_flag() {
+            Some(Health::BeiDouCnav(flag))
+        } else if let Some(flag) = health.as_geo_health_flag() {
+            Some(Health::Sbas(flag))
+        } else {
+            health.as_irnss_health_flag().map(|flag| Health::Irnss {
+                unhealthy: !flag.is_empty(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GalSignalStatus, Health};
+    use crate::navigation::{
+        bds::{BdsHealth, BdsSatH1},
+        gal::GalHealth,
+        geo::GeoHealth,
+        glonass::{GlonassHealth, GlonassHealth2},
+        gps::GpsQzssl1l2l5Health,
+        Ephemeris, OrbitItem,
+    };
+
+    #[test]
+    fn typed_health() {
+        // GPS
+        let eph = Ephemeris::default().with_orbit(
+            "health",
+            OrbitItem::GpsQzssl1l2l5Health(GpsQzssl1l2l5Health::from(0)),
+        );
+        assert!(eph.health().unwrap().is_usable());
+        assert!(eph.sv_healthy());
+
+        let eph = Ephemeris::default().with_orbit(
+            "health",
+            OrbitItem::GpsQzssl1l2l5Health(GpsQzssl1l2l5Health::from(0x3f)),
+        );
+        assert_eq!(
+            eph.health(),
+            Some(Health::GpsQzss {
+                nav_data_bad: true,
+                signals: 0x1f,
+            })
+        );
+        assert!(!eph.sv_healthy());
+
+        // Galileo
+        let eph = Ephemeris::default().with_orbit(
+            "health",
+            OrbitItem::GalHealth(GalHealth::E1B_HS_BIT0 | GalHealth::E1B_HS_BIT1),
+        );
+        match eph.health().unwrap() {
+            Health::Galileo {
+                e1b,
+                e5a,
+                e5b,
+                sisa_m,
+            } => {
+                assert_eq!(e1b.status, GalSignalStatus::InTest);
+                assert!(e5a.is_usable());
+                assert!(e5b.is_usable());
+                assert_eq!(sisa_m, Some(0.0));
+            },
+            health => panic!("bad interpretation: {:?}", health),
+        }
+        assert!(!eph.sv_healthy());
+
+        let eph = Ephemeris::default()
+            .with_orbit("health", OrbitItem::GalHealth(GalHealth::empty()))
+            .with_orbit("sisa", OrbitItem::from(1.2));
+        assert!(eph.sv_healthy());
+
+        // NAPA
+        let eph = eph.with_orbit("sisa", OrbitItem::from(-1.0));
+        assert!(!eph.sv_healthy());
+
+        // DVS
+        let eph = Ephemeris::default()
+            .with_orbit("health", OrbitItem::GalHealth(GalHealth::E5A_DVS))
+            .with_orbit("sisa", OrbitItem::from(1.2));
+        assert!(!eph.sv_healthy());
+
+        // Glonass
+        let eph = Ephemeris::default()
+            .with_orbit("health", OrbitItem::GlonassHealth(GlonassHealth::empty()));
+        assert_eq!(
+            eph.health(),
+            Some(Health::Glonass {
+                bn: false,
+                ln: false,
+                almanac_healthy: None,
+            })
+        );
+        assert!(eph.sv_healthy());
+
+        let eph = eph.with_orbit(
+            "health2",
+            OrbitItem::GlonassHealth2(
+                GlonassHealth2::ALAMANAC_IS_REPORTED | GlonassHealth2::M_K_ONLY_L3_BIT,
+            ),
+        );
+        assert_eq!(
+            eph.health(),
+            Some(Health::Glonass {
+                bn: false,
+                ln: true,
+                almanac_healthy: Some(false),
+            })
+        );
+        assert!(!eph.sv_healthy());
+
+        let eph = Ephemeris::default()
+            .with_orbit("health", OrbitItem::GlonassHealth(GlonassHealth::UNHEALTHY));
+        assert!(!eph.sv_healthy());
+
+        // BeiDou
+        let eph = Ephemeris::default().with_orbit("health", OrbitItem::BdsSatH1(BdsSatH1::empty()));
+        assert_eq!(eph.health(), Some(Health::BeiDou { sat_h1: false }));
+        assert!(eph.sv_healthy());
+
+        let eph =
+            Ephemeris::default().with_orbit("health", OrbitItem::BdsSatH1(BdsSatH1::UNHEALTHY));
+        assert!(!eph.sv_healthy());
+
+        let eph = Ephemeris::default()
+            .with_orbit("health", OrbitItem::BdsHealth(BdsHealth::UnhealthyTesting));
+        assert!(!eph.sv_healthy());
+
+        // SBAS
+        let eph = Ephemeris::default().with_orbit(
+            "health",
+            OrbitItem::GeoHealth(GeoHealth::GEO_HEALTH_MT17_UNAVAILABLE),
+        );
+        assert!(eph.sv_healthy());
+
+        let eph = Ephemeris::default().with_orbit(
+            "health",
+            OrbitItem::GeoHealth(GeoHealth::GEO_URA_INDEX_IS_15),
+        );
+        assert!(!eph.sv_healthy());
+
+        // missing health
+        assert!(Ephemeris::default().health().is_none());
+        assert!(!Ephemeris::default().sv_healthy());
+    }
+}
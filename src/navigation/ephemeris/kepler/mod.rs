@@ -8,6 +8,7 @@ use anise::{
 };
 
 mod helper;
+mod solver;
 pub use helper::Helper;
 
 #[cfg(doc)]
@@ -42,7 +43,7 @@ pub struct Kepler {
 }
 
 /// Orbit [Perturbations]
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Perturbations {
     /// Mean motion difference from computed value (in radians)
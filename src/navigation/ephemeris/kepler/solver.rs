@@ -0,0 +1,380 @@
+//! Broadcast Kepler solver, as per constellations ICD
+use crate::{
+    constants::Constants,
+    navigation::{Ephemeris, KeplerEphemeris},
+    prelude::{Constellation, Duration, Epoch, SV},
+};
+
+#[cfg(doc)]
+use crate::bibliography::Bibliography;
+
+/// Inclination of the BeiDou GEO orbit reference frame (in radians)
+const BDS_GEO_INCLINATION_RAD: f64 = -5.0 * std::f64::consts::PI / 180.0;
+
+/// Eccentric anomaly convergence criteria (in radians)
+const ECCENTRIC_ANOMALY_TOLERANCE_RAD: f64 = 1.0E-13;
+
+/// Solved Kepler equations, at t_k
+struct KeplerSolution {
+    /// Corrected argument of latitude (in radians)
+    u_k: f64,
+    /// Corrected radius (in meters)
+    r_k: f64,
+    /// Corrected inclination (in radians)
+    i_k: f64,
+    /// Corrected longitude of ascending node (in radians)
+    omega_k: f64,
+    /// Argument of latitude rate (in radians.s⁻¹)
+    fd_u_k: f64,
+    /// Radius rate (in m.s⁻¹)
+    fd_r_k: f64,
+    /// Inclination rate (in radians.s⁻¹)
+    fd_i_k: f64,
+    /// Longitude of ascending node rate (in radians.s⁻¹)
+    fd_omega_k: f64,
+    /// Elapsed time since ToE (in seconds)
+    t_k: f64,
+    /// Relativistic clock correction (in seconds)
+    dtr: f64,
+}
+
+impl KeplerSolution {
+    /// Solves Kepler equations for this [KeplerEphemeris] of this [SV] at t_k.
+    fn new(sv: SV, eph: &KeplerEphemeris, t_k: f64) -> Self {
+        let gm_m3_s2 = Constants::gm(sv);
+        let omega_e = Constants::omega(sv);
+
+        let (kepler, perturbations) = (&eph.kepler, &eph.perturbations);
+
+        let n0 = (gm_m3_s2 / kepler.a.powi(3)).sqrt();
+        let n = n0 + perturbations.dn;
+        let m_k = kepler.m_0 + n * t_k;
+
+        // Newton-Raphson
+        let mut e_k = m_k;
+        for _ in 0..Constants::MAX_KEPLER_ITER {
+            let de_k = (e_k - kepler.e * e_k.sin() - m_k) / (1.0 - kepler.e * e_k.cos());
+            e_k -= de_k;
+            if de_k.abs() < ECCENTRIC_ANOMALY_TOLERANCE_RAD {
+                break;
+            }
+        }
+
+        let (sin_e_k, cos_e_k) = e_k.sin_cos();
+        let v_k = ((1.0 - kepler.e.powi(2)).sqrt() * sin_e_k).atan2(cos_e_k - kepler.e);
+
+        let phi_k = v_k + kepler.omega;
+        let (sin_2phi_k, cos_2phi_k) = (2.0 * phi_k).sin_cos();
+
+        let du_k = perturbations.cus * sin_2phi_k + perturbations.cuc * cos_2phi_k;
+        let dr_k = perturbations.crs * sin_2phi_k + perturbations.crc * cos_2phi_k;
+        let di_k = perturbations.cis * sin_2phi_k + perturbations.cic * cos_2phi_k;
+
+        let u_k = phi_k + du_k;
+        let r_k = kepler.a * (1.0 - kepler.e * cos_e_k) + dr_k;
+        let i_k = kepler.i_0 + di_k + perturbations.i_dot * t_k;
+
+        // Earth rotation since ToE: BeiDou GEO are expressed in the inertial frame first
+        let fd_omega_k = if sv.is_beidou_geo() {
+            perturbations.omega_dot
+        } else {
+            perturbations.omega_dot - omega_e
+        };
+
+        let omega_k = kepler.omega_0 + fd_omega_k * t_k - omega_e * kepler.toe;
+
+        // analytical derivatives
+        let fd_e_k = n / (1.0 - kepler.e * cos_e_k);
+        let fd_v_k = fd_e_k * (1.0 - kepler.e.powi(2)).sqrt() / (1.0 - kepler.e * cos_e_k);
+
+        let fd_u_k = fd_v_k
+            * (1.0 + 2.0 * (perturbations.cus * cos_2phi_k - perturbations.cuc * sin_2phi_k));
+
+        let fd_r_k = kepler.a * kepler.e * sin_e_k * fd_e_k
+            + 2.0 * fd_v_k * (perturbations.crs * cos_2phi_k - perturbations.crc * sin_2phi_k);
+
+        let fd_i_k = perturbations.i_dot
+            + 2.0 * fd_v_k * (perturbations.cis * cos_2phi_k - perturbations.cic * sin_2phi_k);
+
+        let dtr = Constants::dtr_f(sv) * kepler.e * kepler.a.sqrt() * sin_e_k;
+
+        Self {
+            u_k,
+            r_k,
+            i_k,
+            omega_k,
+            fd_u_k,
+            fd_r_k,
+            fd_i_k,
+            fd_omega_k,
+            t_k,
+            dtr,
+        }
+    }
+
+    /// Position in the orbital plane (in meters)
+    fn orbital_position(&self) -> (f64, f64) {
+        let (sin_u_k, cos_u_k) = self.u_k.sin_cos();
+        (self.r_k * cos_u_k, self.r_k * sin_u_k)
+    }
+
+    /// Returns the BeiDou GEO Rz(Ωe t_k) Rx(-5°) rotation, and its temporal derivative,
+    /// applied to this (position, velocity) duplet.
+    fn beidou_geo_rotation(
+        &self,
+        sv: SV,
+        (x, y, z): (f64, f64, f64),
+        (vx, vy, vz): (f64, f64, f64),
+    ) -> ((f64, f64, f64), (f64, f64, f64)) {
+        let omega_e = Constants::omega(sv);
+        let (sin_x, cos_x) = BDS_GEO_INCLINATION_RAD.sin_cos();
+        let (sin_z, cos_z) = (omega_e * self.t_k).sin_cos();
+
+        // Rx(-5°)
+        let (y, z) = (cos_x * y + sin_x * z, -sin_x * y + cos_x * z);
+        let (vy, vz) = (cos_x * vy + sin_x * vz, -sin_x * vy + cos_x * vz);
+
+        // Rz(Ωe t_k)
+        let position = (cos_z * x + sin_z * y, -sin_z * x + cos_z * y, z);
+        let velocity = (
+            cos_z * vx + sin_z * vy + omega_e * (-sin_z * x + cos_z * y),
+            -sin_z * vx + cos_z * vy + omega_e * (-cos_z * x - sin_z * y),
+            vz,
+        );
+
+        (position, velocity)
+    }
+
+    /// ECEF position (in meters)
+    fn position(&self, sv: SV) -> (f64, f64, f64) {
+        let (sin_omega_k, cos_omega_k) = self.omega_k.sin_cos();
+        let (sin_i_k, cos_i_k) = self.i_k.sin_cos();
+        let (x_k, y_k) = self.orbital_position();
+
+        let x = x_k * cos_omega_k - y_k * cos_i_k * sin_omega_k;
+        let y = x_k * sin_omega_k + y_k * cos_i_k * cos_omega_k;
+        let z = y_k * sin_i_k;
+
+        if !sv.is_beidou_geo() {
+            return (x, y, z);
+        }
+
+        self.beidou_geo_rotation(sv, (x, y, z), (0.0, 0.0, 0.0)).0
+    }
+
+    /// ECEF velocity (in m.s⁻¹)
+    fn velocity(&self, sv: SV) -> (f64, f64, f64) {
+        let (sin_omega_k, cos_omega_k) = self.omega_k.sin_cos();
+        let (sin_i_k, cos_i_k) = self.i_k.sin_cos();
+        let (sin_u_k, cos_u_k) = self.u_k.sin_cos();
+        let (x_k, y_k) = self.orbital_position();
+
+        // orbital plane velocity
+        let fd_x_k = self.fd_r_k * cos_u_k - self.r_k * self.fd_u_k * sin_u_k;
+        let fd_y_k = self.fd_r_k * sin_u_k + self.r_k * self.fd_u_k * cos_u_k;
+
+        let vx = -x_k * self.fd_omega_k * sin_omega_k + fd_x_k * cos_omega_k
+            - fd_y_k * sin_omega_k * cos_i_k
+            - y_k * (self.fd_omega_k * cos_omega_k * cos_i_k - self.fd_i_k * sin_omega_k * sin_i_k);
+
+        let vy = x_k * self.fd_omega_k * cos_omega_k
+            + fd_x_k * sin_omega_k
+            + fd_y_k * cos_omega_k * cos_i_k
+            - y_k * (self.fd_omega_k * sin_omega_k * cos_i_k + self.fd_i_k * cos_omega_k * sin_i_k);
+
+        let vz = fd_y_k * sin_i_k + y_k * self.fd_i_k * cos_i_k;
+
+        if !sv.is_beidou_geo() {
+            return (vx, vy, vz);
+        }
+
+        let position = (
+            x_k * cos_omega_k - y_k * cos_i_k * sin_omega_k,
+            x_k * sin_omega_k + y_k * cos_i_k * cos_omega_k,
+            y_k * sin_i_k,
+        );
+
+        self.beidou_geo_rotation(sv, position, (vx, vy, vz)).1
+    }
+}
+
+impl Ephemeris {
+    /// Returns elapsed time (in seconds) between ToE and t, in the [SV] timescale.
+    fn kepler_t_k(&self, sv: SV, t: Epoch) -> Option<f64> {
+        let timescale = sv.constellation.timescale()?;
+        let toe = self.toe(sv)?;
+        Some((t.to_time_scale(timescale) - toe).to_seconds())
+    }
+
+    /// Solves the broadcast Kepler equations for this [SV] at t, as described in
+    /// each constellation ICD. Returns None if this [Ephemeris] is not Keplerian.
+    fn kepler_solution(&self, sv: SV, t: Epoch) -> Option<KeplerSolution> {
+        match sv.constellation {
+            Constellation::GPS
+            | Constellation::QZSS
+            | Constellation::Galileo
+            | Constellation::BeiDou => {},
+            _ => return None,
+        }
+
+        let typed = self.to_typed(sv)?;
+        let eph = typed.as_kepler()?;
+        let t_k = self.kepler_t_k(sv, t)?;
+        Some(KeplerSolution::new(sv, eph, t_k))
+    }
+
+    /// Returns ECEF position (in km) of this [SV] at t [Epoch], obtained from
+    /// this broadcast [Ephemeris], as per the ICD Kepler algorithm (GPS, Galileo,
+    /// BeiDou MEO, IGSO and GEO, and QZSS). Earth rotation since
+    /// ToE is compensated, so the position is expressed in the ECEF frame at t.
+    /// t should be the signal transmission time, and should lie within the
+    /// [Ephemeris] validity period. Refer to each constellation ICD, or [Bibliography::BeiDouICD].
+    /// Returns None for Glonass and SBAS, which are not Keplerian.
+    /// ```
+    /// use rinex::navigation::{Ephemeris, Kepler};
+    /// use rinex::prelude::{Epoch, SV, TimeScale};
+    /// use std::str::FromStr;
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    ///
+    /// let eph = Ephemeris::default()
+    ///     .with_week(2138)
+    ///     .with_kepler(Kepler {
+    ///         a: 5153.6_f64.powi(2),
+    ///         e: 0.01,
+    ///         i_0: 0.97,
+    ///         omega_0: -2.2,
+    ///         m_0: 1.1,
+    ///         omega: 0.85,
+    ///         toe: 432000.0,
+    ///     });
+    ///
+    /// let toe = Epoch::from_time_of_week(2138, 432_000_000_000_000, TimeScale::GPST);
+    ///
+    /// let (x_km, y_km, z_km) = eph.sv_position(g01, toe).unwrap();
+    /// let r_km = (x_km.powi(2) + y_km.powi(2) + z_km.powi(2)).sqrt();
+    ///
+    /// assert!(r_km > 26_000.0 && r_km < 27_000.0);
+    /// ```
+    pub fn sv_position(&self, sv: SV, t: Epoch) -> Option<(f64, f64, f64)> {
+        let (x, y, z) = self.kepler_solution(sv, t)?.position(sv);
+        Some((x / 1000.0, y / 1000.0, z / 1000.0))
+    }
+
+    /// Returns ECEF velocity (in km.s⁻¹) of this [SV] at t [Epoch], obtained from
+    /// this broadcast [Ephemeris], by analytical differentiation of the Kepler solution
+    /// (see [Self::sv_position]). This includes the Earth rotation, so this is the
+    /// velocity in the rotating ECEF frame, as required for Doppler prediction.
+    /// Returns None for Glonass and SBAS, which are not Keplerian.
+    /// ```
+    /// use rinex::navigation::{Ephemeris, Kepler};
+    /// use rinex::prelude::{Epoch, SV, TimeScale};
+    /// use std::str::FromStr;
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    ///
+    /// let eph = Ephemeris::default()
+    ///     .with_week(2138)
+    ///     .with_kepler(Kepler {
+    ///         a: 5153.6_f64.powi(2),
+    ///         e: 0.01,
+    ///         i_0: 0.97,
+    ///         omega_0: -2.2,
+    ///         m_0: 1.1,
+    ///         omega: 0.85,
+    ///         toe: 432000.0,
+    ///     });
+    ///
+    /// let toe = Epoch::from_time_of_week(2138, 432_000_000_000_000, TimeScale::GPST);
+    ///
+    /// let (vx, vy, vz) = eph.sv_velocity(g01, toe).unwrap();
+    /// let v_km_s = (vx.powi(2) + vy.powi(2) + vz.powi(2)).sqrt();
+    ///
+    /// // MEO orbital velocity (about 3.9 km/s), minus earth rotation
+    /// assert!(v_km_s > 1.0 && v_km_s < 4.0);
+    /// ```
+    pub fn sv_velocity(&self, sv: SV, t: Epoch) -> Option<(f64, f64, f64)> {
+        let (vx, vy, vz) = self.kepler_solution(sv, t)?.velocity(sv);
+        Some((vx / 1000.0, vy / 1000.0, vz / 1000.0))
+    }
+
+    /// Returns the [SV] clock correction at t [Epoch], obtained from this
+    /// broadcast [Ephemeris] published at toc (Time of Clock): the onboard clock polynomial
+    /// (bias, drift and drift rate), plus the relativistic correction due to the orbit eccentricity,
+    /// so that t = t_sv - correction. This applies to dual frequency users of the reference signals
+    /// of each constellation (GPS L1/L2, Galileo E1/E5, BeiDou B3): single frequency users
+    /// should prefer [Self::sv_single_frequency_clock_correction]. Glonass and SBAS broadcast a
+    /// linear model which already includes the relativistic effects.
+    /// ```
+    /// use rinex::navigation::{Ephemeris, Kepler};
+    /// use rinex::prelude::{Duration, Epoch, SV, TimeScale};
+    /// use std::str::FromStr;
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    ///
+    /// let mut eph = Ephemeris::default()
+    ///     .with_week(2138)
+    ///     .with_kepler(Kepler {
+    ///         a: 5153.6_f64.powi(2),
+    ///         e: 0.01,
+    ///         i_0: 0.97,
+    ///         omega_0: -2.2,
+    ///         m_0: 1.1,
+    ///         omega: 0.85,
+    ///         toe: 432000.0,
+    ///     });
+    ///
+    /// eph.clock_bias = 1.0E-4;
+    /// eph.clock_drift = 1.0E-11;
+    ///
+    /// let toc = Epoch::from_time_of_week(2138, 432_000_000_000_000, TimeScale::GPST);
+    /// let t = toc + Duration::from_seconds(1800.0);
+    ///
+    /// let dt = eph.sv_clock_correction(g01, toc, t).unwrap();
+    ///
+    /// // relativistic correction is a few tens of nanoseconds at most
+    /// assert!((dt.to_seconds() - 1.000180E-4).abs() < 50.0E-9);
+    /// ```
+    pub fn sv_clock_correction(&self, sv: SV, toc: Epoch, t: Epoch) -> Option<Duration> {
+        let correction_s = self.sv_clock_correction_seconds(sv, toc, t)?;
+        Some(Duration::from_seconds(correction_s))
+    }
+
+    /// [Self::sv_clock_correction] expressed in seconds.
+    fn sv_clock_correction_seconds(&self, sv: SV, toc: Epoch, t: Epoch) -> Option<f64> {
+        let timescale = sv.constellation.timescale()?;
+        let dt = (t.to_time_scale(timescale) - toc.to_time_scale(timescale)).to_seconds();
+
+        if sv.constellation.is_sbas() || sv.constellation == Constellation::Glonass {
+            // -TauN + GammaN (t - tb), drift rate field is the message frame time
+            return Some(self.clock_bias + self.clock_drift * dt);
+        }
+
+        let polynomial =
+            self.clock_bias + self.clock_drift * dt + self.clock_drift_rate * dt.powi(2);
+
+        let dtr = self.kepler_solution(sv, t)?.dtr;
+
+        Some(polynomial + dtr)
+    }
+
+    /// Returns the [SV] clock correction at t [Epoch], for single frequency users
+    /// of the primary signal (GPS L1 C/A, Galileo E1, BeiDou B1I): [Self::sv_clock_correction]
+    /// minus the broadcast group delay (GPS TGD, Galileo BGD E1/E5b, BeiDou TGD1),
+    /// when broadcast. This is identical to [Self::sv_clock_correction] for Glonass and SBAS.
+    pub fn sv_single_frequency_clock_correction(
+        &self,
+        sv: SV,
+        toc: Epoch,
+        t: Epoch,
+    ) -> Option<Duration> {
+        let correction_s = self.sv_clock_correction_seconds(sv, toc, t)?;
+
+        let tgd = self
+            .to_typed(sv)
+            .and_then(|typed| typed.as_kepler().and_then(|eph| eph.tgd))
+            .unwrap_or(0.0);
+
+        Some(Duration::from_seconds(correction_s - tgd))
+    }
+}
@@ -5,13 +5,15 @@ mod parsing;
 /// Ephemeris NAV flags definitions & support
 pub mod flags;
 
+/// Typed satellite health
+pub mod health;
+
 use orbits::OrbitItem;
 
-use flags::{
-    bds::{BdsHealth, BdsSatH1},
-    glonass::{GlonassHealth, GlonassHealth2},
-    gps::GpsQzssl1cHealth,
-};
+use flags::bds::BdsHealth;
+
+#[cfg(doc)]
+use health::Health;
 
 #[cfg(feature = "log")]
 use log::error;
@@ -20,6 +22,14 @@ use log::error;
 #[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
 pub mod kepler;
 
+#[cfg(feature = "nav")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+pub mod typed;
+
+#[cfg(feature = "nav")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+mod glonass; // state vector propagation
+
 #[cfg(feature = "nav")]
 use crate::prelude::nav::Almanac;
 
@@ -202,41 +212,11 @@ impl Ephemeris {
     }
 
     /// Returns true if this [Ephemeris] declares attached SV as suitable for navigation.
+    /// See [Health::is_usable] for the interpretation of each constellation.
     pub fn sv_healthy(&self) -> bool {
-        let health = self.orbits.get("health");
-
-        if health.is_none() {
-            return false;
-        }
-
-        let health = health.unwrap();
-
-        if let Some(flag) = health.as_gps_qzss_l1l2l5_health_flag() {
-            flag.healthy()
-        } else if let Some(flag) = health.as_gps_qzss_l1c_health_flag() {
-            !flag.intersects(GpsQzssl1cHealth::UNHEALTHY)
-        } else if let Some(flag) = health.as_glonass_health_flag() {
-            // TODO: Status mask .. ?
-            if let Some(flag2) = self
-                .orbits
-                .get("health2")
-                .and_then(|item| Some(item.as_glonass_health2_flag().unwrap()))
-            {
-                !flag.intersects(GlonassHealth::UNHEALTHY)
-                    && flag2.intersects(GlonassHealth2::HEALTHY_ALMANAC)
-            } else {
-                !flag.intersects(GlonassHealth::UNHEALTHY)
-            }
-        } else if let Some(flag) = health.as_geo_health_flag() {
-            // TODO !
-            false
-        } else if let Some(flag) = health.as_bds_sat_h1_flag() {
-            !flag.intersects(BdsSatH1::UNHEALTHY)
-        } else if let Some(flag) = health.as_bds_health_flag() {
-            flag == BdsHealth::Healthy
-        } else {
-            false
-        }
+        self.health()
+            .map(|health| health.is_usable())
+            .unwrap_or(false)
     }
 
     /// Returns true if this [Ephemeris] message declares this satellite in testing mode.
@@ -309,6 +289,12 @@ impl Ephemeris {
 
     /// Calculates Clock correction for [SV] at [Epoch] based on [Self]
     /// and ToC [Epoch] of publication of [Self] from the free running clock.
+    /// This only evaluates the clock polynomial: [Self::sv_clock_correction]
+    /// also includes the relativistic correction.
+    #[deprecated(
+        since = "0.20.0",
+        note = "use sv_clock_correction(), which includes the relativistic correction"
+    )]
     pub fn clock_correction(
         &self,
         toc: Epoch,
@@ -0,0 +1,308 @@
+//! Typed Ephemeris
+use crate::{
+    navigation::{Ephemeris, Kepler, Perturbations},
+    prelude::{Constellation, SV},
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Onboard [ClockPolynomial], referenced to the Time of Clock
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ClockPolynomial {
+    /// Clock bias (in seconds)
+    pub bias: f64,
+    /// Clock drift (s.s⁻¹)
+    pub drift: f64,
+    /// Clock drift rate (s.s⁻²)
+    pub drift_rate: f64,
+}
+
+/// [KeplerEphemeris] describes the orbit with Keplerian elements:
+/// GPS, Galileo, BeiDou, QZSS and IRNSS.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct KeplerEphemeris {
+    /// Onboard [ClockPolynomial]
+    pub clock: ClockPolynomial,
+    /// Week counter, in the [SV] timescale
+    pub week: u32,
+    /// [Kepler]ian elements
+    pub kepler: Kepler,
+    /// Orbit [Perturbations]
+    pub perturbations: Perturbations,
+    /// Issue of Data (IODE, IODnav or AODE), when broadcast
+    pub iod: Option<u32>,
+    /// Total group delay (in seconds) of the primary signal, when broadcast
+    pub tgd: Option<f64>,
+    /// True if this [SV] is declared suitable for navigation
+    pub healthy: bool,
+}
+
+/// [StateVectorEphemeris] describes the orbit with a state vector
+/// (ECEF position, velocity and acceleration): Glonass and SBAS.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct StateVectorEphemeris {
+    /// Onboard [ClockPolynomial]
+    pub clock: ClockPolynomial,
+    /// ECEF position (in km)
+    pub position_km: (f64, f64, f64),
+    /// ECEF velocity (in km.s⁻¹)
+    pub velocity_km_s: (f64, f64, f64),
+    /// ECEF acceleration (in km.s⁻²)
+    pub acceleration_km_s2: (f64, f64, f64),
+    /// Glonass frequency channel
+    pub channel: Option<i8>,
+    /// Issue of Data (IODN), when broadcast
+    pub iod: Option<u32>,
+    /// True if this [SV] is declared suitable for navigation
+    pub healthy: bool,
+}
+
+/// [TypedEphemeris] is the interpretation of an [Ephemeris] frame, per [Constellation].
+/// Obtain it with [Ephemeris::to_typed]. Data fields that are not interpreted
+/// remain accessible in the raw [Ephemeris] dictionary.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum TypedEphemeris {
+    /// GPS [KeplerEphemeris]
+    GPS(KeplerEphemeris),
+    /// Galileo [KeplerEphemeris]
+    Galileo(KeplerEphemeris),
+    /// BeiDou [KeplerEphemeris]
+    BeiDou(KeplerEphemeris),
+    /// QZSS [KeplerEphemeris]
+    QZSS(KeplerEphemeris),
+    /// IRNSS [KeplerEphemeris]
+    IRNSS(KeplerEphemeris),
+    /// Glonass [StateVectorEphemeris]
+    Glonass(StateVectorEphemeris),
+    /// SBAS [StateVectorEphemeris]
+    SBAS(StateVectorEphemeris),
+}
+
+impl TypedEphemeris {
+    /// Returns onboard [ClockPolynomial]
+    pub fn clock(&self) -> &ClockPolynomial {
+        match self {
+            Self::GPS(eph)
+            | Self::Galileo(eph)
+            | Self::BeiDou(eph)
+            | Self::QZSS(eph)
+            | Self::IRNSS(eph) => &eph.clock,
+            Self::Glonass(eph) | Self::SBAS(eph) => &eph.clock,
+        }
+    }
+
+    /// Returns true if this [SV] is declared suitable for navigation
+    pub fn healthy(&self) -> bool {
+        match self {
+            Self::GPS(eph)
+            | Self::Galileo(eph)
+            | Self::BeiDou(eph)
+            | Self::QZSS(eph)
+            | Self::IRNSS(eph) => eph.healthy,
+            Self::Glonass(eph) | Self::SBAS(eph) => eph.healthy,
+        }
+    }
+
+    /// Unwraps this [TypedEphemeris] as [KeplerEphemeris] (if possible)
+    pub fn as_kepler(&self) -> Option<&KeplerEphemeris> {
+        match self {
+            Self::GPS(eph)
+            | Self::Galileo(eph)
+            | Self::BeiDou(eph)
+            | Self::QZSS(eph)
+            | Self::IRNSS(eph) => Some(eph),
+            _ => None,
+        }
+    }
+
+    /// Unwraps this [TypedEphemeris] as [StateVectorEphemeris] (if possible)
+    pub fn as_state_vector(&self) -> Option<&StateVectorEphemeris> {
+        match self {
+            Self::Glonass(eph) | Self::SBAS(eph) => Some(eph),
+            _ => None,
+        }
+    }
+}
+
+impl Ephemeris {
+    /// Returns the first of these fields that was broadcast, as f64
+    fn first_orbit_f64(&self, fields: &[&str]) -> Option<f64> {
+        fields.iter().find_map(|field| self.get_orbit_f64(field))
+    }
+
+    /// Interprets this [Ephemeris], broadcast by this [SV], as [TypedEphemeris].
+    /// Null orbital fields are not stored in the dictionary, so they are interpreted as zero,
+    /// except for the fields that define the orbit (ToE, Keplerian elements or state vector)
+    /// that are mandatory. Returns None if one of them is missing or if this
+    /// [Constellation] is not supported.
+    /// ```
+    /// use rinex::navigation::{Ephemeris, OrbitItem, TypedEphemeris};
+    /// use rinex::prelude::SV;
+    /// use std::str::FromStr;
+    ///
+    /// let r01 = SV::from_str("R01").unwrap();
+    ///
+    /// let eph = Ephemeris::default()
+    ///     .with_orbit("satPosX", OrbitItem::from(1000.0))
+    ///     .with_orbit("satPosY", OrbitItem::from(2000.0))
+    ///     .with_orbit("satPosZ", OrbitItem::from(3000.0))
+    ///     .with_orbit("velX", OrbitItem::from(1.0));
+    ///
+    /// let typed = eph.to_typed(r01).unwrap();
+    /// let state = typed.as_state_vector().unwrap();
+    ///
+    /// assert_eq!(state.position_km, (1000.0, 2000.0, 3000.0));
+    /// assert_eq!(state.velocity_km_s, (1.0, 0.0, 0.0));
+    ///
+    /// // Keplerian elements are missing
+    /// assert!(eph.to_typed(SV::from_str("G01").unwrap()).is_none());
+    /// ```
+    pub fn to_typed(&self, sv: SV) -> Option<TypedEphemeris> {
+        let clock = ClockPolynomial {
+            bias: self.clock_bias,
+            drift: self.clock_drift,
+            drift_rate: self.clock_drift_rate,
+        };
+
+        let healthy = self.sv_healthy();
+        let f64_or_zero = |field: &str| self.get_orbit_f64(field).unwrap_or(0.0);
+
+        if sv.constellation.is_sbas() || sv.constellation == Constellation::Glonass {
+            let eph = StateVectorEphemeris {
+                clock,
+                position_km: (
+                    self.get_orbit_f64("satPosX")?,
+                    self.get_orbit_f64("satPosY")?,
+                    self.get_orbit_f64("satPosZ")?,
+                ),
+                velocity_km_s: (
+                    f64_or_zero("velX"),
+                    f64_or_zero("velY"),
+                    f64_or_zero("velZ"),
+                ),
+                acceleration_km_s2: (
+                    f64_or_zero("accelX"),
+                    f64_or_zero("accelY"),
+                    f64_or_zero("accelZ"),
+                ),
+                channel: self.glonass_freq_channel(),
+                iod: self.get_orbit_f64("iodn").map(|iod| iod.round() as u32),
+                healthy,
+            };
+
+            return if sv.constellation == Constellation::Glonass {
+                Some(TypedEphemeris::Glonass(eph))
+            } else {
+                Some(TypedEphemeris::SBAS(eph))
+            };
+        }
+
+        let eph = KeplerEphemeris {
+            clock,
+            week: self.get_week()?,
+            kepler: self.kepler()?,
+            perturbations: Perturbations {
+                dn: f64_or_zero("deltaN"),
+                i_dot: f64_or_zero("idot"),
+                omega_dot: f64_or_zero("omegaDot"),
+                cus: f64_or_zero("cus"),
+                cuc: f64_or_zero("cuc"),
+                cis: f64_or_zero("cis"),
+                cic: f64_or_zero("cic"),
+                crs: f64_or_zero("crs"),
+                crc: f64_or_zero("crc"),
+            },
+            iod: self
+                .first_orbit_f64(&["iode", "iodnav", "aode"])
+                .map(|iod| iod.round() as u32),
+            tgd: self.first_orbit_f64(&["tgd", "bgdE5bE1", "tgd1b1b3", "tgdb1b3"]),
+            healthy,
+        };
+
+        match sv.constellation {
+            Constellation::GPS => Some(TypedEphemeris::GPS(eph)),
+            Constellation::Galileo => Some(TypedEphemeris::Galileo(eph)),
+            Constellation::BeiDou => Some(TypedEphemeris::BeiDou(eph)),
+            Constellation::QZSS => Some(TypedEphemeris::QZSS(eph)),
+            Constellation::IRNSS => Some(TypedEphemeris::IRNSS(eph)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypedEphemeris;
+    use crate::{
+        navigation::{gps::GpsQzssl1l2l5Health, Ephemeris, Kepler, OrbitItem},
+        prelude::SV,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn typed_kepler_ephemeris() {
+        let kepler = Kepler {
+            a: 26_560_000.0_f64,
+            e: 0.01,
+            i_0: 0.96,
+            omega_0: 1.2,
+            m_0: -0.5,
+            omega: 0.7,
+            toe: 7200.0,
+        };
+
+        let mut eph = Ephemeris::default()
+            .with_kepler(kepler.clone())
+            .with_week(2100)
+            .with_orbit("crs", OrbitItem::from(-20.0))
+            .with_orbit("iode", OrbitItem::from(42.0))
+            .with_orbit("tgd", OrbitItem::from(-1.0E-8))
+            .with_orbit(
+                "health",
+                OrbitItem::GpsQzssl1l2l5Health(GpsQzssl1l2l5Health::from(0)),
+            );
+
+        eph.clock_bias = 1.0E-4;
+
+        let g01 = SV::from_str("G01").unwrap();
+        let typed = eph.to_typed(g01).unwrap();
+
+        assert!(matches!(typed, TypedEphemeris::GPS(_)));
+        assert!(typed.healthy());
+        assert_eq!(typed.clock().bias, 1.0E-4);
+        assert!(typed.as_state_vector().is_none());
+
+        let kep = typed.as_kepler().unwrap();
+        assert_eq!(kep.week, 2100);
+        assert_eq!(kep.iod, Some(42));
+        assert_eq!(kep.tgd, Some(-1.0E-8));
+        assert_eq!(kep.perturbations.crs, -20.0);
+        assert_eq!(kep.perturbations.cuc, 0.0);
+        assert!((kep.kepler.a - kepler.a).abs() < 1.0E-6);
+        assert_eq!(kep.kepler.e, kepler.e);
+
+        // same frame, other constellations
+        let e01 = SV::from_str("E01").unwrap();
+        assert!(matches!(
+            eph.to_typed(e01),
+            Some(TypedEphemeris::Galileo(_))
+        ));
+
+        let j01 = SV::from_str("J01").unwrap();
+        assert!(matches!(eph.to_typed(j01), Some(TypedEphemeris::QZSS(_))));
+
+        // state vector is missing
+        let r01 = SV::from_str("R01").unwrap();
+        assert!(eph.to_typed(r01).is_none());
+
+        // week counter is mandatory
+        let mut eph = eph.clone();
+        eph.orbits.remove("week");
+        assert!(eph.to_typed(g01).is_none());
+    }
+}
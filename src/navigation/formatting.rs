@@ -13,6 +13,7 @@ pub(crate) struct NavFormatter {
     value: f64,
     width: usize,
     precision: usize,
+    exponent: char,
 }
 
 impl NavFormatter {
@@ -21,14 +22,25 @@ impl NavFormatter {
             value,
             width: 15,
             precision: 12,
+            exponent: 'E',
         }
     }
 
+    /// D19.12 field, following revision: RINEX2 uses 'D' exponents.
+    pub fn new_v2v3(value: f64, v2: bool) -> Self {
+        let mut s = Self::new(value);
+        if v2 {
+            s.exponent = 'D';
+        }
+        s
+    }
+
     pub fn new_iono_alpha_beta(value: f64) -> Self {
         Self {
             value,
             width: 3,
             precision: 4,
+            exponent: 'E',
         }
     }
 
@@ -37,6 +49,7 @@ impl NavFormatter {
             value,
             width: 17,
             precision: 12,
+            exponent: 'E',
         }
     }
 
@@ -45,6 +58,7 @@ impl NavFormatter {
             value,
             width: 14,
             precision: 10,
+            exponent: 'E',
         }
     }
 
@@ -53,6 +67,7 @@ impl NavFormatter {
             value,
             width: 13,
             precision: 9,
+            exponent: 'E',
         }
     }
 }
@@ -79,7 +94,11 @@ impl std::fmt::Display for NavFormatter {
                 .parse::<i32>()
                 .unwrap();
             let formatted_exponent = format!("{}{:02}", exp_sign, exp_value);
-            write!(f, "{}{}E{}", sign_str, base, formatted_exponent)
+            write!(
+                f,
+                "{}{}{}{}",
+                sign_str, base, self.exponent, formatted_exponent
+            )
         } else {
             write!(f, "{}", formatted)
         }
@@ -94,20 +113,18 @@ fn format_epoch_v2v3<W: Write>(
 ) -> std::io::Result<()> {
     let (yyyy, m, d, hh, mm, ss, nanos) = epoch_decomposition(k.epoch);
 
-    let decis = nanos / 100_000;
-
     if v2 && *file_constell != Constellation::Mixed {
+        // I2,1X,I2.2,1X,I2,1X,I2,1X,I2,1X,I2,F5.1
         write!(
             w,
-            "{:02} {:02} {:02} {:02} {:02} {:02} {:2}.{:01}",
+            "{:2} {:02} {:2} {:2} {:2} {:2}{:5.1}",
             k.sv.prn,
-            yyyy - 2000,
+            yyyy % 100,
             m,
             d,
             hh,
             mm,
-            ss,
-            decis
+            ss as f64 + nanos as f64 * 1.0E-9,
         )
     } else {
         write!(
@@ -305,7 +322,7 @@ mod test {
 
         let utf8_ascii = inner.to_ascii_utf8();
 
-        assert_eq!(&utf8_ascii, "01 23 01 01 00 00  0.0");
+        assert_eq!(&utf8_ascii, " 1 23  1  1  0  0  0.0");
     }
 
     #[test]
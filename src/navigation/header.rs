@@ -25,6 +25,14 @@ impl HeaderFields {
         self.time_offsets.push(offset);
     }
 
+    /// Returns [TimeOffset] definition between these two [TimeScale]s, if any.
+    /// See [TimeOffset::evaluate].
+    pub fn time_offset(&self, lhs: TimeScale, rhs: TimeScale) -> Option<&TimeOffset> {
+        self.time_offsets
+            .iter()
+            .find(|offset| offset.lhs == lhs && offset.rhs == rhs)
+    }
+
     /// Format according to RINEX standards
     pub(crate) fn format<W: Write>(
         &self,
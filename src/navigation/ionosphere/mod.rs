@@ -33,62 +33,55 @@ impl IonosphereModel {
     /// In this case, it will apply for the entire day course.
     /// Two models may exist: Klobuchar and NequickG.
     pub(crate) fn from_rinex3_header(header: &str) -> Result<Self, ParsingError> {
-        let (system, model_params) = header.split_at(5);
-        let rem = model_params.replace("D", "E");
-        match system.trim() {
-            /*
-             * Models that only needs 3 fields
-             */
-            "GAL" => {
-                let (a0, rem) = rem.split_at(12);
-                let (a1, rem) = rem.split_at(12);
-                let (a2, _) = rem.split_at(12);
-                let a0 = f64::from_str(a0.trim()).map_err(|_| ParsingError::NequickGData)?;
-                let a1 = f64::from_str(a1.trim()).map_err(|_| ParsingError::NequickGData)?;
-                let a2 = f64::from_str(a2.trim()).map_err(|_| ParsingError::NequickGData)?;
-                Ok(Self::NequickG(NgModel {
-                    a: (a0, a1, a2),
-                    // TODO: is this not the 4th field? double check
-                    region: NgRegionFlags::default(),
+        let header = header.replace('D', "E").replace('d', "e");
+        let (system, model_params) = header.split_at(std::cmp::min(5, header.len()));
+        let system = system.trim();
+
+        // up to 4 coefficients, possibly followed by time mark and SV ID (V3.04)
+        let coefficients = model_params
+            .split_ascii_whitespace()
+            .take(4)
+            .map(f64::from_str)
+            .collect::<Result<Vec<_>, _>>();
+
+        if system == "GAL" {
+            let a = coefficients.map_err(|_| ParsingError::NequickGData)?;
+            if a.len() < 3 {
+                return Err(ParsingError::NequickGData);
+            }
+            // fourth field is the region (disturbance) flags
+            let flags = a.get(3).copied().unwrap_or_default();
+            Ok(Self::NequickG(NgModel {
+                a: (a[0], a[1], a[2]),
+                region: NgRegionFlags::from_bits(flags as u16).unwrap_or(NgRegionFlags::empty()),
+            }))
+        } else {
+            let a = coefficients.map_err(|_| ParsingError::KlobucharData)?;
+            if a.len() < 4 {
+                return Err(ParsingError::KlobucharData);
+            }
+            let a = (a[0], a[1], a[2], a[3]);
+
+            // World or QZSS special orbital plan
+            let region = match system.contains("QZS") {
+                true => KbRegionCode::Japan,
+                false => KbRegionCode::Worldwide,
+            };
+
+            // determine which field we're dealing with
+            if system.ends_with('A') {
+                Ok(Self::Klobuchar(KbModel {
+                    alpha: a,
+                    beta: (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64),
+                    region,
+                }))
+            } else {
+                Ok(Self::Klobuchar(KbModel {
+                    alpha: (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64),
+                    beta: a,
+                    region,
                 }))
-            },
-            system => {
-                /*
-                 * Model has 4 fields
-                 */
-                let (a0, rem) = rem.split_at(12);
-                let (a1, rem) = rem.split_at(12);
-                let (a2, rem) = rem.split_at(12);
-                let (a3, _) = rem.split_at(12);
-                // World or QZSS special orbital plan
-                let region = match system.contains("QZS") {
-                    true => KbRegionCode::Japan,
-                    false => KbRegionCode::Worldwide,
-                };
-                /* determine which field we're dealing with */
-                if system.ends_with('A') {
-                    let a0 = f64::from_str(a0.trim()).map_err(|_| ParsingError::KlobucharData)?;
-                    let a1 = f64::from_str(a1.trim()).map_err(|_| ParsingError::KlobucharData)?;
-                    let a2 = f64::from_str(a2.trim()).map_err(|_| ParsingError::KlobucharData)?;
-                    let a3 = f64::from_str(a3.trim()).map_err(|_| ParsingError::KlobucharData)?;
-
-                    Ok(Self::Klobuchar(KbModel {
-                        alpha: (a0, a1, a2, a3),
-                        beta: (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64),
-                        region,
-                    }))
-                } else {
-                    let b0 = f64::from_str(a0.trim()).map_err(|_| ParsingError::KlobucharData)?;
-                    let b1 = f64::from_str(a1.trim()).map_err(|_| ParsingError::KlobucharData)?;
-                    let b2 = f64::from_str(a2.trim()).map_err(|_| ParsingError::KlobucharData)?;
-                    let b3 = f64::from_str(a3.trim()).map_err(|_| ParsingError::KlobucharData)?;
-                    Ok(Self::Klobuchar(KbModel {
-                        alpha: (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64),
-                        beta: (b0, b1, b2, b3),
-                        region,
-                    }))
-                }
-            },
+            }
         }
     }
 
@@ -205,6 +198,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn rinex3_header_tolerance() {
+        // V3.04 time mark and SV ID
+        let kb = IonosphereModel::from_rinex3_header(
+            "BDSB   1.1878D+05 -1.3107D+05  1.9661D+05 -2.6214D+05 A 12",
+        )
+        .unwrap();
+        assert_eq!(
+            kb.as_klobuchar().unwrap().beta,
+            (1.1878E+05, -1.3107E+05, 1.9661E+05, -2.6214E+05)
+        );
+
+        // trimmed line, region flags
+        let ng = IonosphereModel::from_rinex3_header("GAL    6.6250D+01 -1.6406D-01 -2.4719D-03")
+            .unwrap();
+        assert_eq!(ng.as_nequick_g().unwrap().region, NgRegionFlags::empty());
+
+        let ng = IonosphereModel::from_rinex3_header(
+            "GAL    6.6250D+01 -1.6406D-01 -2.4719D-03  1.0000D+00",
+        )
+        .unwrap();
+        assert_eq!(ng.as_nequick_g().unwrap().region.bits(), 1);
+
+        assert!(IonosphereModel::from_rinex3_header("GPSA   7.4506D-09").is_err());
+        assert!(IonosphereModel::from_rinex3_header("GPS").is_err());
+    }
+
     #[test]
     fn rinex3_kb_header_parsing() {
         // v3 Kb header
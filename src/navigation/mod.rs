@@ -15,18 +15,35 @@ pub(crate) use formatting::format;
 pub(crate) use parsing::{is_new_epoch, parse_epoch};
 
 pub use crate::navigation::{
-    earth_orientation::EarthOrientation,
-    ephemeris::{flags::*, orbits::OrbitItem, Ephemeris},
+    earth_orientation::{EarthOrientation, EarthOrientationParameters},
+    ephemeris::{
+        flags::*,
+        health::{GalSignalHealth, GalSignalStatus, Health},
+        orbits::OrbitItem,
+        Ephemeris,
+    },
     frame::{NavFrame, NavFrameType},
     header::HeaderFields,
     ionosphere::{BdModel, IonosphereModel, KbModel, KbRegionCode, NgModel, NgRegionFlags},
     message::NavMessageType,
-    time::TimeOffset,
+    time::{TimeOffset, UtcProvider},
 };
 
 #[cfg(feature = "nav")]
 pub use crate::navigation::ephemeris::kepler::{Helper, Kepler, Perturbations};
 
+#[cfg(feature = "nav")]
+pub use crate::navigation::ephemeris::typed::{
+    ClockPolynomial, KeplerEphemeris, StateVectorEphemeris, TypedEphemeris,
+};
+
+#[cfg(feature = "nav")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+mod eclipse; // attitude maneuvers
+
+#[cfg(feature = "nav")]
+pub use crate::navigation::eclipse::{sun_position_ecef_km, AttitudeManeuver, EclipseOptions};
+
 #[cfg(feature = "processing")]
 pub(crate) mod mask; // mask Trait implementation
 
@@ -90,6 +90,11 @@ impl Rinex {
     /// regular model updates (reflecting radio message stream).
     /// Klobuchar Ionosphere models exist in RINEX2 and this
     /// method applies similarly.
+    #[deprecated(
+        since = "0.20.0",
+        note = "use klobuchar_models_iter(), which also covers the header models"
+    )]
+    #[allow(deprecated)]
     pub fn nav_klobuchar_models_iter(&self) -> Box<dyn Iterator<Item = (&NavKey, &KbModel)> + '_> {
         Box::new(
             self.nav_ionosphere_models_iter()
@@ -102,6 +107,11 @@ impl Rinex {
 
     /// BDGIM [BdModel] Ionosphere model [Iterator].
     /// Refer to [Self::nav_klobuchar_models_iter] for similar examples.
+    #[deprecated(
+        since = "0.20.0",
+        note = "use bdgim_models_iter(), which also covers the header models"
+    )]
+    #[allow(deprecated)]
     pub fn nav_bdgim_models_iter(&self) -> Box<dyn Iterator<Item = (&NavKey, &BdModel)> + '_> {
         Box::new(
             self.nav_ionosphere_models_iter()
@@ -114,6 +124,11 @@ impl Rinex {
 
     /// Nequick-G [NgModel] Ionosphere model [Iterator].
     /// Refer to [Self::nav_klobuchar_models_iter] for similar examples.
+    #[deprecated(
+        since = "0.20.0",
+        note = "use nequick_g_models_iter(), which also covers the header models"
+    )]
+    #[allow(deprecated)]
     pub fn nav_nequickg_models_iter(&self) -> Box<dyn Iterator<Item = (&NavKey, &NgModel)> + '_> {
         Box::new(
             self.nav_ionosphere_models_iter()
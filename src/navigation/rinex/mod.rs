@@ -12,9 +12,10 @@ mod ut1; // feature dependent high level methods
 
 use crate::{
     navigation::{
-        EarthOrientation, Ephemeris, NavFrame, NavFrameType, NavKey, NavMessageType, TimeOffset,
+        BdModel, EarthOrientation, EarthOrientationParameters, Ephemeris, KbModel, NavFrame,
+        NavFrameType, NavKey, NavMessageType, NgModel, TimeOffset,
     },
-    prelude::{Epoch, Rinex, RinexType, SV},
+    prelude::{Constellation, Duration, Epoch, Rinex, RinexType, TimeScale, SV},
 };
 
 use std::collections::btree_map::Keys;
@@ -65,6 +66,98 @@ impl Rinex {
         }
     }
 
+    /// Selects the best [Ephemeris] broadcast by this [SV] for t [Epoch]: the message whose
+    /// ToE is the closest to t, among those that apply at t. The message should:
+    /// - lie within its fit interval. It is the broadcast fit interval when it exists (GPS),
+    ///   otherwise the constellation [Ephemeris::validity_duration].
+    /// - have consistent Issue of Data: for GPS and QZSS, the 8 LSB of IODC should match IODE,
+    ///   otherwise the message is considered as being in cutover and is discarded.
+    ///
+    /// Glonass and SBAS do not define a ToE: the state vector reference epoch (ToC) is used instead.
+    /// When several messages share the same ToE, the latest publication is preferred.
+    /// Returns (ToC, ToE, [Ephemeris]), or None if no message applies.
+    /// ```
+    /// use rinex::navigation::{Ephemeris, Kepler, NavFrame, NavFrameType, NavKey, NavMessageType, OrbitItem};
+    /// use rinex::prelude::{Epoch, Rinex, SV};
+    /// use std::str::FromStr;
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    ///
+    /// let mut rinex = Rinex::basic_nav();
+    /// let record = rinex.record.as_mut_nav().unwrap();
+    ///
+    /// for (toc, toe, iode) in [
+    ///     ("2020-06-25T00:00:00 GPST", 345600.0, 10.0),
+    ///     ("2020-06-25T02:00:00 GPST", 352800.0, 11.0),
+    /// ] {
+    ///     let eph = Ephemeris::default()
+    ///         .with_week(2111)
+    ///         .with_orbit("toe", OrbitItem::from(toe))
+    ///         .with_orbit("iode", OrbitItem::from(iode))
+    ///         .with_orbit("iodc", OrbitItem::from(iode));
+    ///
+    ///     let key = NavKey {
+    ///         epoch: Epoch::from_str(toc).unwrap(),
+    ///         sv: g01,
+    ///         msgtype: NavMessageType::LNAV,
+    ///         frmtype: NavFrameType::Ephemeris,
+    ///     };
+    ///
+    ///     record.insert(key, NavFrame::EPH(eph));
+    /// }
+    ///
+    /// let t = Epoch::from_str("2020-06-25T01:30:00 GPST").unwrap();
+    /// let (toc, _, eph) = rinex.ephemeris_for(g01, t).unwrap();
+    ///
+    /// assert_eq!(toc, Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap());
+    /// assert_eq!(eph.get_orbit_f64("iode"), Some(11.0));
+    ///
+    /// // beyond fit interval
+    /// let t = Epoch::from_str("2020-06-25T05:00:00 GPST").unwrap();
+    /// assert!(rinex.ephemeris_for(g01, t).is_none());
+    /// ```
+    pub fn ephemeris_for(&self, sv: SV, t: Epoch) -> Option<(Epoch, Epoch, &Ephemeris)> {
+        let max_dt = Ephemeris::validity_duration(sv.constellation)?;
+        let state_vector = sv.constellation.is_sbas() || sv.constellation == Constellation::Glonass;
+        let gps_qzss = matches!(sv.constellation, Constellation::GPS | Constellation::QZSS);
+
+        self.nav_ephemeris_frames_iter()
+            .filter_map(|(k, eph)| {
+                if k.sv != sv {
+                    return None;
+                }
+
+                let toe = if state_vector { k.epoch } else { eph.toe(sv)? };
+
+                // broadcast fit interval (in hours) is centered on ToE
+                let max_dt = match eph.get_orbit_f64("fitInt") {
+                    Some(hours) if gps_qzss && hours > 0.0 => Duration::from_hours(hours / 2.0),
+                    _ => max_dt,
+                };
+
+                if (t - toe).abs() > max_dt {
+                    return None;
+                }
+
+                if gps_qzss {
+                    // null values are not stored
+                    let iode = eph.get_orbit_f64("iode").unwrap_or(0.0).round() as u32;
+                    let iodc = eph.get_orbit_f64("iodc").unwrap_or(0.0).round() as u32;
+                    if iodc & 0xff != iode {
+                        return None;
+                    }
+                }
+
+                Some((k.epoch, toe, eph))
+            })
+            .min_by(|(toc_a, toe_a, _), (toc_b, toe_b, _)| {
+                (t - *toe_a)
+                    .abs()
+                    .cmp(&(t - *toe_b).abs())
+                    .then(toc_b.cmp(toc_a))
+            })
+    }
+
     /// [EarthOrientation] frames [Iterator].
     /// This type of frames exists in NAV V4 only. which may only exist
     pub fn nav_earth_orientation_frames_iter(
@@ -86,6 +179,10 @@ impl Rinex {
 
     /// [IonosphereModel] frames [Iterator].
     /// This type of frames exists in NAV V4 only.
+    #[deprecated(
+        since = "0.20.0",
+        note = "use ionosphere_models_iter(), which also covers the header models"
+    )]
     pub fn nav_ionosphere_models_iter(
         &self,
     ) -> Box<dyn Iterator<Item = (&NavKey, &IonosphereModel)> + '_> {
@@ -103,6 +200,127 @@ impl Rinex {
         }
     }
 
+    /// [IonosphereModel]s [Iterator], from all possible sources, as
+    /// ([Epoch] of validity, [Constellation], [IonosphereModel]).
+    /// This is the single input path for ionosphere compensation:
+    /// - RINEX2 "ION ALPHA" / "ION BETA" and RINEX3 "IONOSPHERIC CORR" header fields
+    ///   apply to the entire day course: they are valid from midnight of the first day of this file.
+    ///   They are only exposed when the first [Epoch] of this file is known.
+    /// - RINEX4 ionosphere frames are valid from their transmission [Epoch].
+    pub fn ionosphere_models_iter(
+        &self,
+    ) -> Box<dyn Iterator<Item = (Epoch, Constellation, IonosphereModel)> + '_> {
+        let t0 = self
+            .header
+            .obs
+            .as_ref()
+            .and_then(|obs| obs.timeof_first_obs)
+            .or(self.first_epoch())
+            .map(|t| t.floor(Duration::from_days(1.0)));
+
+        let header = self
+            .header
+            .ionod_corrections
+            .iter()
+            .filter_map(move |(c, model)| Some((t0?, *c, *model)));
+
+        let body = self
+            .record
+            .as_nav()
+            .into_iter()
+            .flat_map(|rec| rec.iter())
+            .filter_map(|(k, v)| {
+                let model = v.as_ionosphere_model()?;
+                Some((k.epoch, k.sv.constellation, *model))
+            });
+
+        Box::new(header.chain(body))
+    }
+
+    /// Klobuchar [KbModel]s [Iterator], from all possible sources, as
+    /// ([Epoch] of validity, [Constellation], [KbModel]).
+    /// See [Self::ionosphere_models_iter].
+    pub fn klobuchar_models_iter(
+        &self,
+    ) -> Box<dyn Iterator<Item = (Epoch, Constellation, KbModel)> + '_> {
+        Box::new(
+            self.ionosphere_models_iter()
+                .filter_map(|(t, c, model)| Some((t, c, *model.as_klobuchar()?))),
+        )
+    }
+
+    /// Nequick-G [NgModel]s [Iterator], from all possible sources, as
+    /// ([Epoch] of validity, [Constellation], [NgModel]).
+    /// See [Self::ionosphere_models_iter].
+    pub fn nequick_g_models_iter(
+        &self,
+    ) -> Box<dyn Iterator<Item = (Epoch, Constellation, NgModel)> + '_> {
+        Box::new(
+            self.ionosphere_models_iter()
+                .filter_map(|(t, c, model)| Some((t, c, *model.as_nequick_g()?))),
+        )
+    }
+
+    /// BDGIM [BdModel]s [Iterator], from all possible sources, as
+    /// ([Epoch] of validity, [Constellation], [BdModel]).
+    /// See [Self::ionosphere_models_iter].
+    pub fn bdgim_models_iter(
+        &self,
+    ) -> Box<dyn Iterator<Item = (Epoch, Constellation, BdModel)> + '_> {
+        Box::new(
+            self.ionosphere_models_iter()
+                .filter_map(|(t, c, model)| Some((t, c, *model.as_bdgim()?))),
+        )
+    }
+
+    /// Returns the [IonosphereModel] broadcast by this [Constellation] that applies at t [Epoch]:
+    /// the most recent one published prior t, from any source (see [Self::ionosphere_models_iter]).
+    /// Header and RINEX4 models are therefore interchangeable, whatever the revision.
+    /// When a [Constellation] broadcasts several models (like BeiDou Klobuchar and BDGIM),
+    /// the most recent publication is returned.
+    /// ```
+    /// use rinex::prelude::{Constellation, Epoch, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     4.00           N: GNSS NAV DATA    M: MIXED            RINEX VERSION / TYPE
+    ///                                                             END OF HEADER
+    /// > ION E01 IFNV
+    ///     2022 06 08 09 59 57 7.850000000000E+01 5.390625000000E-01 2.713012695312E-02
+    ///      0.000000000000E+00
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let t = Epoch::from_str("2022-06-08T12:00:00 GST").unwrap();
+    ///
+    /// let model = rinex.ionosphere_model(Constellation::Galileo, t).unwrap();
+    /// assert_eq!(model.as_nequick_g().unwrap().a.0, 78.5);
+    ///
+    /// // not published yet
+    /// let t = Epoch::from_str("2022-06-08T09:00:00 GST").unwrap();
+    /// assert!(rinex.ionosphere_model(Constellation::Galileo, t).is_none());
+    /// ```
+    pub fn ionosphere_model(
+        &self,
+        constellation: Constellation,
+        t: Epoch,
+    ) -> Option<IonosphereModel> {
+        let constellation = if constellation.is_sbas() {
+            Constellation::SBAS
+        } else {
+            constellation
+        };
+
+        self.ionosphere_models_iter()
+            .filter(|(t_ref, c, _)| {
+                let c = if c.is_sbas() { Constellation::SBAS } else { *c };
+                c == constellation && *t_ref <= t
+            })
+            .max_by_key(|(t_ref, _, _)| *t_ref)
+            .map(|(_, _, model)| model)
+    }
+
     /// [SystemTime] frames [Iterator].
     /// This type of frames exists in NAV V4 only.
     pub fn nav_system_time_frames_iter(
@@ -122,6 +340,131 @@ impl Rinex {
         }
     }
 
+    /// Returns the lhs - rhs [TimeScale] offset at t [Epoch], evaluated from the
+    /// most recent [TimeOffset] published prior t (or the closest one, when none precedes t),
+    /// either from NAV V4 STO frames, or from the header (RINEX2 and RINEX3).
+    /// [TimeOffset]s published in the opposite direction are inverted.
+    /// ```
+    /// use rinex::prelude::{Epoch, Rinex, TimeScale};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     4.00           N: GNSS NAV DATA    M: MIXED            RINEX VERSION / TYPE
+    ///                                                             END OF HEADER
+    /// > STO E01 IFNV
+    ///     2022 06 10 00 00 00 GAGP
+    ///      2.952840000000E+05 2.000000000000E-09 1.000000000000E-12 0.000000000000E+00
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let t = Epoch::from_str("2022-06-10T00:16:40 GST").unwrap();
+    ///
+    /// let gst_gpst = rinex.nav_time_offset(TimeScale::GST, TimeScale::GPST, t).unwrap();
+    /// assert!((gst_gpst.to_seconds() - 3.0E-9).abs() < 1.0E-15);
+    ///
+    /// let gpst_gst = rinex.nav_time_offset(TimeScale::GPST, TimeScale::GST, t).unwrap();
+    /// assert_eq!(gpst_gst, -gst_gpst);
+    ///
+    /// assert!(rinex.nav_time_offset(TimeScale::BDT, TimeScale::GPST, t).is_none());
+    /// ```
+    pub fn nav_time_offset(&self, lhs: TimeScale, rhs: TimeScale, t: Epoch) -> Option<Duration> {
+        let header = self
+            .header
+            .nav
+            .iter()
+            .flat_map(|nav| nav.time_offsets.iter());
+
+        let frames = self.nav_system_time_frames_iter().map(|(_, sto)| sto);
+
+        let candidates = header
+            .chain(frames)
+            .filter(|sto| (sto.lhs, sto.rhs) == (lhs, rhs) || (sto.lhs, sto.rhs) == (rhs, lhs))
+            .collect::<Vec<_>>();
+
+        let preceding = candidates
+            .iter()
+            .filter(|sto| sto.reference_epoch() <= t)
+            .max_by_key(|sto| sto.reference_epoch());
+
+        let selected = match preceding {
+            Some(sto) => sto,
+            None => candidates
+                .iter()
+                .min_by_key(|sto| (sto.reference_epoch() - t).abs())?,
+        };
+
+        let offset = selected.evaluate(t);
+
+        if selected.lhs == lhs {
+            Some(offset)
+        } else {
+            Some(-offset)
+        }
+    }
+
+    /// Returns the [EarthOrientationParameters] at t [Epoch], from the NAV V4 EOP frames.
+    /// Between two publications, both messages are evaluated at t and linearly
+    /// blended, for the parameters to remain continuous from one message to the next.
+    /// Outside the publication period, the closest message is evaluated.
+    /// This is typically used to refine ECEF to ECI transformations.
+    /// ```
+    /// use rinex::prelude::{Epoch, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     4.00           N: GNSS NAV DATA    M: MIXED            RINEX VERSION / TYPE
+    ///                                                             END OF HEADER
+    /// > EOP G01 LNAV
+    ///     2023 03 14 00 00 00 1.000000000000e-01 1.000000000000e-02 0.000000000000e+00
+    ///                         3.000000000000e-01 0.000000000000e+00 0.000000000000e+00
+    ///      4.392000000000e+03-2.000000000000e-02 0.000000000000e+00 0.000000000000e+00
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let t = Epoch::from_str("2023-03-14T12:00:00 GPST").unwrap();
+    /// let eop = rinex.nav_earth_orientation(t).unwrap();
+    ///
+    /// assert!((eop.x_arcsec - 0.105).abs() < 1.0E-12);
+    /// assert_eq!(eop.y_arcsec, 0.3);
+    /// assert_eq!(eop.delta_ut1_s, -0.02);
+    /// ```
+    pub fn nav_earth_orientation(&self, t: Epoch) -> Option<EarthOrientationParameters> {
+        let frames = self
+            .nav_earth_orientation_frames_iter()
+            .map(|(k, eop)| (k.epoch, eop))
+            .collect::<Vec<_>>();
+
+        let preceding = frames
+            .iter()
+            .filter(|(t_ref, _)| *t_ref <= t)
+            .max_by_key(|(t_ref, _)| *t_ref);
+
+        let following = frames
+            .iter()
+            .filter(|(t_ref, _)| *t_ref > t)
+            .min_by_key(|(t_ref, _)| *t_ref);
+
+        match (preceding, following) {
+            (Some((t_0, eop_0)), Some((t_1, eop_1))) => {
+                let (p_0, p_1) = (eop_0.evaluate(*t_0, t), eop_1.evaluate(*t_1, t));
+                let w = (t - *t_0).to_seconds() / (*t_1 - *t_0).to_seconds();
+                let blend = |v_0: f64, v_1: f64| (1.0 - w) * v_0 + w * v_1;
+
+                Some(EarthOrientationParameters {
+                    x_arcsec: blend(p_0.x_arcsec, p_1.x_arcsec),
+                    y_arcsec: blend(p_0.y_arcsec, p_1.y_arcsec),
+                    delta_ut1_s: blend(p_0.delta_ut1_s, p_1.delta_ut1_s),
+                })
+            },
+            (Some((t_ref, eop)), None) | (None, Some((t_ref, eop))) => {
+                Some(eop.evaluate(*t_ref, t))
+            },
+            (None, None) => None,
+        }
+    }
+
     /// [SV] clock state [Iterator].
     /// ## Inputs
     /// - self: Navigation [Rinex]
@@ -211,3 +554,315 @@ impl Rinex {
     //     ))
     // }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        navigation::{
+            Ephemeris, IonosphereModel, KbModel, KbRegionCode, NavFrame, NavFrameType, NavKey,
+            NavMessageType, OrbitItem, TimeOffset,
+        },
+        prelude::{Constellation, Duration, Epoch, Rinex, TimeScale, SV},
+    };
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    #[test]
+    fn header_ionosphere_models() {
+        let kb = KbModel {
+            alpha: (1.0E-8, 2.0E-8, -6.0E-8, -1.0E-7),
+            beta: (9.0E4, 1.0E5, -6.0E4, -3.0E5),
+            region: KbRegionCode::Worldwide,
+        };
+
+        let mut rinex = Rinex::basic_nav();
+        rinex
+            .header
+            .ionod_corrections
+            .insert(Constellation::GPS, IonosphereModel::Klobuchar(kb));
+
+        assert_eq!(rinex.header.klobuchar_model(Constellation::GPS), Some(&kb));
+        assert_eq!(rinex.header.klobuchar_model(Constellation::Galileo), None);
+
+        // unknown day course
+        assert_eq!(rinex.klobuchar_models_iter().count(), 0);
+
+        let t0 = Epoch::from_str("2020-06-25T12:00:00 GPST").unwrap();
+        rinex.header = rinex.header.with_timeof_first_obs(t0);
+
+        let models = rinex.klobuchar_models_iter().collect::<Vec<_>>();
+        assert_eq!(
+            models,
+            vec![(
+                Epoch::from_str("2020-06-25T00:00:00 GPST").unwrap(),
+                Constellation::GPS,
+                kb
+            )]
+        );
+    }
+
+    #[test]
+    fn v4_ionosphere_models() {
+        let content =
+            "     4.00           N: GNSS NAV DATA    M: MIXED            RINEX VERSION / TYPE
+                                                            END OF HEADER
+> ION G21 LNAV
+    2022 06 08 09 59 48 1.024454832077E-08 2.235174179077E-08-5.960464477539E-08
+    -1.192092895508E-07 9.625600000000E+04 1.310720000000E+05-6.553600000000E+04
+    -5.898240000000E+05 0.000000000000E+00
+> ION E01 IFNV
+    2022 06 08 09 59 57 7.850000000000E+01 5.390625000000E-01 2.713012695312E-02
+     0.000000000000E+00
+> ION C21 CNVX
+    2022 06 08 10 00 00 1.000000000000E+00 2.000000000000E+00 3.000000000000E+00
+     4.000000000000E+00 5.000000000000E+00 6.000000000000E+00 7.000000000000E+00
+     8.000000000000E+00 9.000000000000E+00
+";
+
+        let mut rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        assert_eq!(rinex.ionosphere_models_iter().count(), 3);
+        assert_eq!(rinex.nequick_g_models_iter().count(), 1);
+
+        let (t, c, kb) = rinex.klobuchar_models_iter().next().unwrap();
+        assert_eq!(c, Constellation::GPS);
+        assert_eq!(t, Epoch::from_str("2022-06-08T09:59:48 GPST").unwrap());
+        assert_eq!(kb.alpha.0, 1.024454832077E-08);
+        assert_eq!(kb.beta.3, -5.898240000000E+05);
+        assert_eq!(kb.region, KbRegionCode::Worldwide);
+
+        let (_, c, bd) = rinex.bdgim_models_iter().next().unwrap();
+        assert_eq!(c, Constellation::BeiDou);
+        assert_eq!(bd.alpha, (1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0));
+
+        let t = Epoch::from_str("2022-06-08T12:00:00 GPST").unwrap();
+        assert_eq!(
+            rinex.ionosphere_model(Constellation::GPS, t),
+            Some(IonosphereModel::Klobuchar(kb))
+        );
+        assert_eq!(
+            rinex.ionosphere_model(Constellation::BeiDou, t),
+            Some(IonosphereModel::Bdgim(bd))
+        );
+        assert!(rinex.ionosphere_model(Constellation::QZSS, t).is_none());
+
+        // header model applies from midnight, until a frame is published
+        let header_kb = KbModel {
+            alpha: (1.0E-8, 0.0, 0.0, 0.0),
+            beta: (9.0E4, 0.0, 0.0, 0.0),
+            region: KbRegionCode::Worldwide,
+        };
+
+        rinex
+            .header
+            .ionod_corrections
+            .insert(Constellation::GPS, IonosphereModel::Klobuchar(header_kb));
+
+        assert_eq!(rinex.klobuchar_models_iter().count(), 2);
+
+        let t = Epoch::from_str("2022-06-08T06:00:00 GPST").unwrap();
+        assert_eq!(
+            rinex.ionosphere_model(Constellation::GPS, t),
+            Some(IonosphereModel::Klobuchar(header_kb))
+        );
+
+        let t = Epoch::from_str("2022-06-08T10:00:00 GPST").unwrap();
+        assert_eq!(
+            rinex.ionosphere_model(Constellation::GPS, t),
+            Some(IonosphereModel::Klobuchar(kb))
+        );
+    }
+
+    #[test]
+    fn ephemeris_selection() {
+        let (g01, r01) = (SV::from_str("G01").unwrap(), SV::from_str("R01").unwrap());
+
+        let mut rinex = Rinex::basic_nav();
+        let record = rinex.record.as_mut_nav().unwrap();
+
+        for (sv, toc, toe, iode, iodc, fit_hours) in [
+            (g01, "2020-06-25T00:00:00 GPST", 345600.0, 10.0, 10.0, 0.0),
+            // cutover: inconsistent issue of data
+            (g01, "2020-06-25T01:59:30 GPST", 352800.0, 11.0, 12.0, 0.0),
+            (g01, "2020-06-25T02:00:00 GPST", 352800.0, 11.0, 267.0, 0.0),
+            // extended fit interval
+            (g01, "2020-06-25T10:00:00 GPST", 381600.0, 12.0, 12.0, 6.0),
+            (r01, "2020-06-25T00:15:00 UTC", 0.0, 0.0, 0.0, 0.0),
+        ] {
+            let mut eph = Ephemeris::default();
+
+            if sv == g01 {
+                eph = eph
+                    .with_week(2111)
+                    .with_orbit("toe", OrbitItem::from(toe))
+                    .with_orbit("iode", OrbitItem::from(iode))
+                    .with_orbit("iodc", OrbitItem::from(iodc));
+                if fit_hours > 0.0 {
+                    eph = eph.with_orbit("fitInt", OrbitItem::from(fit_hours));
+                }
+            } else {
+                eph = eph.with_orbit("satPosX", OrbitItem::from(1.0));
+            }
+
+            let key = NavKey {
+                epoch: Epoch::from_str(toc).unwrap(),
+                sv,
+                msgtype: NavMessageType::LNAV,
+                frmtype: NavFrameType::Ephemeris,
+            };
+
+            record.insert(key, NavFrame::EPH(eph));
+        }
+
+        for (t, expected_toc) in [
+            ("2020-06-25T00:30:00 GPST", Some("2020-06-25T00:00:00 GPST")),
+            ("2020-06-25T00:59:00 GPST", Some("2020-06-25T00:00:00 GPST")),
+            // equidistant: latest publication
+            ("2020-06-25T01:00:00 GPST", Some("2020-06-25T02:00:00 GPST")),
+            ("2020-06-25T01:01:00 GPST", Some("2020-06-25T02:00:00 GPST")),
+            ("2020-06-25T03:59:00 GPST", Some("2020-06-25T02:00:00 GPST")),
+            ("2020-06-25T05:00:00 GPST", None),
+            ("2020-06-25T12:59:00 GPST", Some("2020-06-25T10:00:00 GPST")),
+            ("2020-06-25T13:01:00 GPST", None),
+        ] {
+            let t = Epoch::from_str(t).unwrap();
+            let toc = rinex.ephemeris_for(g01, t).map(|(toc, _, _)| toc);
+            let expected_toc = expected_toc.map(|toc| Epoch::from_str(toc).unwrap());
+            assert_eq!(toc, expected_toc, "t={}", t);
+        }
+
+        // issue of data consistency
+        let t = Epoch::from_str("2020-06-25T02:00:00 GPST").unwrap();
+        let (_, toe, eph) = rinex.ephemeris_for(g01, t).unwrap();
+        assert_eq!(toe, t);
+        assert_eq!(eph.get_orbit_f64("iodc"), Some(267.0));
+
+        // state vectors
+        let toc = Epoch::from_str("2020-06-25T00:15:00 UTC").unwrap();
+        let t = toc + Duration::from_seconds(600.0);
+        assert_eq!(
+            rinex.ephemeris_for(r01, t).map(|(toc, toe, _)| (toc, toe)),
+            Some((toc, toc))
+        );
+
+        let t = toc + Duration::from_seconds(3600.0);
+        assert!(rinex.ephemeris_for(r01, t).is_none());
+    }
+
+    #[test]
+    fn system_time_offsets() {
+        let content =
+            "     4.00           N: GNSS NAV DATA    M: MIXED            RINEX VERSION / TYPE
+                                                            END OF HEADER
+> STO G01 LNAV
+    2022 06 10 00 00 00 GPUT                                  UTC(USNO)
+     2.952840000000E+05 1.000000000000E-09 0.000000000000E+00 0.000000000000E+00
+> STO G02 LNAV
+    2022 06 10 12 00 00 GPUT                                  UTC(USNO)
+     2.952840000000E+05 5.000000000000E-09 1.000000000000E-12 0.000000000000E+00
+> STO C01 CNVX
+    2022 06 10 00 00 00 BDGP
+     2.952840000000E+05 1.000000000000E-08 0.000000000000E+00 0.000000000000E+00
+";
+
+        let mut rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let frames = rinex
+            .nav_system_time_frames_iter()
+            .map(|(_, sto)| sto)
+            .collect::<Vec<_>>();
+
+        assert_eq!(frames.len(), 3);
+
+        for sto in frames {
+            if sto.lhs == TimeScale::BDT {
+                assert_eq!(sto.rhs, TimeScale::GPST);
+                assert!(sto.utc.is_none());
+            } else {
+                assert_eq!(sto.lhs, TimeScale::GPST);
+                assert_eq!(sto.rhs, TimeScale::UTC);
+                assert_eq!(sto.utc.as_deref(), Some("UTC(USNO)"));
+            }
+        }
+
+        let t = Epoch::from_str("2022-06-10T06:00:00 GPST").unwrap();
+        let offset = rinex.nav_time_offset(TimeScale::GPST, TimeScale::UTC, t);
+        assert_eq!(offset, Some(Duration::from_nanoseconds(1.0)));
+
+        // most recent publication
+        let t = Epoch::from_str("2022-06-10T12:16:40 GPST").unwrap();
+        let offset = rinex
+            .nav_time_offset(TimeScale::GPST, TimeScale::UTC, t)
+            .unwrap();
+        assert!((offset.to_seconds() - 6.0E-9).abs() < 1.0E-15);
+
+        // prior first publication
+        let t = Epoch::from_str("2022-06-09T23:00:00 GPST").unwrap();
+        let offset = rinex.nav_time_offset(TimeScale::UTC, TimeScale::GPST, t);
+        assert_eq!(offset, Some(Duration::from_nanoseconds(-1.0)));
+
+        let offset = rinex.nav_time_offset(TimeScale::GPST, TimeScale::BDT, t);
+        assert_eq!(offset, Some(Duration::from_nanoseconds(-10.0)));
+
+        assert!(rinex
+            .nav_time_offset(TimeScale::GST, TimeScale::GPST, t)
+            .is_none());
+
+        // header definitions
+        let t_ref = Epoch::from_str("2022-06-10T00:00:00 GST").unwrap();
+        let gagp =
+            TimeOffset::from_epoch(t_ref, TimeScale::GST, TimeScale::GPST, (2.0E-9, 0.0, 0.0));
+
+        let mut header = rinex.header.nav.clone().unwrap_or_default();
+        header.time_offsets.push(gagp);
+        rinex.header.nav = Some(header);
+
+        let offset = rinex.nav_time_offset(TimeScale::GPST, TimeScale::GST, t);
+        assert_eq!(offset, Some(Duration::from_nanoseconds(-2.0)));
+    }
+
+    #[test]
+    fn earth_orientation_interpolation() {
+        let content =
+            "     4.00           N: GNSS NAV DATA    M: MIXED            RINEX VERSION / TYPE
+                                                            END OF HEADER
+> EOP G01 LNAV
+    2023 03 14 00 00 00 1.000000000000e-01 1.000000000000e-02 0.000000000000e+00
+                        3.000000000000e-01 0.000000000000e+00 0.000000000000e+00
+     4.392000000000e+03-2.000000000000e-02 0.000000000000e+00 0.000000000000e+00
+> EOP G01 LNAV
+    2023 03 15 00 00 00 1.200000000000e-01 1.000000000000e-02 0.000000000000e+00
+                        3.100000000000e-01 0.000000000000e+00 0.000000000000e+00
+     4.392000000000e+03-2.100000000000e-02 0.000000000000e+00 0.000000000000e+00
+";
+
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        assert_eq!(rinex.nav_earth_orientation_frames_iter().count(), 2);
+
+        // publications are exact
+        for (t, x, y, dut1) in [
+            ("2023-03-14T00:00:00 GPST", 0.1, 0.3, -0.02),
+            ("2023-03-15T00:00:00 GPST", 0.12, 0.31, -0.021),
+        ] {
+            let t = Epoch::from_str(t).unwrap();
+            let eop = rinex.nav_earth_orientation(t).unwrap();
+            assert!((eop.x_arcsec - x).abs() < 1.0E-12);
+            assert!((eop.y_arcsec - y).abs() < 1.0E-12);
+            assert!((eop.delta_ut1_s - dut1).abs() < 1.0E-12);
+        }
+
+        // blended in between: x = 0.5 * (0.105 + 0.115)
+        let t = Epoch::from_str("2023-03-14T12:00:00 GPST").unwrap();
+        let eop = rinex.nav_earth_orientation(t).unwrap();
+        assert!((eop.x_arcsec - 0.11).abs() < 1.0E-12);
+        assert!((eop.y_arcsec - 0.305).abs() < 1.0E-12);
+        assert!((eop.delta_ut1_s + 0.0205).abs() < 1.0E-12);
+
+        // extrapolated from the latest publication
+        let t = Epoch::from_str("2023-03-16T00:00:00 GPST").unwrap();
+        let eop = rinex.nav_earth_orientation(t).unwrap();
+        assert!((eop.x_arcsec - 0.13).abs() < 1.0E-12);
+
+        assert!(Rinex::basic_obs().nav_earth_orientation(t).is_none());
+    }
+}
@@ -80,21 +80,24 @@ impl TimeOffset {
 
     /// Format [TimeOffset] according to RINEXv3 standard
     pub(crate) fn format_v3<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
-        writeln!(
-            w,
-            "{}",
-            fmt_rinex(
-                &format!(
-                    "{} {}{} {:6}{:5}",
-                    self.to_lhs_rhs_timescales(),
-                    NavFormatter::new_time_system_correction_v3_offset(self.polynomial.0),
-                    NavFormatter::new_time_system_correction_v3_drift(self.polynomial.1),
-                    self.t_ref.1 / 1_000_000_000,
-                    self.t_ref.0
-                ),
-                "TIME SYSTEM CORR"
-            ),
-        )?;
+        let mut content = format!(
+            "{} {}{} {:6}{:5}",
+            self.to_lhs_rhs_timescales(),
+            NavFormatter::new_time_system_correction_v3_offset(self.polynomial.0),
+            NavFormatter::new_time_system_correction_v3_drift(self.polynomial.1),
+            self.t_ref.1 / 1_000_000_000,
+            self.t_ref.0
+        );
+
+        if self.utc.is_some() || self.utc_provider.is_some() {
+            content.push_str(&format!(" {:<5}", self.utc.as_deref().unwrap_or_default()));
+        }
+
+        if let Some(provider) = self.utc_provider {
+            content.push_str(&format!(" {:2}", provider.code()));
+        }
+
+        writeln!(w, "{}", fmt_rinex(&content, "TIME SYSTEM CORR"))?;
 
         Ok(())
     }
@@ -103,8 +106,7 @@ impl TimeOffset {
         let t = Epoch::from_time_of_week(self.t_ref.0, self.t_ref.1, self.lhs);
         let (y, m, d, hh, mm, ss, _) = epoch_decompose(t);
 
-        writeln!(
-            w,
+        let line = format!(
             "    {:04} {:02} {:02} {:02} {:02} {:02} {}",
             y,
             m,
@@ -113,7 +115,12 @@ impl TimeOffset {
             mm,
             ss,
             self.to_lhs_rhs_timescales(),
-        )?;
+        );
+
+        match &self.utc {
+            Some(utc) => writeln!(w, "{:<62}{}", line, utc)?,
+            None => writeln!(w, "{}", line)?,
+        }
 
         writeln!(
             w,
@@ -12,6 +12,60 @@ pub(crate) mod parsing;
 #[cfg(feature = "processing")]
 use qc_traits::TimePolynomial;
 
+/// [UtcProvider] identifies the UTC realization a GNSS broadcasts,
+/// as described by the "TIME SYSTEM CORR" header field (RINEXv3).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UtcProvider {
+    /// Unknown or not specified
+    #[default]
+    Unknown,
+    /// UTC(NIST)
+    NIST,
+    /// UTC(USNO)
+    USNO,
+    /// UTC(SU): Russia
+    SU,
+    /// UTC(BIPM)
+    BIPM,
+    /// UTC(Europe Lab)
+    Europe,
+    /// UTC(CRL): Japan
+    CRL,
+    /// UTC(NTSC): China
+    NTSC,
+}
+
+impl UtcProvider {
+    /// Builds [UtcProvider] from RINEX identifier
+    pub(crate) fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::NIST,
+            2 => Self::USNO,
+            3 => Self::SU,
+            4 => Self::BIPM,
+            5 => Self::Europe,
+            6 => Self::CRL,
+            7 => Self::NTSC,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Returns RINEX identifier
+    pub(crate) fn code(&self) -> u8 {
+        match self {
+            Self::Unknown => 0,
+            Self::NIST => 1,
+            Self::USNO => 2,
+            Self::SU => 3,
+            Self::BIPM => 4,
+            Self::Europe => 5,
+            Self::CRL => 6,
+            Self::NTSC => 7,
+        }
+    }
+}
+
 /// System Time (offset) Message
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -22,8 +76,11 @@ pub struct TimeOffset {
     pub rhs: TimeScale,
     /// Reference time expressed as week counter and nanoseconds of week.
     pub t_ref: (u32, u64),
-    /// Possible UTC ID# in case this came from RINEXv4
+    /// Possible UTC ID# in case this came from RINEXv4,
+    /// or source of this correction (augmentation system) in RINEXv3
     pub utc: Option<String>,
+    /// Possible [UtcProvider], when [Self::rhs] is [TimeScale::UTC] (RINEXv3)
+    pub utc_provider: Option<UtcProvider>,
     /// Interpolation polynomial
     pub polynomial: (f64, f64, f64),
 }
@@ -42,6 +99,7 @@ impl TimeOffset {
             rhs,
             t_ref,
             utc: None,
+            utc_provider: None,
             polynomial,
         }
     }
@@ -58,11 +116,44 @@ impl TimeOffset {
             lhs,
             rhs,
             utc: None,
+            utc_provider: None,
             polynomial,
             t_ref: (t_week, t_nanos),
         }
     }
 
+    /// Returns reference [Epoch] of this [TimeOffset], expressed in [Self::lhs]
+    pub fn reference_epoch(&self) -> Epoch {
+        Epoch::from_time_of_week(self.t_ref.0, self.t_ref.1, self.lhs)
+    }
+
+    /// Evaluates this [TimeOffset] at this [Epoch]: returns [Self::lhs] - [Self::rhs]
+    /// offset, as [Duration], by evaluating the polynomial
+    /// a0 + a1 (t - t_ref) + a2 (t - t_ref)².
+    /// ```
+    /// use rinex::navigation::TimeOffset;
+    /// use rinex::prelude::{Duration, Epoch, TimeScale};
+    ///
+    /// let gput = TimeOffset::from_time_of_week(
+    ///     2138,
+    ///     432_000 * 1_000_000_000,
+    ///     TimeScale::GPST,
+    ///     TimeScale::UTC,
+    ///     (1.0E-9, 1.0E-12, 0.0),
+    /// );
+    ///
+    /// let t_ref = gput.reference_epoch();
+    /// assert_eq!(gput.evaluate(t_ref), Duration::from_nanoseconds(1.0));
+    ///
+    /// let t = t_ref + Duration::from_seconds(1000.0);
+    /// assert_eq!(gput.evaluate(t), Duration::from_nanoseconds(2.0));
+    /// ```
+    pub fn evaluate(&self, t: Epoch) -> Duration {
+        let dt_s = (t - self.reference_epoch()).to_seconds();
+        let (a0, a1, a2) = self.polynomial;
+        Duration::from_seconds(a0 + a1 * dt_s + a2 * dt_s.powi(2))
+    }
+
     fn to_hifitime_polynomial(&self) -> Polynomial {
         Polynomial {
             constant: Duration::from_seconds(self.polynomial.0),
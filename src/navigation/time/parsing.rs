@@ -1,7 +1,7 @@
 use crate::{
     epoch::parse_in_timescale as parse_epoch_in_timescale,
     error::ParsingError,
-    navigation::time::TimeOffset,
+    navigation::time::{TimeOffset, UtcProvider},
     prelude::{Epoch, TimeScale},
 };
 
@@ -22,6 +22,11 @@ impl TimeOffset {
             "BDUT" => Ok((TimeScale::BDT, TimeScale::UTC)),
             "BDGA" => Ok((TimeScale::BDT, TimeScale::GST)),
             "BDGP" => Ok((TimeScale::BDT, TimeScale::GPST)),
+            // inverted definitions
+            "GPBD" => Ok((TimeScale::GPST, TimeScale::BDT)),
+            "QZGA" => Ok((TimeScale::QZSST, TimeScale::GST)),
+            "QZBD" => Ok((TimeScale::QZSST, TimeScale::BDT)),
+            "GABD" => Ok((TimeScale::GST, TimeScale::BDT)),
             // "SBAS"
             "SBUT" => Ok((TimeScale::GPST, TimeScale::UTC)),
             _ => Err(ParsingError::NavInvalidTimescale),
@@ -106,43 +111,45 @@ impl TimeOffset {
 
     /// Parse [TimeOffset] from RINEXv3 standard
     pub fn parse_v3(line: &str) -> Result<Self, ParsingError> {
-        let (timescales, rem) = line.split_at(4);
-        let (a0, rem) = rem.split_at(18);
-        let (a1, rem) = rem.split_at(16);
-        let (seconds, rem) = rem.split_at(7);
-        let (week, _) = rem.split_at(5);
+        // A4,1X,D17.10,D16.9,1X,I6,1X,I4,1X,A5,1X,I2
+        let field = |start: usize, end: usize| -> &str {
+            let end = std::cmp::min(end, line.len());
+            line.get(start..end).unwrap_or_default().trim()
+        };
 
-        let (lhs, rhs) = Self::parse_lhs_rhs_timescales(timescales)?;
+        let (lhs, rhs) = Self::parse_lhs_rhs_timescales(field(0, 4))?;
 
-        let week = week
-            .trim()
+        let week = field(45, 50)
             .parse::<u32>()
             .map_err(|_| ParsingError::NavEpochWeekCounter)?;
 
-        let seconds = seconds
-            .trim()
+        let seconds = field(38, 45)
             .parse::<u64>()
             .map_err(|_| ParsingError::NavEpochWeekCounter)?;
 
-        let a0 = a0
-            .trim()
+        let a0 = field(4, 22)
             .replace('D', "e")
             .parse::<f64>()
             .map_err(|_| ParsingError::NavTimeOffsetParinsg)?;
 
-        let a1 = a1
-            .trim()
+        let a1 = field(22, 38)
             .replace('D', "e")
             .parse::<f64>()
             .map_err(|_| ParsingError::NavTimeOffsetParinsg)?;
 
-        Ok(Self::from_time_of_week(
-            week,
-            seconds * 1_000_000_000,
-            lhs,
-            rhs,
-            (a0, a1, 0.0),
-        ))
+        let mut offset =
+            Self::from_time_of_week(week, seconds * 1_000_000_000, lhs, rhs, (a0, a1, 0.0));
+
+        let source = field(51, 56);
+        if !source.is_empty() {
+            offset.utc = Some(source.to_string());
+        }
+
+        if let Ok(code) = field(57, 60).parse::<u8>() {
+            offset.utc_provider = Some(UtcProvider::from_code(code));
+        }
+
+        Ok(offset)
     }
 
     /// Parse [TimeOffset] from RINEXv4 standard
@@ -152,7 +159,14 @@ impl TimeOffset {
 
         let (lhs, rhs) = Self::parse_lhs_rhs_timescales(timescales)?;
 
-        // let utc = rem.trim().to_string();
+        // UTC ID (A18 starting at column 62), when specified.
+        // It follows the SBAS ID (column 43), which is not retained.
+        let utc = line_1
+            .get(62..)
+            .map(|utc| utc.trim())
+            .filter(|utc| !utc.is_empty())
+            .map(|utc| utc.to_string());
+
         let t_ref = parse_epoch_in_timescale(epoch.trim(), lhs)?;
         let (t_week, t_nanos) = t_ref.to_time_of_week();
 
@@ -182,7 +196,8 @@ impl TimeOffset {
                 .map_err(|_| ParsingError::NavTimeOffsetParinsg)?,
         );
 
-        let time_offset = Self::from_time_of_week(t_week, t_nanos, lhs, rhs, (a0, a1, a2));
+        let mut time_offset = Self::from_time_of_week(t_week, t_nanos, lhs, rhs, (a0, a1, a2));
+        time_offset.utc = utc;
 
         Ok(time_offset)
     }
@@ -193,7 +208,7 @@ mod test {
     use std::io::BufWriter;
     use std::str::FromStr;
 
-    use super::TimeOffset;
+    use super::{TimeOffset, UtcProvider};
     use crate::prelude::{Epoch, TimeScale};
     use crate::tests::formatting::Utf8Buffer;
 
@@ -269,6 +284,28 @@ mod test {
         let content = "XXXX  1.7840648070e-08 5.773159728e-14 432288 2138          ";
         assert!(TimeOffset::parse_v3(content).is_err());
 
+        // augmentation system & UTC provider
+        let content = "SBUT  1.8626451492E-09-8.881784197E-16 432000 2138 EGNOS  5";
+        let parsed = TimeOffset::parse_v3(content).unwrap();
+        assert_eq!(parsed.utc.as_deref(), Some("EGNOS"));
+        assert_eq!(parsed.utc_provider, Some(UtcProvider::Europe));
+
+        let content = "GPUT  1.8626451492E-09-8.881784197E-16 432000 2138        2";
+        let parsed = TimeOffset::parse_v3(content).unwrap();
+        assert_eq!(parsed.utc, None);
+        assert_eq!(parsed.utc_provider, Some(UtcProvider::USNO));
+
+        let mut buf = BufWriter::new(Utf8Buffer::new(1024));
+        parsed.format_v3(&mut buf).unwrap();
+        let formatted = buf.into_inner().unwrap().to_ascii_utf8();
+        assert_eq!(formatted, format!("{:<60}TIME SYSTEM CORR\n", content));
+
+        // truncated
+        let parsed =
+            TimeOffset::parse_v3("GPUT -3.7252902985E-09-1.065814104E-14  61440 2139").unwrap();
+        assert_eq!(parsed.t_ref, (2139, 61440 * 1_000_000_000));
+        assert!(TimeOffset::parse_v3("GPUT -3.7252902985E-09").is_err());
+
         for (content, a0, a1, week, sec, lhs, rhs) in [
             (
                 "GAUT  1.8626451492E-09-8.881784197E-16 432000 2138          TIME SYSTEM CORR\n",
@@ -339,7 +376,7 @@ mod test {
 
     #[test]
     fn parsing_v4() {
-        for (line_1, line_2, lhs, rhs, t_ref, t_sec, a0, a1, a2) in [
+        for (line_1, line_2, lhs, rhs, t_ref, t_sec, a0, a1, a2, utc) in [
             (
                 "    2022 06 08 00 00 00 GAUT                                  UTCGAL",
                 "     2.952070000000E+05-1.862645149231E-09 8.881784197001E-16 0.000000000000E+00",
@@ -350,6 +387,7 @@ mod test {
                 -1.862645149231E-09,
                 8.881784197001E-16,
                 0.0,
+                Some("UTCGAL"),
             ),
             (
                 "    2022 06 10 19 56 48 GPUT                                  UTC(USNO)",
@@ -361,6 +399,32 @@ mod test {
                 9.313225746155E-10,
                 2.664535259100E-15,
                 0.0,
+                Some("UTC(USNO)"),
+            ),
+            (
+                "    2022 06 10 19 56 48 SBUT               WAAS               UTC(USNO)",
+                "     2.952840000000E+05 9.313225746155E-10 2.664535259100E-15 0.000000000000E+00",
+                TimeScale::GPST,
+                TimeScale::UTC,
+                "2022-06-10T19:56:48 GPST",
+                295284,
+                9.313225746155E-10,
+                2.664535259100E-15,
+                0.0,
+                Some("UTC(USNO)"),
+            ),
+            (
+                // SBAS ID only: not to be confused with the UTC ID
+                "    2022 06 10 19 56 48 SBUT               WAAS",
+                "     2.952840000000E+05 9.313225746155E-10 2.664535259100E-15 0.000000000000E+00",
+                TimeScale::GPST,
+                TimeScale::UTC,
+                "2022-06-10T19:56:48 GPST",
+                295284,
+                9.313225746155E-10,
+                2.664535259100E-15,
+                0.0,
+                None,
             ),
         ] {
             let t_ref = Epoch::from_str(t_ref).unwrap();
@@ -374,6 +438,7 @@ mod test {
             //assert_eq!(time_offset.t_ref.1, t_sec * 1_000_000_000);
 
             assert_eq!(time_offset.polynomial, (a0, a1, a2),);
+            assert_eq!(time_offset.utc.as_deref(), utc);
 
             // test reciprocal
             let mut buf = BufWriter::new(Utf8Buffer::new(1024));
@@ -1,5 +1,7 @@
 use crate::prelude::{Carrier, Constellation, Error, ParsingError};
 
+use std::str::FromStr;
+
 /// Observable describes all possible observations,
 /// forming Observation and Meteo RINEX epoch content.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Ord, Eq)]
@@ -123,6 +125,126 @@ impl Observable {
         Carrier::from_observable(c, self)
     }
 
+    /// Returns the wavelength (in meters) of the [Carrier] this [Observable] is sampled on.
+    /// Glonass FDMA signals require the frequency channel number attributed to the vehicle
+    /// (for example from the "GLONASS SLOT / FRQ #" header field), otherwise
+    /// the center frequency is used.
+    /// ```
+    /// use std::str::FromStr;
+    /// use rinex::prelude::*;
+    ///
+    /// let l1c = Observable::from_str("L1C").unwrap();
+    /// let lambda = l1c.wavelength(Constellation::GPS, None).unwrap();
+    /// assert!((lambda - 0.190293672798).abs() < 1.0E-9);
+    ///
+    /// // Glonass FDMA: channel dependent
+    /// let lambda = l1c.wavelength(Constellation::Glonass, Some(-7)).unwrap();
+    /// assert!((lambda - 299_792_458.0 / 1598.0625E6).abs() < 1.0E-9);
+    /// ```
+    pub fn wavelength(
+        &self,
+        constellation: Constellation,
+        glonass_slot: Option<i8>,
+    ) -> Result<f64, Error> {
+        let mut carrier = self.to_carrier(constellation)?;
+        if constellation == Constellation::Glonass {
+            if let Some(channel) = glonass_slot {
+                carrier = carrier.with_glonass_offset(channel);
+            }
+        }
+        Ok(carrier.wavelength())
+    }
+
+    /// Converts a phase measurement expressed in carrier cycles, to meters.
+    /// See [Self::wavelength] for more information.
+    pub fn cycles_to_meters(
+        &self,
+        cycles: f64,
+        constellation: Constellation,
+        glonass_slot: Option<i8>,
+    ) -> Result<f64, Error> {
+        let lambda = self.wavelength(constellation, glonass_slot)?;
+        Ok(cycles * lambda)
+    }
+
+    /// Converts this RINEX2 [Observable] to its RINEX3 equivalent (3 letter code),
+    /// for this [Constellation]. The tracking attribute, that RINEX2 does not describe,
+    /// follows the usual conversion conventions (for example, GPS "P2" becomes "C2W"
+    /// and Galileo "C1" becomes "C1X"). RINEX3 and meteo observables are preserved.
+    /// Returns None when this code does not exist for this [Constellation].
+    /// ```
+    /// use rinex::prelude::{Constellation, Observable};
+    /// use std::str::FromStr;
+    ///
+    /// let p2 = Observable::from_str("P2").unwrap();
+    /// assert_eq!(p2.to_v3(Constellation::GPS), Observable::from_str("C2W").ok());
+    /// assert_eq!(p2.to_v3(Constellation::Glonass), Observable::from_str("C2P").ok());
+    /// assert_eq!(p2.to_v3(Constellation::Galileo), None);
+    ///
+    /// let l1 = Observable::from_str("L1").unwrap();
+    /// assert_eq!(l1.to_v3(Constellation::BeiDou), Observable::from_str("L2I").ok());
+    /// ```
+    pub fn to_v3(&self, constellation: Constellation) -> Option<Self> {
+        let code = match self {
+            Self::PhaseRange(c) | Self::Doppler(c) | Self::SSI(c) | Self::PseudoRange(c) => c,
+            _ => return Some(self.clone()),
+        };
+
+        if code.len() != 2 {
+            return Some(self.clone());
+        }
+
+        let mut chars = code.chars();
+        let (physics, band) = (chars.next()?, chars.next()?);
+
+        let constellation = if constellation.is_sbas() {
+            Constellation::SBAS
+        } else {
+            constellation
+        };
+
+        let (physics, band, attribute) = match (physics, band, constellation) {
+            ('P', '1', Constellation::GPS) => ('C', '1', 'W'),
+            ('P', '2', Constellation::GPS) => ('C', '2', 'W'),
+            ('P', '1', Constellation::Glonass) => ('C', '1', 'P'),
+            ('P', '2', Constellation::Glonass) => ('C', '2', 'P'),
+            ('P', _, _) => return None,
+            (
+                _,
+                '1',
+                Constellation::GPS
+                | Constellation::Glonass
+                | Constellation::QZSS
+                | Constellation::SBAS,
+            ) => (physics, '1', 'C'),
+            (_, '1', Constellation::Galileo) => (physics, '1', 'X'),
+            (_, '1', Constellation::BeiDou) => (physics, '2', 'I'),
+            ('C', '2', Constellation::GPS) => ('C', '2', 'X'),
+            (_, '2', Constellation::GPS) => (physics, '2', 'W'),
+            ('C', '2', Constellation::Glonass) => ('C', '2', 'C'),
+            (_, '2', Constellation::Glonass) => (physics, '2', 'P'),
+            (_, '2', Constellation::QZSS) => (physics, '2', 'X'),
+            (_, '2', Constellation::BeiDou) => (physics, '7', 'I'),
+            (
+                _,
+                '5',
+                Constellation::GPS
+                | Constellation::Galileo
+                | Constellation::QZSS
+                | Constellation::SBAS,
+            ) => (physics, '5', 'X'),
+            (_, '5', Constellation::IRNSS) => (physics, '5', 'A'),
+            (_, '6', Constellation::Galileo | Constellation::QZSS) => (physics, '6', 'X'),
+            (_, '6', Constellation::BeiDou) => (physics, '6', 'I'),
+            (_, '7', Constellation::Galileo) => (physics, '7', 'X'),
+            (_, '7', Constellation::BeiDou) => (physics, '7', 'I'),
+            (_, '8', Constellation::Galileo) => (physics, '8', 'X'),
+            _ => return None,
+        };
+
+        Self::from_str(&format!("{}{}{}", physics, band, attribute)).ok()
+    }
+
     /// Tries to create a Pseudo Range [Observable] from
     /// provided signal frequency in MHz and provided [Constellation].
     /// This requires a 1kHz accuracy on given frequency.
@@ -172,6 +294,7 @@ impl Observable {
                 _ => return Err(Error::UnknownGlonassFrequency),
             },
             Constellation::IRNSS => match carrier {
+                Carrier::L1 => return Ok(Self::PseudoRange("C1X".to_string())),
                 Carrier::L5 => return Ok(Self::PseudoRange("C5X".to_string())),
                 Carrier::S => return Ok(Self::PseudoRange("C9X".to_string())),
                 _ => return Err(Error::UnknownIRNSSFrequency),
@@ -239,6 +362,7 @@ impl Observable {
                 _ => return Err(Error::UnknownGlonassFrequency),
             },
             Constellation::IRNSS => match carrier {
+                Carrier::L1 => return Ok(Self::PhaseRange("L1X".to_string())),
                 Carrier::L5 => return Ok(Self::PhaseRange("L5X".to_string())),
                 Carrier::S => return Ok(Self::PhaseRange("L9X".to_string())),
                 _ => return Err(Error::UnknownIRNSSFrequency),
@@ -378,6 +502,7 @@ impl Observable {
                             "C7Q" => Some(1.0_f64), // TODO
                             "C7X" => Some(1.0_f64), // TODO
                             "C5" => Some(1.0_f64),  // TODO
+                            "C8" => Some(1.0_f64),  // TODO
                             "C8I" => Some(1.0_f64), // TODO
                             "C8Q" => Some(1.0_f64), // TODO
                             "C8X" => Some(1.0_f64), // TODO
@@ -674,4 +799,40 @@ mod test {
         assert!(pr_l1.same_physics(&pr_l2));
         assert!(pr_l1.same_physics(&pr_l2w));
     }
+
+    #[test]
+    fn test_wavelength() {
+        let l1c = Observable::from_str("L1C").unwrap();
+        let l2p = Observable::from_str("L2P").unwrap();
+
+        for (observable, constellation, channel, freq_mhz) in [
+            (&l1c, Constellation::GPS, None, 1575.42),
+            (&l1c, Constellation::Galileo, None, 1575.42),
+            (&l2p, Constellation::GPS, None, 1227.60),
+            // FDMA channel ignored for CDMA
+            (&l1c, Constellation::GPS, Some(1), 1575.42),
+            (&l1c, Constellation::Glonass, None, 1602.0),
+            (&l1c, Constellation::Glonass, Some(1), 1602.5625),
+            (&l1c, Constellation::Glonass, Some(-7), 1598.0625),
+            (&l2p, Constellation::Glonass, None, 1246.0),
+            (&l2p, Constellation::Glonass, Some(6), 1248.625),
+        ] {
+            let lambda = observable.wavelength(constellation, channel).unwrap();
+            let expected = 299_792_458.0 / freq_mhz / 1.0E6;
+            assert!(
+                (lambda - expected).abs() < 1.0E-9,
+                "{} {} {:?}: {} != {}",
+                observable,
+                constellation,
+                channel,
+                lambda,
+                expected
+            );
+
+            let meters = observable
+                .cycles_to_meters(100.0, constellation, channel)
+                .unwrap();
+            assert!((meters - 100.0 * expected).abs() < 1.0E-6);
+        }
+    }
 }
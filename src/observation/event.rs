@@ -0,0 +1,85 @@
+//! Event epochs and their special records
+use crate::{
+    hardware::{Antenna, Receiver},
+    prelude::{Header, ParsingError},
+};
+
+use std::io::BufReader;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [Event] is attached to event epochs (flag 2 to 5), which are followed by
+/// special records instead of observations: header records (antenna being moved,
+/// new site occupation, header information follows) or event descriptions.
+/// Special records are stored as is, so they are written back identically.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Event {
+    /// Special records, as is
+    pub records: Vec<String>,
+}
+
+impl Event {
+    /// Builds a new [Event] from these special records
+    pub fn new(records: Vec<String>) -> Self {
+        Self { records }
+    }
+
+    /// Returns special records that are header records, as (label, content) pairs
+    pub fn header_records(&self) -> Vec<(&str, &str)> {
+        self.records
+            .iter()
+            .filter(|record| record.len() > 60)
+            .map(|record| {
+                let (content, label) = record.split_at(60);
+                (label.trim(), content.trim_end())
+            })
+            .collect()
+    }
+
+    /// Interprets the header records of this [Event], as a [Header] in which
+    /// only the updated fields are defined.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// ESBC00DNK                                                   MARKER NAME
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  3  2
+    /// ESBC01DNK                                                   MARKER NAME
+    ///         0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N
+    /// > 2020 01 01 00 01  0.0000000  0  1
+    /// G01  20832395.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let (_, event) = rinex.events_iter().next().unwrap();
+    /// assert_eq!(event.records.len(), 2);
+    ///
+    /// let update = event.to_header().unwrap();
+    /// assert_eq!(update.geodetic_marker.unwrap().name, "ESBC01DNK");
+    /// ```
+    pub fn to_header(&self) -> Result<Header, ParsingError> {
+        let mut content = self.records.join("\n");
+        content.push_str(&format!("\n{:60}END OF HEADER\n", ""));
+        Header::parse(&mut BufReader::new(content.as_bytes()))
+    }
+
+    /// Returns the new [Receiver] ("REC # / TYPE / VERS") declared by this [Event],
+    /// when the receiver (or its serial number) changed.
+    pub fn receiver(&self) -> Option<Receiver> {
+        self.to_header().ok()?.rcvr
+    }
+
+    /// Returns the new receiver [Antenna] ("ANT # / TYPE") declared by this [Event],
+    /// when the antenna (or its serial number) changed.
+    pub fn antenna(&self) -> Option<Antenna> {
+        self.to_header().ok()?.rcvr_antenna
+    }
+}
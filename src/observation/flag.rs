@@ -36,6 +36,18 @@ impl EpochFlag {
     pub fn is_ok(self) -> bool {
         self == Self::Ok
     }
+
+    /// Returns true if this flag describes an event, followed by special records
+    /// (header records or event descriptions) instead of observations.
+    pub fn is_event(self) -> bool {
+        matches!(
+            self,
+            Self::AntennaBeingMoved
+                | Self::NewSiteOccupation
+                | Self::HeaderInformationFollows
+                | Self::ExternalEvent
+        )
+    }
 }
 
 impl FromStr for EpochFlag {
@@ -2,8 +2,9 @@
 use crate::{
     epoch::format as epoch_format,
     error::FormattingError,
-    observation::{HeaderFields, ObsKey, Observations},
+    observation::{Event, HeaderFields, ObsKey, Observations},
     prelude::{Constellation, RinexType, SV},
+    rounding::{FixedFormatter, RoundingMode},
 };
 
 use itertools::Itertools;
@@ -11,12 +12,14 @@ use itertools::Itertools;
 use std::io::{BufWriter, Write};
 
 impl Observations {
-    /// Format [Observations] according to standard RINEX specifications.
+    /// Format [Observations] according to standard RINEX specifications,
+    /// observed values being rounded with desired [RoundingMode].
     pub fn format<W: Write>(
         &self,
         v2: bool,
         key: &ObsKey,
         header: &HeaderFields,
+        rounding: RoundingMode,
         w: &mut BufWriter<W>,
     ) -> Result<(), FormattingError> {
         let sv_list = self
@@ -29,10 +32,14 @@ impl Observations {
 
         let numsat = sv_list.len();
 
+        if let Some(event) = &self.event {
+            return Self::format_event(w, v2, key, event);
+        }
+
         if v2 {
-            self.format_v2(w, key, &header, &sv_list, numsat)
+            self.format_v2(w, key, &header, &sv_list, numsat, rounding)
         } else {
-            self.format_v3(w, key, &header, &sv_list, numsat)
+            self.format_v3(w, key, &header, &sv_list, numsat, rounding)
         }
     }
 
@@ -44,6 +51,7 @@ impl Observations {
         header: &HeaderFields,
         sv_list: &[SV],
         numsat: usize,
+        rounding: RoundingMode,
     ) -> Result<(), FormattingError> {
         let observables = &header.codes;
 
@@ -83,7 +91,11 @@ impl Observations {
                     .filter(|sig| &sig.sv == sv && &sig.observable == observable)
                     .reduce(|k, _| k)
                 {
-                    write!(w, "{:14.3}", observation.value)?;
+                    write!(
+                        w,
+                        "{}",
+                        FixedFormatter::new_observation(header.scaled_value(observation), rounding)
+                    )?;
 
                     if let Some(lli) = observation.lli {
                         write!(w, "{:x}", lli)?;
@@ -115,48 +127,84 @@ impl Observations {
         Ok(())
     }
 
-    /// Format new Epoch according to V2 RINEX format
-    pub(crate) fn format_epoch_v2<W: Write>(
-        &self,
+    /// Formats event epoch (flag 2 to 5), followed by its special records
+    fn format_event<W: Write>(
         w: &mut BufWriter<W>,
+        v2: bool,
         key: &ObsKey,
-        sv_list: &[SV],
-        numsat: usize,
+        event: &Event,
     ) -> Result<(), FormattingError> {
-        const NUM_SV_PER_LINE: usize = 12;
-        const NEW_LINE_PADDING: &str = "                                ";
-
-        if let Some(clock) = self.clock {
-            write!(
+        if v2 {
+            writeln!(
                 w,
                 " {}  {} {:2}",
                 epoch_format(key.epoch, RinexType::ObservationData, 2),
                 key.flag,
-                numsat,
+                event.records.len(),
             )?;
         } else {
-            write!(
+            writeln!(
                 w,
-                " {}  {} {:2}",
-                epoch_format(key.epoch, RinexType::ObservationData, 2),
+                "> {}  {} {:2}",
+                epoch_format(key.epoch, RinexType::ObservationData, 3),
                 key.flag,
-                numsat,
+                event.records.len(),
             )?;
         }
 
+        for record in event.records.iter() {
+            writeln!(w, "{}", record)?;
+        }
+        Ok(())
+    }
+
+    /// Format new Epoch according to V2 RINEX format
+    pub(crate) fn format_epoch_v2<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        key: &ObsKey,
+        sv_list: &[SV],
+        numsat: usize,
+    ) -> Result<(), FormattingError> {
+        const NUM_SV_PER_LINE: usize = 12;
+        const NEW_LINE_PADDING: &str = "                                ";
+
+        write!(
+            w,
+            " {}  {} {:2}",
+            epoch_format(key.epoch, RinexType::ObservationData, 2),
+            key.flag,
+            numsat,
+        )?;
+
         for (nth, sv) in sv_list.iter().enumerate() {
             if nth > 0 && (nth % NUM_SV_PER_LINE) == 0 {
                 write!(w, "{}", NEW_LINE_PADDING)?;
             }
             write!(w, "{:x}", sv)?;
+
+            // clock offset terminates the first line (F12.9)
+            if let Some(clock) = self
+                .clock
+                .filter(|_| nth == numsat.min(NUM_SV_PER_LINE) - 1)
+            {
+                let blanking = 3 * (NUM_SV_PER_LINE - numsat.min(NUM_SV_PER_LINE));
+                write!(w, "{:blanking$}{:12.9}", "", clock.offset_s)?;
+            }
+
             if nth < numsat - 1 && nth % NUM_SV_PER_LINE == NUM_SV_PER_LINE - 1 {
-                write!(w, "{}", '\n')?;
+                writeln!(w)?;
             }
             if nth == numsat - 1 {
-                write!(w, "{}", '\n')?;
+                writeln!(w)?;
             }
         }
 
+        // no satellites (events)
+        if sv_list.is_empty() {
+            writeln!(w)?;
+        }
+
         Ok(())
     }
 
@@ -168,6 +216,7 @@ impl Observations {
         header: &HeaderFields,
         sv_list: &[SV],
         numsat: usize,
+        rounding: RoundingMode,
     ) -> Result<(), FormattingError> {
         const BLANKING: &str = "                ";
 
@@ -217,7 +266,14 @@ impl Observations {
                         .filter(|sig| sig.sv == *sv && sig.observable == *observable)
                         .reduce(|k, _| k)
                     {
-                        write!(w, "{:14.3}", observation.value)?;
+                        write!(
+                            w,
+                            "{}",
+                            FixedFormatter::new_observation(
+                                header.scaled_value(observation),
+                                rounding
+                            )
+                        )?;
 
                         if let Some(lli) = &observation.lli {
                             write!(w, "{}", lli.bits())?;
@@ -250,7 +306,7 @@ impl Observations {
         if let Some(clock) = self.clock {
             writeln!(
                 w,
-                "> {}  {} {:2}  {:13.4}",
+                "> {}  {} {:2}      {:15.12}",
                 epoch_format(key.epoch, RinexType::ObservationData, 3),
                 key.flag,
                 numsat,
@@ -297,6 +353,7 @@ mod test {
 
         let obs = Observations {
             clock: None,
+            event: None,
             signals: vec![
                 SignalObservation {
                     value: 1.0,
@@ -390,6 +447,7 @@ mod test {
 
         let obs = Observations {
             clock: None,
+            event: None,
             signals: vec![
                 SignalObservation {
                     value: 1.0,
@@ -555,6 +613,7 @@ mod test {
 
         let obs = Observations {
             clock: None,
+            event: None,
             signals: vec![
                 SignalObservation {
                     value: 1.0,
@@ -671,6 +730,7 @@ mod test {
 
         let mut obs = Observations {
             clock: None,
+            event: None,
             signals: Vec::new(),
         };
 
@@ -756,6 +816,7 @@ mod test {
 
         let mut obs = Observations {
             clock: None,
+            event: None,
             signals: Vec::new(),
         };
 
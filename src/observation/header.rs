@@ -3,11 +3,12 @@
 use crate::{
     epoch::epoch_decompose as epoch_decomposition,
     hatanaka::CRINEX,
-    prelude::{Constellation, Epoch, FormattingError, Observable, TimeScale},
+    observation::{PhaseShift, SignalObservation},
+    prelude::{Constellation, Epoch, FormattingError, Observable, TimeScale, SV},
 };
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     io::{BufWriter, Write},
 };
 
@@ -37,6 +38,16 @@ pub struct HeaderFields {
     /// Possible observation scaling, used in high precision
     /// OBS RINEX (down to nano radians precision).
     pub scaling: HashMap<(Constellation, Observable), u16>,
+    /// Phase corrections applied to align phase observations
+    /// to the reference signal of each frequency band  (V3+)
+    pub phase_shifts: Vec<PhaseShift>,
+    /// Number of satellites in the record ("# OF SATELLITES").
+    /// This optional field is deduced from the record when formatting.
+    pub num_sv: Option<usize>,
+    /// Number of observations per [SV], in the order of the declared observables
+    /// ("PRN / # OF OBS"). This optional field is only formatted when not empty:
+    /// it is deduced from the record on request, see [crate::prelude::FormattingOptions].
+    pub prn_obs_counts: BTreeMap<SV, Vec<usize>>,
 }
 
 impl HeaderFields {
@@ -83,7 +94,26 @@ impl HeaderFields {
             _ => self.format_v3_observables(w)?,
         }
 
-        //TODO scaling
+        if major > 2 {
+            for shift in self.phase_shifts.iter() {
+                write!(w, "{}", shift.format())?;
+            }
+        }
+
+        if major > 2 {
+            self.format_scaling_factors(w)?;
+        }
+
+        if self.clock_offset_applied {
+            writeln!(w, "{:6}{:54}RCV CLOCK OFFS APPL", 1, "")?;
+        }
+
+        if let Some(num_sv) = self.num_sv {
+            writeln!(w, "{:6}{:54}# OF SATELLITES", num_sv, "")?;
+        }
+
+        self.format_prn_obs_counts(w)?;
+
         //TODO DCBs
         Ok(())
     }
@@ -98,7 +128,7 @@ impl HeaderFields {
                     write!(w, "      ")?;
                 }
 
-                write!(w, "    {}", observable)?;
+                write!(w, "{:>6}", observable.to_string())?;
 
                 if (nth % 9) == 8 {
                     writeln!(w, "# / TYPES OF OBSERV")?;
@@ -107,7 +137,7 @@ impl HeaderFields {
                 modulo = nth % 9;
             }
 
-            if modulo != 7 {
+            if modulo != 8 {
                 writeln!(
                     w,
                     "{:>width$}",
@@ -131,7 +161,7 @@ impl HeaderFields {
                     write!(w, "      ")?;
                 }
 
-                write!(w, " {}", observable)?;
+                write!(w, " {:<3}", observable.to_string())?;
 
                 if (nth % 13) == 12 {
                     writeln!(w, "  SYS / # / OBS TYPES")?;
@@ -152,6 +182,70 @@ impl HeaderFields {
         Ok(())
     }
 
+    fn format_scaling_factors<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+    ) -> Result<(), FormattingError> {
+        // one block per constellation and factor
+        let blocks = self
+            .scaling
+            .iter()
+            .map(|((c, observable), scaling)| ((*c, *scaling), observable))
+            .into_group_map();
+
+        for ((constell, scaling), observables) in blocks.iter().sorted_by_key(|(k, _)| *k) {
+            let observables = observables
+                .iter()
+                .map(|observable| observable.to_string())
+                .sorted()
+                .collect::<Vec<_>>();
+
+            for (nth, chunk) in observables.chunks(12).enumerate() {
+                let mut content = if nth == 0 {
+                    format!("{:x} {:4}  {:2}", constell, scaling, observables.len())
+                } else {
+                    " ".repeat(10)
+                };
+
+                for observable in chunk {
+                    content.push_str(&format!(" {:<3}", observable));
+                }
+
+                writeln!(w, "{:<60}SYS / SCALE FACTOR", content)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn format_prn_obs_counts<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+        for (sv, counts) in self.prn_obs_counts.iter() {
+            // 9 counts per line, continued on following lines
+            for (nth, chunk) in counts.chunks(9).enumerate() {
+                let mut content = if nth == 0 {
+                    format!("   {:x}", sv)
+                } else {
+                    " ".repeat(6)
+                };
+
+                for count in chunk {
+                    content.push_str(&format!("{:6}", count));
+                }
+
+                writeln!(w, "{:<60}PRN / # OF OBS", content)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns [Observable]s declared for this [SV]
+    pub(crate) fn sv_observables(&self, sv: &SV) -> Option<&Vec<Observable>> {
+        match self.codes.get(&sv.constellation) {
+            Some(observables) => Some(observables),
+            None if sv.constellation.is_sbas() => self.codes.get(&Constellation::SBAS),
+            None => None,
+        }
+    }
+
     /// Add "TIME OF FIRST OBS" field
     pub(crate) fn with_crinex(&self, c: CRINEX) -> Self {
         let mut s = self.clone();
@@ -178,11 +272,21 @@ impl HeaderFields {
         self.scaling.insert((c, observable.clone()), scaling);
     }
 
-    // /// Returns given scaling to apply for given GNSS system
-    // /// and given observation. Returns 1.0 by default, so it always applies
-    // pub(crate) fn scaling(&self, c: Constellation, observable: Observable) -> Option<&u16> {
-    //     self.scaling.get(&(c, observable))
-    // }
+    /// Returns scaling factor that applies to this [Observable], for this [Constellation].
+    /// Stored observations should be divided by this factor when read,
+    /// and multiplied by this factor when written.
+    pub(crate) fn scaling(&self, c: Constellation, observable: &Observable) -> Option<u16> {
+        let c = if c.is_sbas() { Constellation::SBAS } else { c };
+        self.scaling.get(&(c, observable.clone())).copied()
+    }
+
+    /// Returns value of this [SignalObservation], as it should be stored (scaled up).
+    pub(crate) fn scaled_value(&self, signal: &SignalObservation) -> f64 {
+        match self.scaling(signal.sv.constellation, &signal.observable) {
+            Some(scaling) => signal.value * scaling as f64,
+            None => signal.value,
+        }
+    }
 }
 
 impl HeaderFields {
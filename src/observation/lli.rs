@@ -19,5 +19,51 @@ bitflags! {
         /// Observing under anti spoofing,
         /// might suffer from decreased SNR - decreased signal quality
         const UNDER_ANTI_SPOOFING = 0x04;
+        /// RINEX3 (Galileo): BOC tracking of an MBOC modulated signal,
+        /// might suffer from increased noise
+        const BOC_TRACKING = 0x04;
+    }
+}
+
+impl LliFlags {
+    /// Returns true if lock was lost since previous observation
+    pub fn is_lock_loss(&self) -> bool {
+        self.contains(Self::LOCK_LOSS)
+    }
+
+    /// Returns true if a half cycle ambiguity (slip) is possible
+    pub fn is_half_cycle_ambiguity(&self) -> bool {
+        self.contains(Self::HALF_CYCLE_SLIP)
+    }
+
+    /// Returns true if signal was BOC tracked (or under anti spoofing, in RINEX2)
+    pub fn is_boc_tracking(&self) -> bool {
+        self.contains(Self::BOC_TRACKING)
+    }
+
+    /// Returns true if phase continuity is not guaranteed:
+    /// loss of lock or half cycle ambiguity.
+    pub fn is_phase_discontinuity(&self) -> bool {
+        self.intersects(Self::LOCK_LOSS | Self::HALF_CYCLE_SLIP)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LliFlags;
+
+    #[test]
+    fn lli_flags() {
+        let lli = LliFlags::from_bits_truncate(5);
+        assert!(lli.is_lock_loss());
+        assert!(lli.is_boc_tracking());
+        assert!(!lli.is_half_cycle_ambiguity());
+        assert!(lli.is_phase_discontinuity());
+
+        let lli = LliFlags::from_bits_truncate(4);
+        assert_eq!(lli, LliFlags::UNDER_ANTI_SPOOFING);
+        assert!(!lli.is_phase_discontinuity());
+
+        assert!(!LliFlags::OK_OR_UNKNOWN.is_phase_discontinuity());
     }
 }
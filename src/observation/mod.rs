@@ -1,16 +1,58 @@
 //! Observation RINEX module
 mod clock;
+mod event;
 mod flag;
 mod formatting; // formatter
 mod header;
 mod lli;
 mod parsing; // parser
+mod phase_shift;
+mod priority; // signal code priority
 mod rinex; // high level methods
 mod signal;
 mod snr;
 
 #[cfg(feature = "obs")]
-pub use rinex::feature::{Combination, CombinationKey};
+pub use rinex::feature::{Combination, CombinationKey, MultipathKey};
+
+#[cfg(feature = "obs")]
+pub use rinex::dcb::P1C1Biases;
+
+#[cfg(feature = "obs")]
+pub use rinex::differential::{DifferentialReport, SlipRates, SnrDelta};
+
+#[cfg(feature = "obs")]
+pub use rinex::differencing::{SingleDifferenceKey, SvDifferenceKey};
+
+#[cfg(all(feature = "obs", feature = "processing"))]
+pub(crate) use rinex::differencing::sv_differences;
+
+#[cfg(feature = "obs")]
+pub use rinex::clock_drift::{ClockDriftReport, ClockDriftSample, SteeringEvent};
+
+#[cfg(feature = "obs")]
+pub use rinex::timeline::{EventCategory, Timeline, TimelineEvent, TimelineOptions};
+
+#[cfg(feature = "obs")]
+pub use rinex::multipath::MultipathStatistics;
+
+#[cfg(feature = "obs")]
+pub use rinex::doppler::{DopplerAnomaly, DopplerInconsistency, DopplerScreeningOptions};
+
+#[cfg(all(feature = "obs", feature = "processing"))]
+pub(crate) use rinex::doppler::DopplerArc;
+
+#[cfg(feature = "obs")]
+pub use rinex::clock_jumps::{ClockJump, ClockJumpOptions};
+
+#[cfg(feature = "obs")]
+pub use rinex::statistics::{ObservableStatistics, StatisticsReport};
+
+#[cfg(feature = "obs")]
+pub use rinex::residuals::{CycleSlipKey, CycleSlipStatistics, HatchKey, HatchResiduals};
+
+#[cfg(all(feature = "obs", feature = "nav"))]
+pub use rinex::reflectometry::{ReflectometryKey, ReflectometryOptions, ReflectometryPeak};
 
 #[cfg(feature = "processing")]
 pub(crate) mod mask; // mask Trait implementation
@@ -25,13 +67,18 @@ pub(crate) mod repair; // repair Trait implementation
 use serde::{Deserialize, Serialize};
 
 pub use clock::ClockObservation;
+pub use event::Event;
 pub use flag::EpochFlag;
 pub use header::HeaderFields;
 pub use lli::LliFlags;
+pub use phase_shift::PhaseShift;
+pub use priority::SignalPriority;
+pub use rinex::dedup::{DedupPolicy, DedupReport};
+pub use rinex::time_of_obs::TimeOfObsMismatch;
 pub use signal::SignalObservation;
 pub use snr::SNR;
 
-pub(crate) use parsing::{is_new_epoch, parse_epoch};
+pub(crate) use parsing::{event_records_count, is_new_epoch, parse_epoch};
 
 #[cfg(docsrs)]
 use crate::Bibliography;
@@ -52,6 +99,8 @@ pub struct Observations {
     pub clock: Option<ClockObservation>,
     /// List of [SignalObservation]s.
     pub signals: Vec<SignalObservation>,
+    /// [Event] attached to event epochs (flag 2 to 5), which have no [SignalObservation]s
+    pub event: Option<Event>,
 }
 
 impl Default for Observations {
@@ -59,11 +108,18 @@ impl Default for Observations {
         Self {
             clock: None,
             signals: Vec::with_capacity(16),
+            event: None,
         }
     }
 }
 
 impl Observations {
+    /// Returns receiver clock offset to the GNSS [TimeScale], in seconds,
+    /// when reported for this epoch.
+    pub fn clock_offset_s(&self) -> Option<f64> {
+        self.clock.map(|clock| clock.offset_s)
+    }
+
     /// Define [Observations] with Clock offset [s] observed at [Epoch]
     pub fn with_clock_offset_s(&self, timeof_obs: Epoch, offset_s: f64) -> Self {
         let mut s = self.clone();
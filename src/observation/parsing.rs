@@ -2,9 +2,10 @@
 use crate::{
     epoch::{parse_in_timescale as parse_epoch_in_timescale, parse_utc as parse_utc_epoch},
     observation::{
-        ClockObservation, EpochFlag, LliFlags, ObsKey, Observations, SignalObservation, SNR,
+        ClockObservation, EpochFlag, Event, LliFlags, ObsKey, Observations, SignalObservation, SNR,
     },
     prelude::{Constellation, Header, Observable, ParsingError, TimeScale, Version, SV},
+    sv::{is_valid_prn, parse_lenient as parse_sv_lenient},
 };
 
 use std::{
@@ -54,12 +55,36 @@ pub fn is_new_epoch(line: &str, v: Version) -> bool {
     }
 }
 
+/// Returns the number of special records that follow this Observation Epoch,
+/// in case it describes an event (flag 2 to 5). Returns 0 otherwise.
+pub fn event_records_count(line: &str, v: Version) -> usize {
+    let line = line.strip_prefix('>').unwrap_or(line);
+    let offset = if v.major > 2 { 28 } else { 26 };
+
+    let flag = match line
+        .get(offset..offset + 3)
+        .map(|flag| EpochFlag::from_str(flag.trim()))
+    {
+        Some(Ok(flag)) => flag,
+        _ => return 0,
+    };
+
+    if !flag.is_event() {
+        return 0;
+    }
+
+    line.get(offset + 3..offset + 6)
+        .and_then(|count| count.trim().parse::<usize>().ok())
+        .unwrap_or(0)
+}
+
 /// Parses record entries from readable content
 /// ## Input
 ///   - header: reference to previously parsed [Header]
 ///   - content: readable content
 ///   - ts: [TimeScale] defined by [Header]
 ///   - observations: preallocated [Observations] for performance issue.
+///   - dropped: [SV]s that were dropped, because their PRN is not possible
 /// ## Output
 ///   - [ObsKey] record indexer
 pub fn parse_epoch(
@@ -67,6 +92,7 @@ pub fn parse_epoch(
     content: &str,
     ts: TimeScale,
     observations: &mut Observations,
+    dropped: &mut Vec<SV>,
 ) -> Result<ObsKey, ParsingError> {
     let mut lines = content.lines();
 
@@ -108,49 +134,37 @@ pub fn parse_epoch(
         .map_err(|_| ParsingError::NumSatParsing)?;
 
     // grab possible clock offset
-    let offs: Option<&str> = match header.version.major < 2 {
-        true => {
-            // RINEX 2
-            // clock offsets are last 12 characters
-            if line.len() > 60 - 12 {
-                Some(line.split_at(60 - 12).1.trim())
-            } else {
-                None
-            }
-        },
-        false => {
-            // RINEX 3
-            let min_len: usize = 4+1 // y
-                +2+1 // m
-                +2+1 // d
-                +2+1 // h
-                +2+1 // m
-                +11+1// s
-                +3   // flag
-                +3; // n_sat
-            if line.len() > min_len {
-                // RINEX3: clock offset precision was increased
-                Some(line.split_at(min_len).1.trim()) // this handles it naturally
-            } else {
-                None
-            }
-        },
+    let offs = if header.version.major < 3 {
+        // RINEX 2: follows the 12 SV wide list (F12.9)
+        rem.get(12 * 3..).map(|offs| offs.trim())
+    } else {
+        // RINEX 3: 6X,F15.12 (precision was increased)
+        Some(rem.trim())
     };
 
-    if let Some(offset) = offs {
-        if let Ok(offset_s) = offset.parse::<f64>() {
-            observations
-                .with_clock_observation(ClockObservation::default().with_offset_s(epoch, offset_s));
-        }
+    if let Some(Ok(offset_s)) = offs.filter(|offs| !offs.is_empty()).map(f64::from_str) {
+        observations.clock = Some(ClockObservation::default().with_offset_s(epoch, offset_s));
     }
 
     match flag {
         EpochFlag::Ok | EpochFlag::PowerFailure | EpochFlag::CycleSlip => {
-            parse_observations(header, num_sat, rem, lines, &mut observations.signals)?;
+            parse_observations(
+                header,
+                num_sat,
+                rem,
+                lines,
+                &mut observations.signals,
+                dropped,
+            )?;
         },
         _ => {
-            // Hardware events are not supported yet (coming soon)
-            return Err(ParsingError::ObsHardwareEvent);
+            // events: followed by special records, as is
+            let records = lines
+                .take(num_sat as usize)
+                .map(|line| line.trim_end().to_string())
+                .collect::<Vec<_>>();
+
+            observations.event = Some(Event::new(records));
         },
     }
 
@@ -163,6 +177,7 @@ fn parse_observations(
     rem: &str,
     mut lines: Lines<'_>,
     signals: &mut Vec<SignalObservation>,
+    dropped: &mut Vec<SV>,
 ) -> Result<(), ParsingError> {
     // retrieve header specs
     let constellation = header.constellation;
@@ -170,6 +185,9 @@ fn parse_observations(
     let obs = header.obs.as_ref().unwrap();
     let observables = &obs.codes;
 
+    // signals described by this epoch
+    let first = signals.len();
+
     // V1 / V2 tedious case
     let rem = rem.trim();
     let remainder_len = rem.len();
@@ -204,9 +222,19 @@ fn parse_observations(
             observables,
             lines,
             signals,
+            dropped,
         );
     } else {
-        parse_signals_v3(observables, lines, signals);
+        parse_signals_v3(observables, lines, signals, dropped);
+    }
+
+    // stored values are scaled (SYS / SCALE FACTOR)
+    if !obs.scaling.is_empty() {
+        for signal in signals[first..].iter_mut() {
+            if let Some(scaling) = obs.scaling(signal.sv.constellation, &signal.observable) {
+                signal.value /= scaling as f64;
+            }
+        }
     }
 
     Ok(())
@@ -221,6 +249,7 @@ fn parse_observations(
 ///   - constellation: [Constellation] specs defined in [Header]
 ///   - observables: reference to [Observable]s specs defined in [Header]
 ///   - lines: remaing [Lines] Iterator
+///   - dropped: [SV]s that were dropped, because their PRN is not possible
 fn parse_signals_v2(
     systems_str: &str,
     systems_str_len: usize,
@@ -228,6 +257,7 @@ fn parse_signals_v2(
     head_observables: &HashMap<Constellation, Vec<Observable>>,
     lines: Lines<'_>,
     signals: &mut Vec<SignalObservation>,
+    dropped: &mut Vec<SV>,
 ) {
     const SVNN_SIZE: usize = 3; // SVNN standard
     const MAX_OBSERVABLES_LINE: usize = 5; // max in a single line
@@ -246,6 +276,7 @@ fn parse_signals_v2(
     let mut sv = SV::default();
     let mut sv_ptr = 0;
     let mut sv_identified = false;
+    let mut sv_valid = false;
     // let numsat = systems_str_len / SVNN_SIZE;
 
     // observable pointer
@@ -263,31 +294,23 @@ fn parse_signals_v2(
             let sv_end = (sv_ptr + SVNN_SIZE).min(systems_str_len);
             let system = &systems_str[sv_ptr..sv_end].trim();
 
-            // actual parsing
-            if let Ok(found) = SV::from_str(system) {
-                sv = found;
-            } else {
-                // This may fail on very old RINEX mono GNSS
-                // that omit the constellation in the description
-                match head_constellation {
-                    Some(Constellation::Mixed) | None => {
-                        //#[cfg(feature = "log")]
-                        //error!("parse_sig_v2(abort): no constell specs");
-                        break;
-                    },
-                    Some(gnss) => {
-                        if let Ok(prn) = system.trim().parse::<u8>() {
-                            sv = SV {
-                                prn,
-                                constellation: gnss,
-                            };
-                        } else {
-                            //#[cfg(feature = "log")]
-                            //error!("parse_sig_v2(abort): invalid sv");
-                            break;
-                        }
-                    },
-                }
+            // actual parsing: this tolerates very old RINEX mono GNSS
+            // that omit the constellation in the description
+            match parse_sv_lenient(system, head_constellation) {
+                Ok(found) => {
+                    sv = found;
+                    // impossible PRN (=corrupted descriptor): its observations
+                    // are consumed but not retained
+                    sv_valid = is_valid_prn(&sv);
+                    if !sv_valid {
+                        dropped.push(sv);
+                    }
+                },
+                Err(_) => {
+                    //#[cfg(feature = "log")]
+                    //error!("parse_sig_v2(abort): invalid sv");
+                    break;
+                },
             }
 
             // move on to next
@@ -382,7 +405,7 @@ fn parse_signals_v2(
                 //println!("lli: \"{}\"", lli_slice);
                 match lli_slice.parse::<u8>() {
                     Ok(unsigned) => {
-                        lli = LliFlags::from_bits(unsigned);
+                        lli = Some(LliFlags::from_bits_truncate(unsigned));
                     },
                     Err(_) => {},
                 }
@@ -408,13 +431,15 @@ fn parse_signals_v2(
             let end = slice.len().min(OBSERVABLE_F14_WIDTH);
 
             if let Ok(value) = slice[..end].trim().parse::<f64>() {
-                signals.push(SignalObservation {
-                    sv,
-                    snr,
-                    lli,
-                    value,
-                    observable: observables[obs_ptr].clone(),
-                });
+                if sv_valid {
+                    signals.push(SignalObservation {
+                        sv,
+                        snr,
+                        lli,
+                        value,
+                        observable: observables[obs_ptr].clone(),
+                    });
+                }
             }
 
             obs_ptr += 1;
@@ -448,6 +473,7 @@ fn parse_signals_v3(
     head_observables: &HashMap<Constellation, Vec<Observable>>,
     lines: Lines<'_>,
     signals: &mut Vec<SignalObservation>,
+    dropped: &mut Vec<SV>,
 ) {
     const SVNN_SIZE: usize = 3;
     const OBSERVABLE_F14_WIDTH: usize = 14;
@@ -468,6 +494,12 @@ fn parse_signals_v3(
             },
         }
 
+        // impossible PRN (=corrupted line): not retained
+        if !is_valid_prn(&sv) {
+            dropped.push(sv);
+            continue;
+        }
+
         // identify [Observable]s
         let observables = if sv.constellation.is_sbas() {
             head_observables.get(&Constellation::SBAS)
@@ -482,7 +514,7 @@ fn parse_signals_v3(
         let observables = observables.unwrap();
 
         let num_obs = line.len() / OBSERVABLE_WIDTH;
-        let mut offset = SVNN_SIZE + 1;
+        let mut offset = SVNN_SIZE;
 
         for i in 0..num_obs {
             if i == observables.len() {
@@ -496,11 +528,10 @@ fn parse_signals_v3(
             let mut lli = Option::<LliFlags>::None;
 
             if slice.len() > OBSERVABLE_F14_WIDTH {
-                let start = offset + OBSERVABLE_F14_WIDTH - 1;
-                let lli_slice = &line[start..start + 1];
+                let lli_slice = &slice[OBSERVABLE_F14_WIDTH..OBSERVABLE_F14_WIDTH + 1];
                 match lli_slice.parse::<u8>() {
                     Ok(unsigned) => {
-                        lli = LliFlags::from_bits(unsigned);
+                        lli = Some(LliFlags::from_bits_truncate(unsigned));
                     },
                     Err(_e) => {
                         //#[cfg(feature = "log")]
@@ -512,8 +543,7 @@ fn parse_signals_v3(
             let mut snr = Option::<SNR>::None;
 
             if slice.len() > OBSERVABLE_F14_WIDTH + 1 {
-                let start = offset + OBSERVABLE_F14_WIDTH;
-                let snr_slice = &line[start..start + 1];
+                let snr_slice = &slice[OBSERVABLE_F14_WIDTH + 1..OBSERVABLE_F14_WIDTH + 2];
 
                 if let Ok(value) = snr_slice.parse::<u8>() {
                     snr = Some(SNR::from(value));
@@ -541,8 +571,8 @@ fn parse_signals_v3(
 mod test {
     use super::is_new_epoch;
     use crate::{
-        observation::{EpochFlag, SignalObservation, SNR},
-        prelude::{Constellation, Observable, Version, SV},
+        observation::{ClockObservation, EpochFlag, SignalObservation, SNR},
+        prelude::{Constellation, Epoch, Observable, Version, SV},
         tests::toolkit::generic_observation_epoch_decoding_test,
     };
     use std::str::FromStr;
@@ -611,7 +641,10 @@ G09  25493930.890   133971510.403 6        41.250                    25493926.95
             30,
             "2022-03-04T00:00:00 GPST",
             EpochFlag::Ok,
-            None,
+            Some(ClockObservation::default().with_offset_s(
+                Epoch::from_str("2022-03-04T00:00:00 GPST").unwrap(),
+                0.0,
+            )),
             vec![
                 SignalObservation {
                     sv: SV::from_str("G01").unwrap(),
@@ -667,6 +700,60 @@ G04  21342618.100   112156219.39808      2167.688          48.250    21342617.44
         );
     }
 
+    #[test]
+    fn test_parse_v3_invalid_prn() {
+        let content = "> 2022 03 04 00 00  0.0000000  0  3
+G01  20176608.780   106028802.11808     -1009.418          50.250
+G45  20719565.760   108882069.81508       762.203          49.750
+G04  21342618.100   112156219.39808      2167.688          48.250
+";
+        generic_observation_epoch_decoding_test(
+            content,
+            3,
+            Constellation::GPS,
+            &[("GPS", "C1C, L1C, D1C, S1C")],
+            "2022-03-04T00:00:00 GPST",
+            8,
+            "2022-03-04T00:00:00 GPST",
+            EpochFlag::Ok,
+            None,
+            vec![SignalObservation {
+                sv: SV::from_str("G04").unwrap(),
+                observable: Observable::from_str("C1C").unwrap(),
+                value: 21342618.100,
+                lli: None,
+                snr: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn test_parse_v2_invalid_prn() {
+        let content = " 21 01 01 00 00 00.0000000  0  3G07R30G10
+  24178026.635 6  24178024.891 6
+  21866748.928 7  21866750.407 7
+  21458907.960 8  21458908.454 7
+";
+        generic_observation_epoch_decoding_test(
+            content,
+            2,
+            Constellation::Mixed,
+            &[("GPS", "C1, P1"), ("GLO", "C1, P1")],
+            "2021-01-01T00:00:00 GPST",
+            4,
+            "2021-01-01T00:00:00 GPST",
+            EpochFlag::Ok,
+            None,
+            vec![SignalObservation {
+                sv: SV::from_str("G10").unwrap(),
+                observable: Observable::from_str("P1").unwrap(),
+                value: 21458908.454,
+                lli: None,
+                snr: Some(SNR::from(7)),
+            }],
+        );
+    }
+
     #[test]
     fn test_parse_v2_1() {
         let content = " 21 01 01 00 00 00.0000000  0 24G07G08G10G13G15G16G18G20G21G23G26G27
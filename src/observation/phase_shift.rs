@@ -0,0 +1,272 @@
+//! Carrier phase shifts (SYS / PHASE SHIFT)
+use crate::{
+    prelude::{Constellation, Observable, ParsingError, Rinex, SV},
+    record::Record,
+};
+
+use std::str::FromStr;
+
+/// [PhaseShift] describes the phase correction (in cycles) that was applied
+/// to one carrier phase [Observable], to align it to the reference signal of
+/// its frequency band. This is typically a quarter-cycle shift, between
+/// the in-phase and quadrature components. It is described by the
+/// "SYS / PHASE SHIFT" header field (V3+).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhaseShift {
+    /// [Constellation] this correction applies to
+    pub constellation: Constellation,
+    /// Phase [Observable] that was corrected
+    pub observable: Observable,
+    /// Correction that was applied, in cycles
+    pub correction_cycles: f64,
+    /// [SV]s this correction applies to. Empty means all [SV]s of this [Constellation].
+    pub satellites: Vec<SV>,
+}
+
+impl PhaseShift {
+    /// Builds a new [PhaseShift] that applies to all [SV]s of this [Constellation]
+    pub fn new(
+        constellation: Constellation,
+        observable: Observable,
+        correction_cycles: f64,
+    ) -> Self {
+        Self {
+            constellation,
+            observable,
+            correction_cycles,
+            satellites: Default::default(),
+        }
+    }
+
+    /// Copies and returns [PhaseShift] that only applies to these [SV]s
+    pub fn with_satellites(&self, satellites: &[SV]) -> Self {
+        let mut s = self.clone();
+        s.satellites = satellites.to_vec();
+        s
+    }
+
+    /// Returns true if this [PhaseShift] applies to this [SV] and [Observable]
+    pub fn applies(&self, sv: SV, observable: &Observable) -> bool {
+        sv.constellation == self.constellation
+            && *observable == self.observable
+            && (self.satellites.is_empty() || self.satellites.contains(&sv))
+    }
+
+    /// Parses first line of a "SYS / PHASE SHIFT" description
+    pub(crate) fn parse(content: &str) -> Result<Self, ParsingError> {
+        if content.len() < 5 {
+            return Err(ParsingError::PhaseShift);
+        }
+
+        let constellation = Constellation::from_str(content[..1].trim())?;
+        let observable = Observable::from_str(content[2..5].trim())?;
+
+        let correction_cycles = match content.get(5..14).map(|s| s.trim()) {
+            Some(correction) if !correction.is_empty() => correction
+                .parse::<f64>()
+                .or(Err(ParsingError::PhaseShift))?,
+            _ => 0.0,
+        };
+
+        let mut shift = Self::new(constellation, observable, correction_cycles);
+        shift.parse_satellites(content)?;
+        Ok(shift)
+    }
+
+    /// Parses the satellites list of a "SYS / PHASE SHIFT" description,
+    /// which may span several lines.
+    pub(crate) fn parse_satellites(&mut self, content: &str) -> Result<(), ParsingError> {
+        if let Some(list) = content.get(18..) {
+            for sv in list.split_ascii_whitespace() {
+                self.satellites.push(SV::from_str(sv)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats this [PhaseShift] as "SYS / PHASE SHIFT" header lines
+    pub(crate) fn format(&self) -> String {
+        let mut lines = String::new();
+        let mut content = format!(
+            "{:x} {:<3} {:8.5}",
+            self.constellation,
+            self.observable.to_string(),
+            self.correction_cycles
+        );
+
+        if self.satellites.is_empty() {
+            lines.push_str(&format!("{:<60}SYS / PHASE SHIFT\n", content));
+            return lines;
+        }
+
+        content.push_str(&format!("  {:02}", self.satellites.len()));
+
+        for (nth, chunk) in self.satellites.chunks(10).enumerate() {
+            if nth > 0 {
+                content = " ".repeat(18);
+            }
+            for sv in chunk {
+                content.push_str(&format!(" {:x}", sv));
+            }
+            lines.push_str(&format!("{:<60}SYS / PHASE SHIFT\n", content));
+        }
+
+        lines
+    }
+}
+
+/// Shifts all phase observations of this [Record] by `sign` times the
+/// [PhaseShift] correction, when it applies.
+fn shift_phases(record: &mut Record, shifts: &[PhaseShift], sign: f64) {
+    let record = match record.as_mut_obs() {
+        Some(record) => record,
+        None => return,
+    };
+
+    for (_, observations) in record.iter_mut() {
+        for signal in observations.signals.iter_mut() {
+            if !signal.observable.is_phase_range_observable() {
+                continue;
+            }
+            if let Some(shift) = shifts
+                .iter()
+                .find(|shift| shift.applies(signal.sv, &signal.observable))
+            {
+                signal.value += sign * shift.correction_cycles;
+            }
+        }
+    }
+}
+
+impl Rinex {
+    /// Removes the [PhaseShift]s declared in the header from all phase observations,
+    /// which returns the raw phase observations, as tracked by the receiver.
+    /// The header no longer declares any [PhaseShift] afterwards.
+    pub fn remove_phase_shifts_mut(&mut self) {
+        if let Some(obs) = &mut self.header.obs {
+            let shifts = std::mem::take(&mut obs.phase_shifts);
+            shift_phases(&mut self.record, &shifts, -1.0);
+        }
+    }
+
+    /// Copies and returns [Rinex] without any phase shift.
+    /// See [Self::remove_phase_shifts_mut].
+    pub fn remove_phase_shifts(&self) -> Self {
+        let mut s = self.clone();
+        s.remove_phase_shifts_mut();
+        s
+    }
+
+    /// Applies these [PhaseShift]s on all phase observations, to align them to the
+    /// reference signal of each frequency band, which mixed code processing requires.
+    /// The shifts are then declared in the header.
+    pub fn apply_phase_shifts_mut(&mut self, shifts: &[PhaseShift]) {
+        shift_phases(&mut self.record, shifts, 1.0);
+        if let Some(obs) = &mut self.header.obs {
+            obs.phase_shifts.extend_from_slice(shifts);
+        }
+    }
+
+    /// Copies and returns [Rinex] with these [PhaseShift]s applied.
+    /// See [Self::apply_phase_shifts_mut].
+    pub fn apply_phase_shifts(&self, shifts: &[PhaseShift]) -> Self {
+        let mut s = self.clone();
+        s.apply_phase_shifts_mut(shifts);
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PhaseShift;
+    use crate::{
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Constellation, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn phase_shift_parsing() {
+        let g01 = SV::from_str("G01").unwrap();
+        let g12 = SV::from_str("G12").unwrap();
+        let l2s = Observable::from_str("L2S").unwrap();
+
+        let shift = PhaseShift::parse("G L2S -0.25000").unwrap();
+        assert_eq!(
+            shift,
+            PhaseShift::new(Constellation::GPS, l2s.clone(), -0.25)
+        );
+        assert!(shift.applies(g01, &l2s));
+        assert_eq!(
+            shift.format(),
+            format!("{:<60}SYS / PHASE SHIFT\n", "G L2S -0.25000")
+        );
+
+        let mut shift = PhaseShift::parse("G L2S -0.25000  02 G01 G12").unwrap();
+        assert_eq!(shift.satellites, vec![g01, g12]);
+        assert!(!shift.applies(SV::from_str("G02").unwrap(), &l2s));
+
+        // blank correction
+        let shift_blank = PhaseShift::parse("E L1C").unwrap();
+        assert_eq!(shift_blank.correction_cycles, 0.0);
+
+        // continuation
+        let svs = (1..=12)
+            .map(|prn| SV::new(Constellation::GPS, prn))
+            .collect::<Vec<_>>();
+        shift = shift.with_satellites(&svs);
+        let formatted = shift.format();
+        assert_eq!(formatted.lines().count(), 2);
+        for line in formatted.lines() {
+            assert_eq!(line.find("SYS / PHASE SHIFT"), Some(60));
+        }
+
+        // header content: marker is stripped
+        let mut lines = formatted.lines().map(|line| &line[..60]);
+        let mut parsed = PhaseShift::parse(lines.next().unwrap()).unwrap();
+        parsed.parse_satellites(lines.next().unwrap()).unwrap();
+        assert_eq!(parsed, shift);
+    }
+
+    #[test]
+    fn phase_shift_compensation() {
+        let g01 = SV::from_str("G01").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let l2s = Observable::from_str("L2S").unwrap();
+        let c2s = Observable::from_str("C2S").unwrap();
+
+        let mut rinex = Rinex::basic_obs();
+        let key = ObsKey {
+            epoch: Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap(),
+            flag: Default::default(),
+        };
+
+        let mut obs = Observations::default();
+        for observable in [&l1c, &l2s, &c2s] {
+            obs.signals
+                .push(SignalObservation::new(g01, observable.clone(), 100.0));
+        }
+        rinex.record.as_mut_obs().unwrap().insert(key, obs);
+
+        let shifts = [PhaseShift::new(Constellation::GPS, l2s.clone(), -0.25)];
+        let shifted = rinex.apply_phase_shifts(&shifts);
+
+        let values = shifted
+            .signal_observations_iter()
+            .map(|(_, sig)| sig.value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![100.0, 99.75, 100.0]);
+        assert_eq!(shifted.header.obs.as_ref().unwrap().phase_shifts, shifts);
+
+        let restored = shifted.remove_phase_shifts();
+        assert_eq!(restored.record, rinex.record);
+        assert!(restored
+            .header
+            .obs
+            .as_ref()
+            .unwrap()
+            .phase_shifts
+            .is_empty());
+    }
+}
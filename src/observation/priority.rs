@@ -0,0 +1,317 @@
+//! Signal code priority, for best code selection
+use crate::{
+    observation::{Observations, SignalObservation},
+    prelude::{Carrier, Constellation, Observable, SV},
+};
+
+use std::collections::HashMap;
+
+/// [SignalPriority] table, similar to RTKLIB code priorities.
+/// For each [Constellation] and RINEX frequency band number ('1', '2', '5'..),
+/// we store the tracking attributes (last letter of the RINEX code) sorted by
+/// decreasing priority. For example "CPW" means C1C is preferred over C1P,
+/// itself preferred over C1W.
+/// Attributes that are not listed remain selectable, with the lowest priority.
+/// The [Default] table follows RTKLIB defaults.
+/// ```
+/// use std::str::FromStr;
+/// use rinex::prelude::*;
+/// use rinex::observation::{Observations, SignalObservation, SignalPriority};
+///
+/// let g01 = SV::from_str("G01").unwrap();
+///
+/// let mut obs = Observations::default();
+/// for code in ["C1W", "C1C", "C2W"] {
+///     let observable = Observable::from_str(code).unwrap();
+///     obs.signals.push(SignalObservation::new(g01, observable, 20.0E6));
+/// }
+///
+/// let table = SignalPriority::default();
+/// let best = obs.best_pseudo_range(g01, Carrier::L1, &table).unwrap();
+/// assert_eq!(best.observable, Observable::from_str("C1C").unwrap());
+///
+/// // prefer P(Y) code
+/// let table = table.with_priority(Constellation::GPS, '1', "WC");
+/// let best = obs.best_pseudo_range(g01, Carrier::L1, &table).unwrap();
+/// assert_eq!(best.observable, Observable::from_str("C1W").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalPriority {
+    /// Tracking attributes sorted by priority,
+    /// per ([Constellation], frequency band number)
+    pub table: HashMap<(Constellation, char), String>,
+}
+
+impl Default for SignalPriority {
+    fn default() -> Self {
+        let mut table = HashMap::new();
+
+        for (constellation, band, attributes) in [
+            (Constellation::GPS, '1', "CPYWMNSLX"),
+            (Constellation::GPS, '2', "CPYWMNDLSX"),
+            (Constellation::GPS, '5', "IQX"),
+            (Constellation::Glonass, '1', "CP"),
+            (Constellation::Glonass, '2', "CP"),
+            (Constellation::Glonass, '3', "IQX"),
+            (Constellation::Glonass, '4', "ABX"),
+            (Constellation::Glonass, '6', "ABX"),
+            (Constellation::Galileo, '1', "CABXZ"),
+            (Constellation::Galileo, '5', "IQX"),
+            (Constellation::Galileo, '6', "ABCXZ"),
+            (Constellation::Galileo, '7', "IQX"),
+            (Constellation::Galileo, '8', "IQX"),
+            (Constellation::QZSS, '1', "CLSXZ"),
+            (Constellation::QZSS, '2', "LSX"),
+            (Constellation::QZSS, '5', "IQXDPZ"),
+            (Constellation::QZSS, '6', "LSXEZ"),
+            (Constellation::SBAS, '1', "C"),
+            (Constellation::SBAS, '5', "IQX"),
+            (Constellation::BeiDou, '1', "DPXSLZ"),
+            (Constellation::BeiDou, '2', "IQX"),
+            (Constellation::BeiDou, '5', "DPX"),
+            (Constellation::BeiDou, '6', "IQXA"),
+            (Constellation::BeiDou, '7', "IQXDPZ"),
+            (Constellation::BeiDou, '8', "DPX"),
+            (Constellation::IRNSS, '1', "DPX"),
+            (Constellation::IRNSS, '5', "ABCX"),
+            (Constellation::IRNSS, '9', "ABCX"),
+        ] {
+            table.insert((constellation, band), attributes.to_string());
+        }
+
+        Self { table }
+    }
+}
+
+impl SignalPriority {
+    /// Copies and returns [SignalPriority] with tracking `attributes`, sorted
+    /// by decreasing priority, for this [Constellation] and frequency `band` number.
+    pub fn with_priority(
+        &self,
+        constellation: Constellation,
+        band: char,
+        attributes: &str,
+    ) -> Self {
+        let mut s = self.clone();
+        s.table.insert(
+            (Self::table_constellation(constellation), band),
+            attributes.to_string(),
+        );
+        s
+    }
+
+    /// Returns tracking attributes sorted by priority, for this
+    /// [Constellation] and frequency `band` number.
+    pub fn priority(&self, constellation: Constellation, band: char) -> Option<&str> {
+        self.table
+            .get(&(Self::table_constellation(constellation), band))
+            .map(|attributes| attributes.as_str())
+    }
+
+    /// Returns the rank of this [Observable] (0 being the highest priority),
+    /// when observed on this [Constellation].
+    /// RINEX2 codes, that do not have tracking attributes, are interpreted as C/A (C1, C2)
+    /// or P code (P1, P2).
+    pub fn rank(&self, constellation: Constellation, observable: &Observable) -> usize {
+        let (band, attribute) = match Self::band_attribute(observable) {
+            Some(found) => found,
+            None => return usize::MAX,
+        };
+
+        let attributes = match self.priority(constellation, band) {
+            Some(attributes) => attributes,
+            None => return usize::MAX,
+        };
+
+        attributes
+            .chars()
+            .position(|c| c == attribute)
+            .unwrap_or(attributes.len())
+    }
+
+    /// All SBAS share the same priorities
+    fn table_constellation(constellation: Constellation) -> Constellation {
+        if constellation.is_sbas() {
+            Constellation::SBAS
+        } else {
+            constellation
+        }
+    }
+
+    /// Returns (frequency band number, tracking attribute) of this [Observable]
+    fn band_attribute(observable: &Observable) -> Option<(char, char)> {
+        let code = match observable {
+            Observable::PseudoRange(code)
+            | Observable::PhaseRange(code)
+            | Observable::Doppler(code)
+            | Observable::SSI(code) => code,
+            _ => return None,
+        };
+
+        let mut chars = code.chars();
+        let kind = chars.next()?;
+        let band = chars.next()?;
+
+        let attribute = match chars.next() {
+            Some(attribute) => attribute,
+            None => {
+                if kind == 'P' {
+                    'P'
+                } else {
+                    'C'
+                }
+            },
+        };
+
+        Some((band, attribute))
+    }
+}
+
+impl Observations {
+    /// Returns the best [SignalObservation] for this [SV] on this [Carrier],
+    /// among [Observable]s accepted by `filter`, following [SignalPriority].
+    fn best_signal<F: Fn(&Observable) -> bool>(
+        &self,
+        sv: SV,
+        carrier: Carrier,
+        priority: &SignalPriority,
+        filter: F,
+    ) -> Option<&SignalObservation> {
+        self.signals
+            .iter()
+            .filter(|sig| {
+                sig.sv == sv
+                    && filter(&sig.observable)
+                    && sig
+                        .observable
+                        .to_carrier(sv.constellation)
+                        .map(|found| same_carrier(found, carrier))
+                        .unwrap_or(false)
+            })
+            .min_by_key(|sig| priority.rank(sv.constellation, &sig.observable))
+    }
+
+    /// Returns the best pseudo range observation for this [SV] on this [Carrier],
+    /// following [SignalPriority], if such signal was observed.
+    pub fn best_pseudo_range(
+        &self,
+        sv: SV,
+        carrier: Carrier,
+        priority: &SignalPriority,
+    ) -> Option<&SignalObservation> {
+        self.best_signal(sv, carrier, priority, |obs| {
+            obs.is_pseudo_range_observable()
+        })
+    }
+
+    /// Returns the best phase range observation for this [SV] on this [Carrier],
+    /// following [SignalPriority], if such signal was observed.
+    pub fn best_phase_range(
+        &self,
+        sv: SV,
+        carrier: Carrier,
+        priority: &SignalPriority,
+    ) -> Option<&SignalObservation> {
+        self.best_signal(sv, carrier, priority, |obs| obs.is_phase_range_observable())
+    }
+
+    /// Returns the best doppler observation for this [SV] on this [Carrier],
+    /// following [SignalPriority], if such signal was observed.
+    pub fn best_doppler(
+        &self,
+        sv: SV,
+        carrier: Carrier,
+        priority: &SignalPriority,
+    ) -> Option<&SignalObservation> {
+        self.best_signal(sv, carrier, priority, |obs| obs.is_doppler_observable())
+    }
+}
+
+/// Glonass FDMA channels are not considered when comparing [Carrier]s
+fn same_carrier(lhs: Carrier, rhs: Carrier) -> bool {
+    match (lhs, rhs) {
+        (Carrier::G1(_), Carrier::G1(_)) | (Carrier::G2(_), Carrier::G2(_)) => true,
+        (lhs, rhs) => lhs == rhs,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn signal_ranking() {
+        let table = SignalPriority::default();
+
+        for (constellation, code, rank) in [
+            (Constellation::GPS, "C1C", 0),
+            (Constellation::GPS, "C1P", 1),
+            (Constellation::GPS, "C1W", 3),
+            (Constellation::GPS, "L5Q", 1),
+            (Constellation::GPS, "C1", 0),
+            (Constellation::GPS, "P1", 1),
+            (Constellation::Galileo, "C1C", 0),
+            (Constellation::Galileo, "C7Q", 1),
+            (Constellation::EGNOS, "C1C", 0),
+        ] {
+            let observable = Observable::from_str(code).unwrap();
+            assert_eq!(
+                table.rank(constellation, &observable),
+                rank,
+                "{}({}) badly ranked",
+                code,
+                constellation
+            );
+        }
+
+        // unlisted attribute: lowest priority
+        let c1z = Observable::from_str("C1Z").unwrap();
+        assert_eq!(table.rank(Constellation::GPS, &c1z), 9);
+
+        let table = table.with_priority(Constellation::WAAS, '1', "X");
+        assert_eq!(table.priority(Constellation::SBAS, '1'), Some("X"));
+    }
+
+    #[test]
+    fn best_code_selection() {
+        let g01 = SV::from_str("G01").unwrap();
+        let r01 = SV::from_str("R01").unwrap();
+
+        let mut obs = Observations::default();
+        for (sv, code) in [
+            (g01, "C1W"),
+            (g01, "C1C"),
+            (g01, "L1C"),
+            (g01, "C2W"),
+            (g01, "C2L"),
+            (g01, "L2W"),
+            (r01, "C1P"),
+            (r01, "C1C"),
+        ] {
+            let observable = Observable::from_str(code).unwrap();
+            obs.signals
+                .push(SignalObservation::new(sv, observable, 1.0));
+        }
+
+        let table = SignalPriority::default();
+
+        for (sv, carrier, expected) in [
+            (g01, Carrier::L1, Some("C1C")),
+            (g01, Carrier::L2, Some("C2W")),
+            (g01, Carrier::L5, None),
+            (r01, Carrier::G1(None), Some("C1C")),
+            (r01, Carrier::G1(Some(-7)), Some("C1C")),
+        ] {
+            let best = obs
+                .best_pseudo_range(sv, carrier, &table)
+                .map(|sig| sig.observable.to_string());
+            assert_eq!(best.as_deref(), expected, "{}({})", sv, carrier);
+        }
+
+        let best = obs.best_phase_range(g01, Carrier::L2, &table).unwrap();
+        assert_eq!(best.observable, Observable::from_str("L2W").unwrap());
+
+        assert!(obs.best_doppler(g01, Carrier::L1, &table).is_none());
+    }
+}
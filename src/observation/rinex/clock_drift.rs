@@ -0,0 +1,259 @@
+//! Receiver clock drift analysis
+use crate::prelude::{Epoch, Rinex};
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [ClockDriftSample] describes the receiver clock state at one time tag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ClockDriftSample {
+    /// Receiver time tag
+    pub epoch: Epoch,
+    /// Receiver clock offset to GNSS time, in seconds
+    pub offset_s: f64,
+    /// Instantaneous drift (from previous sample), in s.s⁻¹
+    pub drift_s_s: Option<f64>,
+    /// Residual to the fitted model (steering events compensated), in seconds
+    pub residual_s: f64,
+}
+
+/// [SteeringEvent] is a discontinuity of the receiver clock offset,
+/// typically a clock steering (millisecond jump) performed by the receiver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SteeringEvent {
+    /// Time tag of the first sample following the discontinuity
+    pub epoch: Epoch,
+    /// Offset jump, in seconds
+    pub jump_s: f64,
+}
+
+/// [ClockDriftReport] characterizes the receiver oscillator, by comparing
+/// receiver time tags to GNSS time. The clock offset series is
+/// compensated for [SteeringEvent]s then fitted to a linear model.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ClockDriftReport {
+    /// Drift series, in chronological order
+    pub samples: Vec<ClockDriftSample>,
+    /// Detected [SteeringEvent]s
+    pub steering_events: Vec<SteeringEvent>,
+    /// Fitted offset at first time tag, in seconds
+    pub fitted_offset_s: f64,
+    /// Fitted drift rate, in s.s⁻¹
+    pub fitted_rate_s_s: f64,
+    /// RMS of the fit residuals, in seconds
+    pub residual_rms_s: f64,
+}
+
+impl ClockDriftReport {
+    /// Builds a [ClockDriftReport] from a series of receiver clock offsets
+    /// (in seconds) per time tag, for example SPP clock solutions.
+    /// Offset discontinuities larger than `steering_threshold_s`, once
+    /// the nominal drift has been accounted for, are reported as [SteeringEvent]s.
+    /// Returns None if less than two solutions are provided.
+    /// ```
+    /// use rinex::prelude::{Duration, Epoch};
+    /// use rinex::observation::ClockDriftReport;
+    /// use std::str::FromStr;
+    ///
+    /// let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+    /// let dt = Duration::from_seconds(30.0);
+    ///
+    /// // 1E-9 s/s drift and a 1ms steering on 5th epoch
+    /// let solutions = (0..10).map(|i| {
+    ///     let jump = if i >= 5 { -1.0E-3 } else { 0.0 };
+    ///     (t0 + i as f64 * dt, 1.0E-4 + 30.0E-9 * i as f64 + jump)
+    /// });
+    ///
+    /// let report = ClockDriftReport::from_solutions(solutions, 1.0E-6).unwrap();
+    /// assert_eq!(report.steering_events.len(), 1);
+    /// assert!((report.fitted_rate_s_s - 1.0E-9).abs() < 1.0E-15);
+    /// ```
+    pub fn from_solutions<I: IntoIterator<Item = (Epoch, f64)>>(
+        solutions: I,
+        steering_threshold_s: f64,
+    ) -> Option<Self> {
+        let solutions = solutions.into_iter().collect::<BTreeMap<_, _>>();
+
+        if solutions.len() < 2 {
+            return None;
+        }
+
+        let mut report = Self::default();
+
+        let mut samples = Vec::<ClockDriftSample>::with_capacity(solutions.len());
+        for (epoch, offset_s) in solutions.iter() {
+            let drift_s_s = samples
+                .last()
+                .map(|prev| (offset_s - prev.offset_s) / (*epoch - prev.epoch).to_seconds());
+
+            samples.push(ClockDriftSample {
+                epoch: *epoch,
+                offset_s: *offset_s,
+                drift_s_s,
+                residual_s: 0.0,
+            });
+        }
+
+        // nominal drift: robust to discontinuities
+        let mut drifts = samples
+            .iter()
+            .filter_map(|s| s.drift_s_s)
+            .collect::<Vec<_>>();
+
+        drifts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let nominal_s_s = drifts[drifts.len() / 2];
+
+        // steering detection & compensation
+        let t0 = samples[0].epoch;
+        let mut compensation_s = 0.0;
+        let mut compensated = Vec::<(f64, f64)>::with_capacity(samples.len());
+
+        for (i, sample) in samples.iter().enumerate() {
+            if i > 0 {
+                let prev = &samples[i - 1];
+                let dt_s = (sample.epoch - prev.epoch).to_seconds();
+                let jump_s = sample.offset_s - prev.offset_s - nominal_s_s * dt_s;
+
+                if jump_s.abs() > steering_threshold_s {
+                    compensation_s += jump_s;
+                    report.steering_events.push(SteeringEvent {
+                        epoch: sample.epoch,
+                        jump_s,
+                    });
+                }
+            }
+
+            compensated.push((
+                (sample.epoch - t0).to_seconds(),
+                sample.offset_s - compensation_s,
+            ));
+        }
+
+        // least squares linear fit
+        let n = compensated.len() as f64;
+        let mean_t = compensated.iter().map(|(t, _)| t).sum::<f64>() / n;
+        let mean_y = compensated.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+        let var_t = compensated
+            .iter()
+            .map(|(t, _)| (t - mean_t).powi(2))
+            .sum::<f64>();
+
+        let cov_ty = compensated
+            .iter()
+            .map(|(t, y)| (t - mean_t) * (y - mean_y))
+            .sum::<f64>();
+
+        let rate = if var_t > 0.0 { cov_ty / var_t } else { 0.0 };
+        let offset = mean_y - rate * mean_t;
+
+        let mut sum_sq = 0.0;
+        for (sample, (t, y)) in samples.iter_mut().zip(compensated.iter()) {
+            sample.residual_s = y - (offset + rate * t);
+            sum_sq += sample.residual_s.powi(2);
+        }
+
+        report.samples = samples;
+        report.fitted_offset_s = offset;
+        report.fitted_rate_s_s = rate;
+        report.residual_rms_s = (sum_sq / n).sqrt();
+
+        Some(report)
+    }
+}
+
+impl Rinex {
+    /// Characterizes the receiver oscillator by comparing Observation time tags
+    /// to GNSS time, obtained from external clock `solutions` (typically SPP).
+    /// Solutions that do not match an Observation time tag are discarded.
+    /// See [ClockDriftReport::from_solutions] for `steering_threshold_s`.
+    pub fn clock_drift_analysis(
+        &self,
+        solutions: &BTreeMap<Epoch, f64>,
+        steering_threshold_s: f64,
+    ) -> Option<ClockDriftReport> {
+        ClockDriftReport::from_solutions(
+            self.observation_keys()
+                .filter_map(|k| Some((k.epoch, *solutions.get(&k.epoch)?))),
+            steering_threshold_s,
+        )
+    }
+
+    /// Characterizes the receiver oscillator from the receiver clock offsets
+    /// reported in this Observation [Rinex], when they exist.
+    /// See [ClockDriftReport::from_solutions] for `steering_threshold_s`.
+    pub fn receiver_clock_drift(&self, steering_threshold_s: f64) -> Option<ClockDriftReport> {
+        ClockDriftReport::from_solutions(
+            self.clock_observations_iter()
+                .map(|(k, clock)| (k.epoch, clock.offset_s)),
+            steering_threshold_s,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ClockDriftReport;
+    use crate::{
+        observation::{ClockObservation, ObsKey, Observations},
+        prelude::{Duration, Epoch, Rinex},
+    };
+    use std::{collections::BTreeMap, str::FromStr};
+
+    #[test]
+    fn clock_drift_analysis() {
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let mut rinex = Rinex::basic_obs();
+        let mut solutions = BTreeMap::new();
+
+        for i in 0..20 {
+            let epoch = t0 + i as f64 * dt;
+
+            let steering = if i >= 12 { 1.0E-3 } else { 0.0 };
+            let offset_s = -2.0E-4 + 5.0E-9 * 30.0 * i as f64 + steering;
+
+            // one solution does not match any time tag
+            solutions.insert(epoch, offset_s);
+            solutions.insert(epoch + Duration::from_seconds(1.0), 0.0);
+
+            let key = ObsKey {
+                epoch,
+                flag: Default::default(),
+            };
+
+            let obs = Observations::default()
+                .with_clock_observation(ClockObservation::default().with_offset_s(epoch, offset_s));
+
+            rinex.record.as_mut_obs().unwrap().insert(key, obs);
+        }
+
+        for report in [
+            rinex.clock_drift_analysis(&solutions, 1.0E-6).unwrap(),
+            rinex.receiver_clock_drift(1.0E-6).unwrap(),
+        ] {
+            assert_eq!(report.samples.len(), 20);
+            assert_eq!(report.steering_events.len(), 1);
+
+            let event = report.steering_events[0];
+            assert_eq!(event.epoch, t0 + 12.0 * dt);
+            assert!((event.jump_s - 1.0E-3).abs() < 1.0E-12);
+
+            assert!((report.fitted_rate_s_s - 5.0E-9).abs() < 1.0E-15);
+            assert!((report.fitted_offset_s + 2.0E-4).abs() < 1.0E-12);
+            assert!(report.residual_rms_s < 1.0E-12);
+
+            assert!(report.samples[0].drift_s_s.is_none());
+            let drift = report.samples[1].drift_s_s.unwrap();
+            assert!((drift - 5.0E-9).abs() < 1.0E-15);
+        }
+
+        assert!(ClockDriftReport::from_solutions([(t0, 0.0)], 1.0E-6).is_none());
+    }
+}
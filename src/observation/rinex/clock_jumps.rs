@@ -0,0 +1,412 @@
+//! Receiver clock jumps (millisecond steering) detection and repair
+use crate::{
+    constants::SPEED_OF_LIGHT_M_S,
+    observation::{EpochFlag, LliFlags},
+    prelude::{Epoch, Observable, Rinex, SV},
+};
+
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Range equivalent of a 1 ms receiver clock jump
+const MILLISECOND_M: f64 = SPEED_OF_LIGHT_M_S * 1.0E-3;
+
+/// [ClockJump] is one receiver clock jump, detected by [Rinex::clock_jumps]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ClockJump {
+    /// [Epoch] of the first sample following the jump
+    pub epoch: Epoch,
+    /// [EpochFlag]: sampling conditions
+    pub flag: EpochFlag,
+    /// Jump, in milliseconds
+    pub jump_ms: i64,
+    /// Number of [SV]s that presented this jump
+    pub svs: usize,
+    /// True if phase observations jumped as well,
+    /// false if only pseudo ranges did
+    pub phase: bool,
+}
+
+/// [ClockJumpOptions] to tune [Rinex::clock_jumps]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ClockJumpOptions {
+    /// Maximal deviation of each pseudo range step to an integer
+    /// number of milliseconds, in meters
+    pub tolerance_m: f64,
+    /// Minimal number of [SV]s that must present the same step
+    pub min_svs: usize,
+}
+
+impl Default for ClockJumpOptions {
+    fn default() -> Self {
+        Self {
+            tolerance_m: 1.0E3,
+            min_svs: 3,
+        }
+    }
+}
+
+impl ClockJumpOptions {
+    /// Copies and returns [ClockJumpOptions] with desired tolerance, in meters
+    pub fn with_tolerance_m(&self, tolerance_m: f64) -> Self {
+        let mut s = *self;
+        s.tolerance_m = tolerance_m;
+        s
+    }
+
+    /// Copies and returns [ClockJumpOptions] with desired minimal number of [SV]s
+    pub fn with_min_svs(&self, min_svs: usize) -> Self {
+        let mut s = *self;
+        s.min_svs = min_svs;
+        s
+    }
+}
+
+/// Latest (repaired) sample of one signal, in meters
+struct RangeState {
+    t: Epoch,
+    range_m: f64,
+    rate_m_s: Option<f64>,
+}
+
+/// Returns the common jump of these step residuals, in milliseconds,
+/// if they all describe the same non null integer number of milliseconds.
+fn common_jump_ms(residuals: &[(SV, f64)], opts: &ClockJumpOptions) -> Option<(i64, usize)> {
+    let (_, first) = residuals.first()?;
+    let jump_ms = (first / MILLISECOND_M).round() as i64;
+
+    if jump_ms == 0 {
+        return None;
+    }
+
+    let jump_m = jump_ms as f64 * MILLISECOND_M;
+
+    if residuals
+        .iter()
+        .any(|(_, residual)| (residual - jump_m).abs() > opts.tolerance_m)
+    {
+        return None;
+    }
+
+    let mut svs = residuals.iter().map(|(sv, _)| *sv).collect::<Vec<_>>();
+    svs.sort();
+    svs.dedup();
+
+    Some((jump_ms, svs.len()))
+}
+
+impl Rinex {
+    /// Detects receiver clock jumps: simultaneous steps of an integer number of
+    /// milliseconds (about 299792.458 m) in all pseudo ranges, typical of receivers that
+    /// steer their clock. Each pseudo range is predicted from its two previous
+    /// samples, so data gaps interrupt the detection. Phase observations
+    /// are tested as well, to determine whether they jumped with the pseudo ranges.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use rinex::observation::ClockJumpOptions;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20000000.000
+    /// G02  21000000.000
+    /// > 2020 01 01 00 00 30.0000000  0  2
+    /// G01  20003000.000
+    /// G02  20997000.000
+    /// > 2020 01 01 00 01  0.0000000  0  2
+    /// G01  20305792.458
+    /// G02  21293792.458
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let opts = ClockJumpOptions::default().with_min_svs(2);
+    /// let jumps = rinex.clock_jumps(&opts);
+    ///
+    /// assert_eq!(jumps.len(), 1);
+    /// assert_eq!(jumps[0].jump_ms, 1);
+    /// assert!(!jumps[0].phase);
+    /// ```
+    pub fn clock_jumps(&self, opts: &ClockJumpOptions) -> Vec<ClockJump> {
+        let mut ret = Vec::new();
+
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return ret, // can't proceed without sampling interval guess.
+        };
+
+        let mut states = HashMap::<(SV, Observable), RangeState>::new();
+
+        // accumulated jumps, in meters
+        let (mut code_jumps_m, mut phase_jumps_m) = (0.0_f64, 0.0_f64);
+
+        for (k, v) in self.observations_iter() {
+            let mut samples = Vec::<(SV, Observable, bool, f64)>::new();
+
+            let mut code_residuals = Vec::<(SV, f64)>::new();
+            let mut phase_residuals = Vec::<(SV, f64)>::new();
+
+            for sig in v.signals.iter() {
+                let is_phase = sig.observable.is_phase_range_observable();
+
+                let range_m = if is_phase {
+                    match self.header.sv_carrier(sig.sv, &sig.observable) {
+                        Ok(carrier) => sig.value * carrier.wavelength() - phase_jumps_m,
+                        Err(_) => continue,
+                    }
+                } else if sig.observable.is_pseudo_range_observable() {
+                    sig.value - code_jumps_m
+                } else {
+                    continue;
+                };
+
+                let lock_loss = is_phase
+                    && sig
+                        .lli
+                        .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                        .unwrap_or(false);
+
+                let key = (sig.sv, sig.observable.clone());
+
+                if let Some(state) = states.get(&key) {
+                    let dt = k.epoch - state.t;
+                    if !lock_loss && dt <= dominant_sampling {
+                        if let Some(rate_m_s) = state.rate_m_s {
+                            let predicted = state.range_m + rate_m_s * dt.to_seconds();
+                            if is_phase {
+                                phase_residuals.push((sig.sv, range_m - predicted));
+                            } else {
+                                code_residuals.push((sig.sv, range_m - predicted));
+                            }
+                        }
+                    }
+                }
+
+                samples.push((sig.sv, sig.observable.clone(), lock_loss, range_m));
+            }
+
+            let mut phase_jumped = false;
+            let mut jump_m = 0.0;
+
+            if let Some((jump_ms, svs)) = common_jump_ms(&code_residuals, opts) {
+                if svs >= opts.min_svs {
+                    jump_m = jump_ms as f64 * MILLISECOND_M;
+
+                    phase_jumped = !phase_residuals.is_empty()
+                        && common_jump_ms(&phase_residuals, opts)
+                            .map(|(phase_ms, _)| phase_ms == jump_ms)
+                            .unwrap_or(false);
+
+                    code_jumps_m += jump_m;
+                    if phase_jumped {
+                        phase_jumps_m += jump_m;
+                    }
+
+                    ret.push(ClockJump {
+                        epoch: k.epoch,
+                        flag: k.flag,
+                        jump_ms,
+                        svs,
+                        phase: phase_jumped,
+                    });
+                }
+            }
+
+            for (sv, observable, lock_loss, mut range_m) in samples {
+                let is_phase = observable.is_phase_range_observable();
+
+                if !is_phase || phase_jumped {
+                    range_m -= jump_m;
+                }
+
+                let key = (sv, observable);
+
+                let rate_m_s = match states.get(&key) {
+                    Some(state) if !lock_loss && k.epoch - state.t <= dominant_sampling => {
+                        Some((range_m - state.range_m) / (k.epoch - state.t).to_seconds())
+                    },
+                    _ => None,
+                };
+
+                states.insert(
+                    key,
+                    RangeState {
+                        t: k.epoch,
+                        range_m,
+                        rate_m_s,
+                    },
+                );
+            }
+        }
+
+        ret
+    }
+
+    /// Detects receiver clock jumps (see [Self::clock_jumps]) and repairs them, in place:
+    /// the accumulated jumps are removed from all following pseudo ranges, and from
+    /// phase observations when they jumped as well, so code and phase remain consistent.
+    /// Returns the [ClockJump]s that were repaired.
+    pub fn repair_clock_jumps_mut(&mut self, opts: &ClockJumpOptions) -> Vec<ClockJump> {
+        let jumps = self.clock_jumps(opts);
+
+        if jumps.is_empty() {
+            return jumps;
+        }
+
+        let header = &self.header;
+
+        if let Some(record) = self.record.as_mut_obs() {
+            let (mut code_jumps_m, mut phase_jumps_m) = (0.0_f64, 0.0_f64);
+            let mut pending = jumps.iter().peekable();
+
+            for (k, v) in record.iter_mut() {
+                while let Some(jump) = pending.peek() {
+                    if jump.epoch > k.epoch {
+                        break;
+                    }
+                    let jump_m = jump.jump_ms as f64 * MILLISECOND_M;
+                    code_jumps_m += jump_m;
+                    if jump.phase {
+                        phase_jumps_m += jump_m;
+                    }
+                    pending.next();
+                }
+
+                for sig in v.signals.iter_mut() {
+                    if sig.observable.is_pseudo_range_observable() {
+                        sig.value -= code_jumps_m;
+                    } else if sig.observable.is_phase_range_observable() {
+                        if let Ok(carrier) = header.sv_carrier(sig.sv, &sig.observable) {
+                            sig.value -= phase_jumps_m / carrier.wavelength();
+                        }
+                    }
+                }
+            }
+        }
+
+        self.header.push_processing_log(
+            None,
+            "CLOCK JUMP REPAIR",
+            &format!("{} jump(s)", jumps.len()),
+        );
+
+        jumps
+    }
+
+    /// Copies and returns a [Rinex] where receiver clock jumps were repaired,
+    /// along with the list of repaired [ClockJump]s. See [Self::repair_clock_jumps_mut].
+    pub fn repair_clock_jumps(&self, opts: &ClockJumpOptions) -> (Self, Vec<ClockJump>) {
+        let mut s = self.clone();
+        let jumps = s.repair_clock_jumps_mut(opts);
+        (s, jumps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ClockJumpOptions, MILLISECOND_M};
+    use crate::{
+        carrier::Carrier,
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    fn steered_rinex() -> Rinex {
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+        let w1 = Carrier::L1.wavelength();
+
+        let svs = ["G01", "G02", "G03", "G04"]
+            .iter()
+            .map(|sv| SV::from_str(sv).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(Duration::from_seconds(30.0));
+
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        for i in 0..12 {
+            let mut obs = Observations::default();
+
+            // +1 ms on 5th epoch (code only), -1 ms on 10th epoch (code and phase)
+            let code_jump_m = if i >= 4 { MILLISECOND_M } else { 0.0 };
+            let phase_jump_m = if i >= 9 { -MILLISECOND_M } else { 0.0 };
+
+            for (j, sv) in svs.iter().enumerate() {
+                let t = i as f64 * 30.0;
+                let range_m = 20_000_000.0 + 500.0 * j as f64 * t + 0.01 * t * t;
+
+                obs.signals.push(SignalObservation::new(
+                    *sv,
+                    Observable::from_str("C1C").unwrap(),
+                    range_m + code_jump_m + phase_jump_m,
+                ));
+                obs.signals.push(SignalObservation::new(
+                    *sv,
+                    Observable::from_str("L1C").unwrap(),
+                    (range_m + phase_jump_m) / w1 + 1000.0,
+                ));
+            }
+
+            let key = ObsKey {
+                epoch: t0 + i as f64 * Duration::from_seconds(30.0),
+                flag: Default::default(),
+            };
+            record.insert(key, obs);
+        }
+
+        rinex
+    }
+
+    #[test]
+    fn clock_jumps_detection_and_repair() {
+        let rinex = steered_rinex();
+        let t0 = rinex.first_epoch().unwrap();
+
+        let jumps = rinex.clock_jumps(&ClockJumpOptions::default());
+        assert_eq!(jumps.len(), 2);
+
+        assert_eq!(jumps[0].epoch, t0 + Duration::from_seconds(120.0));
+        assert_eq!(jumps[0].jump_ms, 1);
+        assert_eq!(jumps[0].svs, 4);
+        assert!(!jumps[0].phase);
+
+        assert_eq!(jumps[1].epoch, t0 + Duration::from_seconds(270.0));
+        assert_eq!(jumps[1].jump_ms, -1);
+        assert!(jumps[1].phase);
+
+        // not enough SVs
+        let opts = ClockJumpOptions::default().with_min_svs(5);
+        assert!(rinex.clock_jumps(&opts).is_empty());
+
+        let (repaired, repaired_jumps) = rinex.repair_clock_jumps(&ClockJumpOptions::default());
+        assert_eq!(repaired_jumps, jumps);
+        assert!(repaired
+            .clock_jumps(&ClockJumpOptions::default())
+            .is_empty());
+
+        // code and phase remain consistent
+        let w1 = Carrier::L1.wavelength();
+        for (_, v) in repaired.observations_iter() {
+            for code in v
+                .signals
+                .iter()
+                .filter(|sig| sig.observable.is_pseudo_range_observable())
+            {
+                let phase = v
+                    .signals
+                    .iter()
+                    .find(|sig| sig.sv == code.sv && sig.observable.is_phase_range_observable())
+                    .unwrap();
+                let delta_m = code.value - (phase.value - 1000.0) * w1;
+                assert!(delta_m.abs() < 1.0E-3, "{}", delta_m);
+            }
+        }
+    }
+}
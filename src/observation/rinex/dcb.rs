@@ -0,0 +1,184 @@
+//! Cross-correlation receivers (C1/P1) bias compensation
+use crate::{
+    constants::SPEED_OF_LIGHT_M_S,
+    error::ParsingError,
+    prelude::{Observable, Rinex, SV},
+};
+
+use std::{collections::HashMap, str::FromStr};
+
+/// [P1C1Biases] is a table of satellite P1-C1 differential code biases,
+/// typically published monthly by CODE (P1C1yymm.DCB files).
+/// It is used to make C1 pseudo ranges, tracked by legacy cross-correlation
+/// receivers, consistent with P1 pseudo ranges.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct P1C1Biases {
+    /// Bias per [SV], in nanoseconds
+    pub biases: HashMap<SV, f64>,
+}
+
+impl P1C1Biases {
+    /// Copies and returns [P1C1Biases] with one more bias, in nanoseconds
+    pub fn with_bias_ns(&self, sv: SV, bias_ns: f64) -> Self {
+        let mut s = self.clone();
+        s.biases.insert(sv, bias_ns);
+        s
+    }
+
+    /// Returns P1-C1 bias for this [SV], in nanoseconds
+    pub fn bias_ns(&self, sv: SV) -> Option<f64> {
+        self.biases.get(&sv).copied()
+    }
+
+    /// Returns P1-C1 bias for this [SV], as meters of propagation
+    pub fn bias_m(&self, sv: SV) -> Option<f64> {
+        self.bias_ns(sv).map(|ns| ns * 1.0E-9 * SPEED_OF_LIGHT_M_S)
+    }
+}
+
+impl FromStr for P1C1Biases {
+    type Err = ParsingError;
+    /// Parses [P1C1Biases] from CODE DCB file content.
+    /// Satellite entries ("PRN VALUE (NS) RMS (NS)") are retained,
+    /// header lines and station entries are discarded.
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut biases = HashMap::new();
+
+        for line in content.lines() {
+            let mut items = line.split_ascii_whitespace();
+
+            let (sv, value) = match (items.next(), items.next()) {
+                (Some(sv), Some(value)) => (sv, value),
+                _ => continue,
+            };
+
+            let sv = match SV::from_str(sv) {
+                Ok(sv) => sv,
+                Err(_) => continue,
+            };
+
+            let value = value.parse::<f64>().map_err(|_| ParsingError::DCBParsing)?;
+
+            biases.insert(sv, value);
+        }
+
+        Ok(Self { biases })
+    }
+}
+
+impl Rinex {
+    /// Compensates C1 pseudo ranges (C1 in RINEX2, C1C in RINEX3),
+    /// produced by legacy cross-correlation receivers, with given P1-C1 [P1C1Biases],
+    /// so they become consistent with P1 pseudo ranges. This is equivalent
+    /// to the historical cc2noncc tool, or teqc -C1.
+    /// [SV] not described in the bias table are left untouched.
+    /// The operation is documented in the header comments.
+    pub fn observation_c1p1_correction_mut(&mut self, biases: &P1C1Biases) {
+        let c1 = Observable::PseudoRange("C1".to_string());
+        let c1c = Observable::PseudoRange("C1C".to_string());
+
+        if let Some(rec) = self.record.as_mut_obs() {
+            for (_, obs) in rec.iter_mut() {
+                for signal in obs.signals.iter_mut() {
+                    if signal.observable != c1 && signal.observable != c1c {
+                        continue;
+                    }
+                    if let Some(bias_m) = biases.bias_m(signal.sv) {
+                        signal.value += bias_m;
+                    }
+                }
+            }
+
+            self.header.push_processing_log(
+                None,
+                "C1P1 CORRECTION",
+                &format!("P1-C1 DCB applied to {} satellites", biases.biases.len()),
+            );
+        }
+    }
+
+    /// Copies and returns [Rinex] with C1 pseudo ranges compensated.
+    /// See [Self::observation_c1p1_correction_mut].
+    pub fn observation_c1p1_correction(&self, biases: &P1C1Biases) -> Self {
+        let mut s = self.clone();
+        s.observation_c1p1_correction_mut(biases);
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::Epoch,
+    };
+
+    #[test]
+    fn code_dcb_parsing() {
+        let content =
+            "CODE'S MONTHLY GNSS P1-C1 DCB SOLUTION, YEAR-MONTH 09-01               06-FEB-09 07:32
+--------------------------------------------------------------------------------
+
+DIFFERENTIAL (P1-C1) CODE BIASES FOR SATELLITES AND RECEIVERS:
+
+PRN / STATION NAME        VALUE (NS)  RMS (NS)
+***   ****************    *****.***   *****.***
+G01                          -0.743     0.010
+G02                           0.512     0.012
+R07                           1.012     0.025
+";
+        let biases = P1C1Biases::from_str(content).unwrap();
+        assert_eq!(biases.biases.len(), 3);
+
+        let g01 = SV::from_str("G01").unwrap();
+        assert_eq!(biases.bias_ns(g01), Some(-0.743));
+        assert!((biases.bias_m(g01).unwrap() - (-0.743E-9 * SPEED_OF_LIGHT_M_S)).abs() < 1.0E-9);
+
+        assert_eq!(biases.bias_ns(SV::from_str("R07").unwrap()), Some(1.012));
+        assert_eq!(biases.bias_ns(SV::from_str("G03").unwrap()), None);
+    }
+
+    #[test]
+    fn c1p1_correction() {
+        let g01 = SV::from_str("G01").unwrap();
+        let g02 = SV::from_str("G02").unwrap();
+
+        let c1 = Observable::from_str("C1").unwrap();
+        let p2 = Observable::from_str("P2").unwrap();
+
+        let mut rinex = Rinex::basic_obs();
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        let mut obs = Observations::default();
+        obs.signals
+            .push(SignalObservation::new(g01, c1.clone(), 20.0E6));
+        obs.signals
+            .push(SignalObservation::new(g01, p2.clone(), 20.0E6));
+        obs.signals
+            .push(SignalObservation::new(g02, c1.clone(), 21.0E6));
+
+        record.insert(
+            ObsKey {
+                epoch: Epoch::default(),
+                flag: Default::default(),
+            },
+            obs,
+        );
+
+        let biases = P1C1Biases::default().with_bias_ns(g01, 1.0);
+
+        let corrected = rinex.observation_c1p1_correction(&biases);
+
+        let signals = corrected
+            .signal_observations_iter()
+            .map(|(_, sig)| sig.value)
+            .collect::<Vec<_>>();
+
+        assert!((signals[0] - (20.0E6 + 1.0E-9 * SPEED_OF_LIGHT_M_S)).abs() < 1.0E-6);
+        assert_eq!(signals[1], 20.0E6);
+        assert_eq!(signals[2], 21.0E6);
+
+        assert_eq!(corrected.header.comments.len(), 2);
+    }
+}
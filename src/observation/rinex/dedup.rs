@@ -0,0 +1,200 @@
+//! Duplicated and unsorted epochs management
+use crate::{
+    observation::ObsKey,
+    prelude::{Epoch, Rinex},
+};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [DedupPolicy] selects the content retained for epochs
+/// that were found more than once, see [Rinex::dedup_epochs].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum DedupPolicy {
+    /// First occurrence is retained
+    KeepFirst,
+    /// Latest occurrence is retained, which is the parser behavior
+    #[default]
+    KeepLast,
+    /// All occurrences are combined: missing signals, clock and event
+    /// are completed, latest occurrence prevails on conflicts.
+    Merge,
+}
+
+/// [DedupReport] describes what [Rinex::dedup_epochs] fixed
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DedupReport {
+    /// [DedupPolicy] that was applied
+    pub policy: DedupPolicy,
+    /// Number of superseded occurrences, per duplicated [ObsKey]
+    pub duplicated: BTreeMap<ObsKey, usize>,
+    /// Epochs that were found prior their predecessor, and were sorted
+    pub unsorted: Vec<Epoch>,
+}
+
+impl DedupReport {
+    /// Returns true if this [Rinex] did not contain duplicated or unsorted epochs
+    pub fn is_clean(&self) -> bool {
+        self.duplicated.is_empty() && self.unsorted.is_empty()
+    }
+}
+
+impl Rinex {
+    /// Resolves epochs that were found more than once while parsing, with this
+    /// [DedupPolicy], in place. See [Self::dedup_epochs].
+    pub fn dedup_epochs_mut(&mut self, policy: DedupPolicy) -> DedupReport {
+        let mut report = DedupReport {
+            policy,
+            duplicated: Default::default(),
+            unsorted: self.summary.unsorted_epochs.clone(),
+        };
+
+        let superseded = std::mem::take(&mut self.summary.superseded);
+
+        let record = match self.record.as_mut_obs() {
+            Some(record) => record,
+            None => return report,
+        };
+
+        let mut first = BTreeSet::<ObsKey>::new();
+
+        for (k, superseded) in superseded.into_iter() {
+            *report.duplicated.entry(k).or_default() += 1;
+
+            let retained = match record.get_mut(&k) {
+                Some(retained) => retained,
+                None => continue, // already removed
+            };
+
+            match policy {
+                DedupPolicy::KeepLast => {},
+                DedupPolicy::KeepFirst => {
+                    if first.insert(k) {
+                        *retained = superseded;
+                    }
+                },
+                DedupPolicy::Merge => {
+                    for sig in superseded.signals.into_iter() {
+                        if !retained
+                            .signals
+                            .iter()
+                            .any(|s| s.sv == sig.sv && s.observable == sig.observable)
+                        {
+                            retained.signals.push(sig);
+                        }
+                    }
+                    if retained.clock.is_none() {
+                        retained.clock = superseded.clock;
+                    }
+                    if retained.event.is_none() {
+                        retained.event = superseded.event;
+                    }
+                },
+            }
+        }
+
+        report
+    }
+
+    /// Copies and returns a [Rinex] where epochs that were found more than once
+    /// while parsing (typically after a receiver reboot) are resolved with this
+    /// [DedupPolicy], with a [DedupReport] of what was fixed. The record is always
+    /// chronologically sorted: out of order epochs are only reported.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use rinex::observation::DedupPolicy;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832392.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let (rinex, report) = rinex.dedup_epochs(DedupPolicy::KeepFirst);
+    ///
+    /// assert_eq!(report.duplicated.len(), 1);
+    /// assert_eq!(report.unsorted.len(), 1);
+    ///
+    /// let values = rinex.signal_observations_iter()
+    ///     .map(|(_, sig)| sig.value)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, vec![20832392.682, 20832393.682]);
+    /// ```
+    pub fn dedup_epochs(&self, policy: DedupPolicy) -> (Self, DedupReport) {
+        let mut s = self.clone();
+        let report = s.dedup_epochs_mut(policy);
+        (s, report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DedupPolicy;
+    use crate::prelude::{Observable, Rinex};
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    const CONTENT: &str =
+        "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  2
+G01  20832393.682   109474991.854
+G02  21832393.682
+> 2020 01 01 00 00 30.0000000  0  1
+G01  20832394.682   109474992.854
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20832395.682
+";
+
+    #[test]
+    fn dedup_epochs() {
+        let rinex = Rinex::parse(&mut BufReader::new(CONTENT.as_bytes())).unwrap();
+
+        let summary = rinex.parse_summary();
+        assert_eq!(summary.duplicated_epochs.len(), 1);
+        assert_eq!(summary.unsorted_epochs.len(), 1);
+
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        // parser behavior
+        let (deduped, report) = rinex.dedup_epochs(DedupPolicy::KeepLast);
+        assert_eq!(report.duplicated.len(), 1);
+        assert!(!report.is_clean());
+        assert_eq!(deduped.record, rinex.record);
+
+        let first = &deduped.observations_iter().next().unwrap().1.signals;
+        assert_eq!(first.len(), 1);
+
+        let (deduped, _) = rinex.dedup_epochs(DedupPolicy::KeepFirst);
+        let first = &deduped.observations_iter().next().unwrap().1.signals;
+        assert_eq!(first.len(), 3);
+
+        let (deduped, _) = rinex.dedup_epochs(DedupPolicy::Merge);
+        let first = &deduped.observations_iter().next().unwrap().1.signals;
+        assert_eq!(first.len(), 3);
+
+        let value = first
+            .iter()
+            .find(|sig| sig.observable == c1c)
+            .unwrap()
+            .value;
+        assert_eq!(value, 20832395.682);
+
+        // nothing left to fix
+        let (_, report) = deduped.dedup_epochs(DedupPolicy::Merge);
+        assert!(report.duplicated.is_empty());
+    }
+}
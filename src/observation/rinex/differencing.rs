@@ -0,0 +1,377 @@
+//! Observation differencing
+use crate::prelude::{Constellation, Epoch, Observable, Rinex, SV};
+
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashMap};
+
+/// [SingleDifferenceKey] is how we sort between receivers single differences
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct SingleDifferenceKey {
+    /// [Epoch] of sampling, common to both receivers
+    pub epoch: Epoch,
+    /// [SV]: signal source, observed by both receivers
+    pub sv: SV,
+    /// [Observable] of both receivers
+    pub observable: Observable,
+}
+
+/// [SvDifferenceKey] is how we sort between satellites differences
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct SvDifferenceKey {
+    /// [Epoch] of sampling
+    pub epoch: Epoch,
+    /// [SV]: differenced signal source
+    pub sv: SV,
+    /// Reference [SV], of the same [Constellation]
+    pub reference: SV,
+    /// Differenced [Observable]
+    pub observable: Observable,
+}
+
+/// Differences each value to the value of the reference [SV] of the same [Constellation],
+/// the same [Observable] and the same [Epoch]. Values must be sorted by [Epoch].
+pub(crate) fn sv_differences<'a, I, F>(values: I, reference: F) -> BTreeMap<SvDifferenceKey, f64>
+where
+    I: Iterator<Item = (Epoch, SV, &'a Observable, f64)>,
+    F: Fn(Epoch, Constellation) -> Option<SV>,
+{
+    let mut ret = BTreeMap::new();
+
+    for (epoch, values) in values.chunk_by(|(epoch, _, _, _)| *epoch).into_iter() {
+        let values = values.collect::<Vec<_>>();
+
+        let references = values
+            .iter()
+            .filter(|(_, sv, _, _)| reference(epoch, sv.constellation) == Some(*sv))
+            .map(|(_, sv, observable, value)| ((*sv, *observable), *value))
+            .collect::<HashMap<_, _>>();
+
+        for (_, sv, observable, value) in values.iter() {
+            let reference = match reference(epoch, sv.constellation) {
+                Some(reference) if reference != *sv => reference,
+                _ => continue,
+            };
+
+            if let Some(ref_value) = references.get(&(reference, *observable)) {
+                let key = SvDifferenceKey {
+                    epoch,
+                    sv: *sv,
+                    reference,
+                    observable: (*observable).clone(),
+                };
+                ret.insert(key, value - ref_value);
+            }
+        }
+    }
+
+    ret
+}
+
+impl Rinex {
+    /// Forms between receivers single differences (self - other), of this Observation [Rinex]
+    /// and another Observation [Rinex], from a co-located receiver. Epochs are aligned,
+    /// and each [Observable] is differenced to the same [Observable] of the same [SV].
+    /// Satellite clock errors cancel out, while atmospheric (and orbital) errors cancel out
+    /// on short baselines: this is typically used to evaluate receivers on zero or short baselines.
+    /// Differences are expressed in the [Observable] unit (cycles for phase observations).
+    /// Only valid (sane) epochs of both receivers are considered.
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let rover = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C L1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20000010.000   105100050.000
+    /// G02  21000010.000
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20000110.000   105100575.000
+    /// ";
+    ///
+    /// let base = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C L1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20000000.000   105100000.000
+    /// > 2020 01 01 00 01  0.0000000  0  1
+    /// G01  20000200.000   105101050.000
+    /// ";
+    ///
+    /// let rover = Rinex::parse(&mut BufReader::new(rover.as_bytes())).unwrap();
+    /// let base = Rinex::parse(&mut BufReader::new(base.as_bytes())).unwrap();
+    ///
+    /// let sd = rover.single_difference(&base);
+    ///
+    /// // single common epoch, for G01 only
+    /// assert_eq!(sd.len(), 2);
+    ///
+    /// let c1c = Observable::from_str("C1C").unwrap();
+    /// let (_, value) = sd.iter().find(|(k, _)| k.observable == c1c).unwrap();
+    /// assert_eq!(*value, 10.0);
+    /// ```
+    pub fn single_difference(&self, other: &Self) -> BTreeMap<SingleDifferenceKey, f64> {
+        let mut ret = BTreeMap::new();
+
+        let other_epochs = other
+            .observations_iter()
+            .filter(|(k, _)| k.flag.is_ok())
+            .map(|(k, v)| (k.epoch, v))
+            .collect::<BTreeMap<_, _>>();
+
+        for (k, v) in self.observations_iter() {
+            if !k.flag.is_ok() {
+                continue;
+            }
+
+            let other = match other_epochs.get(&k.epoch) {
+                Some(other) => other,
+                None => continue,
+            };
+
+            let other = other
+                .signals
+                .iter()
+                .map(|sig| ((sig.sv, &sig.observable), sig.value))
+                .collect::<HashMap<_, _>>();
+
+            for sig in v.signals.iter() {
+                if let Some(value) = other.get(&(sig.sv, &sig.observable)) {
+                    let key = SingleDifferenceKey {
+                        epoch: k.epoch,
+                        sv: sig.sv,
+                        observable: sig.observable.clone(),
+                    };
+                    ret.insert(key, sig.value - value);
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Forms between satellites single differences of this Observation [Rinex],
+    /// relative to this reference [SV]: at each epoch where the reference is observed,
+    /// each [Observable] of each [SV] of the same [Constellation] is differenced to
+    /// the same [Observable] of the reference (sv - reference). Receiver clock errors
+    /// cancel out. Other [Constellation]s are not differenced, because they do not share
+    /// the same system time and frequencies. Differences are expressed in the [Observable] unit
+    /// (cycles for phase observations). Only valid (sane) epochs are considered.
+    /// ```
+    /// use rinex::prelude::{Rinex, SV};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    /// E    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  3
+    /// G01  20000000.000
+    /// G02  21000000.000
+    /// E01  23000000.000
+    /// > 2020 01 01 00 00 30.0000000  0  2
+    /// G02  21000100.000
+    /// G03  22000100.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let sd = rinex.between_sv_difference(g01);
+    ///
+    /// // G01 is not observed at 2nd epoch, Galileo is not differenced
+    /// assert_eq!(sd.len(), 1);
+    ///
+    /// let (k, value) = sd.iter().next().unwrap();
+    /// assert_eq!(k.sv, SV::from_str("G02").unwrap());
+    /// assert_eq!(k.reference, g01);
+    /// assert_eq!(*value, 1_000_000.0);
+    /// ```
+    pub fn between_sv_difference(&self, reference: SV) -> BTreeMap<SvDifferenceKey, f64> {
+        let values = self
+            .signal_observations_iter()
+            .filter(|(k, _)| k.flag.is_ok())
+            .map(|(k, sig)| (k.epoch, sig.sv, &sig.observable, sig.value));
+
+        sv_differences(values, |_, constellation| {
+            if constellation == reference.constellation {
+                Some(reference)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        observation::{EpochFlag, ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn between_receivers_single_difference() {
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let (g01, g02) = (SV::from_str("G01").unwrap(), SV::from_str("G02").unwrap());
+
+        let (c1c, l1c) = (
+            Observable::from_str("C1C").unwrap(),
+            Observable::from_str("L1C").unwrap(),
+        );
+
+        let mut rover = Rinex::basic_obs();
+        let mut base = Rinex::basic_obs();
+
+        for i in 0..6 {
+            let epoch = t0 + i as f64 * dt;
+
+            let mut obs = Observations::default();
+            for (sv, offset) in [(g01, 0.0), (g02, 1000.0)] {
+                let range = 20_000_000.0 + offset + i as f64;
+                obs.signals
+                    .push(SignalObservation::new(sv, c1c.clone(), range + 1.5));
+                obs.signals
+                    .push(SignalObservation::new(sv, l1c.clone(), range / 0.19 + 3.0));
+            }
+
+            let flag = if i == 2 {
+                EpochFlag::PowerFailure
+            } else {
+                EpochFlag::Ok
+            };
+
+            rover
+                .record
+                .as_mut_obs()
+                .unwrap()
+                .insert(ObsKey { epoch, flag }, obs);
+
+            // base misses one epoch, and G02 phase
+            if i == 4 {
+                continue;
+            }
+
+            let mut obs = Observations::default();
+            for (sv, offset) in [(g01, 0.0), (g02, 1000.0)] {
+                let range = 20_000_000.0 + offset + i as f64;
+                obs.signals
+                    .push(SignalObservation::new(sv, c1c.clone(), range));
+                if sv == g01 {
+                    obs.signals
+                        .push(SignalObservation::new(sv, l1c.clone(), range / 0.19));
+                }
+            }
+
+            base.record.as_mut_obs().unwrap().insert(
+                ObsKey {
+                    epoch,
+                    flag: EpochFlag::Ok,
+                },
+                obs,
+            );
+        }
+
+        let sd = rover.single_difference(&base);
+
+        // 4 valid common epochs, 3 common signals
+        assert_eq!(sd.len(), 12);
+
+        for (k, value) in sd.iter() {
+            assert!(k.epoch != t0 + 2.0 * dt);
+            assert!(k.epoch != t0 + 4.0 * dt);
+
+            if k.observable == c1c {
+                assert!((value - 1.5).abs() < 1e-6);
+            } else {
+                assert_eq!(k.sv, g01);
+                assert!((value - 3.0).abs() < 1e-6);
+            }
+        }
+
+        // zero baseline
+        let sd = rover.single_difference(&rover);
+        assert!(sd.values().all(|value| *value == 0.0));
+    }
+
+    #[test]
+    fn between_sv_single_difference() {
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let (g01, g02, g03, e01) = (
+            SV::from_str("G01").unwrap(),
+            SV::from_str("G02").unwrap(),
+            SV::from_str("G03").unwrap(),
+            SV::from_str("E01").unwrap(),
+        );
+
+        let (c1c, l1c) = (
+            Observable::from_str("C1C").unwrap(),
+            Observable::from_str("L1C").unwrap(),
+        );
+
+        let mut rinex = Rinex::basic_obs();
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        for i in 0..4 {
+            let epoch = t0 + i as f64 * dt;
+
+            // receiver clock offset, common to all SV
+            let clock_m = 100.0 * i as f64;
+
+            let mut obs = Observations::default();
+            for (sv, range) in [(g01, 20.0E6), (g02, 21.0E6), (g03, 22.0E6), (e01, 23.0E6)] {
+                // G01 is lost at last epoch
+                if sv == g01 && i == 3 {
+                    continue;
+                }
+                obs.signals
+                    .push(SignalObservation::new(sv, c1c.clone(), range + clock_m));
+                // G03 does not track phase
+                if sv != g03 {
+                    obs.signals
+                        .push(SignalObservation::new(sv, l1c.clone(), range + clock_m));
+                }
+            }
+
+            record.insert(
+                ObsKey {
+                    epoch,
+                    flag: EpochFlag::Ok,
+                },
+                obs,
+            );
+        }
+
+        let sd = rinex.between_sv_difference(g01);
+
+        // 3 epochs: (G02, G03) code and G02 phase
+        assert_eq!(sd.len(), 9);
+
+        for (k, value) in sd.iter() {
+            assert_eq!(k.reference, g01);
+            assert!(k.epoch < t0 + 3.0 * dt);
+
+            let expected = if k.sv == g02 { 1.0E6 } else { 2.0E6 };
+            assert_eq!(*value, expected);
+
+            if k.sv == g03 {
+                assert_eq!(k.observable, c1c);
+            }
+        }
+
+        // reference is never observed
+        let e02 = SV::from_str("E02").unwrap();
+        assert!(rinex.between_sv_difference(e02).is_empty());
+
+        // single Galileo satellite
+        let sd = rinex.between_sv_difference(e01);
+        assert!(sd.is_empty());
+    }
+}
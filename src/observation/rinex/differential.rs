@@ -0,0 +1,346 @@
+//! Differential (base vs rover) quality control
+use crate::{
+    observation::LliFlags,
+    prelude::{Epoch, Observable, Rinex, SV},
+};
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "qc")]
+use qc_traits::{html, Markup, QcHtmlReporting};
+
+/// [SlipRates] compares phase tracking continuity of both receivers,
+/// for one [SV]. Only common epochs are considered.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SlipRates {
+    /// Total number of phase observations, on rover side
+    pub rover_observations: usize,
+    /// Total number of lock losses (LLI), on rover side
+    pub rover_slips: usize,
+    /// Total number of phase observations, on base side
+    pub base_observations: usize,
+    /// Total number of lock losses (LLI), on base side
+    pub base_slips: usize,
+}
+
+impl SlipRates {
+    /// Returns rover slip rate, as slips per phase observation
+    pub fn rover_rate(&self) -> f64 {
+        if self.rover_observations == 0 {
+            0.0
+        } else {
+            self.rover_slips as f64 / self.rover_observations as f64
+        }
+    }
+
+    /// Returns base slip rate, as slips per phase observation
+    pub fn base_rate(&self) -> f64 {
+        if self.base_observations == 0 {
+            0.0
+        } else {
+            self.base_slips as f64 / self.base_observations as f64
+        }
+    }
+
+    /// Returns differential slip rate (rover - base).
+    /// Positive values mean the rover loses lock more often.
+    pub fn differential_rate(&self) -> f64 {
+        self.rover_rate() - self.base_rate()
+    }
+}
+
+/// [SnrDelta] compares signal strength of both receivers (rover - base),
+/// for one [SV] and one signal strength [Observable], in dB.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SnrDelta {
+    /// Number of common samples
+    pub samples: usize,
+    /// Mean difference, in dB
+    pub mean_db: f64,
+    /// Standard deviation of the difference, in dB
+    pub std_dev_db: f64,
+}
+
+/// [DifferentialReport] compares a rover [Rinex] to a co-located base [Rinex].
+/// This is typically used to validate a new receiver against a reference unit.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DifferentialReport {
+    /// Number of epochs sampled by the rover
+    pub rover_epochs: usize,
+    /// Number of epochs sampled by the base
+    pub base_epochs: usize,
+    /// Epochs sampled by both receivers
+    pub common_epochs: Vec<Epoch>,
+    /// [SV]s tracked by both receivers
+    pub common_sv: Vec<SV>,
+    /// [SV]s only tracked by the rover
+    pub rover_only_sv: Vec<SV>,
+    /// [SV]s only tracked by the base
+    pub base_only_sv: Vec<SV>,
+    /// [SlipRates] per common [SV]
+    pub slip_rates: BTreeMap<SV, SlipRates>,
+    /// [SnrDelta] per common [SV] and signal strength [Observable]
+    pub snr_deltas: BTreeMap<(SV, Observable), SnrDelta>,
+}
+
+impl Rinex {
+    /// Compares this rover Observation [Rinex] to a co-located base (reference)
+    /// Observation [Rinex], and returns a [DifferentialReport]:
+    /// common [SV]s, differential slip rates (from LLI flags) and
+    /// signal strength deltas (from signal strength observations).
+    /// Slip rates and signal strength deltas are evaluated over common epochs only.
+    pub fn differential_qc(&self, base: &Self) -> DifferentialReport {
+        let mut report = DifferentialReport {
+            rover_epochs: self.observation_keys().count(),
+            base_epochs: base.observation_keys().count(),
+            ..Default::default()
+        };
+
+        let rover_sv = self.sv_iter().collect::<BTreeSet<_>>();
+        let base_sv = base.sv_iter().collect::<BTreeSet<_>>();
+
+        report.common_sv = rover_sv.intersection(&base_sv).copied().collect();
+        report.rover_only_sv = rover_sv.difference(&base_sv).copied().collect();
+        report.base_only_sv = base_sv.difference(&rover_sv).copied().collect();
+
+        let base_epochs = base
+            .observations_iter()
+            .map(|(k, v)| (k.epoch, v))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut snr = HashMap::<(SV, Observable), Vec<f64>>::new();
+
+        for (k, rover) in self.observations_iter() {
+            let base = match base_epochs.get(&k.epoch) {
+                Some(base) => base,
+                None => continue,
+            };
+
+            report.common_epochs.push(k.epoch);
+
+            for sv in report.common_sv.iter() {
+                let rates = report.slip_rates.entry(*sv).or_default();
+
+                for (signals, observations, slips) in [
+                    (
+                        &rover.signals,
+                        &mut rates.rover_observations,
+                        &mut rates.rover_slips,
+                    ),
+                    (
+                        &base.signals,
+                        &mut rates.base_observations,
+                        &mut rates.base_slips,
+                    ),
+                ] {
+                    for sig in signals
+                        .iter()
+                        .filter(|sig| sig.sv == *sv && sig.observable.is_phase_range_observable())
+                    {
+                        *observations += 1;
+                        if sig
+                            .lli
+                            .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                            .unwrap_or(false)
+                        {
+                            *slips += 1;
+                        }
+                    }
+                }
+            }
+
+            for rover in rover
+                .signals
+                .iter()
+                .filter(|sig| sig.observable.is_ssi_observable())
+            {
+                if let Some(base) = base
+                    .signals
+                    .iter()
+                    .find(|sig| sig.sv == rover.sv && sig.observable == rover.observable)
+                {
+                    snr.entry((rover.sv, rover.observable.clone()))
+                        .or_default()
+                        .push(rover.value - base.value);
+                }
+            }
+        }
+
+        // SVs not sampled on common epochs
+        report
+            .slip_rates
+            .retain(|_, rates| rates.rover_observations > 0 || rates.base_observations > 0);
+
+        for (key, deltas) in snr {
+            let n = deltas.len() as f64;
+            let mean_db = deltas.iter().sum::<f64>() / n;
+            let var = deltas.iter().map(|d| (d - mean_db).powi(2)).sum::<f64>() / n;
+            report.snr_deltas.insert(
+                key,
+                SnrDelta {
+                    samples: deltas.len(),
+                    mean_db,
+                    std_dev_db: var.sqrt(),
+                },
+            );
+        }
+
+        report
+    }
+}
+
+#[cfg(feature = "qc")]
+impl QcHtmlReporting for DifferentialReport {
+    fn render(&self) -> Markup {
+        html! {
+            div class="table-container" {
+                table class="table is-bordered" {
+                    tbody {
+                        tr {
+                            th { "Epochs" }
+                            td {
+                                (format!("{} common (rover: {}, base: {})",
+                                    self.common_epochs.len(), self.rover_epochs, self.base_epochs))
+                            }
+                        }
+                        tr {
+                            th { "Common SV" }
+                            td { (self.common_sv.iter().map(|sv| sv.to_string()).collect::<Vec<_>>().join(", ")) }
+                        }
+                        tr {
+                            th { "Rover only" }
+                            td { (self.rover_only_sv.iter().map(|sv| sv.to_string()).collect::<Vec<_>>().join(", ")) }
+                        }
+                        tr {
+                            th { "Base only" }
+                            td { (self.base_only_sv.iter().map(|sv| sv.to_string()).collect::<Vec<_>>().join(", ")) }
+                        }
+                    }
+                }
+                table class="table is-bordered" {
+                    thead {
+                        tr {
+                            th { "SV" }
+                            th { "Rover slip rate" }
+                            th { "Base slip rate" }
+                            th { "Differential" }
+                        }
+                    }
+                    tbody {
+                        @for (sv, rates) in self.slip_rates.iter() {
+                            tr {
+                                td { (sv.to_string()) }
+                                td { (format!("{:.3E}", rates.rover_rate())) }
+                                td { (format!("{:.3E}", rates.base_rate())) }
+                                td { (format!("{:.3E}", rates.differential_rate())) }
+                            }
+                        }
+                    }
+                }
+                table class="table is-bordered" {
+                    thead {
+                        tr {
+                            th { "SV" }
+                            th { "Observable" }
+                            th { "Samples" }
+                            th { "Mean (dB)" }
+                            th { "Std dev (dB)" }
+                        }
+                    }
+                    tbody {
+                        @for ((sv, observable), delta) in self.snr_deltas.iter() {
+                            tr {
+                                td { (sv.to_string()) }
+                                td { (observable.to_string()) }
+                                td { (delta.samples) }
+                                td { (format!("{:.2}", delta.mean_db)) }
+                                td { (format!("{:.2}", delta.std_dev_db)) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        observation::{LliFlags, ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn differential_report() {
+        let g01 = SV::from_str("G01").unwrap();
+        let g02 = SV::from_str("G02").unwrap();
+        let g03 = SV::from_str("G03").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let s1c = Observable::from_str("S1C").unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let mut rover = Rinex::basic_obs();
+        let mut base = Rinex::basic_obs();
+
+        for i in 0..4 {
+            let key = ObsKey {
+                epoch: t0 + i as f64 * dt,
+                flag: Default::default(),
+            };
+
+            let mut obs = Observations::default();
+            let mut phase = SignalObservation::new(g01, l1c.clone(), 1.0);
+            if i == 2 {
+                phase.lli = Some(LliFlags::LOCK_LOSS);
+            }
+            obs.signals.push(phase);
+            obs.signals
+                .push(SignalObservation::new(g01, s1c.clone(), 45.0));
+            obs.signals
+                .push(SignalObservation::new(g02, l1c.clone(), 1.0));
+            rover.record.as_mut_obs().unwrap().insert(key, obs);
+
+            // base misses last epoch
+            if i < 3 {
+                let mut obs = Observations::default();
+                obs.signals
+                    .push(SignalObservation::new(g01, l1c.clone(), 1.0));
+                obs.signals
+                    .push(SignalObservation::new(g01, s1c.clone(), 43.0 + i as f64));
+                obs.signals
+                    .push(SignalObservation::new(g03, l1c.clone(), 1.0));
+                base.record.as_mut_obs().unwrap().insert(key, obs);
+            }
+        }
+
+        let report = rover.differential_qc(&base);
+
+        assert_eq!(report.rover_epochs, 4);
+        assert_eq!(report.base_epochs, 3);
+        assert_eq!(report.common_epochs.len(), 3);
+        assert_eq!(report.common_sv, vec![g01]);
+        assert_eq!(report.rover_only_sv, vec![g02]);
+        assert_eq!(report.base_only_sv, vec![g03]);
+
+        let rates = report.slip_rates.get(&g01).unwrap();
+        assert_eq!(rates.rover_observations, 3);
+        assert_eq!(rates.rover_slips, 1);
+        assert_eq!(rates.base_slips, 0);
+        assert!((rates.differential_rate() - 1.0 / 3.0).abs() < 1.0E-9);
+
+        let delta = report.snr_deltas.get(&(g01, s1c)).unwrap();
+        assert_eq!(delta.samples, 3);
+        assert!((delta.mean_db - 1.0).abs() < 1.0E-9);
+        assert!((delta.std_dev_db - (2.0_f64 / 3.0).sqrt()).abs() < 1.0E-9);
+    }
+}
@@ -0,0 +1,350 @@
+//! Doppler consistency screening
+use crate::{
+    observation::{EpochFlag, SignalObservation},
+    prelude::{Duration, Epoch, Observable, Rinex, SV},
+};
+
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [DopplerAnomaly] sorts [DopplerInconsistency]s
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum DopplerAnomaly {
+    /// All phase observations jumped by the same range,
+    /// which is typical of a receiver clock jump
+    ClockJump,
+    /// Some phase observations are inconsistent with their Doppler,
+    /// which is typical of a tracking anomaly (cycle slip, loss of lock)
+    Tracking,
+}
+
+impl std::fmt::Display for DopplerAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::ClockJump => write!(f, "Clock Jump"),
+            Self::Tracking => write!(f, "Tracking"),
+        }
+    }
+}
+
+/// [DopplerInconsistency] is one epoch flagged by [Rinex::doppler_screening]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DopplerInconsistency {
+    /// [Epoch] of sampling
+    pub epoch: Epoch,
+    /// [EpochFlag]: sampling conditions
+    pub flag: EpochFlag,
+    /// [DopplerAnomaly]
+    pub anomaly: DopplerAnomaly,
+    /// [SV]s that were flagged
+    pub svs: Vec<SV>,
+    /// Largest phase rate to integrated Doppler residual, in meters
+    pub residual_m: f64,
+}
+
+/// [DopplerScreeningOptions] to tune [Rinex::doppler_screening]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DopplerScreeningOptions {
+    /// Phase variations that differ from integrated Doppler
+    /// by more than this value are flagged, in meters
+    pub threshold_m: f64,
+    /// Minimal number of [SV]s that must be simultaneously flagged,
+    /// with consistent residuals, to declare a [DopplerAnomaly::ClockJump]
+    pub min_clock_jump_svs: usize,
+}
+
+impl Default for DopplerScreeningOptions {
+    fn default() -> Self {
+        Self {
+            threshold_m: 1.0,
+            min_clock_jump_svs: 2,
+        }
+    }
+}
+
+impl DopplerScreeningOptions {
+    /// Copies and returns [DopplerScreeningOptions] with desired threshold, in meters
+    pub fn with_threshold_m(&self, threshold_m: f64) -> Self {
+        let mut s = *self;
+        s.threshold_m = threshold_m;
+        s
+    }
+
+    /// Copies and returns [DopplerScreeningOptions] with desired minimal number
+    /// of [SV]s to declare a [DopplerAnomaly::ClockJump]
+    pub fn with_min_clock_jump_svs(&self, min_svs: usize) -> Self {
+        let mut s = *self;
+        s.min_clock_jump_svs = min_svs;
+        s
+    }
+}
+
+/// Previous phase and Doppler samples of one signal
+pub(crate) struct DopplerArc {
+    t: Epoch,
+    phase: f64,
+    doppler: f64,
+}
+
+impl DopplerArc {
+    /// Builds a new [DopplerArc] from these phase and Doppler samples
+    pub(crate) fn new(t: Epoch, phase: f64, doppler: f64) -> Self {
+        Self { t, phase, doppler }
+    }
+
+    /// Returns the phase variation (in cycles) up to this new sample, that
+    /// the Doppler does not predict. Returns None on data gaps (above max_dt).
+    pub(crate) fn residual_cycles(
+        &self,
+        t: Epoch,
+        phase: f64,
+        doppler: f64,
+        max_dt: Duration,
+    ) -> Option<f64> {
+        let dt = t - self.t;
+        if dt > max_dt {
+            return None;
+        }
+        // phase decreases when satellite is approaching (positive Doppler)
+        let predicted = -(self.doppler + doppler) / 2.0 * dt.to_seconds();
+        Some(phase - self.phase - predicted)
+    }
+
+    /// Returns the Doppler observation that matches this phase observation
+    /// (same [SV] and signal code), among these signals.
+    pub(crate) fn matching_doppler(
+        signals: &[SignalObservation],
+        phase: &SignalObservation,
+    ) -> Option<f64> {
+        let code = phase.observable.code()?;
+
+        signals
+            .iter()
+            .find(|sig| {
+                sig.sv == phase.sv
+                    && sig.observable.is_doppler_observable()
+                    && sig.observable.code().as_ref() == Some(&code)
+            })
+            .map(|doppler| doppler.value)
+    }
+}
+
+impl Rinex {
+    /// Compares the phase rate to the integrated Doppler, per [SV] and signal
+    /// (same signal code), between consecutive epochs, and returns the epochs
+    /// where they are inconsistent. When all [SV]s present the same residual,
+    /// the epoch is flagged as [DopplerAnomaly::ClockJump] (the Doppler is not
+    /// impacted by receiver clock jumps), otherwise as [DopplerAnomaly::Tracking].
+    /// Data gaps interrupt the comparison.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use rinex::observation::{DopplerAnomaly, DopplerScreeningOptions};
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 L1C D1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01 109474991.854       -1000.000
+    /// G02 119474991.854        2000.000
+    /// > 2020 01 01 00 00  1.0000000  0  2
+    /// G01 109475991.854       -1000.000
+    /// G02 119472991.854        2000.000
+    /// > 2020 01 01 00 00  2.0000000  0  2
+    /// G01 109477991.854       -1000.000
+    /// G02 119471991.854        2000.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // both phases jumped by 1000 cycles on 3rd epoch
+    /// let flagged = rinex.doppler_screening(&DopplerScreeningOptions::default());
+    /// assert_eq!(flagged.len(), 1);
+    /// assert_eq!(flagged[0].anomaly, DopplerAnomaly::ClockJump);
+    /// assert_eq!(flagged[0].svs.len(), 2);
+    /// ```
+    pub fn doppler_screening(&self, opts: &DopplerScreeningOptions) -> Vec<DopplerInconsistency> {
+        let mut ret = Vec::new();
+
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return ret, // can't proceed without sampling interval guess.
+        };
+
+        let mut arcs = HashMap::<(SV, Observable), DopplerArc>::new();
+
+        for (k, v) in self.observations_iter() {
+            // residuals of this epoch, per SV
+            let mut residuals = BTreeMap::<SV, Vec<f64>>::new();
+
+            for phase in v.signals.iter() {
+                if !phase.observable.is_phase_range_observable() {
+                    continue;
+                }
+
+                let doppler = match DopplerArc::matching_doppler(&v.signals, phase) {
+                    Some(doppler) => doppler,
+                    None => continue,
+                };
+
+                let wavelength = match self.header.sv_carrier(phase.sv, &phase.observable) {
+                    Ok(carrier) => carrier.wavelength(),
+                    Err(_) => continue,
+                };
+
+                let arc = (phase.sv, phase.observable.clone());
+
+                if let Some(state) = arcs.get(&arc) {
+                    if let Some(residual) =
+                        state.residual_cycles(k.epoch, phase.value, doppler, dominant_sampling)
+                    {
+                        residuals
+                            .entry(phase.sv)
+                            .or_default()
+                            .push(residual * wavelength);
+                    }
+                }
+
+                arcs.insert(arc, DopplerArc::new(k.epoch, phase.value, doppler));
+            }
+
+            // worst residual, per SV
+            let residuals = residuals
+                .into_iter()
+                .filter_map(|(sv, values)| {
+                    let worst = values
+                        .into_iter()
+                        .max_by(|a, b| a.abs().total_cmp(&b.abs()))?;
+                    Some((sv, worst))
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            let svs = residuals
+                .iter()
+                .filter_map(|(sv, residual_m)| {
+                    if residual_m.abs() > opts.threshold_m {
+                        Some(*sv)
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if svs.is_empty() {
+                continue;
+            }
+
+            let (min, max) = residuals
+                .values()
+                .fold((f64::MAX, f64::MIN), |(min, max), r| {
+                    (min.min(*r), max.max(*r))
+                });
+
+            let residual_m = if max.abs() > min.abs() { max } else { min };
+
+            let anomaly = if svs.len() == residuals.len()
+                && svs.len() >= opts.min_clock_jump_svs
+                && max - min <= opts.threshold_m
+            {
+                DopplerAnomaly::ClockJump
+            } else {
+                DopplerAnomaly::Tracking
+            };
+
+            ret.push(DopplerInconsistency {
+                epoch: k.epoch,
+                flag: k.flag,
+                anomaly,
+                svs,
+                residual_m,
+            });
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DopplerAnomaly, DopplerScreeningOptions};
+    use crate::{
+        carrier::Carrier,
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn doppler_screening() {
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+        let w1 = Carrier::L1.wavelength();
+
+        let svs = ["G01", "G02", "G03"]
+            .iter()
+            .map(|sv| SV::from_str(sv).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(Duration::from_seconds(1.0));
+
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        for i in 0..10 {
+            let mut obs = Observations::default();
+
+            for (j, sv) in svs.iter().enumerate() {
+                let doppler = 1000.0 * (j as f64 - 1.0);
+                let mut phase = 100_000_000.0 - doppler * i as f64;
+
+                // 1 ms receiver clock jump on 5th epoch
+                if i >= 4 {
+                    phase += 299_792.458 / w1;
+                }
+
+                // G02 tracking anomaly on 8th epoch
+                if j == 1 && i >= 7 {
+                    phase += 20.0;
+                }
+
+                obs.signals.push(SignalObservation::new(
+                    *sv,
+                    Observable::from_str("L1C").unwrap(),
+                    phase,
+                ));
+                obs.signals.push(SignalObservation::new(
+                    *sv,
+                    Observable::from_str("D1C").unwrap(),
+                    doppler,
+                ));
+            }
+
+            let key = ObsKey {
+                epoch: t0 + i as f64 * Duration::from_seconds(1.0),
+                flag: Default::default(),
+            };
+            record.insert(key, obs);
+        }
+
+        let flagged = rinex.doppler_screening(&DopplerScreeningOptions::default());
+        assert_eq!(flagged.len(), 2);
+
+        assert_eq!(flagged[0].epoch, t0 + 4.0 * Duration::from_seconds(1.0));
+        assert_eq!(flagged[0].anomaly, DopplerAnomaly::ClockJump);
+        assert_eq!(flagged[0].svs, svs);
+        assert!((flagged[0].residual_m - 299_792.458).abs() < 1.0E-3);
+
+        assert_eq!(flagged[1].epoch, t0 + 7.0 * Duration::from_seconds(1.0));
+        assert_eq!(flagged[1].anomaly, DopplerAnomaly::Tracking);
+        assert_eq!(flagged[1].svs, vec![svs[1]]);
+        assert!((flagged[1].residual_m - 20.0 * w1).abs() < 1.0E-3);
+
+        // not enough SVs to declare a clock jump
+        let opts = DopplerScreeningOptions::default().with_min_clock_jump_svs(4);
+        let flagged = rinex.doppler_screening(&opts);
+        assert_eq!(flagged[0].anomaly, DopplerAnomaly::Tracking);
+    }
+}
@@ -255,259 +255,357 @@ impl Rinex {
     /// Unit is depend on [Observable]s being combined.
     /// But usually, [Observable::PhaseRange] and [Observable::PseudoRange] are intended here,
     /// meaning the return value is in meters of recombined frequency propagation.
+    /// The L1 signal of each constellation is used as reference.
     pub fn signals_combination(&self, combination: Combination) -> BTreeMap<CombinationKey, f64> {
+        self.reference_signals_combination(combination, &|sv, observable| {
+            observable.is_l1_pivot(sv.constellation)
+        })
+    }
+
+    /// Form designed signal [Combination], like [Self::signals_combination],
+    /// but using this reference [Carrier] (rather than L1) for each [SV] that
+    /// is sampled on it. This allows forming any dual frequency combination,
+    /// for example L2/L5 for GPS, or E5a/E5b for Galileo.
+    pub fn signals_combination_with_reference(
+        &self,
+        combination: Combination,
+        reference: Carrier,
+    ) -> BTreeMap<CombinationKey, f64> {
+        self.reference_signals_combination(combination, &|sv, observable| {
+            observable.to_carrier(sv.constellation).ok() == Some(reference)
+        })
+    }
+
+    fn reference_signals_combination(
+        &self,
+        combination: Combination,
+        is_reference: &dyn Fn(SV, &Observable) -> bool,
+    ) -> BTreeMap<CombinationKey, f64> {
         let mut ret = BTreeMap::new();
 
         if combination == Combination::MelbourneWubbena {
-            for ((k_1, v_1), (_, v_2)) in self
-                .signals_combination(Combination::WideLane)
-                .iter()
+            // NL code combinations, indexed by sampling and LHS code
+            let code_nl = self
+                .reference_signals_combination(Combination::NarrowLane, is_reference)
+                .into_iter()
+                .filter(|(k, _)| k.reference.is_pseudo_range_observable())
+                .filter_map(|(k, v)| Some(((k.epoch, k.flag, k.sv, k.lhs.code()?), v)))
+                .collect::<HashMap<_, _>>();
+
+            for (k, phase_wl) in self
+                .reference_signals_combination(Combination::WideLane, is_reference)
+                .into_iter()
                 .filter(|(k, _)| k.reference.is_phase_range_observable())
-                .zip(
-                    self.signals_combination(Combination::NarrowLane)
-                        .iter()
-                        .filter(|(k, _)| k.reference.is_pseudo_range_observable()),
-                )
-                .filter_map(|((k_1, v_1), (k_2, v_2))| {
-                    let lhs_1_code = k_1.lhs.code()?;
-                    let lhs_2_code = k_2.lhs.code()?;
-                    if lhs_1_code == lhs_2_code {
-                        Some(((k_1, v_1), (k_2, v_2)))
-                    } else {
-                        None
-                    }
-                })
             {
-                let key = CombinationKey {
-                    epoch: k_1.epoch,
-                    flag: k_1.flag,
-                    sv: k_1.sv,
-                    lhs: k_1.lhs.clone(),
-                    reference: k_1.reference.clone(),
+                let code = match k.lhs.code() {
+                    Some(code) => code,
+                    None => continue,
                 };
-                ret.insert(key, v_1 - v_2);
-            }
-        } else {
-            let dominant_sampling = if let Some(dt) = self.header.sampling_interval {
-                dt
-            } else {
-                if let Some(dt) = self.dominant_sampling_interval() {
-                    dt
-                } else {
-                    // can't proceed without sampling interval guess.
-                    return ret;
-                }
-            };
 
-            let mut pr_ref_value = HashMap::<SV, (Epoch, Observable, f64, f64, f64)>::new();
-            let mut phase_ref_value = HashMap::<SV, (Epoch, Observable, f64, f64, f64)>::new();
-
-            for (k, v) in self.signal_observations_iter() {
-                let is_phase_range = v.observable.is_phase_range_observable();
-                let is_pseudo_range = v.observable.is_pseudo_range_observable();
-
-                if !is_phase_range && !is_pseudo_range {
-                    continue;
+                // same signal, at this very epoch
+                if let Some(code_nl) = code_nl.get(&(k.epoch, k.flag, k.sv, code)) {
+                    ret.insert(k, phase_wl - code_nl);
                 }
+            }
+        } else {
+            for (k, v) in self.observations_iter() {
+                for (sv, signals) in v.signals.iter().into_group_map_by(|sig| sig.sv) {
+                    for is_phase_range in [true, false] {
+                        // first reference signal of same physics, at this very epoch
+                        let reference = signals.iter().find(|sig| {
+                            let same_physics = if is_phase_range {
+                                sig.observable.is_phase_range_observable()
+                            } else {
+                                sig.observable.is_pseudo_range_observable()
+                            };
+                            same_physics && is_reference(sv, &sig.observable)
+                        });
+
+                        let reference = match reference {
+                            Some(reference) => reference,
+                            None => continue,
+                        };
 
-                let is_l1_pivot = v.observable.is_l1_pivot(v.sv.constellation);
+                        let (f_1, w_1) = match self.header.sv_carrier(sv, &reference.observable) {
+                            Ok(carrier) => (carrier.frequency(), carrier.wavelength()),
+                            Err(_) => continue,
+                        };
 
-                let carrier = v.observable.to_carrier(v.sv.constellation);
-                if carrier.is_err() {
-                    continue;
+                        for sig in signals.iter().filter(|sig| {
+                            let same_physics = if is_phase_range {
+                                sig.observable.is_phase_range_observable()
+                            } else {
+                                sig.observable.is_pseudo_range_observable()
+                            };
+                            same_physics && !is_reference(sv, &sig.observable)
+                        }) {
+                            let (freq, lambda) = match self.header.sv_carrier(sv, &sig.observable) {
+                                Ok(carrier) => (carrier.frequency(), carrier.wavelength()),
+                                Err(_) => continue,
+                            };
+
+                            let (alpha, beta, gamma) = match combination {
+                                Combination::GeometryFree => (1.0, -1.0, 1.0),
+                                Combination::IonosphereFree => {
+                                    (f_1.powi(2), -freq.powi(2), f_1.powi(2) - freq.powi(2))
+                                },
+                                Combination::WideLane => (f_1, -freq, f_1 - freq),
+                                Combination::NarrowLane => (f_1, freq, f_1 + freq),
+                                Combination::MelbourneWubbena => {
+                                    unreachable!("mw combination");
+                                },
+                            };
+
+                            // phase ranges are scaled to meters.
+                            // GF code combination is C2-C1, so it has the sign of the phase GF.
+                            let (v_lhs, v_rhs) = if is_phase_range {
+                                (reference.value * w_1, sig.value * lambda)
+                            } else if combination == Combination::GeometryFree {
+                                (sig.value, reference.value)
+                            } else {
+                                (reference.value, sig.value)
+                            };
+
+                            let key = CombinationKey {
+                                epoch: k.epoch,
+                                flag: k.flag,
+                                sv,
+                                lhs: sig.observable.clone(),
+                                reference: reference.observable.clone(),
+                            };
+
+                            ret.insert(key, (alpha * v_lhs + beta * v_rhs) / gamma);
+                        }
+                    }
                 }
+            }
+        }
+        ret
+    }
 
-                let carrier = carrier.unwrap();
-                let freq = carrier.frequency();
-                let lambda = carrier.wavelength();
-
-                if is_l1_pivot {
-                    if is_phase_range {
-                        phase_ref_value
-                            .insert(v.sv, (k.epoch, v.observable.clone(), freq, lambda, v.value));
-                    } else {
-                        pr_ref_value
-                            .insert(v.sv, (k.epoch, v.observable.clone(), freq, lambda, v.value));
-                    };
-                } else {
-                    let reference = if is_phase_range {
-                        &phase_ref_value
-                    } else {
-                        &pr_ref_value
-                    };
+    /// Forms the geometry free (GF) combination, for each [SV] and each epoch,
+    /// between the L1 signal (reference) and each other signal of the same physics:
+    /// - phase: λ1 L1 - λj Lj, in meters
+    /// - code: Cj - C1, in meters, so both have the sign of the ionospheric delay.
+    ///
+    /// Signals are only combined within a given epoch. The GF combination cancels
+    /// the geometry and clocks, which makes it the basic observable for ionospheric
+    /// and cycle slip analysis.
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    4 C1C L1C C2W L2W                                      SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682   109474991.854    20832397.682    85305196.437
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let gf = rinex.geo_free();
+    /// assert_eq!(gf.len(), 2);
+    ///
+    /// let (_, code_gf) = gf
+    ///     .iter()
+    ///     .find(|(k, _)| k.lhs == Observable::from_str("C2W").unwrap())
+    ///     .unwrap();
+    ///
+    /// assert!((code_gf - 4.0).abs() < 1.0E-6);
+    /// ```
+    pub fn geo_free(&self) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination(Combination::GeometryFree)
+    }
 
-                    if let Some((last_seen, reference, f_1, w_1, ref_value)) = reference.get(&v.sv)
-                    {
-                        if k.epoch - *last_seen > dominant_sampling {
-                            // avoid moving forward on data gaps
-                            // guards against substraction (a - b) from two different epochs
-                            continue;
-                        }
+    /// Forms the ionosphere free (IF) combination of phase and code, for each [SV]
+    /// and each epoch, between the L1 signal (reference) and each other signal
+    /// of the same physics, in meters:
+    /// (f1² X1 - fj² Xj) / (f1² - fj²).
+    /// Frequencies are constellation dependent (and channel dependent for Glonass).
+    /// The IF combination cancels the first order ionospheric delay, which makes
+    /// it the usual input to PPP. Use [Self::iono_free_with_reference] for other pairs.
+    /// ```
+    /// use rinex::prelude::{Carrier, Observable, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+    /// G    3 C1C C2W C5Q                                          SYS / # / OBS TYPES
+    /// E    2 C1C C5Q                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682    20832397.682    20832398.682
+    /// E05  23000000.000    23000005.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // E1/E5a and L1/L2 and L1/L5
+    /// assert_eq!(rinex.iono_free().len(), 3);
+    ///
+    /// // L2/L1 and L2/L5
+    /// let l2_ref = rinex.iono_free_with_reference(Carrier::L2);
+    /// assert_eq!(l2_ref.len(), 2);
+    ///
+    /// let c5q = Observable::from_str("C5Q").unwrap();
+    /// let (key, _) = l2_ref.iter().find(|(k, _)| k.lhs == c5q).unwrap();
+    /// assert_eq!(key.reference, Observable::from_str("C2W").unwrap());
+    /// ```
+    pub fn iono_free(&self) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination(Combination::IonosphereFree)
+    }
 
-                        let alpha = match combination {
-                            Combination::GeometryFree => 1.0,
-                            Combination::IonosphereFree => f_1.powi(2),
-                            Combination::WideLane => *f_1,
-                            Combination::NarrowLane => *f_1,
-                            Combination::MelbourneWubbena => {
-                                unreachable!("mw combination");
-                            },
-                        };
+    /// Forms the ionosphere free (IF) combination of phase and code, like [Self::iono_free],
+    /// but using this reference [Carrier] for each [SV] that is sampled on it.
+    pub fn iono_free_with_reference(&self, reference: Carrier) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination_with_reference(Combination::IonosphereFree, reference)
+    }
 
-                        let beta = match combination {
-                            Combination::GeometryFree => -1.0,
-                            Combination::IonosphereFree => -freq.powi(2),
-                            Combination::WideLane => -freq,
-                            Combination::NarrowLane => freq,
-                            Combination::MelbourneWubbena => {
-                                unreachable!("mw combination");
-                            },
-                        };
+    /// Forms the wide lane (WL) combination of phase and code, for each [SV]
+    /// and each epoch, between the L1 signal (reference) and each other signal
+    /// of the same physics, in meters: (f1 X1 - fj Xj) / (f1 - fj).
+    /// Use [Self::wide_lane_with_reference] for other pairs.
+    /// ```
+    /// use rinex::prelude::{Carrier, Rinex};
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    3 L1C L2W L5Q                                          SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01 109474991.854    85305196.437    81748937.863
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // L1/L2 and L1/L5
+    /// assert_eq!(rinex.wide_lane().len(), 2);
+    ///
+    /// // L2/L1 and L2/L5
+    /// assert_eq!(rinex.wide_lane_with_reference(Carrier::L2).len(), 2);
+    /// ```
+    pub fn wide_lane(&self) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination(Combination::WideLane)
+    }
 
-                        let gamma = match combination {
-                            Combination::GeometryFree => 1.0,
-                            Combination::IonosphereFree => f_1.powi(2) - freq.powi(2),
-                            Combination::WideLane => *f_1 - freq,
-                            Combination::NarrowLane => *f_1 + freq,
-                            Combination::MelbourneWubbena => {
-                                unreachable!("mw combination");
-                            },
-                        };
+    /// Forms the wide lane (WL) combination of phase and code, like [Self::wide_lane],
+    /// but using this reference [Carrier] for each [SV] that is sampled on it.
+    pub fn wide_lane_with_reference(&self, reference: Carrier) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination_with_reference(Combination::WideLane, reference)
+    }
 
-                        let (v_lhs, v_rhs) = match combination {
-                            Combination::GeometryFree => {
-                                if v.observable.is_phase_range_observable() {
-                                    (*ref_value * w_1, v.value * lambda)
-                                } else {
-                                    (v.value, *ref_value)
-                                }
-                            },
-                            _ => {
-                                if v.observable.is_phase_range_observable() {
-                                    (*ref_value * w_1, v.value * lambda)
-                                } else {
-                                    (*ref_value, v.value)
-                                }
-                            },
-                        };
+    /// Forms the narrow lane (NL) combination of phase and code, for each [SV]
+    /// and each epoch, between the L1 signal (reference) and each other signal
+    /// of the same physics, in meters: (f1 X1 + fj Xj) / (f1 + fj).
+    /// Use [Self::narrow_lane_with_reference] for other pairs.
+    pub fn narrow_lane(&self) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination(Combination::NarrowLane)
+    }
 
-                        let combination = CombinationKey {
-                            epoch: k.epoch,
-                            flag: k.flag,
-                            sv: v.sv,
-                            lhs: v.observable.clone(),
-                            reference: reference.clone(),
-                        };
+    /// Forms the narrow lane (NL) combination of phase and code, like [Self::narrow_lane],
+    /// but using this reference [Carrier] for each [SV] that is sampled on it.
+    pub fn narrow_lane_with_reference(&self, reference: Carrier) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination_with_reference(Combination::NarrowLane, reference)
+    }
 
-                        ret.insert(combination, (alpha * v_lhs + beta * v_rhs) / gamma);
-                    }
-                }
-            }
-        }
-        ret
+    /// Forms the Melbourne-Wübbena (MW) combination, for each [SV] and each epoch:
+    /// the wide lane phase combination minus the narrow lane code combination
+    /// of the same signals, in meters, with L1 as reference.
+    /// MW cancels geometry, clocks and ionosphere, and is constant
+    /// (wide lane ambiguity) along each [SV] arc, until a cycle slip occurs.
+    /// It is the standard input to cycle slip and ambiguity screening.
+    /// Signals are only combined when phase and code were both sampled at this epoch.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    4 C1C L1C C2W L2W                                      SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682   109474991.854    20832397.682    85305196.437
+    /// G02  20832393.682   109474991.854
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832493.682   109475516.584                    85305605.330
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // G02 is single frequency, C2W is missing on 2nd epoch
+    /// let mw = rinex.melbourne_wubbena();
+    /// assert_eq!(mw.len(), 1);
+    /// ```
+    pub fn melbourne_wubbena(&self) -> BTreeMap<CombinationKey, f64> {
+        self.signals_combination(Combination::MelbourneWubbena)
     }
 
     /// Calculates the signal multipath bias (as meters of propagation delay)
-    /// for all SV in sight and from dual frequency phase measurement.
+    /// for all SV in sight and from dual frequency phase measurement:
+    /// MPi = Ci - Li - 2 fj² / (fi² - fj²) (Li - Lj), where Li is the phase
+    /// of the same signal as Ci, and Lj any phase on another frequency.
     /// Note that this is not the absolute multipath bias because
     /// - it does not integrate the K_1_j "static" bias induced by differential code bias
-    /// between SV and RX. You should apply it yourself on the provided results
+    /// between SV and RX, and the phase ambiguities. Use [Self::code_multipath] to remove it.
     /// - the receiver own noise is contained in the result. Results should be averaged to mitigate.
     pub fn signals_multipath(&self) -> HashMap<MultipathKey, f64> {
         let mut ret = HashMap::new();
 
-        let dominant_sampling = if let Some(dt) = self.header.sampling_interval {
-            dt
-        } else {
-            if let Some(dt) = self.dominant_sampling_interval() {
-                dt
-            } else {
-                // can't proceed without sampling interval guess.
-                return ret;
-            }
-        };
-
-        let mut t = Epoch::default();
+        for (k, v) in self.observations_iter() {
+            for code in v.signals.iter() {
+                if !code.observable.is_pseudo_range_observable() {
+                    continue;
+                }
 
-        // stores all encountered pseudo range
-        let mut rho = HashMap::<(SV, Observable), f64>::new();
-        // stores all encountered phase range
-        let mut phi = HashMap::<(SV, Observable), (f64, f64)>::new();
+                let code_signal = code.observable.code();
+                if code_signal.is_none() {
+                    continue;
+                }
 
-        for (k, v) in self.signal_observations_iter() {
-            let is_ph = v.observable.is_phase_range_observable();
-            let is_pr = v.observable.is_pseudo_range_observable();
-            let carrier = v.observable.to_carrier(v.sv.constellation);
+                // phase of same signal
+                let phase = v.signals.iter().find(|ph| {
+                    ph.sv == code.sv
+                        && ph.observable.is_phase_range_observable()
+                        && ph.observable.code() == code_signal
+                });
 
-            if !is_ph && !is_pr || carrier.is_err() {
-                continue;
-            }
+                let phase = match phase {
+                    Some(phase) => phase,
+                    None => continue,
+                };
 
-            let carrier = carrier.unwrap();
-            let freq = carrier.frequency().powi(2);
-            let lambda = carrier.wavelength();
+                let carrier_i = match self.header.sv_carrier(phase.sv, &phase.observable) {
+                    Ok(carrier) => carrier,
+                    Err(_) => continue,
+                };
 
-            if k.epoch - t > dominant_sampling {
-                // data gap
-                rho.clear();
-                phi.clear();
-            }
+                let f_i = carrier_i.frequency().powi(2);
+                let l_i = phase.value * carrier_i.wavelength();
 
-            if k.epoch - t == dominant_sampling {
-                // new epoch
-                // try to compute from previously stored data, then reset
-                for ((phi_sv, phi_obs), (freq, phi_value)) in phi.iter() {
-                    let phi_code = phi_obs.code();
-                    if phi_code.is_none() {
+                for rhs in v.signals.iter() {
+                    if rhs.sv != code.sv || !rhs.observable.is_phase_range_observable() {
                         continue;
                     }
 
-                    let phi_code = phi_code.unwrap();
+                    let carrier_j = match self.header.sv_carrier(rhs.sv, &rhs.observable) {
+                        Ok(carrier) if carrier != carrier_i => carrier,
+                        _ => continue,
+                    };
 
-                    // locate associated pr
-                    for ((rho_sv, rho_obs), rho) in rho.iter() {
-                        let rho_code = rho_obs.code();
-                        if rho_code.is_none() {
-                            continue;
-                        }
+                    let f_j = carrier_j.frequency().powi(2);
+                    let l_j = rhs.value * carrier_j.wavelength();
 
-                        let rho_code = rho_code.unwrap();
-
-                        // same modulation / signal as reference phase
-                        if rho_code == phi_code && rho_sv == phi_sv {
-                            // now compute for any different l_j phase
-                            for ((phi_sv_j, phi_obs_j), (freq_j, phi_j)) in phi.iter() {
-                                if phi_sv_j == phi_sv {
-                                    if phi_obs_j != phi_obs {
-                                        let key = MultipathKey {
-                                            epoch: t,
-                                            sv: *phi_sv,
-                                            signal: phi_obs.clone(),
-                                            rhs: phi_obs_j.clone(),
-                                        };
-                                        let value = rho
-                                            - phi_value
-                                            - 2.0 * freq_j / (freq - freq_j) * (phi_value - phi_j);
-                                        ret.insert(key, value);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                //reset
-                rho.clear();
-                phi.clear();
-            }
+                    let key = MultipathKey {
+                        epoch: k.epoch,
+                        sv: code.sv,
+                        signal: phase.observable.clone(),
+                        rhs: rhs.observable.clone(),
+                    };
 
-            // store value
-            if is_pr {
-                rho.insert((v.sv, v.observable.clone()), v.value);
-            } else {
-                phi.insert((v.sv, v.observable.clone()), (freq, v.value * lambda));
+                    let value = code.value - l_i - 2.0 * f_j / (f_i - f_j) * (l_i - l_j);
+                    ret.insert(key, value);
+                }
             }
-
-            t = k.epoch;
         }
         ret
     }
@@ -517,7 +615,44 @@ impl Rinex {
 mod test {
 
     use super::Combination;
-    use crate::prelude::{Carrier, Rinex};
+    use crate::{
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Carrier, Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::{io::BufReader, str::FromStr};
+
+    #[test]
+    fn e5_altboc_signal_combination() {
+        let e01 = SV::from_str("E01").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let l8q = Observable::from_str("L8Q").unwrap();
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(Duration::from_seconds(30.0));
+
+        let key = ObsKey {
+            epoch: Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap(),
+            flag: Default::default(),
+        };
+
+        let mut obs = Observations::default();
+        obs.signals
+            .push(SignalObservation::new(e01, l1c.clone(), 120.0E6));
+        obs.signals
+            .push(SignalObservation::new(e01, l8q.clone(), 90.0E6));
+        rinex.record.as_mut_obs().unwrap().insert(key, obs);
+
+        let combinations = rinex.signals_combination(Combination::GeometryFree);
+        assert_eq!(combinations.len(), 1);
+
+        let (k, value) = combinations.iter().next().unwrap();
+        assert_eq!(k.reference, l1c);
+        assert_eq!(k.lhs, l8q);
+        assert_eq!(
+            *value,
+            120.0E6 * Carrier::E1.wavelength() - 90.0E6 * Carrier::E5a5b.wavelength()
+        );
+    }
 
     #[test]
     fn gf_signal_combination() {
@@ -608,6 +743,177 @@ mod test {
         assert_eq!(tests_passed, 10);
     }
 
+    #[test]
+    fn gf_combination_same_epoch() {
+        let content =
+            "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    4 C2W L2W C1C L1C                                      SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20832397.682    85305196.437    20832393.682   109474991.854
+> 2020 01 01 00 00 30.0000000  0  1
+G01  20832398.682    85305197.437
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let gf = rinex.geo_free();
+
+        // L1 reference is missing on 2nd epoch
+        assert_eq!(gf.len(), 2);
+
+        let (w1, w2) = (Carrier::L1.wavelength(), Carrier::L2.wavelength());
+
+        for (k, value) in gf.iter() {
+            assert_eq!(
+                k.epoch,
+                Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap()
+            );
+            match k.lhs.to_string().as_str() {
+                "C2W" => {
+                    assert_eq!(k.reference, Observable::from_str("C1C").unwrap());
+                    assert!((value - 4.0).abs() < 1.0E-6);
+                },
+                "L2W" => {
+                    assert_eq!(k.reference, Observable::from_str("L1C").unwrap());
+                    let expected = 109474991.854 * w1 - 85305196.437 * w2;
+                    assert!((value - expected).abs() < 1.0E-6);
+                },
+                lhs => panic!("invalid LHS observable: {}", lhs),
+            }
+        }
+    }
+
+    #[test]
+    fn if_combination_dual_frequency_pairs() {
+        let content =
+            "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+G    3 C1C C2W C5Q                                          SYS / # / OBS TYPES
+E    2 L1C L5Q                                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  2
+G01  20832393.682    20832397.682    20832398.682
+E05 120000000.000    90000000.000
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let (f_e1, f_e5) = (Carrier::E1.frequency(), Carrier::E5a.frequency());
+        let (w_e1, w_e5) = (Carrier::E1.wavelength(), Carrier::E5a.wavelength());
+
+        let iono_free = rinex.iono_free();
+        let (_, value) = iono_free
+            .iter()
+            .find(|(k, _)| k.sv == SV::from_str("E05").unwrap())
+            .unwrap();
+
+        let expected = (f_e1.powi(2) * 120.0E6 * w_e1 - f_e5.powi(2) * 90.0E6 * w_e5)
+            / (f_e1.powi(2) - f_e5.powi(2));
+        assert!((value - expected).abs() < 1.0E-6);
+
+        let (f_l2, f_l5) = (Carrier::L2.frequency(), Carrier::L5.frequency());
+
+        let iono_free = rinex.iono_free_with_reference(Carrier::L2);
+        let (_, value) = iono_free
+            .iter()
+            .find(|(k, _)| k.lhs == Observable::from_str("C5Q").unwrap())
+            .unwrap();
+
+        let expected = (f_l2.powi(2) * 20832397.682 - f_l5.powi(2) * 20832398.682)
+            / (f_l2.powi(2) - f_l5.powi(2));
+        assert!((value - expected).abs() < 1.0E-6);
+    }
+
+    #[test]
+    fn mw_combination_arc() {
+        let g01 = SV::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+
+        let (f1, f2) = (Carrier::L1.frequency(), Carrier::L2.frequency());
+        let (w1, w2) = (Carrier::L1.wavelength(), Carrier::L2.wavelength());
+        let (n1, n2) = (1234.0, 1000.0);
+
+        let mut rinex = Rinex::basic_obs();
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        for (i, range_m) in [20_000_000.0, 20_000_300.0, 20_000_600.0]
+            .iter()
+            .enumerate()
+        {
+            let key = ObsKey {
+                epoch: t0 + i as f64 * Duration::from_seconds(30.0),
+                flag: Default::default(),
+            };
+
+            let mut obs = Observations::default();
+            for (observable, value) in [
+                ("C1C", *range_m),
+                ("L1C", range_m / w1 + n1),
+                ("C2W", *range_m),
+                ("L2W", range_m / w2 + n2),
+            ] {
+                obs.signals.push(SignalObservation::new(
+                    g01,
+                    Observable::from_str(observable).unwrap(),
+                    value,
+                ));
+            }
+            record.insert(key, obs);
+        }
+
+        let mw = rinex.melbourne_wubbena();
+        assert_eq!(mw.len(), 3);
+
+        // constant along the arc: wide lane ambiguity
+        let lambda_wl = 299_792_458.0 / (f1 - f2);
+        for (k, value) in mw.iter() {
+            assert_eq!(k.lhs, Observable::from_str("L2W").unwrap());
+            assert!((value - lambda_wl * (n1 - n2)).abs() < 1.0E-3);
+        }
+    }
+
+    #[test]
+    fn wl_nl_combinations() {
+        let content =
+            "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    6 C1C L1C C2W L2W C5Q L5Q                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20832393.682   109474991.854    20832397.682    85305196.437    20832398.682    81748937.863
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let (f1, f2, f5) = (
+            Carrier::L1.frequency(),
+            Carrier::L2.frequency(),
+            Carrier::L5.frequency(),
+        );
+        let (w2, w5) = (Carrier::L2.wavelength(), Carrier::L5.wavelength());
+
+        let l2w = Observable::from_str("L2W").unwrap();
+        let c2w = Observable::from_str("C2W").unwrap();
+        let l5q = Observable::from_str("L5Q").unwrap();
+
+        let wide_lane = rinex.wide_lane();
+        assert_eq!(wide_lane.len(), 4);
+
+        let narrow_lane = rinex.narrow_lane();
+        assert_eq!(narrow_lane.len(), 4);
+
+        let (_, value) = narrow_lane.iter().find(|(k, _)| k.lhs == c2w).unwrap();
+        let expected = (f1 * 20832393.682 + f2 * 20832397.682) / (f1 + f2);
+        assert!((value - expected).abs() < 1.0E-6);
+
+        // L2/L5 wide lane: about 5.86m wavelength
+        let wide_lane = rinex.wide_lane_with_reference(Carrier::L2);
+        let (k, value) = wide_lane.iter().find(|(k, _)| k.lhs == l5q).unwrap();
+        assert_eq!(k.reference, l2w);
+
+        let expected = (f2 * 85305196.437 * w2 - f5 * 81748937.863 * w5) / (f2 - f5);
+        assert!((value - expected).abs() < 1.0E-6);
+        assert!((299_792_458.0 / (f2 - f5) - 5.861).abs() < 1.0E-3);
+
+        let narrow_lane = rinex.narrow_lane_with_reference(Carrier::L5);
+        assert_eq!(narrow_lane.len(), 4);
+    }
+
     #[test]
     fn if_signal_combination() {
         let fullpath = format!(
@@ -1,20 +1,84 @@
 //! Observation specific high level methods
 use crate::{
-    observation::{ClockObservation, ObsKey, Observations, SignalObservation},
-    prelude::{Rinex, RinexType},
+    hardware::{Antenna, Receiver},
+    observation::{
+        ClockObservation, Event, LliFlags, ObsKey, Observations, SignalObservation, SNR,
+    },
+    prelude::{Constellation, Epoch, Observable, Rinex, RinexType, SV},
 };
 
+use itertools::Itertools;
+
 #[cfg(feature = "obs")]
 #[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
 pub(crate) mod feature; // feature dependent, high level methods
 
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod residuals; // processing internals
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod dcb; // cross-correlation receivers
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod differential; // base vs rover QC
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod clock_drift; // receiver oscillator analysis
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod timeline; // station events
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod multipath; // code multipath QC
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod doppler; // phase rate screening
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod statistics; // data screening
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod phase_alignment; // phase to code
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod clock_jumps; // receiver clock steering
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod differencing; // single and double differences
+
+#[cfg(feature = "obs")]
+#[cfg(feature = "nav")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "nav")))]
+pub(crate) mod reflectometry; // obs + nav capabilities
+
 #[cfg(feature = "obs")]
 #[cfg(feature = "ionex")]
 #[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
 #[cfg_attr(docsrs, doc(cfg(feature = "ionex")))]
 pub(crate) mod tec; // ionex + obs capabilities
 
-use std::collections::btree_map::{Iter, IterMut, Keys};
+pub(crate) mod dedup; // record consistency
+pub(crate) mod prn_obs; // header generation
+pub(crate) mod rcv_clock; // time transfer
+pub(crate) mod time_of_obs; // header consistency
+pub(crate) mod upgrade; // RINEX2 to RINEX3
+
+use std::collections::{
+    btree_map::{Iter, IterMut, Keys},
+    HashSet,
+};
 
 impl Rinex {
     /// Returns true if [Rinex] format is [RinexType::ObservationData].
@@ -67,6 +131,92 @@ impl Rinex {
         )
     }
 
+    /// Returns a flat Iterator over all signal observations, in chronological order,
+    /// as (sampling [Epoch], [SV], [Observable], value, [LliFlags], [SNR]) tuples,
+    /// so standard iterator adapters can be used for analysis.
+    /// Event epochs are not filtered out. This is empty when this is not
+    /// an Observation [Rinex].
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C S1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682          40.000
+    /// G07  20832394.682          35.000
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832395.682          41.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let s1c = Observable::from_str("S1C").unwrap();
+    ///
+    /// let weak = rinex
+    ///     .observation_iter()
+    ///     .filter(|(_, _, observable, value, _, _)| **observable == s1c && *value < 40.0)
+    ///     .map(|(_, sv, _, _, _, _)| sv.to_string())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(weak, vec!["G07"]);
+    /// ```
+    pub fn observation_iter(
+        &self,
+    ) -> Box<dyn Iterator<Item = (Epoch, SV, &Observable, f64, Option<LliFlags>, Option<SNR>)> + '_>
+    {
+        Box::new(
+            self.record
+                .as_obs()
+                .into_iter()
+                .flat_map(|record| record.iter())
+                .flat_map(|(key, obs)| {
+                    obs.signals.iter().map(|sig| {
+                        (
+                            key.epoch,
+                            sig.sv,
+                            &sig.observable,
+                            sig.value,
+                            sig.lli,
+                            sig.snr,
+                        )
+                    })
+                }),
+        )
+    }
+
+    /// Returns [Event]s Iterator: event epochs (flag 2 to 5),
+    /// with their special records.
+    pub fn events_iter(&self) -> Box<dyn Iterator<Item = (&ObsKey, &Event)> + '_> {
+        Box::new(
+            self.record
+                .as_obs()
+                .into_iter()
+                .flat_map(|record| record.iter())
+                .filter_map(|(key, obs)| Some((key, obs.event.as_ref()?))),
+        )
+    }
+
+    /// Returns [Receiver] changes Iterator: receivers (or serial numbers)
+    /// declared by event epochs, in chronological order.
+    pub fn receiver_changes_iter(&self) -> Box<dyn Iterator<Item = (Epoch, Receiver)> + '_> {
+        Box::new(
+            self.events_iter()
+                .filter_map(|(key, event)| Some((key.epoch, event.receiver()?))),
+        )
+    }
+
+    /// Returns receiver [Antenna] changes Iterator: antennas (or serial numbers)
+    /// declared by event epochs, in chronological order.
+    pub fn antenna_changes_iter(&self) -> Box<dyn Iterator<Item = (Epoch, Antenna)> + '_> {
+        Box::new(
+            self.events_iter()
+                .filter_map(|(key, event)| Some((key.epoch, event.antenna()?))),
+        )
+    }
+
     /// Mutable [SignalObservation]s Iterator.
     pub fn signal_observations_iter_mut(
         &mut self,
@@ -152,41 +302,242 @@ impl Rinex {
         }))
     }
 
-    //  /// Applies given AND mask in place, to all observations.
-    // /// This has no effect on non observation records.
-    // /// This also drops observations that did not come with an LLI flag.
-    // /// Only relevant on OBS RINEX.
-    // pub fn lli_and_mask_mut(&mut self, mask: observation::LliFlags) {
-    //     if !self.is_observation_rinex() {
-    //         return; // nothing to browse
-    //     }
-    //     let record = self.record.as_mut_obs().unwrap();
-    //     for (_e, (_clk, sv)) in record.iter_mut() {
-    //         for (_sv, obs) in sv.iter_mut() {
-    //             obs.retain(|_, data| {
-    //                 if let Some(lli) = data.lli {
-    //                     lli.intersects(mask)
-    //                 } else {
-    //                     false // drops data with no LLI attached
-    //                 }
-    //             })
-    //         }
-    //     }
-    // }
+    /// Returns [Observable]s Iterator, actually observed for this [Constellation].
+    /// All SBAS systems are gathered when [Constellation::SBAS] is requested,
+    /// similarly to the header description.
+    pub fn constellation_observables_iter(
+        &self,
+        constellation: Constellation,
+    ) -> Box<dyn Iterator<Item = &Observable> + '_> {
+        Box::new(
+            self.signal_observations_iter()
+                .filter_map(move |(_, sig)| {
+                    let matches = if constellation == Constellation::SBAS {
+                        sig.sv.constellation.is_sbas()
+                    } else {
+                        sig.sv.constellation == constellation
+                    };
+                    if matches {
+                        Some(&sig.observable)
+                    } else {
+                        None
+                    }
+                })
+                .unique()
+                .sorted(),
+        )
+    }
 
-    // /// Removes all observations where receiver phase lock was lost.
-    // /// This is only relevant on OBS RINEX.
-    // pub fn lock_loss_filter_mut(&mut self) {
-    //     self.lli_and_mask_mut(observation::LliFlags::LOCK_LOSS)
-    // }
+    /// Returns [Observable]s declared in the header for this [Constellation].
+    fn constellation_declared_observables(&self, constellation: Constellation) -> &[Observable] {
+        let constellation = if constellation.is_sbas() {
+            Constellation::SBAS
+        } else {
+            constellation
+        };
+        self.header
+            .obs
+            .as_ref()
+            .and_then(|obs| obs.codes.get(&constellation))
+            .map(|codes| codes.as_slice())
+            .unwrap_or_default()
+    }
 
-    // /// [`Rinex::lli_and_mask`] immutable implementation.
-    // /// Only relevant on OBS RINEX.
-    // pub fn lli_and_mask(&self, mask: observation::LliFlags) -> Self {
-    //     let mut c = self.clone();
-    //     c.lli_and_mask_mut(mask);
-    //     c
-    // }
+    /// Returns [Observable]s declared in the header for this [Constellation],
+    /// that were never observed in the record.
+    pub fn constellation_missing_observables(
+        &self,
+        constellation: Constellation,
+    ) -> Vec<Observable> {
+        let observed = self
+            .constellation_observables_iter(constellation)
+            .collect::<Vec<_>>();
+
+        self.constellation_declared_observables(constellation)
+            .iter()
+            .filter(|observable| !observed.contains(observable))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns [Observable]s observed for this [Constellation],
+    /// that the header does not declare. This typically happens when the
+    /// record was modified, or for [Constellation]s that the header omits.
+    pub fn constellation_undeclared_observables(
+        &self,
+        constellation: Constellation,
+    ) -> Vec<Observable> {
+        let declared = self.constellation_declared_observables(constellation);
+
+        self.constellation_observables_iter(constellation)
+            .filter(|observable| !declared.contains(observable))
+            .cloned()
+            .collect()
+    }
+
+    /// Retains [SignalObservation]s whose LLI flag does not intersect this [LliFlags] mask.
+    /// Observations that come without LLI flag are preserved. Epochs that no longer
+    /// contain any [SignalObservation] are dropped, except event epochs.
+    pub fn retain_lli_unset_mut(&mut self, mask: LliFlags) {
+        if let Some(record) = self.record.as_mut_obs() {
+            record.retain(|_, obs| {
+                if obs.event.is_some() {
+                    return true;
+                }
+                obs.signals
+                    .retain(|sig| !sig.lli.map(|lli| lli.intersects(mask)).unwrap_or(false));
+                !obs.signals.is_empty()
+            });
+        }
+    }
+
+    /// Copies and returns [Rinex] with [SignalObservation]s matching this [LliFlags] mask
+    /// dropped. See [Self::retain_lli_unset_mut].
+    pub fn retain_lli_unset(&self, mask: LliFlags) -> Self {
+        let mut s = self.clone();
+        s.retain_lli_unset_mut(mask);
+        s
+    }
+
+    /// Retains [SignalObservation]s that were not flagged for loss of lock or
+    /// half cycle ambiguity, which is typically required prior phase processing.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 L1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  3
+    /// G01 109474991.854
+    /// G02 109474991.8541
+    /// G03 109474991.8544
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // G02 lost lock, G03 is BOC tracked
+    /// let rinex = rinex.retain_unflagged();
+    /// let sv = rinex.sv_iter().collect::<Vec<_>>();
+    /// assert_eq!(sv.len(), 2);
+    /// assert!(sv.iter().all(|sv| sv.prn != 2));
+    /// ```
+    pub fn retain_unflagged_mut(&mut self) {
+        self.retain_lli_unset_mut(LliFlags::LOCK_LOSS | LliFlags::HALF_CYCLE_SLIP);
+    }
+
+    /// Copies and returns [Rinex] with flagged [SignalObservation]s dropped.
+    /// See [Self::retain_unflagged_mut].
+    pub fn retain_unflagged(&self) -> Self {
+        let mut s = self.clone();
+        s.retain_unflagged_mut();
+        s
+    }
+
+    /// Returns the C/N0 estimate (in dB-Hz) of this [SignalObservation] within these
+    /// [Observations]: the S observation of the same signal (or same carrier), otherwise
+    /// the upper bound of its [SNR] interval.
+    pub(crate) fn signal_dbhz(&self, obs: &Observations, sig: &SignalObservation) -> Option<f64> {
+        if sig.observable.is_ssi_observable() {
+            return Some(sig.value);
+        }
+
+        let carrier = self.header.sv_carrier(sig.sv, &sig.observable).ok();
+        let code = sig.observable.code();
+
+        let ssi = obs
+            .signals
+            .iter()
+            .filter(|ssi| ssi.sv == sig.sv && ssi.observable.is_ssi_observable())
+            .filter(|ssi| match (&code, ssi.observable.code()) {
+                (Some(code), Some(ssi_code)) => *code == ssi_code,
+                _ => {
+                    carrier.is_some()
+                        && self.header.sv_carrier(ssi.sv, &ssi.observable).ok() == carrier
+                },
+            })
+            .map(|ssi| ssi.value)
+            .next();
+
+        ssi.or(sig.snr.and_then(|snr| Some(snr.dbhz_range()?.end)))
+    }
+
+    /// Returns the [SignalObservation]s whose C/N0 lies below this threshold (in dB-Hz).
+    /// C/N0 is obtained from the S observation of the same signal (or the same carrier,
+    /// when signal codes are not specified), otherwise from the SSI flag, in which case
+    /// this observation is reported only if its entire [SNR] interval lies below the threshold.
+    /// Observations that come without any C/N0 information are never reported.
+    pub fn snr_below(&self, threshold_dbhz: f64) -> Vec<(ObsKey, &SignalObservation)> {
+        self.record
+            .as_obs()
+            .into_iter()
+            .flat_map(|record| record.iter())
+            .flat_map(|(k, obs)| {
+                obs.signals
+                    .iter()
+                    .filter(|sig| {
+                        self.signal_dbhz(obs, sig)
+                            .map(|dbhz| dbhz < threshold_dbhz)
+                            .unwrap_or(false)
+                    })
+                    .map(|sig| (*k, sig))
+            })
+            .collect()
+    }
+
+    /// Removes [SignalObservation]s whose C/N0 lies below this threshold (in dB-Hz),
+    /// see [Self::snr_below]. Epochs that no longer contain any [SignalObservation]
+    /// are dropped, except event epochs.
+    pub fn mask_snr_below_mut(&mut self, threshold_dbhz: f64) {
+        let masked = self
+            .snr_below(threshold_dbhz)
+            .into_iter()
+            .map(|(k, sig)| (k, sig.sv, sig.observable.clone()))
+            .collect::<HashSet<_>>();
+
+        if let Some(record) = self.record.as_mut_obs() {
+            record.retain(|k, obs| {
+                if obs.event.is_some() {
+                    return true;
+                }
+                obs.signals
+                    .retain(|sig| !masked.contains(&(*k, sig.sv, sig.observable.clone())));
+                !obs.signals.is_empty()
+            });
+        }
+    }
+
+    /// Copies and returns [Rinex] where [SignalObservation]s whose C/N0 lies
+    /// below this threshold (in dB-Hz) were removed, which is a common
+    /// preprocessing step prior positioning. See [Self::snr_below].
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    3 C1C L1C S1C                                          SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  3
+    /// G01  20832393.682   109474991.854          45.000
+    /// G02  20832393.682   109474991.854          25.000
+    /// G03  20832393.682 2 109474991.854 7
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // G02 is weak, G03 SSI (12-17 dB-Hz) is too weak
+    /// let masked = rinex.mask_snr_below(30.0);
+    /// let sv = masked.sv_iter().collect::<Vec<_>>();
+    /// assert_eq!(sv.len(), 2);
+    /// assert!(sv.iter().all(|sv| sv.prn != 2));
+    ///
+    /// assert_eq!(masked.signal_observations_iter().count(), 4);
+    /// ```
+    pub fn mask_snr_below(&self, threshold_dbhz: f64) -> Self {
+        let mut s = self.clone();
+        s.mask_snr_below_mut(threshold_dbhz);
+        s
+    }
 
     // /// Aligns Phase observations at origin
     // pub fn observation_phase_align_origin_mut(&mut self) {
@@ -247,3 +598,108 @@ impl Rinex {
     //     s
     // }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Constellation, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn mixed_constellations() {
+        let g01 = SV::from_str("G01").unwrap();
+        let e05 = SV::from_str("E05").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let c5q = Observable::from_str("C5Q").unwrap();
+
+        let mut rinex = Rinex::basic_obs();
+
+        // header only declares GPS
+        rinex
+            .header
+            .obs
+            .as_mut()
+            .unwrap()
+            .codes
+            .insert(Constellation::GPS, vec![c1c.clone(), l1c.clone()]);
+
+        let mut obs = Observations::default();
+        obs.signals
+            .push(SignalObservation::new(g01, c1c.clone(), 1.0));
+        obs.signals
+            .push(SignalObservation::new(e05, c5q.clone(), 2.0));
+
+        rinex.record.as_mut_obs().unwrap().insert(
+            ObsKey {
+                epoch: Epoch::default(),
+                flag: Default::default(),
+            },
+            obs,
+        );
+
+        assert_eq!(
+            rinex.constellations(),
+            vec![Constellation::GPS, Constellation::Galileo]
+        );
+
+        assert_eq!(
+            rinex
+                .constellation_observables_iter(Constellation::GPS)
+                .collect::<Vec<_>>(),
+            vec![&c1c]
+        );
+
+        assert_eq!(
+            rinex.constellation_missing_observables(Constellation::GPS),
+            vec![l1c]
+        );
+        assert!(rinex
+            .constellation_undeclared_observables(Constellation::GPS)
+            .is_empty());
+
+        assert!(rinex
+            .constellation_missing_observables(Constellation::Galileo)
+            .is_empty());
+        assert_eq!(
+            rinex.constellation_undeclared_observables(Constellation::Galileo),
+            vec![c5q]
+        );
+    }
+
+    #[test]
+    fn snr_masking_v2() {
+        let content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     4    C1    L1    L2    S1                              # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  2G01G02
+  20832393.682   109474991.854    85305012.123          45.000
+  20832393.682   109474991.854    85305012.123          20.000
+";
+        let rinex = Rinex::parse(&mut std::io::BufReader::new(content.as_bytes())).unwrap();
+
+        let g02 = SV::from_str("G02").unwrap();
+        let l2 = Observable::from_str("L2").unwrap();
+
+        // S1 applies to L1 carrier only
+        let weak = rinex.snr_below(30.0);
+        assert_eq!(weak.len(), 3);
+        assert!(weak.iter().all(|(_, sig)| sig.sv == g02));
+
+        let masked = rinex.mask_snr_below(30.0);
+        let g02_signals = masked
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.sv == g02)
+            .map(|(_, sig)| sig.observable.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(g02_signals, vec![l2]);
+
+        // nothing below
+        let masked = rinex.mask_snr_below(10.0);
+        assert_eq!(masked.record, rinex.record);
+    }
+}
@@ -0,0 +1,236 @@
+//! Code multipath (MP) assessment
+use crate::{
+    observation::{LliFlags, MultipathKey},
+    prelude::{Epoch, Observable, Rinex, SV},
+};
+
+use std::collections::{BTreeMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [MultipathStatistics] summarizes the code multipath of one signal,
+/// once the average of each arc was removed.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MultipathStatistics {
+    /// Number of samples
+    pub samples: usize,
+    /// Number of arcs
+    pub arcs: usize,
+    /// Root mean square of the multipath, in meters
+    pub rms_m: f64,
+}
+
+/// Mean removed code multipath arcs, per (SV, signal, rhs)
+type MultipathArcs = BTreeMap<(SV, Observable, Observable), Vec<Vec<(Epoch, f64)>>>;
+
+impl Rinex {
+    /// Gathers [Self::signals_multipath] into continuous arcs, interrupted
+    /// by data gaps and phase lock losses, then removes the mean of each arc.
+    fn code_multipath_arcs(&self) -> MultipathArcs {
+        let mut arcs = MultipathArcs::new();
+
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return arcs, // can't proceed without sampling interval guess.
+        };
+
+        let lock_losses = self
+            .signal_observations_iter()
+            .filter_map(|(k, sig)| {
+                if sig.lli?.intersects(LliFlags::LOCK_LOSS) {
+                    Some((k.epoch, sig.sv, sig.observable.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect::<HashSet<_>>();
+
+        let multipath = self
+            .signals_multipath()
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        for (k, value) in multipath {
+            let lock_loss = lock_losses.contains(&(k.epoch, k.sv, k.signal.clone()))
+                || lock_losses.contains(&(k.epoch, k.sv, k.rhs.clone()));
+
+            let arcs = arcs.entry((k.sv, k.signal, k.rhs)).or_default();
+
+            let new_arc = lock_loss
+                || arcs
+                    .last()
+                    .and_then(|arc| arc.last())
+                    .map(|(t, _)| k.epoch - *t > dominant_sampling)
+                    .unwrap_or(true);
+
+            if new_arc {
+                arcs.push(vec![(k.epoch, value)]);
+            } else if let Some(arc) = arcs.last_mut() {
+                arc.push((k.epoch, value));
+            }
+        }
+
+        for arc in arcs.values_mut().flatten() {
+            let mean = arc.iter().map(|(_, value)| value).sum::<f64>() / arc.len() as f64;
+            for (_, value) in arc.iter_mut() {
+                *value -= mean;
+            }
+        }
+
+        arcs
+    }
+
+    /// Returns the code multipath (MP) time series, per [SV] and frequency,
+    /// like "MP1" and "MP2" of teqc: [Self::signals_multipath] combination,
+    /// of which the average of each arc is removed, so the constant
+    /// ambiguity and hardware bias terms cancel out.
+    /// Arcs are interrupted by data gaps and phase lock losses (LLI).
+    pub fn code_multipath(&self) -> BTreeMap<MultipathKey, f64> {
+        let mut ret = BTreeMap::new();
+        for ((sv, signal, rhs), arcs) in self.code_multipath_arcs() {
+            for (epoch, value) in arcs.into_iter().flatten() {
+                let key = MultipathKey {
+                    epoch,
+                    sv,
+                    signal: signal.clone(),
+                    rhs: rhs.clone(),
+                };
+                ret.insert(key, value);
+            }
+        }
+        ret
+    }
+
+    /// Returns the [MultipathStatistics] of each signal, indexed by [SV],
+    /// signal and comparison phase [Observable]s, from [Self::code_multipath].
+    /// Arcs of a single sample carry no information and are not accounted for.
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex, SV};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    3 C1C L1C L2W                                          SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20000000.000   105100000.000    81900000.000
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20000001.000   105100000.000    81900000.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let l1c = Observable::from_str("L1C").unwrap();
+    /// let l2w = Observable::from_str("L2W").unwrap();
+    ///
+    /// let stats = rinex.code_multipath_statistics();
+    /// let mp1 = stats.get(&(g01, l1c, l2w)).unwrap();
+    ///
+    /// assert_eq!(mp1.samples, 2);
+    /// assert_eq!(mp1.arcs, 1);
+    /// assert!((mp1.rms_m - 0.5).abs() < 1.0E-6);
+    /// ```
+    pub fn code_multipath_statistics(
+        &self,
+    ) -> BTreeMap<(SV, Observable, Observable), MultipathStatistics> {
+        let mut ret = BTreeMap::new();
+
+        for (signal, arcs) in self.code_multipath_arcs() {
+            let mut stats = MultipathStatistics::default();
+            let mut sum_sq = 0.0;
+
+            for arc in arcs.iter().filter(|arc| arc.len() > 1) {
+                stats.arcs += 1;
+                stats.samples += arc.len();
+                sum_sq += arc.iter().map(|(_, value)| value.powi(2)).sum::<f64>();
+            }
+
+            if stats.samples > 0 {
+                stats.rms_m = (sum_sq / stats.samples as f64).sqrt();
+                ret.insert(signal, stats);
+            }
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        carrier::Carrier,
+        observation::{LliFlags, ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn code_multipath_arcs() {
+        let g01 = SV::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+
+        let (w1, w2) = (Carrier::L1.wavelength(), Carrier::L2.wavelength());
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(Duration::from_seconds(30.0));
+
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        // 1m sine multipath on C1C; L2 ambiguity changes after lock loss
+        for i in 0..8 {
+            let range_m = 20_000_000.0 + 300.0 * i as f64;
+            let mp = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let n2 = if i < 4 { 1000.0 } else { 5000.0 };
+
+            let mut obs = Observations::default();
+            obs.signals.push(SignalObservation::new(
+                g01,
+                Observable::from_str("C1C").unwrap(),
+                range_m + mp,
+            ));
+            obs.signals.push(SignalObservation::new(
+                g01,
+                Observable::from_str("L1C").unwrap(),
+                range_m / w1 + 1234.0,
+            ));
+
+            let mut l2 = SignalObservation::new(
+                g01,
+                Observable::from_str("L2W").unwrap(),
+                range_m / w2 + n2,
+            );
+            if i == 4 {
+                l2.lli = Some(LliFlags::LOCK_LOSS);
+            }
+            obs.signals.push(l2);
+
+            let key = ObsKey {
+                epoch: t0 + i as f64 * Duration::from_seconds(30.0),
+                flag: Default::default(),
+            };
+            record.insert(key, obs);
+        }
+
+        let mp = rinex.code_multipath();
+        assert_eq!(mp.len(), 8);
+
+        for (i, (k, value)) in mp.iter().enumerate() {
+            assert_eq!(k.signal, Observable::from_str("L1C").unwrap());
+            assert_eq!(k.rhs, Observable::from_str("L2W").unwrap());
+
+            let expected = if i % 2 == 0 { 1.0 } else { -1.0 };
+            assert!((value - expected).abs() < 1.0E-3, "#{}: {}", i, value);
+        }
+
+        let stats = rinex.code_multipath_statistics();
+        assert_eq!(stats.len(), 1);
+
+        let (_, stats) = stats.iter().next().unwrap();
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.arcs, 2);
+        assert!((stats.rms_m - 1.0).abs() < 1.0E-3);
+    }
+}
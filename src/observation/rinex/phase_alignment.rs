@@ -0,0 +1,227 @@
+//! Phase to code alignment
+use crate::{
+    observation::LliFlags,
+    prelude::{Epoch, Observable, Rinex, SV},
+};
+
+use std::collections::HashMap;
+
+/// Integer cycle offset of the current phase arc
+struct PhaseArc {
+    t: Epoch,
+    offset: f64,
+}
+
+impl Rinex {
+    /// Aligns all phase observations to the pseudo range of the same carrier, in place.
+    /// See [Self::align_phase].
+    pub fn align_phase_mut(&mut self) {
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return, // can't proceed without sampling interval guess.
+        };
+
+        let header = &self.header;
+
+        let record = match self.record.as_mut_obs() {
+            Some(record) => record,
+            None => return,
+        };
+
+        let mut arcs = HashMap::<(SV, Observable), PhaseArc>::new();
+
+        for (k, v) in record.iter_mut() {
+            let codes = v
+                .signals
+                .iter()
+                .filter(|sig| sig.observable.is_pseudo_range_observable())
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for phase in v.signals.iter_mut() {
+                if !phase.observable.is_phase_range_observable() {
+                    continue;
+                }
+
+                let carrier = match header.sv_carrier(phase.sv, &phase.observable) {
+                    Ok(carrier) => carrier,
+                    Err(_) => continue,
+                };
+
+                let arc = (phase.sv, phase.observable.clone());
+
+                let lock_loss = phase
+                    .lli
+                    .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                    .unwrap_or(false);
+
+                let continuous = match arcs.get(&arc) {
+                    Some(state) => !lock_loss && k.epoch - state.t <= dominant_sampling,
+                    None => false,
+                };
+
+                if !continuous {
+                    // new arc: same signal is preferred, otherwise same carrier
+                    let same_carrier = codes
+                        .iter()
+                        .filter(|code| {
+                            code.sv == phase.sv
+                                && header
+                                    .sv_carrier(code.sv, &code.observable)
+                                    .map(|c| c == carrier)
+                                    .unwrap_or(false)
+                        })
+                        .collect::<Vec<_>>();
+
+                    let code = same_carrier
+                        .iter()
+                        .find(|code| code.observable.code() == phase.observable.code())
+                        .or(same_carrier.first());
+
+                    match code {
+                        Some(code) => {
+                            let offset = (phase.value - code.value / carrier.wavelength()).round();
+                            arcs.insert(arc.clone(), PhaseArc { t: k.epoch, offset });
+                        },
+                        None => {
+                            // can't align without code
+                            arcs.remove(&arc);
+                            continue;
+                        },
+                    }
+                }
+
+                if let Some(state) = arcs.get_mut(&arc) {
+                    state.t = k.epoch;
+                    phase.value -= state.offset;
+                }
+            }
+        }
+
+        self.header
+            .push_processing_log(None, "PHASE ALIGNMENT", "code");
+    }
+
+    /// Copies and returns a [Rinex] where the arbitrary integer number of cycles
+    /// of each phase arc was removed, so phase observations are aligned to the pseudo range
+    /// of the same carrier (same signal is preferred) at the start of each arc.
+    /// Aligned phase observations are easier to plot and compare to code, for multipath analysis.
+    /// Arcs are interrupted by data gaps and phase lock losses (LLI): a new offset
+    /// is then determined, once a pseudo range is available. Phase observations
+    /// that could not be aligned are preserved as is.
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C L1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20000000.000   105100000.000
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20000100.000   105100525.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let aligned = rinex.align_phase();
+    ///
+    /// let l1c = Observable::from_str("L1C").unwrap();
+    ///
+    /// let phase = aligned
+    ///     .signal_observations_iter()
+    ///     .filter(|(_, sig)| sig.observable == l1c)
+    ///     .map(|(_, sig)| sig.value)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// // about 20000 km, once converted to meters
+    /// assert!((phase[0] * 0.19029367 - 20_000_000.0).abs() < 1.0);
+    ///
+    /// // integer offset is preserved along the arc
+    /// assert_eq!(phase[1] - phase[0], 525.0);
+    /// ```
+    pub fn align_phase(&self) -> Self {
+        let mut s = self.clone();
+        s.align_phase_mut();
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        carrier::Carrier,
+        observation::{LliFlags, ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn phase_alignment_arcs() {
+        let g01 = SV::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+
+        let (c1c, l1c) = (
+            Observable::from_str("C1C").unwrap(),
+            Observable::from_str("L1C").unwrap(),
+        );
+
+        let w1 = Carrier::L1.wavelength();
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(Duration::from_seconds(30.0));
+
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        for i in 0..10 {
+            // data gap
+            if i == 7 {
+                continue;
+            }
+
+            let range_m = 20_000_000.0 + 100.0 * i as f64;
+            let n1 = if i < 4 { 123_456.0 } else { -4321.0 };
+
+            let mut obs = Observations::default();
+            obs.signals
+                .push(SignalObservation::new(g01, c1c.clone(), range_m));
+
+            let mut phase = SignalObservation::new(g01, l1c.clone(), range_m / w1 + n1);
+            if i == 4 {
+                phase.lli = Some(LliFlags::LOCK_LOSS);
+            }
+            obs.signals.push(phase);
+
+            let key = ObsKey {
+                epoch: t0 + i as f64 * Duration::from_seconds(30.0),
+                flag: Default::default(),
+            };
+            record.insert(key, obs);
+        }
+
+        let aligned = rinex.align_phase();
+
+        let raw = rinex
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.observable == l1c)
+            .map(|(_, sig)| sig.value)
+            .collect::<Vec<_>>();
+
+        let aligned = aligned
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.observable == l1c)
+            .map(|(_, sig)| sig.value)
+            .collect::<Vec<_>>();
+
+        assert_eq!(aligned.len(), 9);
+
+        for (i, (raw, aligned)) in raw.iter().zip(aligned.iter()).enumerate() {
+            // integer offsets
+            assert_eq!((raw - aligned).fract(), 0.0, "#{}", i);
+
+            let i = if i >= 7 { i + 1 } else { i };
+            let range_m = 20_000_000.0 + 100.0 * i as f64;
+            assert!((aligned * w1 - range_m).abs() < w1, "#{}", i);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! # OF SATELLITES and PRN / # OF OBS generation
+use crate::prelude::{Header, Rinex, SV};
+
+use std::collections::BTreeMap;
+
+impl Rinex {
+    /// Returns the number of observations of each [SV] in this Observation [Rinex],
+    /// in the order of the observables declared in the [Header], as described
+    /// by the optional "PRN / # OF OBS" header field. Null values are not counted.
+    /// ```
+    /// use rinex::prelude::{Rinex, SV};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C L1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682   109474991.854
+    /// G07  20832394.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832395.682   109474992.854
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let counts = rinex.prn_obs_counts();
+    ///
+    /// assert_eq!(counts[&SV::from_str("G01").unwrap()], vec![2, 2]);
+    /// assert_eq!(counts[&SV::from_str("G07").unwrap()], vec![1, 0]);
+    /// ```
+    pub fn prn_obs_counts(&self) -> BTreeMap<SV, Vec<usize>> {
+        let mut counts = BTreeMap::<SV, Vec<usize>>::new();
+
+        let (obs, record) = match (&self.header.obs, self.record.as_obs()) {
+            (Some(obs), Some(record)) => (obs, record),
+            _ => return counts,
+        };
+
+        for signal in record
+            .values()
+            .flat_map(|observations| observations.signals.iter())
+        {
+            let observables = match obs.sv_observables(&signal.sv) {
+                Some(observables) => observables,
+                None => continue,
+            };
+
+            if let Some(index) = observables.iter().position(|ob| *ob == signal.observable) {
+                let sv_counts = counts
+                    .entry(signal.sv)
+                    .or_insert_with(|| vec![0; observables.len()]);
+                sv_counts[index] += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Returns a copy of this [Header], with "# OF SATELLITES" deduced from the record,
+    /// and "PRN / # OF OBS" as well, when requested.
+    pub(crate) fn header_with_sv_counts(&self, header: &Header, prn_obs_counts: bool) -> Header {
+        let mut header = header.clone();
+        if let Some(obs) = header.obs.as_mut() {
+            obs.num_sv = Some(self.sv_count());
+            if prn_obs_counts {
+                obs.prn_obs_counts = self.prn_obs_counts();
+            }
+        }
+        header
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        observation::{EpochFlag, ObsKey, Observations, SignalObservation},
+        prelude::{Constellation, Epoch, FormattingOptions, Observable, Rinex, SV},
+    };
+    use std::io::BufWriter;
+    use std::str::FromStr;
+
+    #[test]
+    fn prn_obs_counts_formatting() {
+        let g01 = SV::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+
+        let codes = [
+            "C1C", "L1C", "D1C", "S1C", "C2W", "L2W", "D2W", "S2W", "C5Q", "L5Q",
+        ]
+        .iter()
+        .map(|code| Observable::from_str(code).unwrap())
+        .collect::<Vec<_>>();
+
+        let mut rinex = Rinex::basic_obs();
+        let obs = rinex.header.obs.as_mut().unwrap();
+        obs.codes.insert(Constellation::GPS, codes.clone());
+
+        let mut observations = Observations::default();
+        for code in codes.iter().skip(1) {
+            observations
+                .signals
+                .push(SignalObservation::new(g01, code.clone(), 20832393.682));
+        }
+
+        rinex.record.as_mut_obs().unwrap().insert(
+            ObsKey {
+                epoch: t0,
+                flag: EpochFlag::Ok,
+            },
+            observations,
+        );
+
+        // not emitted by default, unlike # OF SATELLITES
+        let mut w = BufWriter::new(Vec::new());
+        rinex.format(&mut w).unwrap();
+        let content = String::from_utf8(w.into_inner().unwrap()).unwrap();
+        assert!(!content.contains("PRN / # OF OBS"));
+        assert!(content.contains(
+            "     1                                                      # OF SATELLITES\n"
+        ));
+
+        let opts = FormattingOptions::default().with_prn_obs_counts();
+        let mut w = BufWriter::new(Vec::new());
+        rinex.format_with_options(&mut w, &opts).unwrap();
+        let content = String::from_utf8(w.into_inner().unwrap()).unwrap();
+
+        // C1C is missing, 10th observable is continued on next line
+        assert!(content.contains(
+            "   G01     0     1     1     1     1     1     1     1     1PRN / # OF OBS\n           1                                                PRN / # OF OBS\n"
+        ));
+    }
+}
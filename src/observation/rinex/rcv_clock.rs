@@ -0,0 +1,199 @@
+//! Receiver clock offset compensation
+use crate::{
+    constants::SPEED_OF_LIGHT_M_S,
+    prelude::{Duration, Epoch, Rinex},
+};
+
+impl Rinex {
+    /// Returns receiver clock offset to the GNSS timescale, in seconds,
+    /// reported for this [Epoch], if any.
+    /// ```
+    /// use rinex::prelude::{Epoch, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1       0.000123456789
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+    /// assert_eq!(rinex.clock_offset_s(t0), Some(0.000123456789));
+    ///
+    /// let t1 = Epoch::from_str("2020-01-01T00:00:30 GPST").unwrap();
+    /// assert_eq!(rinex.clock_offset_s(t1), None);
+    /// ```
+    pub fn clock_offset_s(&self, epoch: Epoch) -> Option<f64> {
+        self.clock_observations_iter()
+            .find(|(k, _)| k.epoch == epoch)
+            .map(|(_, clock)| clock.offset_s)
+    }
+
+    /// Returns receiver clock offsets Iterator, in seconds, per [Epoch]
+    pub fn clock_offsets_iter(&self) -> Box<dyn Iterator<Item = (Epoch, f64)> + '_> {
+        Box::new(
+            self.clock_observations_iter()
+                .map(|(k, clock)| (k.epoch, clock.offset_s)),
+        )
+    }
+
+    /// Applies the receiver clock offsets to this Observation [Rinex],
+    /// as if the receiver had steered its clock to the GNSS timescale:
+    /// epochs, pseudo ranges and phase observations of each epoch reporting
+    /// a clock offset are corrected and "RCV CLOCK OFFS APPL" is set.
+    /// Clock offsets are preserved, so this operation can be reverted with
+    /// [Self::remove_clock_offsets_mut]. Nothing happens when offsets
+    /// were already applied.
+    pub fn apply_clock_offsets_mut(&mut self) {
+        self.compensate_clock_offsets(true);
+    }
+
+    /// Copies and returns [Rinex] with receiver clock offsets applied.
+    /// See [Self::apply_clock_offsets_mut].
+    pub fn apply_clock_offsets(&self) -> Self {
+        let mut s = self.clone();
+        s.apply_clock_offsets_mut();
+        s
+    }
+
+    /// Removes the receiver clock offsets previously applied to this Observation [Rinex]
+    /// (either by the receiver or [Self::apply_clock_offsets_mut]), restoring raw
+    /// receiver time tags and observations, and clears "RCV CLOCK OFFS APPL".
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C L1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1       0.001000000000
+    /// G01  20832393.682   109474991.854
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let applied = rinex.apply_clock_offsets();
+    /// assert!(applied.header.obs.as_ref().unwrap().clock_offset_applied);
+    /// assert_ne!(applied.first_epoch(), rinex.first_epoch());
+    ///
+    /// let raw = applied.remove_clock_offsets();
+    /// assert_eq!(raw.first_epoch(), rinex.first_epoch());
+    /// ```
+    pub fn remove_clock_offsets_mut(&mut self) {
+        self.compensate_clock_offsets(false);
+    }
+
+    /// Copies and returns [Rinex] with receiver clock offsets removed.
+    /// See [Self::remove_clock_offsets_mut].
+    pub fn remove_clock_offsets(&self) -> Self {
+        let mut s = self.clone();
+        s.remove_clock_offsets_mut();
+        s
+    }
+
+    /// Applies (or removes) the receiver clock offsets, when not yet applied (or applied).
+    fn compensate_clock_offsets(&mut self, apply: bool) {
+        let obs = match self.header.obs.as_mut() {
+            Some(obs) if obs.clock_offset_applied != apply => obs,
+            _ => return,
+        };
+
+        let record = match self.record.as_mut_obs() {
+            Some(record) => record,
+            None => return,
+        };
+
+        let sign = if apply { -1.0 } else { 1.0 };
+
+        *record = std::mem::take(record)
+            .into_iter()
+            .map(|(mut k, mut v)| {
+                if let Some(dt_s) = v.clock_offset_s() {
+                    let dt_s = sign * dt_s;
+                    k.epoch += Duration::from_seconds(dt_s);
+
+                    for signal in v.signals.iter_mut() {
+                        if signal.observable.is_pseudo_range_observable() {
+                            signal.value += dt_s * SPEED_OF_LIGHT_M_S;
+                        } else if signal.observable.is_phase_range_observable() {
+                            if let Ok(carrier) =
+                                signal.observable.to_carrier(signal.sv.constellation)
+                            {
+                                signal.value += dt_s * carrier.frequency();
+                            }
+                        }
+                    }
+                }
+                (k, v)
+            })
+            .collect();
+
+        obs.clock_offset_applied = apply;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Duration, Epoch, Rinex};
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    #[test]
+    fn clock_offsets_compensation() {
+        let content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     3    C1    L1    D1                                    # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  1G01                                   0.000100000
+  20832393.682   109474991.854        -812.123
+ 20  1  1  0  0 30.0000000  0  1G01
+  20832394.682   109474992.854        -812.123
+";
+
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+
+        assert_eq!(rinex.clock_offset_s(t0), Some(1.0E-4));
+        assert_eq!(rinex.clock_offsets_iter().count(), 1);
+
+        let applied = rinex.apply_clock_offsets();
+        assert!(applied.header.obs.as_ref().unwrap().clock_offset_applied);
+
+        let epochs = applied.epoch_iter().collect::<Vec<_>>();
+        assert_eq!(epochs[0], t0 - Duration::from_microseconds(100.0));
+        assert_eq!(epochs[1], rinex.epoch_iter().nth(1).unwrap());
+
+        let signals = applied
+            .signal_observations_iter()
+            .map(|(_, sig)| sig.value)
+            .collect::<Vec<_>>();
+
+        assert!((signals[0] - (20832393.682 - 29979.2458)).abs() < 1.0E-6);
+        assert!((signals[1] - (109474991.854 - 157542.0)).abs() < 1.0E-6);
+        assert_eq!(signals[2], -812.123);
+        assert_eq!(signals[3], 20832394.682);
+
+        // applying twice has no effect
+        assert_eq!(applied.apply_clock_offsets().record, applied.record);
+
+        let raw = applied.remove_clock_offsets();
+        assert!(!raw.header.obs.as_ref().unwrap().clock_offset_applied);
+        assert_eq!(
+            raw.epoch_iter().collect::<Vec<_>>(),
+            rinex.epoch_iter().collect::<Vec<_>>()
+        );
+
+        for ((_, lhs), (_, rhs)) in raw
+            .signal_observations_iter()
+            .zip(rinex.signal_observations_iter())
+        {
+            assert!((lhs.value - rhs.value).abs() < 1.0E-6);
+        }
+    }
+}
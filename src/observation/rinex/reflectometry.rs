@@ -0,0 +1,400 @@
+//! GNSS interferometric reflectometry (GNSS-IR) from SNR observations
+use crate::{
+    navigation::Ephemeris,
+    prelude::{nav::Almanac, Epoch, Observable, Rinex, SV},
+};
+
+use nalgebra::{Matrix3, Vector3};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    f64::consts::PI,
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [ReflectometryKey] is how we sort SNR spectral analysis, one per satellite pass.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ReflectometryKey {
+    /// [SV]: signal source
+    pub sv: SV,
+    /// SNR [Observable]
+    pub observable: Observable,
+    /// [Epoch] at which this pass starts (within elevation mask)
+    pub start: Epoch,
+}
+
+/// [ReflectometryPeak] describes the dominant Lomb-Scargle periodogram peak,
+/// of an SNR versus sine(elevation) series, for one satellite pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ReflectometryPeak {
+    /// [Epoch] at which this pass ends (within elevation mask)
+    pub end: Epoch,
+    /// Number of SNR samples in this pass
+    pub samples: usize,
+    /// True when the satellite is rising, false when setting
+    pub rising: bool,
+    /// Peak frequency, in cycles per unit of sine(elevation)
+    pub frequency: f64,
+    /// Peak amplitude (of the detrended, linear SNR), in V/V
+    pub amplitude: f64,
+    /// Reflector height (antenna phase center to reflecting surface), in meters.
+    /// Snow depth or water level variations are deduced from its evolution.
+    pub reflector_height_m: f64,
+}
+
+/// [ReflectometryOptions] to customize the SNR spectral analysis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectometryOptions {
+    /// Minimal elevation angle, in degrees
+    pub min_elevation_deg: f64,
+    /// Maximal elevation angle, in degrees
+    pub max_elevation_deg: f64,
+    /// Smallest reflector height we search for, in meters
+    pub min_height_m: f64,
+    /// Largest reflector height we search for, in meters
+    pub max_height_m: f64,
+    /// Reflector height resolution, in meters
+    pub height_step_m: f64,
+    /// Minimal number of samples for one pass to be analyzed
+    pub min_samples: usize,
+}
+
+impl Default for ReflectometryOptions {
+    /// Builds default [ReflectometryOptions], suited for
+    /// geodetic antennas located a few meters above ground.
+    fn default() -> Self {
+        Self {
+            min_elevation_deg: 5.0,
+            max_elevation_deg: 30.0,
+            min_height_m: 0.5,
+            max_height_m: 8.0,
+            height_step_m: 0.005,
+            min_samples: 20,
+        }
+    }
+}
+
+impl ReflectometryOptions {
+    /// Copies and returns [ReflectometryOptions] with desired elevation mask, in degrees
+    pub fn with_elevation_range_deg(&self, min: f64, max: f64) -> Self {
+        let mut s = *self;
+        s.min_elevation_deg = min;
+        s.max_elevation_deg = max;
+        s
+    }
+
+    /// Copies and returns [ReflectometryOptions] with desired reflector height
+    /// search range, in meters
+    pub fn with_height_range_m(&self, min: f64, max: f64) -> Self {
+        let mut s = *self;
+        s.min_height_m = min;
+        s.max_height_m = max;
+        s
+    }
+
+    /// Copies and returns [ReflectometryOptions] with desired reflector height
+    /// resolution, in meters
+    pub fn with_height_step_m(&self, step: f64) -> Self {
+        let mut s = *self;
+        s.height_step_m = step;
+        s
+    }
+
+    /// Copies and returns [ReflectometryOptions] with minimal number of samples per pass
+    pub fn with_min_samples(&self, min_samples: usize) -> Self {
+        let mut s = *self;
+        s.min_samples = min_samples;
+        s
+    }
+}
+
+/// Satellite pass being gathered
+struct Pass {
+    start: Epoch,
+    end: Epoch,
+    rising: Option<bool>,
+    elevation_deg: f64,
+    sin_e: Vec<f64>,
+    snr_dbhz: Vec<f64>,
+}
+
+impl Pass {
+    fn new(t: Epoch, elevation_deg: f64, snr_dbhz: f64) -> Self {
+        Self {
+            start: t,
+            end: t,
+            rising: None,
+            elevation_deg,
+            sin_e: vec![elevation_deg.to_radians().sin()],
+            snr_dbhz: vec![snr_dbhz],
+        }
+    }
+}
+
+/// Removes the direct signal contribution (low order polynomial in sine(elevation))
+/// from the linear SNR, leaving the multipath interference pattern.
+fn detrend(x: &[f64], y: &[f64]) -> Option<Vec<f64>> {
+    let mut ata = Matrix3::<f64>::zeros();
+    let mut atb = Vector3::<f64>::zeros();
+
+    for (x, y) in x.iter().zip(y.iter()) {
+        let row = Vector3::new(1.0, *x, x * x);
+        ata += row * row.transpose();
+        atb += row * *y;
+    }
+
+    let coefs = ata.try_inverse()? * atb;
+
+    Some(
+        x.iter()
+            .zip(y.iter())
+            .map(|(x, y)| y - (coefs[0] + coefs[1] * x + coefs[2] * x * x))
+            .collect(),
+    )
+}
+
+/// Lomb-Scargle power of unevenly sampled series `y(x)`, at frequency `f`.
+fn lomb_scargle_power(x: &[f64], y: &[f64], f: f64) -> f64 {
+    let w = 2.0 * PI * f;
+
+    let (s2, c2) = x.iter().fold((0.0, 0.0), |(s, c), x| {
+        (s + (2.0 * w * x).sin(), c + (2.0 * w * x).cos())
+    });
+
+    let tau = s2.atan2(c2) / 2.0 / w;
+
+    let (mut yc, mut ys, mut cc, mut ss) = (0.0, 0.0, 0.0, 0.0);
+
+    for (x, y) in x.iter().zip(y.iter()) {
+        let (sin, cos) = (w * (x - tau)).sin_cos();
+        yc += y * cos;
+        ys += y * sin;
+        cc += cos * cos;
+        ss += sin * sin;
+    }
+
+    if cc == 0.0 || ss == 0.0 {
+        return 0.0;
+    }
+
+    0.5 * (yc * yc / cc + ys * ys / ss)
+}
+
+/// Spectral analysis of one pass: returns (frequency, amplitude, reflector height)
+/// of the dominant periodogram peak.
+fn pass_periodogram(
+    sin_e: &[f64],
+    snr_dbhz: &[f64],
+    wavelength_m: f64,
+    opts: &ReflectometryOptions,
+) -> Option<(f64, f64, f64)> {
+    if sin_e.len() < opts.min_samples.max(3) || opts.height_step_m <= 0.0 {
+        return None;
+    }
+
+    // dB-Hz to linear (V/V) scale
+    let linear = snr_dbhz
+        .iter()
+        .map(|snr| 10.0_f64.powf(snr / 20.0))
+        .collect::<Vec<_>>();
+
+    let residuals = detrend(sin_e, &linear)?;
+
+    let n = residuals.len() as f64;
+    let mut peak = Option::<(f64, f64, f64)>::None;
+
+    let mut h = opts.min_height_m.max(opts.height_step_m);
+
+    while h <= opts.max_height_m {
+        // SNR ~ A cos(4 PI H / lambda sin(e) + phi)
+        let f = 2.0 * h / wavelength_m;
+        let power = lomb_scargle_power(sin_e, &residuals, f);
+
+        match peak {
+            Some((_, peak_power, _)) if peak_power >= power => {},
+            _ => peak = Some((f, power, h)),
+        }
+
+        h += opts.height_step_m;
+    }
+
+    let (f, power, h) = peak?;
+    Some((f, (4.0 * power / n).sqrt(), h))
+}
+
+impl Rinex {
+    /// GNSS interferometric reflectometry (GNSS-IR): Lomb-Scargle spectral analysis
+    /// of the SNR versus sine(elevation) series, for each satellite pass
+    /// and each SNR [Observable]. The direct signal contribution is removed by
+    /// a second order polynomial fit, prior to the analysis.
+    /// The dominant frequency is converted to a reflector height, from which
+    /// snow depth or water level is deduced.
+    /// Passes are split on data gaps and when the satellite changes direction
+    /// (rising / setting).
+    /// ## Inputs
+    /// - brdc: Navigation [Rinex] used to determine the elevation angles
+    /// - rx_position_km: static receiver position, expressed in km in ECEF
+    /// - almanac: [Almanac] context
+    /// - opts: [ReflectometryOptions]
+    /// ## Returns
+    /// - [ReflectometryPeak] per [ReflectometryKey]
+    pub fn snr_reflectometry(
+        &self,
+        brdc: &Rinex,
+        rx_position_km: (f64, f64, f64),
+        almanac: &Almanac,
+        opts: &ReflectometryOptions,
+    ) -> BTreeMap<ReflectometryKey, ReflectometryPeak> {
+        let mut ret = BTreeMap::new();
+
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return ret, // can't proceed without sampling interval guess.
+        };
+
+        let mut elevations = HashMap::<(SV, Epoch), Option<f64>>::new();
+        let mut passes = HashMap::<(SV, Observable), Pass>::new();
+
+        for (k, sig) in self.ssi_observations_iter() {
+            let elevation_deg = *elevations.entry((sig.sv, k.epoch)).or_insert_with(|| {
+                let orbit = brdc.sv_orbit(sig.sv, k.epoch)?;
+                let sv_position_km = (orbit.radius_km.x, orbit.radius_km.y, orbit.radius_km.z);
+                let azelrange = Ephemeris::elevation_azimuth_range(
+                    k.epoch,
+                    almanac,
+                    orbit.frame,
+                    sv_position_km,
+                    rx_position_km,
+                )
+                .ok()?;
+                Some(azelrange.elevation_deg)
+            });
+
+            let elevation_deg = match elevation_deg {
+                Some(elev) if elev >= opts.min_elevation_deg && elev <= opts.max_elevation_deg => {
+                    elev
+                },
+                _ => continue,
+            };
+
+            let pass_key = (sig.sv, sig.observable.clone());
+
+            let concluded = match passes.get_mut(&pass_key) {
+                Some(pass) => {
+                    let rising = elevation_deg > pass.elevation_deg;
+                    let continuous = k.epoch - pass.end <= dominant_sampling
+                        && pass.rising.map(|r| r == rising).unwrap_or(true);
+
+                    if continuous {
+                        pass.end = k.epoch;
+                        pass.rising = Some(rising);
+                        pass.elevation_deg = elevation_deg;
+                        pass.sin_e.push(elevation_deg.to_radians().sin());
+                        pass.snr_dbhz.push(sig.value);
+                        None
+                    } else {
+                        passes.insert(
+                            pass_key.clone(),
+                            Pass::new(k.epoch, elevation_deg, sig.value),
+                        )
+                    }
+                },
+                None => {
+                    passes.insert(
+                        pass_key.clone(),
+                        Pass::new(k.epoch, elevation_deg, sig.value),
+                    );
+                    None
+                },
+            };
+
+            if let Some(pass) = concluded {
+                self.reflectometry_conclude(pass_key, pass, opts, &mut ret);
+            }
+        }
+
+        for (pass_key, pass) in passes.into_iter() {
+            self.reflectometry_conclude(pass_key, pass, opts, &mut ret);
+        }
+
+        ret
+    }
+
+    /// Analyzes one concluded pass
+    fn reflectometry_conclude(
+        &self,
+        pass_key: (SV, Observable),
+        pass: Pass,
+        opts: &ReflectometryOptions,
+        ret: &mut BTreeMap<ReflectometryKey, ReflectometryPeak>,
+    ) {
+        let (sv, observable) = pass_key;
+
+        let wavelength_m = match self.header.sv_carrier(sv, &observable) {
+            Ok(carrier) => carrier.wavelength(),
+            Err(_) => return,
+        };
+
+        if let Some((frequency, amplitude, reflector_height_m)) =
+            pass_periodogram(&pass.sin_e, &pass.snr_dbhz, wavelength_m, opts)
+        {
+            ret.insert(
+                ReflectometryKey {
+                    sv,
+                    observable,
+                    start: pass.start,
+                },
+                ReflectometryPeak {
+                    end: pass.end,
+                    samples: pass.sin_e.len(),
+                    rising: pass.rising.unwrap_or_default(),
+                    frequency,
+                    amplitude,
+                    reflector_height_m,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::Carrier;
+
+    #[test]
+    fn reflector_height_periodogram() {
+        let lambda = Carrier::L1.wavelength();
+        let height = 2.0;
+
+        let sin_e = (0..300)
+            .map(|i| (5.0 + i as f64 * 25.0 / 300.0).to_radians().sin())
+            .collect::<Vec<_>>();
+
+        // direct signal trend + multipath interference
+        let snr_dbhz = sin_e
+            .iter()
+            .map(|x| {
+                let direct = 50.0 + 200.0 * x;
+                let multipath = 10.0 * (4.0 * PI * height / lambda * x + 0.3).cos();
+                20.0 * (direct + multipath).log10()
+            })
+            .collect::<Vec<_>>();
+
+        let opts = ReflectometryOptions::default();
+
+        let (frequency, amplitude, reflector_height_m) =
+            pass_periodogram(&sin_e, &snr_dbhz, lambda, &opts).unwrap();
+
+        assert!((reflector_height_m - height).abs() < 0.02);
+        assert!((frequency - 2.0 * height / lambda).abs() < 0.2);
+        assert!((amplitude - 10.0).abs() < 1.0, "amplitude: {}", amplitude);
+
+        let opts = opts.with_min_samples(1000);
+        assert!(pass_periodogram(&sin_e, &snr_dbhz, lambda, &opts).is_none());
+    }
+}
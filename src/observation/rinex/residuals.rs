@@ -0,0 +1,291 @@
+//! Smoothing and cycle slip detection internals, exposed as time series
+use crate::{
+    observation::{Combination, EpochFlag, LliFlags},
+    prelude::{Epoch, Observable, Rinex, SV},
+};
+
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [HatchKey] is how we sort Hatch filter internals
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HatchKey {
+    /// [Epoch] of sampling
+    pub epoch: Epoch,
+    /// [SV]: signal source
+    pub sv: SV,
+    /// Smoothed Pseudo Range [Observable]
+    pub code: Observable,
+    /// Phase Range [Observable] used in the smoothing process
+    pub phase: Observable,
+}
+
+/// [HatchResiduals] describes the Hatch filter state
+/// for one [HatchKey]. All values are expressed in meters.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HatchResiduals {
+    /// Number of samples currently in the filter, up to the window length.
+    /// 1 means the filter was (re)initialized at this [Epoch].
+    pub samples: usize,
+    /// Smoothed pseudo range
+    pub smoothed: f64,
+    /// Prefit residual: raw pseudo range minus the phase propagated
+    /// prediction (=innovation). Null on (re)initialization.
+    pub prefit: f64,
+    /// Postfit residual: raw pseudo range minus the smoothed pseudo range.
+    pub postfit: f64,
+}
+
+/// Internal Hatch filter state
+struct HatchState {
+    t: Epoch,
+    phase_m: f64,
+    smoothed: f64,
+    samples: usize,
+}
+
+/// [CycleSlipStatistics] describes the geometry free (GF) cycle slip
+/// detector internals for one [CycleSlipKey]. All values are expressed in meters.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CycleSlipStatistics {
+    /// Geometry free phase combination
+    pub gf: f64,
+    /// Test statistic: absolute epoch to epoch variation of the GF combination.
+    /// A cycle slip is usually declared when it exceeds a few centimeters.
+    pub test_statistic: f64,
+}
+
+/// [CycleSlipKey] is how we sort cycle slip detector internals
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CycleSlipKey {
+    /// [Epoch] of sampling
+    pub epoch: Epoch,
+    /// [EpochFlag]: sampling conditions
+    pub flag: EpochFlag,
+    /// [SV]: signal source
+    pub sv: SV,
+    /// Left Hand Side Phase [Observable]
+    pub lhs: Observable,
+    /// Reference Phase [Observable]
+    pub reference: Observable,
+}
+
+impl Rinex {
+    /// Runs the Hatch filter (carrier smoothing of pseudo ranges) over all observed
+    /// signals, and exposes its internals as time series.
+    /// Each pseudo range is smoothed by the phase range observed on the same [Carrier]
+    /// (same signal code is preferred when several exist).
+    /// The filter is reset on data gaps and on phase lock loss (LLI).
+    /// ## Inputs
+    /// - window: maximal number of samples (filter length), 100 is a typical value at 1s.
+    /// ## Returns
+    /// - [HatchResiduals] per [HatchKey]: smoothed value, prefit and postfit residuals.
+    pub fn hatch_filter_residuals(&self, window: usize) -> BTreeMap<HatchKey, HatchResiduals> {
+        let mut ret = BTreeMap::new();
+
+        let window = window.max(1);
+
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return ret, // can't proceed without sampling interval guess.
+        };
+
+        let mut states = HashMap::<(SV, Observable), HatchState>::new();
+
+        for (k, v) in self.observations_iter() {
+            for code in v.signals.iter() {
+                if !code.observable.is_pseudo_range_observable() {
+                    continue;
+                }
+
+                let carrier = match self.header.sv_carrier(code.sv, &code.observable) {
+                    Ok(carrier) => carrier,
+                    Err(_) => continue,
+                };
+
+                let code_signal = code.observable.code();
+
+                // locate phase on same carrier, prefer same signal
+                let phase = v
+                    .signals
+                    .iter()
+                    .filter(|ph| {
+                        ph.sv == code.sv
+                            && ph.observable.is_phase_range_observable()
+                            && self.header.sv_carrier(ph.sv, &ph.observable).ok() == Some(carrier)
+                    })
+                    .max_by_key(|ph| ph.observable.code() == code_signal);
+
+                let phase = match phase {
+                    Some(phase) => phase,
+                    None => continue,
+                };
+
+                let phase_m = phase.value * carrier.wavelength();
+
+                let lock_loss = phase
+                    .lli
+                    .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                    .unwrap_or(false);
+
+                let key = HatchKey {
+                    epoch: k.epoch,
+                    sv: code.sv,
+                    code: code.observable.clone(),
+                    phase: phase.observable.clone(),
+                };
+
+                let residuals = match states.get_mut(&(code.sv, code.observable.clone())) {
+                    Some(state) if !lock_loss && k.epoch - state.t <= dominant_sampling => {
+                        state.samples = (state.samples + 1).min(window);
+
+                        let n = state.samples as f64;
+                        let predicted = state.smoothed + phase_m - state.phase_m;
+
+                        state.smoothed = code.value / n + (n - 1.0) / n * predicted;
+                        state.phase_m = phase_m;
+                        state.t = k.epoch;
+
+                        HatchResiduals {
+                            samples: state.samples,
+                            smoothed: state.smoothed,
+                            prefit: code.value - predicted,
+                            postfit: code.value - state.smoothed,
+                        }
+                    },
+                    _ => {
+                        // (re)initialization
+                        states.insert(
+                            (code.sv, code.observable.clone()),
+                            HatchState {
+                                t: k.epoch,
+                                phase_m,
+                                smoothed: code.value,
+                                samples: 1,
+                            },
+                        );
+
+                        HatchResiduals {
+                            samples: 1,
+                            smoothed: code.value,
+                            prefit: 0.0,
+                            postfit: 0.0,
+                        }
+                    },
+                };
+
+                ret.insert(key, residuals);
+            }
+        }
+
+        ret
+    }
+
+    /// Runs the geometry free (GF) cycle slip detector over all phase observations,
+    /// and exposes its internals as time series. The test statistic is the absolute
+    /// variation of the GF combination between two consecutive epochs.
+    /// No statistic is produced on the first epoch of each arc (data gaps start new arcs).
+    pub fn cycle_slip_statistics(&self) -> BTreeMap<CycleSlipKey, CycleSlipStatistics> {
+        let mut ret = BTreeMap::new();
+
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return ret, // can't proceed without sampling interval guess.
+        };
+
+        let mut past = HashMap::<(SV, Observable, Observable), (Epoch, f64)>::new();
+
+        // sorted by epoch first
+        for (k, gf) in self
+            .signals_combination(Combination::GeometryFree)
+            .iter()
+            .filter(|(k, _)| k.lhs.is_phase_range_observable())
+        {
+            let arc = (k.sv, k.lhs.clone(), k.reference.clone());
+
+            if let Some((t, prev)) = past.get(&arc) {
+                if k.epoch - *t <= dominant_sampling {
+                    let key = CycleSlipKey {
+                        epoch: k.epoch,
+                        flag: k.flag,
+                        sv: k.sv,
+                        lhs: k.lhs.clone(),
+                        reference: k.reference.clone(),
+                    };
+
+                    ret.insert(
+                        key,
+                        CycleSlipStatistics {
+                            gf: *gf,
+                            test_statistic: (gf - prev).abs(),
+                        },
+                    );
+                }
+            }
+
+            past.insert(arc, (k.epoch, *gf));
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Carrier, Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn hatch_residuals() {
+        let g01 = SV::from_str("G01").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let lambda = Carrier::L1.wavelength();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(dt);
+
+        // constant range, noisy code
+        let record = rinex.record.as_mut_obs().unwrap();
+        for (i, noise) in [0.0, 1.0, -1.0, 0.5].iter().enumerate() {
+            let key = ObsKey {
+                epoch: t0 + i as f64 * dt,
+                flag: Default::default(),
+            };
+            let mut obs = Observations::default();
+            obs.signals
+                .push(SignalObservation::new(g01, c1c.clone(), 20.0E6 + noise));
+            obs.signals
+                .push(SignalObservation::new(g01, l1c.clone(), 20.0E6 / lambda));
+            record.insert(key, obs);
+        }
+
+        let residuals = rinex.hatch_filter_residuals(100);
+        assert_eq!(residuals.len(), 4);
+
+        let values = residuals.values().collect::<Vec<_>>();
+
+        assert_eq!(values[0].samples, 1);
+        assert_eq!(values[0].prefit, 0.0);
+
+        assert_eq!(values[1].samples, 2);
+        assert!((values[1].prefit - 1.0).abs() < 1.0E-6);
+        assert!((values[1].smoothed - (20.0E6 + 0.5)).abs() < 1.0E-6);
+        assert!((values[1].postfit - 0.5).abs() < 1.0E-6);
+
+        assert_eq!(values[3].samples, 4);
+        assert!((values[3].smoothed - (20.0E6 + 0.125)).abs() < 1.0E-6);
+    }
+}
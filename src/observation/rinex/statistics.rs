@@ -0,0 +1,217 @@
+//! Observation statistics, for quick data screening
+use crate::prelude::{Constellation, Observable, Rinex, SV};
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [ObservableStatistics] describes all samples of one [Observable]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ObservableStatistics {
+    /// Number of samples
+    pub count: usize,
+    /// Smallest value
+    pub min: f64,
+    /// Largest value
+    pub max: f64,
+    /// Average value
+    pub mean: f64,
+    /// Standard deviation
+    pub std_dev: f64,
+}
+
+impl Default for ObservableStatistics {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: 0.0,
+            std_dev: 0.0,
+        }
+    }
+}
+
+impl ObservableStatistics {
+    /// Accounts for a new sample (Welford algorithm)
+    fn push(&mut self, value: f64) {
+        let n = self.count as f64;
+        let m2 = self.std_dev.powi(2) * n;
+
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+
+        let m2 = m2 + delta * (value - self.mean);
+        self.std_dev = (m2 / self.count as f64).sqrt();
+    }
+
+    /// Merges two [ObservableStatistics] (Chan algorithm),
+    /// so statistics can be aggregated (per [Constellation] for example)
+    pub fn merge(&self, rhs: &Self) -> Self {
+        if self.count == 0 {
+            return *rhs;
+        }
+        if rhs.count == 0 {
+            return *self;
+        }
+
+        let (n_a, n_b) = (self.count as f64, rhs.count as f64);
+        let n = n_a + n_b;
+
+        let delta = rhs.mean - self.mean;
+        let m2 =
+            self.std_dev.powi(2) * n_a + rhs.std_dev.powi(2) * n_b + delta.powi(2) * n_a * n_b / n;
+
+        Self {
+            count: self.count + rhs.count,
+            min: self.min.min(rhs.min),
+            max: self.max.max(rhs.max),
+            mean: self.mean + delta * n_b / n,
+            std_dev: (m2 / n).sqrt(),
+        }
+    }
+}
+
+/// [StatisticsReport] gathers [ObservableStatistics] per [SV] and [Observable],
+/// see [Rinex::statistics].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct StatisticsReport {
+    /// [ObservableStatistics] per [SV] and [Observable]
+    pub signals: BTreeMap<SV, BTreeMap<Observable, ObservableStatistics>>,
+}
+
+impl StatisticsReport {
+    /// Returns [ObservableStatistics] of this [SV] and [Observable]
+    pub fn get(&self, sv: SV, observable: &Observable) -> Option<&ObservableStatistics> {
+        self.signals.get(&sv)?.get(observable)
+    }
+
+    /// Returns total number of samples
+    pub fn count(&self) -> usize {
+        self.signals
+            .values()
+            .flat_map(|stats| stats.values())
+            .map(|stats| stats.count)
+            .sum()
+    }
+
+    /// Aggregates [ObservableStatistics] per [Constellation] and [Observable]
+    pub fn constellation_statistics(
+        &self,
+    ) -> BTreeMap<Constellation, BTreeMap<Observable, ObservableStatistics>> {
+        let mut ret = BTreeMap::<Constellation, BTreeMap<Observable, ObservableStatistics>>::new();
+        for (sv, signals) in self.signals.iter() {
+            let aggregated = ret.entry(sv.constellation).or_default();
+            for (observable, stats) in signals.iter() {
+                let entry = aggregated.entry(observable.clone()).or_default();
+                *entry = entry.merge(stats);
+            }
+        }
+        ret
+    }
+}
+
+impl Rinex {
+    /// Computes [ObservableStatistics] (count, min, max, mean and standard deviation)
+    /// per [SV] and [Observable] of this Observation [Rinex], for quick data screening.
+    /// Use [StatisticsReport::constellation_statistics] to aggregate them per [Constellation].
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex, SV};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C S1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682          40.000
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682          44.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let report = rinex.statistics();
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let s1c = Observable::from_str("S1C").unwrap();
+    ///
+    /// let stats = report.get(g01, &s1c).unwrap();
+    /// assert_eq!(stats.count, 2);
+    /// assert_eq!(stats.min, 40.0);
+    /// assert_eq!(stats.max, 44.0);
+    /// assert_eq!(stats.mean, 42.0);
+    /// assert_eq!(stats.std_dev, 2.0);
+    /// ```
+    pub fn statistics(&self) -> StatisticsReport {
+        let mut report = StatisticsReport::default();
+        for (_, sig) in self.signal_observations_iter() {
+            report
+                .signals
+                .entry(sig.sv)
+                .or_default()
+                .entry(sig.observable.clone())
+                .or_default()
+                .push(sig.value);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ObservableStatistics;
+    use crate::prelude::{Constellation, Observable, Rinex};
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    #[test]
+    fn observation_statistics() {
+        let mut stats = ObservableStatistics::default();
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            stats.push(value);
+        }
+        assert_eq!(stats.count, 8);
+        assert_eq!(stats.mean, 5.0);
+        assert!((stats.std_dev - 2.0).abs() < 1.0E-12);
+
+        let content =
+            "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+G    1 S1C                                                  SYS / # / OBS TYPES
+E    1 S1C                                                  SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  3
+G01        40.000
+G02        44.000
+E01        30.000
+> 2020 01 01 00 00 30.0000000  0  2
+G01        42.000
+G02        46.000
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let report = rinex.statistics();
+
+        assert_eq!(report.count(), 5);
+        assert_eq!(report.signals.len(), 3);
+
+        let s1c = Observable::from_str("S1C").unwrap();
+
+        let constellations = report.constellation_statistics();
+        let gps = constellations[&Constellation::GPS][&s1c];
+        assert_eq!(gps.count, 4);
+        assert_eq!(gps.min, 40.0);
+        assert_eq!(gps.max, 46.0);
+        assert_eq!(gps.mean, 43.0);
+        assert!((gps.std_dev - 5.0_f64.sqrt()).abs() < 1.0E-12);
+
+        let gal = constellations[&Constellation::Galileo][&s1c];
+        assert_eq!(gal.count, 1);
+        assert_eq!(gal.std_dev, 0.0);
+    }
+}
@@ -0,0 +1,197 @@
+//! TIME OF FIRST / LAST OBS consistency
+use crate::prelude::{Epoch, Header, Rinex};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [TimeOfObsMismatch] reports a "TIME OF FIRST OBS" or "TIME OF LAST OBS"
+/// header field that does not match the actual time span of the record.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum TimeOfObsMismatch {
+    /// "TIME OF FIRST OBS" differs from the first [Epoch] of the record
+    FirstObs {
+        /// [Epoch] declared in the header
+        header: Epoch,
+        /// first [Epoch] of the record
+        record: Epoch,
+    },
+    /// "TIME OF LAST OBS" differs from the last [Epoch] of the record
+    LastObs {
+        /// [Epoch] declared in the header
+        header: Epoch,
+        /// last [Epoch] of the record
+        record: Epoch,
+    },
+}
+
+impl std::fmt::Display for TimeOfObsMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::FirstObs { header, record } => write!(
+                f,
+                "TIME OF FIRST OBS {} does not match first epoch {}",
+                header, record
+            ),
+            Self::LastObs { header, record } => write!(
+                f,
+                "TIME OF LAST OBS {} does not match last epoch {}",
+                header, record
+            ),
+        }
+    }
+}
+
+impl Rinex {
+    /// Compares "TIME OF FIRST OBS" and "TIME OF LAST OBS" of this Observation [Rinex]
+    /// to the actual time span of its record, and returns all [TimeOfObsMismatch]es.
+    /// Fields that are not declared are not reported.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    ///   2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+    ///   2020     1     1     0     1    0.0000000     GPS         TIME OF LAST OBS
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // file was truncated
+    /// let mismatches = rinex.timeof_obs_mismatches();
+    /// assert_eq!(mismatches.len(), 1);
+    /// ```
+    pub fn timeof_obs_mismatches(&self) -> Vec<TimeOfObsMismatch> {
+        let mut mismatches = Vec::new();
+
+        let obs = match &self.header.obs {
+            Some(obs) => obs,
+            None => return mismatches,
+        };
+
+        if let (Some(header), Some(record)) = (obs.timeof_first_obs, self.first_epoch()) {
+            if header != record {
+                mismatches.push(TimeOfObsMismatch::FirstObs { header, record });
+            }
+        }
+
+        if let (Some(header), Some(record)) = (obs.timeof_last_obs, self.last_epoch()) {
+            if header != record {
+                mismatches.push(TimeOfObsMismatch::LastObs { header, record });
+            }
+        }
+
+        mismatches
+    }
+
+    /// Returns a copy of the [Header] of this Observation [Rinex], with
+    /// "TIME OF FIRST OBS" and "TIME OF LAST OBS" deduced from the record,
+    /// when they are not declared. Returns None when there is nothing to complete.
+    pub(crate) fn header_with_timeof_obs(&self) -> Option<Header> {
+        let obs = self.header.obs.as_ref()?;
+
+        if obs.timeof_first_obs.is_some() && obs.timeof_last_obs.is_some() {
+            return None;
+        }
+
+        let (first, last) = (self.first_epoch()?, self.last_epoch()?);
+
+        // declared timescale prevails, otherwise use the record's own timescale
+        let ts = obs
+            .timeof_first_obs
+            .or(obs.timeof_last_obs)
+            .map(|t| t.time_scale)
+            .unwrap_or(first.time_scale);
+
+        let mut obs = obs.clone();
+
+        if obs.timeof_first_obs.is_none() {
+            obs = obs.with_timeof_first_obs(first.to_time_scale(ts));
+        }
+
+        if obs.timeof_last_obs.is_none() {
+            obs = obs.with_timeof_last_obs(last.to_time_scale(ts));
+        }
+
+        let mut header = self.header.clone();
+        header.obs = Some(obs);
+        Some(header)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeOfObsMismatch;
+    use crate::{
+        observation::{EpochFlag, ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::io::BufWriter;
+    use std::str::FromStr;
+
+    #[test]
+    fn timeof_obs_autofill_and_mismatches() {
+        let g01 = SV::from_str("G01").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let mut rinex = Rinex::basic_obs();
+
+        for i in 0..3 {
+            let mut obs = Observations::default();
+            obs.signals
+                .push(SignalObservation::new(g01, c1c.clone(), 20832393.682));
+
+            rinex.record.as_mut_obs().unwrap().insert(
+                ObsKey {
+                    epoch: t0 + i as f64 * dt,
+                    flag: EpochFlag::Ok,
+                },
+                obs,
+            );
+        }
+
+        // nothing declared: nothing to report, but completed when formatted
+        assert!(rinex.timeof_obs_mismatches().is_empty());
+
+        let header = rinex.header_with_timeof_obs().unwrap();
+        let obs = header.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, Some(t0));
+        assert_eq!(obs.timeof_last_obs, Some(t0 + 2.0 * dt));
+
+        let mut w = BufWriter::new(Vec::new());
+        rinex.format(&mut w).unwrap();
+        let content = String::from_utf8(w.into_inner().unwrap()).unwrap();
+        assert!(content.contains(
+            "  2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS"
+        ));
+        assert!(content.contains(
+            "  2020     1     1     0     1    0.0000000     GPS         TIME OF LAST OBS"
+        ));
+
+        // declared timescale prevails
+        let gst = (t0 - dt).to_time_scale(hifitime::TimeScale::GST);
+        rinex.header.obs = Some(obs.clone().with_timeof_first_obs(gst));
+        rinex.header.obs.as_mut().unwrap().timeof_last_obs = None;
+
+        let header = rinex.header_with_timeof_obs().unwrap();
+        let last = header.obs.as_ref().unwrap().timeof_last_obs.unwrap();
+        assert_eq!(last.time_scale, hifitime::TimeScale::GST);
+        assert_eq!(last, t0 + 2.0 * dt);
+
+        assert_eq!(
+            rinex.timeof_obs_mismatches(),
+            vec![TimeOfObsMismatch::FirstObs {
+                header: gst,
+                record: t0,
+            }]
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Station events timeline
+use crate::{
+    observation::{EpochFlag, LliFlags},
+    prelude::{Epoch, Rinex, SV},
+};
+
+use std::collections::BTreeSet;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [EventCategory] sorts [TimelineEvent]s
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum EventCategory {
+    /// Receiver event, described by an epoch flag (power failure,
+    /// antenna being moved, new site occupation, external event)
+    ReceiverEvent,
+    /// Header information was modified within the record
+    HeaderChange,
+    /// Receiver clock discontinuity (steering)
+    ClockJump,
+    /// Phase tracking discontinuity
+    CycleSlip,
+}
+
+impl std::fmt::Display for EventCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::ReceiverEvent => write!(f, "Receiver Event"),
+            Self::HeaderChange => write!(f, "Header Change"),
+            Self::ClockJump => write!(f, "Clock Jump"),
+            Self::CycleSlip => write!(f, "Cycle Slip"),
+        }
+    }
+}
+
+/// [TimelineEvent] is one entry of the [Timeline]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TimelineEvent {
+    /// [Epoch] of occurrence
+    pub epoch: Epoch,
+    /// [EventCategory]
+    pub category: EventCategory,
+    /// Source of this event, typically the station name
+    pub source: String,
+    /// [SV] this event applies to, if it applies to a single [SV]
+    pub sv: Option<SV>,
+    /// Readable description
+    pub description: String,
+}
+
+/// [TimelineOptions] to customize events detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimelineOptions {
+    /// Receiver clock discontinuities larger than this value are
+    /// reported as [EventCategory::ClockJump], in seconds
+    pub clock_jump_threshold_s: f64,
+    /// Geometry free variations larger than this value are
+    /// reported as [EventCategory::CycleSlip], in meters
+    pub cycle_slip_threshold_m: f64,
+}
+
+impl Default for TimelineOptions {
+    fn default() -> Self {
+        Self {
+            clock_jump_threshold_s: 1.0E-6,
+            cycle_slip_threshold_m: 0.05,
+        }
+    }
+}
+
+impl TimelineOptions {
+    /// Copies and returns [TimelineOptions] with desired clock jump threshold, in seconds
+    pub fn with_clock_jump_threshold_s(&self, threshold_s: f64) -> Self {
+        let mut s = *self;
+        s.clock_jump_threshold_s = threshold_s;
+        s
+    }
+
+    /// Copies and returns [TimelineOptions] with desired cycle slip threshold, in meters
+    pub fn with_cycle_slip_threshold_m(&self, threshold_m: f64) -> Self {
+        let mut s = *self;
+        s.cycle_slip_threshold_m = threshold_m;
+        s
+    }
+}
+
+/// [Timeline] gathers all events in chronological order,
+/// possibly from several [Rinex] (see [Timeline::merge]).
+/// It is serializable (JSON) to feed station operation dashboards.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Timeline {
+    /// [TimelineEvent]s in chronological order
+    pub events: Vec<TimelineEvent>,
+}
+
+impl Timeline {
+    /// Inserts a new [TimelineEvent], preserving chronological order.
+    pub fn insert(&mut self, event: TimelineEvent) {
+        let index = self.events.partition_point(|e| e.epoch <= event.epoch);
+        self.events.insert(index, event);
+    }
+
+    /// Merges all events of `rhs` into self, preserving chronological order.
+    pub fn merge(&mut self, rhs: &Self) {
+        self.events.extend_from_slice(&rhs.events);
+        self.events.sort_by_key(|e| e.epoch);
+    }
+
+    /// Returns total number of events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if this [Timeline] is empty
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Iterates all [TimelineEvent]s of this [EventCategory], in chronological order
+    pub fn category_iter(
+        &self,
+        category: EventCategory,
+    ) -> Box<dyn Iterator<Item = &TimelineEvent> + '_> {
+        Box::new(self.events.iter().filter(move |e| e.category == category))
+    }
+}
+
+impl Rinex {
+    /// Extracts a [Timeline] of all events from this Observation [Rinex]:
+    /// - epoch flags other than Ok and header changes (with their comments)
+    /// - receiver clock jumps, when clock offsets were reported
+    /// - cycle slips, either declared by the receiver (LLI) or detected
+    ///   by the geometry free detector
+    ///
+    /// Events are tagged with the station name of this [Rinex].
+    pub fn event_timeline(&self, opts: &TimelineOptions) -> Timeline {
+        let mut timeline = Timeline::default();
+        let source = self.production.name.clone();
+
+        let event = |epoch, category, sv, description| TimelineEvent {
+            epoch,
+            category,
+            source: source.clone(),
+            sv,
+            description,
+        };
+
+        for (k, _) in self.observations_iter() {
+            let description = match k.flag {
+                EpochFlag::Ok => continue,
+                EpochFlag::PowerFailure => "power failure".to_string(),
+                EpochFlag::AntennaBeingMoved => "antenna being moved".to_string(),
+                EpochFlag::NewSiteOccupation => "new site occupation".to_string(),
+                EpochFlag::ExternalEvent => "external event".to_string(),
+                EpochFlag::CycleSlip => "cycle slip records".to_string(),
+                EpochFlag::HeaderInformationFollows => {
+                    let comments = self
+                        .comments
+                        .get(&k.epoch)
+                        .map(|comments| comments.join(" "))
+                        .unwrap_or_default();
+
+                    timeline.insert(event(k.epoch, EventCategory::HeaderChange, None, comments));
+                    continue;
+                },
+            };
+
+            let category = if k.flag == EpochFlag::CycleSlip {
+                EventCategory::CycleSlip
+            } else {
+                EventCategory::ReceiverEvent
+            };
+
+            timeline.insert(event(k.epoch, category, None, description));
+        }
+
+        if let Some(report) = self.receiver_clock_drift(opts.clock_jump_threshold_s) {
+            for steering in report.steering_events.iter() {
+                timeline.insert(event(
+                    steering.epoch,
+                    EventCategory::ClockJump,
+                    None,
+                    format!("clock jump: {:.3E} s", steering.jump_s),
+                ));
+            }
+        }
+
+        // one cycle slip event per SV and epoch
+        let mut slips = BTreeSet::<(Epoch, SV)>::new();
+
+        for (k, sig) in self.signal_observations_iter() {
+            if !sig.observable.is_phase_range_observable() {
+                continue;
+            }
+            let lock_loss = sig
+                .lli
+                .map(|lli| lli.intersects(LliFlags::LOCK_LOSS))
+                .unwrap_or(false);
+
+            if lock_loss && slips.insert((k.epoch, sig.sv)) {
+                timeline.insert(event(
+                    k.epoch,
+                    EventCategory::CycleSlip,
+                    Some(sig.sv),
+                    format!("{} lock loss", sig.observable),
+                ));
+            }
+        }
+
+        for (k, stats) in self.cycle_slip_statistics() {
+            if stats.test_statistic > opts.cycle_slip_threshold_m && slips.insert((k.epoch, k.sv)) {
+                timeline.insert(event(
+                    k.epoch,
+                    EventCategory::CycleSlip,
+                    Some(k.sv),
+                    format!(
+                        "{}/{} GF variation: {:.3} m",
+                        k.lhs, k.reference, stats.test_statistic
+                    ),
+                ));
+            }
+        }
+
+        timeline
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EventCategory, Timeline, TimelineOptions};
+    use crate::{
+        observation::{
+            ClockObservation, EpochFlag, LliFlags, ObsKey, Observations, SignalObservation,
+        },
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn event_timeline() {
+        let g01 = SV::from_str("G01").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.production.name = "ESBC".to_string();
+
+        for i in 0..6 {
+            let epoch = t0 + i as f64 * dt;
+            let flag = match i {
+                2 => EpochFlag::PowerFailure,
+                4 => EpochFlag::HeaderInformationFollows,
+                _ => EpochFlag::Ok,
+            };
+
+            let steering = if i >= 3 { 1.0E-3 } else { 0.0 };
+            let mut obs = Observations::default()
+                .with_clock_observation(ClockObservation::default().with_offset_s(epoch, steering));
+
+            let mut phase = SignalObservation::new(g01, l1c.clone(), 1.0);
+            if i == 5 {
+                phase.lli = Some(LliFlags::LOCK_LOSS);
+            }
+            obs.signals.push(phase);
+
+            rinex
+                .record
+                .as_mut_obs()
+                .unwrap()
+                .insert(ObsKey { epoch, flag }, obs);
+        }
+
+        rinex
+            .comments
+            .insert(t0 + 4.0 * dt, vec!["NEW ANTENNA".to_string()]);
+
+        let timeline = rinex.event_timeline(&TimelineOptions::default());
+
+        let summary = timeline
+            .events
+            .iter()
+            .map(|e| (e.epoch, e.category))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                (t0 + 2.0 * dt, EventCategory::ReceiverEvent),
+                (t0 + 3.0 * dt, EventCategory::ClockJump),
+                (t0 + 4.0 * dt, EventCategory::HeaderChange),
+                (t0 + 5.0 * dt, EventCategory::CycleSlip),
+            ]
+        );
+
+        assert!(timeline.events.iter().all(|e| e.source == "ESBC"));
+        assert_eq!(timeline.events[2].description, "NEW ANTENNA");
+        assert_eq!(timeline.events[3].sv, Some(g01));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&timeline).unwrap();
+            assert!(json.contains("\"HeaderChange\""));
+            assert!(json.contains("NEW ANTENNA"));
+        }
+
+        // merging preserves chronological order
+        let mut merged = Timeline::default();
+        merged.merge(&timeline);
+        merged.merge(&timeline);
+        assert_eq!(merged.len(), 8);
+        assert!(merged.events.windows(2).all(|w| w[0].epoch <= w[1].epoch));
+        assert_eq!(merged.category_iter(EventCategory::CycleSlip).count(), 2);
+    }
+}
@@ -0,0 +1,180 @@
+//! RINEX2 to RINEX3 observation conversion
+use crate::prelude::{Constellation, Observable, Rinex, Version};
+
+use std::collections::HashMap;
+
+impl Rinex {
+    /// Converts this RINEX2 Observation [Rinex] to RINEX3, in place.
+    /// See [Self::upgrade_observations].
+    pub fn upgrade_observations_mut(&mut self) {
+        if !self.is_observation_rinex() || self.header.version.major != 2 {
+            return;
+        }
+
+        let present = self.constellations_iter().collect::<Vec<_>>();
+
+        if let Some(record) = self.record.as_mut_obs() {
+            for obs in record.values_mut() {
+                obs.signals
+                    .retain_mut(|sig| match sig.observable.to_v3(sig.sv.constellation) {
+                        Some(observable) => {
+                            sig.observable = observable;
+                            true
+                        },
+                        None => false,
+                    });
+            }
+        }
+
+        if let Some(obs) = &mut self.header.obs {
+            // RINEX2 declares the same observables for all constellations
+            if !present.is_empty() {
+                obs.codes.retain(|c, _| {
+                    present.contains(c) || (c.is_sbas() && present.iter().any(|p| p.is_sbas()))
+                });
+            }
+
+            for (constellation, codes) in obs.codes.iter_mut() {
+                let mut upgraded = Vec::<Observable>::with_capacity(codes.len());
+                for code in codes.iter() {
+                    if let Some(code) = code.to_v3(*constellation) {
+                        if !upgraded.contains(&code) {
+                            upgraded.push(code);
+                        }
+                    }
+                }
+                *codes = upgraded;
+            }
+
+            obs.codes.retain(|_, codes| !codes.is_empty());
+
+            obs.scaling = obs
+                .scaling
+                .iter()
+                .filter_map(|((c, observable), scaling)| {
+                    let observable = observable.to_v3(*c)?;
+                    Some(((*c, observable), *scaling))
+                })
+                .collect::<HashMap<(Constellation, Observable), u16>>();
+
+            // "PRN / # OF OBS" follows the declared observables
+            obs.prn_obs_counts.clear();
+
+            if let Some(crinex) = &mut obs.crinex {
+                crinex.version = Version::new(3, 0);
+            }
+        }
+
+        self.header.version = Version::new(3, 5);
+    }
+
+    /// Converts this RINEX2 Observation [Rinex] to RINEX3, so old archives can be modernized:
+    /// observables are translated per [Constellation] (see [Observable::to_v3]),
+    /// observables that have no RINEX3 equivalent are dropped, and the header tables
+    /// are rebuilt, for the [Constellation]s actually present. The returned [Rinex]
+    /// is formatted in RINEX3 (CRINEX3 for compressed files).
+    /// This has no effect on other [Rinex] types and revisions.
+    /// ```
+    /// use rinex::prelude::{Constellation, Observable, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     2.11           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+    ///      3    C1    P2    L1                                    # / TYPES OF OBSERV
+    ///                                                             END OF HEADER
+    ///  20  1  1  0  0  0.0000000  0  2G01R01
+    ///   20832393.682    20832395.682   109474991.854
+    ///   21832393.682    21832395.682   119474991.854
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let rinex = rinex.upgrade_observations();
+    ///
+    /// assert_eq!(rinex.header.version.major, 3);
+    ///
+    /// let obs = rinex.header.obs.as_ref().unwrap();
+    /// assert_eq!(obs.codes.len(), 2);
+    ///
+    /// assert_eq!(
+    ///     obs.codes[&Constellation::GPS],
+    ///     vec![
+    ///         Observable::from_str("C1C").unwrap(),
+    ///         Observable::from_str("C2W").unwrap(),
+    ///         Observable::from_str("L1C").unwrap(),
+    ///     ]
+    /// );
+    ///
+    /// assert_eq!(
+    ///     obs.codes[&Constellation::Glonass],
+    ///     vec![
+    ///         Observable::from_str("C1C").unwrap(),
+    ///         Observable::from_str("C2P").unwrap(),
+    ///         Observable::from_str("L1C").unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn upgrade_observations(&self) -> Self {
+        let mut s = self.clone();
+        s.upgrade_observations_mut();
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Constellation, Observable, Rinex};
+    use std::io::{BufReader, BufWriter};
+    use std::str::FromStr;
+
+    #[test]
+    fn upgrade_v2_observations() {
+        let content =
+            "     2.11           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+     4    C1    L1    C5    S1                              # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  2G01E01
+  20832393.682   109474991.854    20832394.682          40.000
+  23832393.682   123474991.854    23832394.682          41.000
+ 20  1  1  0  0 30.0000000  0  1G01
+  20832395.682   109474993.854    20832396.682          42.000
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let upgraded = rinex.upgrade_observations();
+
+        assert_eq!(upgraded.header.version.major, 3);
+        assert_eq!(
+            upgraded.signal_observations_iter().count(),
+            rinex.signal_observations_iter().count()
+        );
+
+        let obs = upgraded.header.obs.as_ref().unwrap();
+        assert_eq!(obs.codes.len(), 2);
+        assert_eq!(
+            obs.codes[&Constellation::Galileo],
+            ["C1X", "L1X", "C5X", "S1X"]
+                .iter()
+                .map(|c| Observable::from_str(c).unwrap())
+                .collect::<Vec<_>>()
+        );
+
+        // all record observables are declared
+        for (_, sig) in upgraded.signal_observations_iter() {
+            let codes = &obs.codes[&sig.sv.constellation];
+            assert!(codes.contains(&sig.observable), "{}", sig.observable);
+        }
+
+        // RINEX3 formatting
+        let mut buf = BufWriter::new(Vec::new());
+        upgraded.format(&mut buf).unwrap();
+        let formatted = String::from_utf8(buf.into_inner().unwrap()).unwrap();
+
+        assert!(formatted.contains("SYS / # / OBS TYPES"));
+        assert!(formatted.contains("> 2020 01 01 00 00  0.0000000  0  2"));
+
+        let parsed = Rinex::parse(&mut BufReader::new(formatted.as_bytes())).unwrap();
+        assert_eq!(parsed.record, upgraded.record);
+
+        // no effect on RINEX3
+        assert_eq!(upgraded.upgrade_observations().record, upgraded.record);
+    }
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{ops::Range, str::FromStr};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -13,7 +13,7 @@ pub enum Error {
 #[derive(Default, PartialOrd, Ord, PartialEq, Eq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SNR {
-    /// SNR ~= 0 dB/Hz
+    /// SNR not known / don't care
     DbHz0,
     /// SNR < 12 dB/Hz
     DbHz12,
@@ -97,25 +97,7 @@ impl FromStr for SNR {
 
 impl From<f64> for SNR {
     fn from(f_db: f64) -> Self {
-        if f_db < 12.0 {
-            Self::DbHz12
-        } else if f_db <= 17.0 {
-            Self::DbHz12_17
-        } else if f_db <= 23.0 {
-            Self::DbHz18_23
-        } else if f_db <= 29.0 {
-            Self::DbHz24_29
-        } else if f_db <= 35.0 {
-            Self::DbHz30_35
-        } else if f_db <= 41.0 {
-            Self::DbHz36_41
-        } else if f_db <= 47.0 {
-            Self::DbHz42_47
-        } else if f_db <= 53.0 {
-            Self::DbHz48_53
-        } else {
-            Self::DbHz54
-        }
+        Self::from_dbhz(f_db)
     }
 }
 
@@ -153,7 +135,85 @@ impl From<u8> for SNR {
     }
 }
 
+impl From<SNR> for u8 {
+    fn from(val: SNR) -> Self {
+        match val {
+            SNR::DbHz0 => 0,
+            SNR::DbHz12 => 1,
+            SNR::DbHz12_17 => 2,
+            SNR::DbHz18_23 => 3,
+            SNR::DbHz24_29 => 4,
+            SNR::DbHz30_35 => 5,
+            SNR::DbHz36_41 => 6,
+            SNR::DbHz42_47 => 7,
+            SNR::DbHz48_53 => 8,
+            SNR::DbHz54 => 9,
+        }
+    }
+}
+
 impl SNR {
+    /// Builds [SNR] from a raw C/N0 value in dB-Hz, as reported by receivers,
+    /// following the 6 dB-Hz wide intervals of the RINEX specifications.
+    /// This is typically used when producing files from raw receiver data.
+    /// ```
+    /// use rinex::observation::SNR;
+    ///
+    /// let snr = SNR::from_dbhz(43.5);
+    /// assert_eq!(snr, SNR::DbHz42_47);
+    /// assert_eq!(u8::from(snr), 7);
+    /// assert_eq!(snr.dbhz_range(), Some(42.0..48.0));
+    ///
+    /// // SSI are ordered by signal strength
+    /// assert!(SNR::from_dbhz(17.9) < SNR::from_dbhz(18.0));
+    /// assert!(snr >= SNR::DbHz30_35);
+    /// ```
+    pub fn from_dbhz(dbhz: f64) -> Self {
+        if dbhz < 12.0 {
+            Self::DbHz12
+        } else if dbhz < 18.0 {
+            Self::DbHz12_17
+        } else if dbhz < 24.0 {
+            Self::DbHz18_23
+        } else if dbhz < 30.0 {
+            Self::DbHz24_29
+        } else if dbhz < 36.0 {
+            Self::DbHz30_35
+        } else if dbhz < 42.0 {
+            Self::DbHz36_41
+        } else if dbhz < 48.0 {
+            Self::DbHz42_47
+        } else if dbhz < 54.0 {
+            Self::DbHz48_53
+        } else {
+            Self::DbHz54
+        }
+    }
+
+    /// Returns the C/N0 interval in dB-Hz this [SNR] describes.
+    /// [SNR::DbHz0] (not known, don't care) describes no interval.
+    pub fn dbhz_range(&self) -> Option<Range<f64>> {
+        match self {
+            Self::DbHz0 => None,
+            Self::DbHz12 => Some(0.0..12.0),
+            Self::DbHz12_17 => Some(12.0..18.0),
+            Self::DbHz18_23 => Some(18.0..24.0),
+            Self::DbHz24_29 => Some(24.0..30.0),
+            Self::DbHz30_35 => Some(30.0..36.0),
+            Self::DbHz36_41 => Some(36.0..42.0),
+            Self::DbHz42_47 => Some(42.0..48.0),
+            Self::DbHz48_53 => Some(48.0..54.0),
+            Self::DbHz54 => Some(54.0..f64::INFINITY),
+        }
+    }
+
+    /// Returns true if this C/N0 value in dB-Hz lies within this [SNR] interval
+    pub fn contains_dbhz(&self, dbhz: f64) -> bool {
+        self.dbhz_range()
+            .map(|range| range.contains(&dbhz))
+            .unwrap_or(false)
+    }
+
     /// Returns true if self describes a bad signal level
     pub fn bad(self) -> bool {
         self <= SNR::DbHz18_23
@@ -214,5 +274,22 @@ mod test {
         assert!(SNR::from_str("weak").unwrap().weak());
         assert!(SNR::from_str("strong").unwrap().strong());
         assert!(SNR::from_str("excellent").unwrap().excellent());
+
+        for code in 0..10_u8 {
+            let snr = SNR::from(code);
+            assert_eq!(u8::from(snr), code);
+
+            if let Some(range) = snr.dbhz_range() {
+                assert_eq!(SNR::from_dbhz(range.start), snr);
+                assert!(snr.contains_dbhz(range.start));
+                assert!(!snr.contains_dbhz(range.end));
+            } else {
+                assert_eq!(snr, SNR::DbHz0);
+            }
+        }
+
+        assert_eq!(SNR::from_dbhz(17.5), SNR::DbHz12_17);
+        assert_eq!(SNR::from_dbhz(54.0), SNR::DbHz54);
+        assert!(SNR::from_dbhz(-1.0) < SNR::from_dbhz(60.0));
     }
 }
@@ -0,0 +1,25 @@
+//! [Rinex] parsing options
+
+#[cfg(doc)]
+use crate::prelude::{ParsingError, ParsingSummary, Rinex};
+
+/// [ParsingOptions] customize how a [Rinex] is parsed, see [Rinex::parse_with_options].
+/// By default, the parser is lenient: satellites whose PRN is not possible
+/// for their constellation (corrupted descriptors) are dropped and reported
+/// in the [ParsingSummary].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsingOptions {
+    /// Abort with [ParsingError::InvalidPRN] when a satellite PRN is not possible
+    /// for its constellation, instead of dropping it.
+    pub strict_prn: bool,
+}
+
+impl ParsingOptions {
+    /// Copies and returns [ParsingOptions] that reject invalid PRNs,
+    /// with [ParsingError::InvalidPRN].
+    pub fn with_strict_prn(&self) -> Self {
+        let mut s = self.clone();
+        s.strict_prn = true;
+        s
+    }
+}
@@ -0,0 +1,371 @@
+//! Cycle slip detection
+use crate::{
+    observation::{DopplerArc, LliFlags},
+    prelude::{Epoch, Observable, Rinex, SV},
+};
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [CycleSlipDetector] is the method that detected a [CycleSlip]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum CycleSlipDetector {
+    /// Epoch to epoch variation of the geometry free (GF) phase combination
+    GeometryFree,
+    /// Deviation of the Melbourne-Wübbena (MW) combination from its arc average
+    MelbourneWubbena,
+    /// Phase variation not predicted by the Doppler observation
+    /// (single frequency fallback)
+    Doppler,
+}
+
+/// [CycleSlip] is one cycle slip event, detected on a phase [Observable].
+/// Dual frequency detectors cannot tell which signal slipped:
+/// both phase signals are then reported.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CycleSlip {
+    /// [Epoch] of the first sample following the slip
+    pub epoch: Epoch,
+    /// [SV]: signal source
+    pub sv: SV,
+    /// Phase [Observable] that (possibly) slipped
+    pub observable: Observable,
+    /// [CycleSlipDetector] that declared this slip
+    pub detector: CycleSlipDetector,
+}
+
+/// [CycleSlipOptions] to tune the cycle slip detectors.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CycleSlipOptions {
+    /// Slip is declared when the GF combination varies by more than this value
+    /// between two consecutive epochs, in meters.
+    pub gf_threshold_m: f64,
+    /// Slip is declared when the MW combination deviates from its arc average
+    /// by more than this value, in meters (one wide lane cycle is about 86 cm on L1/L2).
+    pub mw_threshold_m: f64,
+    /// Slip is declared when the phase variation differs from the Doppler
+    /// prediction by more than this value, in cycles.
+    /// Only used for single frequency signals (no GF / MW combination possible).
+    pub doppler_threshold_cycles: f64,
+}
+
+impl Default for CycleSlipOptions {
+    fn default() -> Self {
+        Self {
+            gf_threshold_m: 0.05,
+            mw_threshold_m: 1.5,
+            doppler_threshold_cycles: 5.0,
+        }
+    }
+}
+
+impl CycleSlipOptions {
+    /// Copies and returns [CycleSlipOptions] with desired GF threshold, in meters
+    pub fn with_gf_threshold_m(&self, threshold_m: f64) -> Self {
+        let mut s = *self;
+        s.gf_threshold_m = threshold_m;
+        s
+    }
+
+    /// Copies and returns [CycleSlipOptions] with desired MW threshold, in meters
+    pub fn with_mw_threshold_m(&self, threshold_m: f64) -> Self {
+        let mut s = *self;
+        s.mw_threshold_m = threshold_m;
+        s
+    }
+
+    /// Copies and returns [CycleSlipOptions] with desired Doppler threshold, in cycles
+    pub fn with_doppler_threshold_cycles(&self, threshold_cycles: f64) -> Self {
+        let mut s = *self;
+        s.doppler_threshold_cycles = threshold_cycles;
+        s
+    }
+}
+
+/// MW arc state: running average
+struct MwArc {
+    t: Epoch,
+    mean: f64,
+    samples: usize,
+}
+
+impl Rinex {
+    /// Detects cycle slips on all phase observations of this Observation [Rinex],
+    /// per [SV] arc, using the geometry free (GF) and Melbourne-Wübbena (MW)
+    /// combinations on dual frequency signals, and the Doppler observations on
+    /// single frequency signals (same signal code). Arcs are interrupted by data gaps:
+    /// nothing is declared on the first epoch of each arc.
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex};
+    /// use rinex::prelude::processing::{CycleSlipDetector, CycleSlipOptions};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 L1C D1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01 109474991.854       -1000.000
+    /// > 2020 01 01 00 00  1.0000000  0  1
+    /// G01 109475991.854       -1000.000
+    /// > 2020 01 01 00 00  2.0000000  0  1
+    /// G01 109477001.854       -1000.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // 10 cycles jump on 3rd epoch
+    /// let slips = rinex.cycle_slips(&CycleSlipOptions::default());
+    /// assert_eq!(slips.len(), 1);
+    /// assert_eq!(slips[0].observable, Observable::from_str("L1C").unwrap());
+    /// assert_eq!(slips[0].detector, CycleSlipDetector::Doppler);
+    /// ```
+    pub fn cycle_slips(&self, opts: &CycleSlipOptions) -> Vec<CycleSlip> {
+        let dominant_sampling = match self.sampling_interval() {
+            Some(dt) => dt,
+            None => return Vec::new(), // can't proceed without sampling interval guess.
+        };
+
+        // dual frequency: signals that are combined at each epoch
+        let mut combined = BTreeMap::<(Epoch, SV), BTreeSet<Observable>>::new();
+        let mut detected = BTreeMap::<(Epoch, SV, Observable), CycleSlipDetector>::new();
+
+        // GF: epoch to epoch variation
+        let mut past = HashMap::<(SV, Observable, Observable), (Epoch, f64)>::new();
+
+        for (k, gf) in self
+            .geo_free()
+            .into_iter()
+            .filter(|(k, _)| k.lhs.is_phase_range_observable())
+        {
+            let combined = combined.entry((k.epoch, k.sv)).or_default();
+            combined.insert(k.lhs.clone());
+            combined.insert(k.reference.clone());
+
+            let arc = (k.sv, k.lhs.clone(), k.reference.clone());
+
+            if let Some((t, prev)) = past.get(&arc) {
+                if k.epoch - *t <= dominant_sampling && (gf - prev).abs() > opts.gf_threshold_m {
+                    for observable in [&k.lhs, &k.reference] {
+                        detected
+                            .entry((k.epoch, k.sv, observable.clone()))
+                            .or_insert(CycleSlipDetector::GeometryFree);
+                    }
+                }
+            }
+
+            past.insert(arc, (k.epoch, gf));
+        }
+
+        // MW: deviation from arc average
+        let mut arcs = HashMap::<(SV, Observable, Observable), MwArc>::new();
+
+        for (k, mw) in self.melbourne_wubbena() {
+            let arc = (k.sv, k.lhs.clone(), k.reference.clone());
+
+            // None: new arc
+            let slip = match arcs.get_mut(&arc) {
+                Some(state) if k.epoch - state.t <= dominant_sampling => {
+                    if (mw - state.mean).abs() > opts.mw_threshold_m {
+                        Some(true)
+                    } else {
+                        state.samples += 1;
+                        state.mean += (mw - state.mean) / state.samples as f64;
+                        state.t = k.epoch;
+                        Some(false)
+                    }
+                },
+                _ => None,
+            };
+
+            if slip == Some(true) {
+                for observable in [&k.lhs, &k.reference] {
+                    detected
+                        .entry((k.epoch, k.sv, observable.clone()))
+                        .or_insert(CycleSlipDetector::MelbourneWubbena);
+                }
+            }
+
+            if slip != Some(false) {
+                arcs.insert(
+                    arc,
+                    MwArc {
+                        t: k.epoch,
+                        mean: mw,
+                        samples: 1,
+                    },
+                );
+            }
+        }
+
+        // single frequency: Doppler fallback
+        let mut arcs = HashMap::<(SV, Observable), DopplerArc>::new();
+
+        for (k, v) in self.observations_iter() {
+            for phase in v.signals.iter() {
+                if !phase.observable.is_phase_range_observable() {
+                    continue;
+                }
+
+                let is_combined = combined
+                    .get(&(k.epoch, phase.sv))
+                    .map(|combined| combined.contains(&phase.observable))
+                    .unwrap_or(false);
+
+                if is_combined {
+                    continue;
+                }
+
+                let doppler = match DopplerArc::matching_doppler(&v.signals, phase) {
+                    Some(doppler) => doppler,
+                    None => continue,
+                };
+
+                let arc = (phase.sv, phase.observable.clone());
+
+                if let Some(state) = arcs.get(&arc) {
+                    if let Some(residual) =
+                        state.residual_cycles(k.epoch, phase.value, doppler, dominant_sampling)
+                    {
+                        if residual.abs() > opts.doppler_threshold_cycles {
+                            detected
+                                .entry((k.epoch, phase.sv, phase.observable.clone()))
+                                .or_insert(CycleSlipDetector::Doppler);
+                        }
+                    }
+                }
+
+                arcs.insert(arc, DopplerArc::new(k.epoch, phase.value, doppler));
+            }
+        }
+
+        detected
+            .into_iter()
+            .map(|((epoch, sv, observable), detector)| CycleSlip {
+                epoch,
+                sv,
+                observable,
+                detector,
+            })
+            .collect()
+    }
+
+    /// Detects cycle slips (see [Self::cycle_slips]) and sets the
+    /// [LliFlags::LOCK_LOSS] flag of each phase observation that (possibly) slipped.
+    pub fn flag_cycle_slips_mut(&mut self, opts: &CycleSlipOptions) {
+        let slips = self
+            .cycle_slips(opts)
+            .into_iter()
+            .map(|slip| (slip.epoch, slip.sv, slip.observable))
+            .collect::<BTreeSet<_>>();
+
+        if let Some(record) = self.record.as_mut_obs() {
+            for (k, v) in record.iter_mut() {
+                for sig in v.signals.iter_mut() {
+                    if slips.contains(&(k.epoch, sig.sv, sig.observable.clone())) {
+                        sig.lli =
+                            Some(sig.lli.unwrap_or(LliFlags::OK_OR_UNKNOWN) | LliFlags::LOCK_LOSS);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Copies and returns [Rinex] with LLI flags set on detected cycle slips.
+    /// See [Self::flag_cycle_slips_mut].
+    pub fn flag_cycle_slips(&self, opts: &CycleSlipOptions) -> Self {
+        let mut s = self.clone();
+        s.flag_cycle_slips_mut(opts);
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CycleSlipDetector, CycleSlipOptions};
+    use crate::{
+        carrier::Carrier,
+        observation::{LliFlags, ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn dual_frequency_cycle_slips() {
+        let g01 = SV::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+
+        let (w1, w2) = (Carrier::L1.wavelength(), Carrier::L2.wavelength());
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(Duration::from_seconds(30.0));
+
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        for i in 0..10 {
+            let range_m = 20_000_000.0 + 300.0 * i as f64;
+
+            // L2: 1 cycle slip on 5th epoch
+            let n2 = if i < 5 { 1000.0 } else { 1001.0 };
+
+            // 10th epoch: data gap
+            let epoch = if i == 9 {
+                t0 + 20.0 * Duration::from_seconds(30.0)
+            } else {
+                t0 + i as f64 * Duration::from_seconds(30.0)
+            };
+
+            let mut obs = Observations::default();
+            for (observable, value) in [
+                ("C1C", range_m),
+                ("L1C", range_m / w1 + 1234.0),
+                ("C2W", range_m),
+                ("L2W", range_m / w2 + n2 + if i == 9 { 10.0 } else { 0.0 }),
+            ] {
+                obs.signals.push(SignalObservation::new(
+                    g01,
+                    Observable::from_str(observable).unwrap(),
+                    value,
+                ));
+            }
+
+            let key = ObsKey {
+                epoch,
+                flag: Default::default(),
+            };
+            record.insert(key, obs);
+        }
+
+        let slips = rinex.cycle_slips(&CycleSlipOptions::default());
+
+        // both L1 and L2 are reported
+        assert_eq!(slips.len(), 2, "{:#?}", slips);
+        for slip in slips.iter() {
+            assert_eq!(slip.epoch, t0 + 5.0 * Duration::from_seconds(30.0));
+            assert_eq!(slip.sv, g01);
+            assert_eq!(slip.detector, CycleSlipDetector::GeometryFree);
+        }
+
+        // GF is disabled: MW still detects it
+        let opts = CycleSlipOptions::default()
+            .with_gf_threshold_m(f64::INFINITY)
+            .with_mw_threshold_m(0.5);
+        let slips = rinex.cycle_slips(&opts);
+        assert_eq!(slips.len(), 2, "{:#?}", slips);
+        assert!(slips
+            .iter()
+            .all(|slip| slip.detector == CycleSlipDetector::MelbourneWubbena));
+
+        let flagged = rinex.flag_cycle_slips(&CycleSlipOptions::default());
+        let num_flagged = flagged
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.lli == Some(LliFlags::LOCK_LOSS))
+            .count();
+        assert_eq!(num_flagged, 2);
+    }
+}
@@ -1,8 +1,12 @@
 use crate::{
-    clock::record::clock_decim_mut, doris::decim::decim_mut as doris_decim_mut,
-    ionex::decim_mut as ionex_decim_mut, meteo::decim::decim_mut as meteo_decim_mut,
+    clock::record::clock_decim_mut,
+    doris::decim::decim_mut as doris_decim_mut,
+    ionex::decim_mut as ionex_decim_mut,
+    meteo::decim::decim_mut as meteo_decim_mut,
     navigation::decim::decim_mut as navigation_decim_mut,
-    observation::decim::decim_mut as observation_decim_mut, prelude::Rinex,
+    observation::decim::decim_mut as observation_decim_mut,
+    prelude::{Duration, Rinex},
+    processing::decimation_filter_log,
 };
 
 use qc_traits::{Decimate, DecimationFilter};
@@ -15,6 +19,8 @@ impl Decimate for Rinex {
     }
     fn decimate_mut(&mut self, f: &DecimationFilter) {
         self.header.decimate_mut(f);
+        self.header
+            .push_processing_log(None, "DECIMATION", &decimation_filter_log(f));
 
         if let Some(rec) = self.record.as_mut_obs() {
             observation_decim_mut(rec, f)
@@ -29,5 +35,74 @@ impl Decimate for Rinex {
         } else if let Some(rec) = self.record.as_mut_ionex() {
             ionex_decim_mut(rec, f)
         }
+
+        // preserve INTERVAL consistency
+        if self.header.sampling_interval.is_some() {
+            if let Some(dt) = self.dominant_sampling_interval() {
+                self.header.sampling_interval = Some(dt);
+            }
+        }
+    }
+}
+
+impl Rinex {
+    /// Decimates this [Rinex] in place, retaining one epoch every `n` epochs.
+    /// The "INTERVAL" header field, when declared, is updated accordingly.
+    /// See [Self::decimate_by_ratio].
+    pub fn decimate_by_ratio_mut(&mut self, n: u32) {
+        self.decimate_mut(&DecimationFilter::modulo(n.max(1)));
+    }
+
+    /// Copies and returns a [Rinex] that only retains one epoch every `n` epochs,
+    /// starting with the first one. Applies to all epoch indexed records
+    /// (Observation, Meteo, Clock..).
+    /// The "INTERVAL" header field, when declared, is updated accordingly.
+    /// ```
+    /// use rinex::prelude::{Duration, Rinex};
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    ///     30.000                                                  INTERVAL
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// > 2020 01 01 00 01  0.0000000  0  1
+    /// G01  20832395.682
+    /// > 2020 01 01 00 01 30.0000000  0  1
+    /// G01  20832396.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let decimated = rinex.decimate_by_ratio(2);
+    /// assert_eq!(decimated.epoch_iter().count(), 2);
+    ///
+    /// assert_eq!(
+    ///     decimated.header.sampling_interval,
+    ///     Some(Duration::from_seconds(60.0)));
+    /// ```
+    pub fn decimate_by_ratio(&self, n: u32) -> Self {
+        let mut s = self.clone();
+        s.decimate_by_ratio_mut(n);
+        s
+    }
+
+    /// Decimates this [Rinex] in place, so epochs are spaced by at least `interval`.
+    /// See [Self::decimate_by_interval].
+    pub fn decimate_by_interval_mut(&mut self, interval: Duration) {
+        self.decimate_mut(&DecimationFilter::duration(interval));
+    }
+
+    /// Copies and returns a [Rinex] where epochs are spaced by at least `interval`,
+    /// starting with the first one. Applies to all epoch indexed records
+    /// (Observation, Meteo, Clock..).
+    /// The "INTERVAL" header field, when declared, is updated accordingly.
+    pub fn decimate_by_interval(&self, interval: Duration) -> Self {
+        let mut s = self.clone();
+        s.decimate_by_interval_mut(interval);
+        s
     }
 }
@@ -0,0 +1,253 @@
+//! Double differences
+use crate::{
+    observation::SvDifferenceKey,
+    prelude::{Constellation, Epoch, Rinex, SV},
+};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// [ReferenceStrategy] selects the reference [SV] of each [Constellation],
+/// at each [Epoch], in [double_difference].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ReferenceStrategy {
+    /// This [SV] is the reference at all epochs where it is observed by both receivers.
+    /// Other [Constellation]s are not differenced.
+    Fixed(SV),
+    /// The [SV] with the strongest C/N0 (on rover side) is selected,
+    /// among [SV]s observed by both receivers. Lowest PRN is selected
+    /// when C/N0 is not available.
+    #[default]
+    StrongestSignal,
+    /// The [SV] with the lowest PRN is selected, among [SV]s observed by both receivers.
+    LowestPrn,
+}
+
+/// Forms double differences of a rover and a base Observation [Rinex]:
+/// between receivers single differences (rover - base, see [Rinex::single_difference]) are
+/// differenced between satellites (sv - reference, see [Rinex::between_sv_difference]), to a
+/// reference [SV] of the same [Constellation] selected at each [Epoch] with this [ReferenceStrategy].
+/// Receiver and satellite clock errors cancel out, which leaves the baseline geometry
+/// and phase ambiguities. [SV]s whose reference lacks an [Observable] are not differenced
+/// for that [Observable]. Double differences are expressed in the [Observable] unit
+/// (cycles for phase observations).
+/// ```
+/// use rinex::prelude::{processing::{double_difference, ReferenceStrategy}, Rinex, SV};
+/// use std::io::BufReader;
+/// use std::str::FromStr;
+///
+/// let rover = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+/// G    2 C1C S1C                                              SYS / # / OBS TYPES
+///                                                             END OF HEADER
+/// > 2020 01 01 00 00  0.0000000  0  3
+/// G01  20000010.000          40.000
+/// G02  21000020.000          45.000
+/// G03  22000040.000          35.000
+/// ";
+///
+/// let base = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+/// G    1 C1C                                                  SYS / # / OBS TYPES
+///                                                             END OF HEADER
+/// > 2020 01 01 00 00  0.0000000  0  3
+/// G01  20000000.000
+/// G02  21000000.000
+/// G03  22000000.000
+/// ";
+///
+/// let rover = Rinex::parse(&mut BufReader::new(rover.as_bytes())).unwrap();
+/// let base = Rinex::parse(&mut BufReader::new(base.as_bytes())).unwrap();
+///
+/// let dd = double_difference(&rover, &base, ReferenceStrategy::StrongestSignal);
+/// assert_eq!(dd.len(), 2);
+///
+/// let g02 = SV::from_str("G02").unwrap();
+///
+/// for (k, value) in dd.iter() {
+///     assert_eq!(k.reference, g02);
+///     if k.sv == SV::from_str("G01").unwrap() {
+///         assert_eq!(*value, -10.0);
+///     } else {
+///         assert_eq!(*value, 20.0);
+///     }
+/// }
+/// ```
+pub fn double_difference(
+    rover: &Rinex,
+    base: &Rinex,
+    strategy: ReferenceStrategy,
+) -> BTreeMap<SvDifferenceKey, f64> {
+    let single_differences = rover.single_difference(base);
+
+    let common = single_differences
+        .keys()
+        .map(|k| (k.epoch, k.sv))
+        .collect::<BTreeSet<_>>();
+
+    let mut references = BTreeMap::<(Epoch, Constellation), SV>::new();
+
+    match strategy {
+        ReferenceStrategy::Fixed(reference) => {
+            for (epoch, _) in common.iter().filter(|(_, sv)| *sv == reference) {
+                references.insert((*epoch, reference.constellation), reference);
+            }
+        },
+        ReferenceStrategy::LowestPrn => {
+            for (epoch, sv) in common.iter() {
+                // sorted by SV
+                references.entry((*epoch, sv.constellation)).or_insert(*sv);
+            }
+        },
+        ReferenceStrategy::StrongestSignal => {
+            let mut strongest = BTreeMap::<(Epoch, Constellation), (SV, f64)>::new();
+
+            for (k, obs) in rover.observations_iter() {
+                for sig in obs.signals.iter() {
+                    if !common.contains(&(k.epoch, sig.sv)) {
+                        continue;
+                    }
+
+                    let dbhz = rover.signal_dbhz(obs, sig).unwrap_or(f64::NEG_INFINITY);
+
+                    strongest
+                        .entry((k.epoch, sig.sv.constellation))
+                        .and_modify(|(sv, max)| {
+                            if dbhz > *max || (dbhz == *max && sig.sv < *sv) {
+                                *sv = sig.sv;
+                                *max = dbhz;
+                            }
+                        })
+                        .or_insert((sig.sv, dbhz));
+                }
+            }
+
+            references = strongest.into_iter().map(|(k, (sv, _))| (k, sv)).collect();
+        },
+    }
+
+    let values = single_differences
+        .iter()
+        .map(|(k, value)| (k.epoch, k.sv, &k.observable, *value));
+
+    crate::observation::sv_differences(values, |epoch, constellation| {
+        references.get(&(epoch, constellation)).copied()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{double_difference, ReferenceStrategy};
+    use crate::{
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn double_differences() {
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let (g01, g02, g03, e01, e02) = (
+            SV::from_str("G01").unwrap(),
+            SV::from_str("G02").unwrap(),
+            SV::from_str("G03").unwrap(),
+            SV::from_str("E01").unwrap(),
+            SV::from_str("E02").unwrap(),
+        );
+
+        let (c1c, s1c) = (
+            Observable::from_str("C1C").unwrap(),
+            Observable::from_str("S1C").unwrap(),
+        );
+
+        let geometry = |sv: SV| match sv.prn {
+            1 => 1.0,
+            2 => 2.0,
+            _ => 3.0,
+        };
+
+        let mut rover = Rinex::basic_obs();
+        let mut base = Rinex::basic_obs();
+
+        for i in 0..3 {
+            let epoch = t0 + i as f64 * dt;
+            let key = ObsKey {
+                epoch,
+                flag: Default::default(),
+            };
+
+            let mut rover_obs = Observations::default();
+            let mut base_obs = Observations::default();
+
+            for sv in [g01, g02, g03, e01, e02] {
+                // satellite clock (cancels out), receiver clocks (cancel out)
+                let sv_clock = 10.0 * sv.prn as f64;
+                let range = 20.0E6 + sv_clock + 1000.0 * i as f64;
+
+                rover_obs.signals.push(SignalObservation::new(
+                    sv,
+                    c1c.clone(),
+                    range + 5.0 + geometry(sv),
+                ));
+
+                // G03 is the strongest, except at last epoch
+                let dbhz = if sv == g03 && i < 2 { 50.0 } else { 40.0 };
+                rover_obs
+                    .signals
+                    .push(SignalObservation::new(sv, s1c.clone(), dbhz));
+
+                // G01 is not observed by the base, at first epoch
+                if sv == g01 && i == 0 {
+                    continue;
+                }
+
+                base_obs
+                    .signals
+                    .push(SignalObservation::new(sv, c1c.clone(), range - 7.0));
+            }
+
+            rover.record.as_mut_obs().unwrap().insert(key, rover_obs);
+            base.record.as_mut_obs().unwrap().insert(key, base_obs);
+        }
+
+        let dd = double_difference(&rover, &base, ReferenceStrategy::Fixed(g01));
+
+        // G01 common to both at 2 epochs
+        assert_eq!(dd.len(), 4);
+        for (k, value) in dd.iter() {
+            assert_eq!(k.reference, g01);
+            assert_eq!(k.observable, c1c);
+            assert!(k.epoch > t0);
+            assert_eq!(*value, geometry(k.sv) - geometry(g01));
+        }
+
+        let dd = double_difference(&rover, &base, ReferenceStrategy::LowestPrn);
+
+        // (G03, G02)/(G01) + (E02/E01) at 2 epochs, (G03/G02) + (E02/E01) at first epoch
+        assert_eq!(dd.len(), 8);
+        for (k, value) in dd.iter() {
+            if k.epoch == t0 && k.sv.constellation == g01.constellation {
+                assert_eq!(k.reference, g02);
+            } else if k.sv.constellation == g01.constellation {
+                assert_eq!(k.reference, g01);
+            } else {
+                assert_eq!(k.reference, e01);
+            }
+            assert_eq!(*value, geometry(k.sv) - geometry(k.reference));
+        }
+
+        let dd = double_difference(&rover, &base, ReferenceStrategy::StrongestSignal);
+        assert_eq!(dd.len(), 8);
+        for (k, value) in dd.iter() {
+            if k.sv.constellation == g01.constellation {
+                if k.epoch < t0 + 2.0 * dt {
+                    assert_eq!(k.reference, g03);
+                } else {
+                    assert_eq!(k.reference, g01);
+                }
+            } else {
+                assert_eq!(k.reference, e01);
+            }
+            assert_eq!(*value, geometry(k.sv) - geometry(k.reference));
+        }
+    }
+}
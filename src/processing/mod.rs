@@ -1,10 +1,103 @@
-use qc_traits::Preprocessing;
+use qc_traits::{
+    DecimationFilter, DecimationFilterType, FilterItem, MaskFilter, MaskOperand, Preprocessing,
+};
 
 use crate::prelude::Rinex;
 
+use itertools::Itertools;
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod cycle_slips; // phase processing
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod differencing; // double differences
+
+#[cfg(feature = "obs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "obs")))]
+pub(crate) mod smoothing; // carrier smoothing
+
 mod decim;
 mod repair;
 mod split;
 mod timeshift;
 
 impl Preprocessing for Rinex {}
+
+/// Describes this [FilterItem] in processing logs, as (name, value)
+fn filter_item_log(item: &FilterItem) -> (&'static str, String) {
+    match item {
+        FilterItem::EpochItem(t) => ("epoch", t.to_string()),
+        FilterItem::DurationItem(dt) => ("duration", dt.to_string()),
+        FilterItem::SNRItem(snr) => ("snr", snr.to_string()),
+        FilterItem::ElevationItem(elev) => ("elevation", elev.to_string()),
+        FilterItem::AzimuthItem(azim) => ("azimuth", azim.to_string()),
+        FilterItem::SvItem(svs) => ("sv", svs.iter().join(",")),
+        FilterItem::ConstellationItem(gnss) => ("gnss", gnss.iter().join(",")),
+        FilterItem::ClockItem => ("clock", String::new()),
+        FilterItem::ComplexItem(items) => ("items", items.join(",")),
+    }
+}
+
+/// Describes this [MaskFilter] in processing logs, for example "elevation>10"
+pub(crate) fn mask_filter_log(f: &MaskFilter) -> String {
+    let operand = match f.operand {
+        MaskOperand::Equals => "=",
+        MaskOperand::NotEquals => "!=",
+        MaskOperand::GreaterThan => ">",
+        MaskOperand::GreaterEquals => ">=",
+        MaskOperand::LowerThan => "<",
+        MaskOperand::LowerEquals => "<=",
+    };
+    let (name, value) = filter_item_log(&f.item);
+    format!("{}{}{}", name, operand, value)
+}
+
+/// Describes this [DecimationFilter] in processing logs, for example "modulo=2"
+pub(crate) fn decimation_filter_log(f: &DecimationFilter) -> String {
+    let filter = match f.filter {
+        DecimationFilterType::Modulo(modulo) => format!("modulo={}", modulo),
+        DecimationFilterType::Duration(dt) => format!("interval={}", dt),
+    };
+    match &f.item {
+        Some(item) => {
+            let (name, value) = filter_item_log(item);
+            format!("{} {}={}", filter, name, value)
+        },
+        None => filter,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decimation_filter_log, mask_filter_log};
+    use crate::prelude::{Duration, SV};
+    use qc_traits::{DecimationFilter, FilterItem, MaskFilter, MaskOperand};
+    use std::str::FromStr;
+
+    #[test]
+    fn processing_log_parameters() {
+        let mask = MaskFilter {
+            item: FilterItem::ElevationItem(10.0),
+            operand: MaskOperand::GreaterEquals,
+        };
+        assert_eq!(mask_filter_log(&mask), "elevation>=10");
+
+        let mask = MaskFilter {
+            item: FilterItem::SvItem(vec![
+                SV::from_str("G01").unwrap(),
+                SV::from_str("E05").unwrap(),
+            ]),
+            operand: MaskOperand::NotEquals,
+        };
+        assert_eq!(mask_filter_log(&mask), "sv!=G01,E05");
+
+        let decim = DecimationFilter::duration(Duration::from_seconds(30.0));
+        assert_eq!(decimation_filter_log(&decim), "interval=30 s");
+
+        let mut decim = DecimationFilter::modulo(2);
+        decim.item = Some(FilterItem::SNRItem(30.0));
+        assert_eq!(decimation_filter_log(&decim), "modulo=2 snr=30");
+    }
+}
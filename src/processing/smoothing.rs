@@ -0,0 +1,233 @@
+//! Carrier smoothing of pseudo ranges
+use crate::{prelude::Rinex, processing::cycle_slips::CycleSlipOptions};
+
+use std::collections::HashMap;
+
+/// [HatchOptions] to tune [Rinex::hatch_filter]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HatchOptions {
+    /// Maximal number of samples (filter length)
+    pub window: usize,
+    /// Reset the filter on phase discontinuities that were not flagged by the receiver,
+    /// using this cycle slip detector (see [Rinex::cycle_slips]).
+    /// When None, the filter is only reset on data gaps and LLI lock losses.
+    pub cycle_slips: Option<CycleSlipOptions>,
+}
+
+impl Default for HatchOptions {
+    /// Builds [HatchOptions] with a 100 samples window
+    /// (typical value at 1s) and default cycle slip detection
+    fn default() -> Self {
+        Self {
+            window: 100,
+            cycle_slips: Some(CycleSlipOptions::default()),
+        }
+    }
+}
+
+impl HatchOptions {
+    /// Copies and returns [HatchOptions] with desired filter length, in samples
+    pub fn with_window(&self, window: usize) -> Self {
+        let mut s = *self;
+        s.window = window;
+        s
+    }
+
+    /// Copies and returns [HatchOptions] with desired cycle slip detection
+    pub fn with_cycle_slip_detection(&self, opts: CycleSlipOptions) -> Self {
+        let mut s = *self;
+        s.cycle_slips = Some(opts);
+        s
+    }
+
+    /// Copies and returns [HatchOptions] without cycle slip detection:
+    /// the filter is only reset on data gaps and LLI lock losses
+    pub fn without_cycle_slip_detection(&self) -> Self {
+        let mut s = *self;
+        s.cycle_slips = None;
+        s
+    }
+}
+
+impl Rinex {
+    /// Smoothes all pseudo range observations with the phase range observed on the
+    /// same carrier (Hatch filter), in place. See [Self::hatch_filter].
+    pub fn hatch_filter_mut(&mut self, opts: &HatchOptions) {
+        let smoothed = match opts.cycle_slips {
+            Some(cs_opts) => self
+                .flag_cycle_slips(&cs_opts)
+                .hatch_filter_residuals(opts.window),
+            None => self.hatch_filter_residuals(opts.window),
+        }
+        .into_iter()
+        .map(|(k, residuals)| ((k.epoch, k.sv, k.code), residuals.smoothed))
+        .collect::<HashMap<_, _>>();
+
+        if let Some(record) = self.record.as_mut_obs() {
+            for (k, v) in record.iter_mut() {
+                for sig in v.signals.iter_mut() {
+                    let key = (k.epoch, sig.sv, sig.observable.clone());
+                    if let Some(smoothed) = smoothed.get(&key) {
+                        sig.value = *smoothed;
+                    }
+                }
+            }
+        }
+
+        self.header
+            .push_processing_log(None, "HATCH FILTER", &format!("window={}", opts.window));
+    }
+
+    /// Copies and returns a [Rinex] where all pseudo range observations were
+    /// smoothed by the phase range observed on the same carrier (Hatch filter).
+    /// Phase (and other) observations are preserved, as well as pseudo ranges
+    /// that could not be smoothed (no phase on that carrier).
+    /// The filter is reset on data gaps, on phase lock losses (LLI) and,
+    /// if desired, on cycle slips detected with [HatchOptions::cycle_slips].
+    /// Each smoothing step is described by [Self::hatch_filter_residuals].
+    /// ```
+    /// use rinex::prelude::{Observable, Rinex};
+    /// use rinex::prelude::processing::HatchOptions;
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    2 C1C L1C                                              SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20000001.000   105100000.000
+    /// > 2020 01 01 00 00  1.0000000  0  1
+    /// G01  19999999.000   105100000.000
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let opts = HatchOptions::default()
+    ///     .with_window(2);
+    ///
+    /// let smoothed = rinex.hatch_filter(&opts);
+    ///
+    /// let c1c = Observable::from_str("C1C").unwrap();
+    ///
+    /// let values = smoothed
+    ///     .signal_observations_iter()
+    ///     .filter(|(_, sig)| sig.observable == c1c)
+    ///     .map(|(_, sig)| sig.value)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// // pseudo range noise was averaged out
+    /// assert_eq!(values, vec![20000001.0, 20000000.0]);
+    /// ```
+    pub fn hatch_filter(&self, opts: &HatchOptions) -> Self {
+        let mut s = self.clone();
+        s.hatch_filter_mut(opts);
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HatchOptions;
+    use crate::{
+        carrier::Carrier,
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Duration, Epoch, Observable, Rinex, SV},
+    };
+    use std::str::FromStr;
+
+    fn dual_frequency_rinex(slip_epoch: usize) -> Rinex {
+        let g01 = SV::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 GPST").unwrap();
+
+        let (w1, w2) = (Carrier::L1.wavelength(), Carrier::L2.wavelength());
+
+        let mut rinex = Rinex::basic_obs();
+        rinex.header.sampling_interval = Some(Duration::from_seconds(30.0));
+
+        let record = rinex.record.as_mut_obs().unwrap();
+
+        for i in 0..10 {
+            let range_m = 20_000_000.0 + 100.0 * i as f64;
+            let noise = if i % 2 == 0 { 2.0 } else { -2.0 };
+            let n1 = if i < slip_epoch { 1000.0 } else { 1010.0 };
+
+            let mut obs = Observations::default();
+            obs.signals.push(SignalObservation::new(
+                g01,
+                Observable::from_str("C1C").unwrap(),
+                range_m + noise,
+            ));
+            obs.signals.push(SignalObservation::new(
+                g01,
+                Observable::from_str("L1C").unwrap(),
+                range_m / w1 + n1,
+            ));
+            obs.signals.push(SignalObservation::new(
+                g01,
+                Observable::from_str("L2W").unwrap(),
+                range_m / w2 + 2000.0,
+            ));
+
+            let key = ObsKey {
+                epoch: t0 + i as f64 * Duration::from_seconds(30.0),
+                flag: Default::default(),
+            };
+            record.insert(key, obs);
+        }
+
+        rinex
+    }
+
+    fn smoothed_code(rinex: &Rinex) -> Vec<f64> {
+        let c1c = Observable::from_str("C1C").unwrap();
+        rinex
+            .signal_observations_iter()
+            .filter(|(_, sig)| sig.observable == c1c)
+            .map(|(_, sig)| sig.value)
+            .collect()
+    }
+
+    #[test]
+    fn hatch_filter_derived_record() {
+        let rinex = dual_frequency_rinex(10);
+        let raw = smoothed_code(&rinex);
+
+        let opts = HatchOptions::default().with_window(4);
+        let smoothed = rinex.hatch_filter(&opts);
+
+        // phase observations are preserved
+        assert_eq!(
+            smoothed.signal_observations_iter().count(),
+            rinex.signal_observations_iter().count()
+        );
+
+        let values = smoothed_code(&smoothed);
+        assert_eq!(values[0], raw[0]);
+
+        // noise is reduced once the filter has converged
+        for (i, value) in values.iter().enumerate().skip(4) {
+            let range_m = 20_000_000.0 + 100.0 * i as f64;
+            assert!((value - range_m).abs() < 1.0, "#{}: {}", i, value);
+        }
+    }
+
+    #[test]
+    fn hatch_filter_cycle_slip_reset() {
+        let rinex = dual_frequency_rinex(6);
+        let raw = smoothed_code(&rinex);
+
+        let opts = HatchOptions::default().with_window(4);
+
+        // filter is reset on the undeclared cycle slip
+        let values = smoothed_code(&rinex.hatch_filter(&opts));
+        assert_eq!(values[6], raw[6]);
+        assert_ne!(values[7], raw[7]);
+
+        // phase jump corrupts the smoothed pseudo range
+        let values = smoothed_code(&rinex.hatch_filter(&opts.without_cycle_slip_detection()));
+        assert_ne!(values[6], raw[6]);
+
+        let range_m = 20_000_000.0 + 600.0;
+        assert!((values[6] - range_m).abs() > 1.0);
+    }
+}
@@ -1,9 +1,27 @@
-use crate::header::Header;
+use crate::{
+    header::Header,
+    prelude::{Duration, Epoch},
+};
 
 use qc_traits::Split;
 
+impl Header {
+    /// Returns the (first, last) time frame described by this [Header], if any.
+    fn split_time_frame(&self) -> Option<(Epoch, Epoch)> {
+        if let Some(obs) = &self.obs {
+            Some((obs.timeof_first_obs?, obs.timeof_last_obs?))
+        } else if let Some(doris) = &self.doris {
+            Some((doris.timeof_first_obs?, doris.timeof_last_obs?))
+        } else {
+            self.ionex
+                .as_ref()
+                .map(|ionex| (ionex.epoch_of_first_map, ionex.epoch_of_last_map))
+        }
+    }
+}
+
 impl Split for Header {
-    fn split(&self, t: hifitime::Epoch) -> (Self, Self)
+    fn split(&self, t: Epoch) -> (Self, Self)
     where
         Self: Sized,
     {
@@ -14,7 +32,7 @@ impl Split for Header {
                 *timeof = std::cmp::min(*timeof, t);
             }
             if let Some(timeof) = &mut obs.timeof_last_obs {
-                *timeof = std::cmp::max(*timeof, t);
+                *timeof = std::cmp::min(*timeof, t);
             }
         }
 
@@ -22,6 +40,9 @@ impl Split for Header {
             if let Some(timeof) = &mut obs.timeof_first_obs {
                 *timeof = std::cmp::max(*timeof, t);
             }
+            if let Some(timeof) = &mut obs.timeof_last_obs {
+                *timeof = std::cmp::max(*timeof, t);
+            }
         }
 
         if let Some(doris) = &mut a.doris {
@@ -29,38 +50,59 @@ impl Split for Header {
                 *timeof = std::cmp::min(*timeof, t);
             }
             if let Some(timeof) = &mut doris.timeof_last_obs {
-                *timeof = std::cmp::max(*timeof, t);
+                *timeof = std::cmp::min(*timeof, t);
             }
         }
 
-        if let Some(obs) = &mut b.doris {
-            if let Some(timeof) = &mut obs.timeof_first_obs {
+        if let Some(doris) = &mut b.doris {
+            if let Some(timeof) = &mut doris.timeof_first_obs {
+                *timeof = std::cmp::max(*timeof, t);
+            }
+            if let Some(timeof) = &mut doris.timeof_last_obs {
                 *timeof = std::cmp::max(*timeof, t);
             }
         }
 
         if let Some(ion) = &mut a.ionex {
             ion.epoch_of_first_map = std::cmp::min(ion.epoch_of_first_map, t);
-            ion.epoch_of_last_map = std::cmp::max(ion.epoch_of_last_map, t);
+            ion.epoch_of_last_map = std::cmp::min(ion.epoch_of_last_map, t);
         }
 
         if let Some(ion) = &mut b.ionex {
             ion.epoch_of_first_map = std::cmp::max(ion.epoch_of_first_map, t);
+            ion.epoch_of_last_map = std::cmp::max(ion.epoch_of_last_map, t);
         }
 
         (a, b)
     }
 
-    fn split_even_dt(&self, _dt: hifitime::Duration) -> Vec<Self>
+    fn split_even_dt(&self, dt: Duration) -> Vec<Self>
     where
         Self: Sized,
     {
-        let ret = Vec::<Self>::new();
+        let (first, last) = match self.split_time_frame() {
+            Some(frame) if dt.total_nanoseconds() > 0 => frame,
+            _ => return vec![self.clone()],
+        };
+
+        let mut ret = Vec::new();
+        let mut remainder = self.clone();
+        let mut t = first.floor(dt) + dt;
+
+        while t <= last {
+            let (a, b) = remainder.split(t);
+            ret.push(a);
+            remainder = b;
+            t += dt;
+        }
+
+        ret.push(remainder);
         ret
     }
 
-    fn split_mut(&mut self, _t: hifitime::Epoch) -> Self {
-        let copy = self.clone();
-        copy
+    fn split_mut(&mut self, t: Epoch) -> Self {
+        let (a, b) = self.split(t);
+        *self = a;
+        b
     }
 }
@@ -1,168 +1,122 @@
-use crate::prelude::{Duration, Epoch, Header, Record, Rinex};
+use crate::prelude::{Duration, Epoch, Rinex};
 
 use gnss_qc_traits::Split;
 
-mod clock;
-mod doris;
 mod header;
-mod ionex;
-mod meteo;
-mod nav;
-mod obs;
 mod production;
 
-use obs::{split as obs_split, split_even_dt as obs_split_even_dt, split_mut as obs_split_mut};
-
-use nav::{split as nav_split, split_even_dt as nav_split_even_dt, split_mut as nav_split_mut};
-
-use meteo::{
-    split as meteo_split, split_even_dt as meteo_split_even_dt, split_mut as meteo_split_mut,
-};
-
-use clock::{
-    split as clock_split, split_even_dt as clock_split_even_dt, split_mut as clock_split_mut,
-};
-
-use ionex::{
-    split as ionex_split, split_even_dt as ionex_split_even_dt, split_mut as ionex_split_mut,
-};
-
-use doris::{
-    split as doris_split, split_even_dt as doris_split_even_dt, split_mut as doris_split_mut,
-};
-
+/// [Split] is implemented on top of [Rinex::split_at] and [Rinex::split_every],
+/// which also update the header time frame and split the comments timewise.
 impl Split for Rinex {
     fn split(&self, t: Epoch) -> (Self, Self) {
-        let (h0, h1) = self.header.split(t);
-        let (p0, p1) = self.production.split(t);
-
-        let (r0, r1) = if let Some(r) = self.record.as_obs() {
-            let (r0, r1) = obs_split(r, t);
-            (Record::ObsRecord(r0), Record::ObsRecord(r1))
-        } else if let Some(r) = self.record.as_nav() {
-            let (r0, r1) = nav_split(r, t);
-            (Record::NavRecord(r0), Record::NavRecord(r1))
-        } else if let Some(r) = self.record.as_clock() {
-            let (r0, r1) = clock_split(r, t);
-            (Record::ClockRecord(r0), Record::ClockRecord(r1))
-        } else if let Some(r) = self.record.as_ionex() {
-            let (r0, r1) = ionex_split(r, t);
-            (Record::IonexRecord(r0), Record::IonexRecord(r1))
-        } else if let Some(r) = self.record.as_doris() {
-            let (r0, r1) = doris_split(r, t);
-            (Record::DorisRecord(r0), Record::DorisRecord(r1))
-        } else if let Some(r) = self.record.as_meteo() {
-            let (r0, r1) = meteo_split(r, t);
-            (Record::MeteoRecord(r0), Record::MeteoRecord(r1))
-        } else {
-            (
-                Record::ObsRecord(Default::default()),
-                Record::ObsRecord(Default::default()),
-            )
-        };
-
-        // TODO: improve this
-        //  split comments timewise
-        //  implement Split for Header
-        //  implement Split for production attributes
-        (
-            Rinex {
-                record: r0,
-                header: h0,
-                production: p0,
-                comments: self.comments.clone(),
-            },
-            Rinex {
-                record: r1,
-                header: h1,
-                production: p1,
-                comments: self.comments.clone(),
-            },
-        )
+        let (mut a, mut b) = self.split_at(t);
+        (a.production, b.production) = self.production.split(t);
+        (a, b)
     }
 
     fn split_mut(&mut self, t: Epoch) -> Self {
-        self.header.program = Some(format!(
-            "rs-rust v{}",
-            Header::format_pkg_version(env!("CARGO_PKG_VERSION"),)
-        ));
-
-        let record = if let Some(r) = self.record.as_mut_obs() {
-            Record::ObsRecord(obs_split_mut(r, t))
-        } else if let Some(r) = self.record.as_mut_nav() {
-            Record::NavRecord(nav_split_mut(r, t))
-        } else if let Some(r) = self.record.as_mut_clock() {
-            Record::ClockRecord(clock_split_mut(r, t))
-        } else if let Some(r) = self.record.as_mut_ionex() {
-            Record::IonexRecord(ionex_split_mut(r, t))
-        } else if let Some(r) = self.record.as_mut_doris() {
-            Record::DorisRecord(doris_split_mut(r, t))
-        } else if let Some(r) = self.record.as_mut_meteo() {
-            Record::MeteoRecord(meteo_split_mut(r, t))
-        } else {
-            self.record.clone()
-        };
-
-        // TODO: improve this
-        //  split comments timewise
-        //  implement Split for Header ?
-        //  implement Split for production attributes ?
-        Self {
-            record,
-            header: self.header.clone(),
-            comments: self.comments.clone(),
-            production: self.production.clone(),
-        }
+        let (a, b) = self.split(t);
+        *self = a;
+        b
     }
 
     fn split_even_dt(&self, dt: Duration) -> Vec<Self> {
-        let records = if let Some(r) = self.record.as_obs() {
-            obs_split_even_dt(r, dt)
-                .into_iter()
-                .map(|rec| Record::ObsRecord(rec))
-                .collect::<Vec<_>>()
-        } else if let Some(r) = self.record.as_clock() {
-            clock_split_even_dt(r, dt)
-                .into_iter()
-                .map(|rec| Record::ClockRecord(rec))
-                .collect::<Vec<_>>()
-        } else if let Some(r) = self.record.as_meteo() {
-            meteo_split_even_dt(r, dt)
-                .into_iter()
-                .map(|rec| Record::MeteoRecord(rec))
-                .collect::<Vec<_>>()
-        } else if let Some(r) = self.record.as_ionex() {
-            ionex_split_even_dt(r, dt)
-                .into_iter()
-                .map(|rec| Record::IonexRecord(rec))
-                .collect::<Vec<_>>()
-        } else if let Some(r) = self.record.as_doris() {
-            doris_split_even_dt(r, dt)
-                .into_iter()
-                .map(|rec| Record::DorisRecord(rec))
-                .collect::<Vec<_>>()
-        } else if let Some(r) = self.record.as_nav() {
-            nav_split_even_dt(r, dt)
-                .into_iter()
-                .map(|rec| Record::NavRecord(rec))
-                .collect::<Vec<_>>()
-        } else {
-            Vec::new()
-        };
-
-        // TODO:
-        // comments (timewise) should be split
-        // header section could be split as well:
-        //  impl split_event_dt on Header directly
-
-        records
-            .iter()
-            .map(|rec| Rinex {
-                header: self.header.clone(),
-                comments: self.comments.clone(),
-                production: self.production.clone(),
-                record: rec.clone(),
+        self.split_every(dt)
+            .into_iter()
+            .enumerate()
+            .map(|(batch, mut rinex)| {
+                if let Some(details) = &mut rinex.production.v3_details {
+                    details.batch = batch as u8;
+                }
+                rinex
             })
             .collect()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Duration, Epoch, Rinex};
+    use gnss_qc_traits::Split;
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    const CONTENT: &str =
+        "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+  2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+  2020     1     1     0     1   30.0000000     GPS         TIME OF LAST OBS
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20832393.682
+> 2020 01 01 00 00 30.0000000  0  1
+G01  20832394.682
+> 2020 01 01 00 01  0.0000000  0  1
+G01  20832395.682
+> 2020 01 01 00 01 30.0000000  0  1
+G01  20832396.682
+";
+
+    #[test]
+    fn split_headers() {
+        let rinex = Rinex::parse(&mut BufReader::new(CONTENT.as_bytes())).unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let t1 = Epoch::from_str("2020-01-01T00:00:30 GPST").unwrap();
+        let t2 = Epoch::from_str("2020-01-01T00:01:00 GPST").unwrap();
+        let t3 = Epoch::from_str("2020-01-01T00:01:30 GPST").unwrap();
+
+        let (a, b) = rinex.split(t2);
+        assert_eq!(a.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+        assert_eq!(b.epoch_iter().collect::<Vec<_>>(), vec![t2, t3]);
+
+        let obs = a.header.obs.as_ref().unwrap();
+        assert_eq!(
+            (obs.timeof_first_obs, obs.timeof_last_obs),
+            (Some(t0), Some(t1))
+        );
+
+        let obs = b.header.obs.as_ref().unwrap();
+        assert_eq!(
+            (obs.timeof_first_obs, obs.timeof_last_obs),
+            (Some(t2), Some(t3))
+        );
+
+        // header only split: bounded by the split epoch
+        let (a, b) = rinex.header.split(t2);
+        let obs = a.obs.as_ref().unwrap();
+        assert_eq!(
+            (obs.timeof_first_obs, obs.timeof_last_obs),
+            (Some(t0), Some(t2))
+        );
+        let obs = b.obs.as_ref().unwrap();
+        assert_eq!(
+            (obs.timeof_first_obs, obs.timeof_last_obs),
+            (Some(t2), Some(t3))
+        );
+
+        let mut a = rinex.clone();
+        let b = a.split_mut(t2);
+        assert_eq!(a.epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+        assert_eq!(b.epoch_iter().collect::<Vec<_>>(), vec![t2, t3]);
+        assert_eq!(a.header.obs.as_ref().unwrap().timeof_last_obs, Some(t1));
+
+        let chunks = rinex.split_even_dt(Duration::from_seconds(60.0));
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].epoch_iter().collect::<Vec<_>>(), vec![t0, t1]);
+        assert_eq!(chunks[1].epoch_iter().collect::<Vec<_>>(), vec![t2, t3]);
+        let obs = chunks[1].header.obs.as_ref().unwrap();
+        assert_eq!(
+            (obs.timeof_first_obs, obs.timeof_last_obs),
+            (Some(t2), Some(t3))
+        );
+
+        let headers = rinex.header.split_even_dt(Duration::from_seconds(60.0));
+        assert_eq!(headers.len(), 2);
+        let obs = headers[1].obs.as_ref().unwrap();
+        assert_eq!(
+            (obs.timeof_first_obs, obs.timeof_last_obs),
+            (Some(t2), Some(t3))
+        );
+    }
+}
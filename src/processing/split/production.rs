@@ -27,7 +27,9 @@ impl Split for ProductionAttributes {
         Default::default()
     }
 
-    fn split_mut(&mut self, _t: hifitime::Epoch) -> Self {
-        Default::default()
+    fn split_mut(&mut self, t: hifitime::Epoch) -> Self {
+        let (a, b) = self.split(t);
+        *self = a;
+        b
     }
 }
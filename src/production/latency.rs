@@ -0,0 +1,217 @@
+//! Real-time stream latency analysis
+use crate::prelude::{Constellation, Duration, Epoch, SV};
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [LatencyStatistics] of a real-time data stream:
+/// difference between message arrival time and the epoch it describes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct LatencyStatistics {
+    /// Number of messages
+    pub count: usize,
+    /// Mean latency
+    pub mean: Duration,
+    /// 95th percentile of the latency
+    pub percentile_95: Duration,
+    /// Worst latency
+    pub max: Duration,
+}
+
+impl LatencyStatistics {
+    /// Builds [LatencyStatistics] from latencies, in seconds
+    fn from_latencies(latencies_s: &[f64]) -> Option<Self> {
+        if latencies_s.is_empty() {
+            return None;
+        }
+
+        let mut sorted = latencies_s.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let count = sorted.len();
+        let mean_s = sorted.iter().sum::<f64>() / count as f64;
+
+        // nearest rank method
+        let rank = ((0.95 * count as f64).ceil() as usize).clamp(1, count);
+
+        Some(Self {
+            count,
+            mean: Duration::from_seconds(mean_s),
+            percentile_95: Duration::from_seconds(sorted[rank - 1]),
+            max: Duration::from_seconds(sorted[count - 1]),
+        })
+    }
+}
+
+/// [LatencyMonitor] records the arrival time of each message of a
+/// real-time stream (RTCM, BINEX..), compared to the [Epoch] it describes,
+/// which is a key operational metric of real-time networks.
+/// ```
+/// use rinex::prelude::{Duration, Epoch, SV};
+/// use rinex::prod::LatencyMonitor;
+/// use std::str::FromStr;
+///
+/// let g01 = SV::from_str("G01").unwrap();
+/// let t = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+///
+/// let mut monitor = LatencyMonitor::default();
+/// monitor.record(g01, t, t + Duration::from_milliseconds(800.0));
+/// monitor.record(g01, t + Duration::from_seconds(1.0), t + Duration::from_seconds(2.2));
+///
+/// let stats = monitor.sv_statistics(g01).unwrap();
+/// assert_eq!(stats.count, 2);
+/// assert_eq!(stats.mean, Duration::from_seconds(1.0));
+/// assert_eq!(stats.max, Duration::from_milliseconds(1200.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyMonitor {
+    /// Latencies, in seconds, per [SV], in order of arrival
+    latencies_s: BTreeMap<SV, Vec<f64>>,
+}
+
+impl LatencyMonitor {
+    /// Records the arrival of a new message, describing this [Epoch] for this [SV].
+    /// Arrival time is typically [Epoch::now] when consuming a live stream.
+    pub fn record(&mut self, sv: SV, epoch: Epoch, arrival: Epoch) {
+        self.latencies_s
+            .entry(sv)
+            .or_default()
+            .push((arrival - epoch).to_seconds());
+    }
+
+    /// Returns total number of messages recorded
+    pub fn len(&self) -> usize {
+        self.latencies_s.values().map(|v| v.len()).sum()
+    }
+
+    /// Returns true if no message was recorded
+    pub fn is_empty(&self) -> bool {
+        self.latencies_s.is_empty()
+    }
+
+    /// Resets this [LatencyMonitor], typically when a new collection period starts.
+    pub fn reset(&mut self) {
+        self.latencies_s.clear();
+    }
+
+    /// Returns [LatencyStatistics] of this [SV]
+    pub fn sv_statistics(&self, sv: SV) -> Option<LatencyStatistics> {
+        LatencyStatistics::from_latencies(self.latencies_s.get(&sv)?)
+    }
+
+    /// Returns [LatencyStatistics] of each [SV]
+    pub fn statistics_per_sv(&self) -> BTreeMap<SV, LatencyStatistics> {
+        self.latencies_s
+            .iter()
+            .filter_map(|(sv, latencies)| {
+                let stats = LatencyStatistics::from_latencies(latencies)?;
+                Some((*sv, stats))
+            })
+            .collect()
+    }
+
+    /// Returns [LatencyStatistics] of this [Constellation]
+    pub fn constellation_statistics(
+        &self,
+        constellation: Constellation,
+    ) -> Option<LatencyStatistics> {
+        let latencies = self
+            .latencies_s
+            .iter()
+            .filter(|(sv, _)| sv.constellation == constellation)
+            .flat_map(|(_, latencies)| latencies.iter().copied())
+            .collect::<Vec<_>>();
+
+        LatencyStatistics::from_latencies(&latencies)
+    }
+
+    /// Returns [LatencyStatistics] of each [Constellation]
+    pub fn statistics_per_constellation(&self) -> BTreeMap<Constellation, LatencyStatistics> {
+        let mut latencies = BTreeMap::<Constellation, Vec<f64>>::new();
+        for (sv, values) in self.latencies_s.iter() {
+            latencies
+                .entry(sv.constellation)
+                .or_default()
+                .extend_from_slice(values);
+        }
+
+        latencies
+            .iter()
+            .filter_map(|(constellation, latencies)| {
+                let stats = LatencyStatistics::from_latencies(latencies)?;
+                Some((*constellation, stats))
+            })
+            .collect()
+    }
+
+    /// Returns [LatencyStatistics] of the whole stream
+    pub fn statistics(&self) -> Option<LatencyStatistics> {
+        let latencies = self
+            .latencies_s
+            .values()
+            .flat_map(|latencies| latencies.iter().copied())
+            .collect::<Vec<_>>();
+
+        LatencyStatistics::from_latencies(&latencies)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LatencyMonitor;
+    use crate::prelude::{Constellation, Duration, Epoch, SV};
+    use std::str::FromStr;
+
+    #[test]
+    fn latency_statistics() {
+        let g01 = SV::from_str("G01").unwrap();
+        let g02 = SV::from_str("G02").unwrap();
+        let e05 = SV::from_str("E05").unwrap();
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+
+        let mut monitor = LatencyMonitor::default();
+        assert!(monitor.statistics().is_none());
+
+        // G01: 1..=20 x 100ms
+        for i in 1..=20 {
+            let epoch = t0 + i as f64 * Duration::from_seconds(1.0);
+            let latency = Duration::from_milliseconds(100.0 * i as f64);
+            monitor.record(g01, epoch, epoch + latency);
+        }
+
+        monitor.record(g02, t0, t0 + Duration::from_seconds(1.0));
+        monitor.record(e05, t0, t0 + Duration::from_seconds(3.0));
+
+        assert_eq!(monitor.len(), 22);
+
+        let stats = monitor.sv_statistics(g01).unwrap();
+        assert_eq!(stats.count, 20);
+        assert!((stats.mean.to_seconds() - 1.05).abs() < 1.0E-9);
+        assert!((stats.percentile_95.to_seconds() - 1.9).abs() < 1.0E-9);
+        assert!((stats.max.to_seconds() - 2.0).abs() < 1.0E-9);
+
+        let per_constellation = monitor.statistics_per_constellation();
+        assert_eq!(per_constellation.len(), 2);
+        assert_eq!(per_constellation[&Constellation::GPS].count, 21);
+        assert_eq!(
+            per_constellation[&Constellation::Galileo].max,
+            Duration::from_seconds(3.0)
+        );
+        assert_eq!(
+            monitor.constellation_statistics(Constellation::GPS),
+            Some(per_constellation[&Constellation::GPS])
+        );
+
+        assert_eq!(monitor.statistics_per_sv().len(), 3);
+        assert_eq!(
+            monitor.statistics().unwrap().max,
+            Duration::from_seconds(3.0)
+        );
+
+        monitor.reset();
+        assert!(monitor.is_empty());
+    }
+}
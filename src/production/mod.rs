@@ -19,6 +19,7 @@
 use thiserror::Error;
 
 mod ffu;
+mod latency;
 mod postponing;
 mod ppu;
 mod sequence;
@@ -26,6 +27,7 @@ mod snapshot;
 mod source;
 
 pub use ffu::FFU;
+pub use latency::{LatencyMonitor, LatencyStatistics};
 pub use postponing::Postponing;
 pub use ppu::PPU;
 pub use snapshot::SnapshotMode;
@@ -1,11 +1,11 @@
 use crate::prelude::{
     qc::{Merge, MergeError},
-    Constellation, Epoch, Header,
+    Constellation, Header,
 };
 
 use super::{
-    merge_mut_option, merge_mut_unique_vec, merge_mut_vec, merge_obsrinex_observables,
-    merge_time_of_first_obs, merge_time_of_last_obs,
+    merge_mut_option, merge_mut_unique_vec, merge_obsrinex_observables, merge_time_of_first_obs,
+    merge_time_of_last_obs,
 };
 
 impl Merge for Header {
@@ -46,10 +46,12 @@ impl Merge for Header {
             },
         }
 
-        merge_mut_vec(&mut self.comments, &rhs.comments);
+        // identical comments (like a common header section) are not repeated
+        merge_mut_unique_vec(&mut self.comments, &rhs.comments);
         merge_mut_option(&mut self.geodetic_marker, &rhs.geodetic_marker);
         merge_mut_option(&mut self.license, &rhs.license);
         merge_mut_option(&mut self.doi, &rhs.doi);
+        merge_mut_option(&mut self.station_url, &rhs.station_url);
         merge_mut_option(&mut self.leap, &rhs.leap);
         merge_mut_option(&mut self.rcvr, &rhs.rcvr);
         merge_mut_option(&mut self.cospar, &rhs.cospar);
@@ -59,37 +61,31 @@ impl Merge for Header {
         merge_mut_option(&mut self.wavelengths, &rhs.wavelengths);
 
         // DCBS compensation is preserved, only if both A&B both have it
-        if self.dcb_compensations.is_empty() || rhs.dcb_compensations.is_empty() {
-            self.dcb_compensations.clear(); // drop everything
-        } else {
-            let rhs_constellations: Vec<_> = rhs
-                .dcb_compensations
-                .iter()
-                .map(|dcb| dcb.constellation)
-                .collect();
-            self.dcb_compensations
-                .iter_mut()
-                .filter(|dcb| rhs_constellations.contains(&dcb.constellation))
-                .count();
-        }
+        let rhs_constellations: Vec<_> = rhs
+            .dcb_compensations
+            .iter()
+            .map(|dcb| dcb.constellation)
+            .collect();
+        self.dcb_compensations
+            .retain(|dcb| rhs_constellations.contains(&dcb.constellation));
 
         // PCV compensation : same logic
         // only preserve compensations present in both A & B
-        if self.pcv_compensations.is_empty() || rhs.pcv_compensations.is_empty() {
-            self.pcv_compensations.clear(); // drop everything
-        } else {
-            let rhs_constellations: Vec<_> = rhs
-                .pcv_compensations
-                .iter()
-                .map(|pcv| pcv.constellation)
-                .collect();
-            self.dcb_compensations
-                .iter_mut()
-                .filter(|pcv| rhs_constellations.contains(&pcv.constellation))
-                .count();
+        let rhs_constellations: Vec<_> = rhs
+            .pcv_compensations
+            .iter()
+            .map(|pcv| pcv.constellation)
+            .collect();
+        self.pcv_compensations
+            .retain(|pcv| rhs_constellations.contains(&pcv.constellation));
+
+        for (sv, channel) in rhs.glo_channels.iter() {
+            self.glo_channels.entry(*sv).or_insert(*channel);
         }
 
-        // TODO: merge::merge_mut(&mut self.glo_channels, &rhs.glo_channels);
+        for (observable, bias) in rhs.glo_biases.iter() {
+            self.glo_biases.entry(observable.clone()).or_insert(*bias);
+        }
 
         // RINEX specific operation
         if let Some(lhs) = &mut self.antex {
@@ -122,8 +118,17 @@ impl Merge for Header {
                 merge_time_of_first_obs(&mut lhs.timeof_first_obs, &rhs.timeof_first_obs);
                 merge_time_of_last_obs(&mut lhs.timeof_last_obs, &rhs.timeof_last_obs);
 
+                merge_mut_unique_vec(&mut lhs.phase_shifts, &rhs.phase_shifts);
+
+                // scaling is described per observable: A prevails
+                for (key, scaling) in rhs.scaling.iter() {
+                    lhs.scaling.entry(key.clone()).or_insert(*scaling);
+                }
+
                 // TODO: lhs.clock_offset_applied |= rhs.clock_offset_applied;
             }
+        } else if rhs.obs.is_some() {
+            self.obs = rhs.obs.clone();
         }
         if let Some(lhs) = &mut self.meteo {
             if let Some(rhs) = &rhs.meteo {
@@ -175,16 +180,12 @@ impl Merge for Header {
             }
         }
 
-        // add special comment
-        let now = Epoch::now().map_err(|_| MergeError::Other)?;
-
         self.program = Some(format!(
             "rs-rinex v{}",
             Self::format_pkg_version(env!("CARGO_PKG_VERSION"))
         ));
 
-        let merge_comment = Self::merge_comment(env!("CARGO_PKG_VERSION"), now);
-        self.comments.push(merge_comment);
+        self.push_processing_log(None, "FILE MERGE", "");
         Ok(())
     }
 }
@@ -11,7 +11,7 @@ mod header;
 mod ionex;
 mod meteo;
 mod nav;
-mod obs;
+pub(crate) mod obs;
 mod prod;
 // mod io; // I/O is work in progress
 
@@ -26,13 +26,6 @@ use obs::merge_mut as merge_mut_obs;
 use std::cmp::PartialEq;
 use std::collections::HashMap;
 
-/// Appends given vector into self.
-pub(crate) fn merge_mut_vec<T: Clone>(lhs: &mut Vec<T>, rhs: &Vec<T>) {
-    for item in rhs {
-        lhs.push(item.clone());
-    }
-}
-
 /// Merges given vector into self, but ensures values are unique.
 pub(crate) fn merge_mut_unique_vec<T: Clone + PartialEq>(lhs: &mut Vec<T>, rhs: &Vec<T>) {
     for item in rhs {
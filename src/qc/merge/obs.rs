@@ -1,16 +1,104 @@
-use crate::{observation::Record, prelude::qc::MergeError};
+use crate::{
+    observation::{ObsKey, Record},
+    prelude::{
+        qc::{Merge, MergeError},
+        Duration, Epoch, Rinex,
+    },
+};
+
+use std::collections::BTreeMap;
+
+/// [MergePolicy] resolves conflicts when both A and B
+/// observed the same signal at the same epoch
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// A prevails: B only completes missing LLI and SSI flags
+    #[default]
+    Preserve,
+    /// B prevails: A is replaced
+    Overwrite,
+    /// Both values are averaged, A flags prevail
+    Average,
+}
+
+/// [ObsMergeOptions] to tune [Rinex::merge_observations]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct ObsMergeOptions {
+    /// [MergePolicy] for conflicting observations
+    pub policy: MergePolicy,
+    /// B epochs spaced from an A epoch by less than this [Duration]
+    /// are aligned (merged into) that A epoch. Epochs are only aligned
+    /// when they strictly match, by default.
+    pub alignment: Duration,
+}
+
+impl ObsMergeOptions {
+    /// Copies and returns [ObsMergeOptions] with desired [MergePolicy]
+    pub fn with_policy(&self, policy: MergePolicy) -> Self {
+        let mut s = *self;
+        s.policy = policy;
+        s
+    }
+
+    /// Copies and returns [ObsMergeOptions] with desired alignment tolerance
+    pub fn with_alignment(&self, alignment: Duration) -> Self {
+        let mut s = *self;
+        s.alignment = alignment;
+        s
+    }
+}
 
 pub fn merge_mut(rec: &mut Record, rhs: &Record) -> Result<(), MergeError> {
+    merge_mut_with(rec, rhs, &ObsMergeOptions::default())
+}
+
+/// Locates the A [ObsKey] that B [ObsKey] should be merged into
+fn aligned_key(index: &BTreeMap<Epoch, Vec<ObsKey>>, k: &ObsKey, alignment: Duration) -> ObsKey {
+    index
+        .range(k.epoch - alignment..=k.epoch + alignment)
+        .flat_map(|(_, keys)| keys.iter())
+        .filter(|key| key.flag == k.flag)
+        .min_by_key(|key| (key.epoch - k.epoch).abs())
+        .copied()
+        .unwrap_or(*k)
+}
+
+fn merge_mut_with(
+    rec: &mut Record,
+    rhs: &Record,
+    opts: &ObsMergeOptions,
+) -> Result<(), MergeError> {
+    let mut index = BTreeMap::<Epoch, Vec<ObsKey>>::new();
+    for k in rec.keys() {
+        index.entry(k.epoch).or_default().push(*k);
+    }
+
     for (k, rhs) in rhs.iter() {
-        if let Some(lhs) = rec.get_mut(k) {
+        let k = aligned_key(&index, k, opts.alignment);
+
+        if let Some(lhs) = rec.get_mut(&k) {
             // TODO: could merge clock field
             //  but only if receivers do match exactly !
+            if lhs.event.is_none() {
+                lhs.event = rhs.event.clone();
+            }
+
             for rhs in rhs.signals.iter() {
                 if let Some(lhs) = lhs
                     .signals
                     .iter_mut()
                     .find(|sig| sig.sv == rhs.sv && sig.observable == rhs.observable)
                 {
+                    match opts.policy {
+                        MergePolicy::Preserve => {},
+                        MergePolicy::Overwrite => {
+                            *lhs = rhs.clone();
+                            continue;
+                        },
+                        MergePolicy::Average => {
+                            lhs.value = (lhs.value + rhs.value) / 2.0;
+                        },
+                    }
                     if let Some(lli) = rhs.lli {
                         if lhs.lli.is_none() {
                             lhs.lli = Some(lli);
@@ -26,8 +114,87 @@ pub fn merge_mut(rec: &mut Record, rhs: &Record) -> Result<(), MergeError> {
                 }
             }
         } else {
-            rec.insert(*k, rhs.clone());
+            rec.insert(k, rhs.clone());
+            index.entry(k.epoch).or_default().push(k);
         }
     }
     Ok(())
 }
+
+impl Rinex {
+    /// Merges B Observation [Rinex] into this Observation [Rinex], in place.
+    /// See [Self::merge_observations].
+    pub fn merge_observations_mut(
+        &mut self,
+        rhs: &Self,
+        opts: &ObsMergeOptions,
+    ) -> Result<(), MergeError> {
+        let lhs = self
+            .record
+            .as_mut_obs()
+            .ok_or(MergeError::FileTypeMismatch)?;
+        let rhs_rec = rhs.record.as_obs().ok_or(MergeError::FileTypeMismatch)?;
+
+        self.header.merge_mut(&rhs.header)?;
+        self.production.merge_mut(&rhs.production)?;
+
+        merge_mut_with(lhs, rhs_rec, opts)?;
+
+        for (t, comments) in rhs.comments.iter() {
+            self.comments
+                .entry(*t)
+                .or_default()
+                .extend(comments.iter().cloned());
+        }
+
+        Ok(())
+    }
+
+    /// Merges (splices) B Observation [Rinex] into this Observation [Rinex] and returns the
+    /// result: epochs are aligned (see [ObsMergeOptions::alignment]), observables and [SV]s
+    /// are unified, and conflicting observations are resolved with [MergePolicy].
+    /// The merged header describes both files (observables, time frame..).
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use rinex::prelude::qc::{MergePolicy, ObsMergeOptions};
+    /// use std::io::BufReader;
+    ///
+    /// let a = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20000000.000
+    /// ";
+    ///
+    /// let b = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20000002.000
+    /// G02  21000000.000
+    /// ";
+    ///
+    /// let a = Rinex::parse(&mut BufReader::new(a.as_bytes())).unwrap();
+    /// let b = Rinex::parse(&mut BufReader::new(b.as_bytes())).unwrap();
+    ///
+    /// let opts = ObsMergeOptions::default()
+    ///     .with_policy(MergePolicy::Average);
+    ///
+    /// let merged = a.merge_observations(&b, &opts).unwrap();
+    ///
+    /// let values = merged.signal_observations_iter()
+    ///     .map(|(_, sig)| sig.value)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, vec![20000001.0, 21000000.0]);
+    /// ```
+    pub fn merge_observations(
+        &self,
+        rhs: &Self,
+        opts: &ObsMergeOptions,
+    ) -> Result<Self, MergeError> {
+        let mut s = self.clone();
+        s.merge_observations_mut(rhs, opts)?;
+        Ok(s)
+    }
+}
@@ -1 +1 @@
-mod merge;
+pub(crate) mod merge;
@@ -0,0 +1,201 @@
+//! Partial parsing and resume
+use crate::{
+    hatanaka::DecompressorExpert,
+    prelude::{Epoch, Header, ParsingError, ParsingOptions, Rinex},
+    record::Record,
+    summary::ParsingSummary,
+};
+
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [ParsingCheckpoint] describes the state of the record parser, when parsing
+/// was interrupted (see [Rinex::parse_partial]), so it can be resumed later on
+/// (see [Rinex::resume_parsing]). This allows processing huge files in chunks,
+/// in memory constrained or interruptible environments.
+///
+/// [ParsingCheckpoint] can be serialized, including the CRINEX decompression state,
+/// so compressed files may be resumed by another process.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParsingCheckpoint {
+    /// Position in the stream, in bytes, from which parsing should be resumed
+    pub offset: u64,
+    /// First line(s) of the next epoch, already consumed
+    pub(crate) pending: String,
+    /// Comments being collected are indexed by this [Epoch]
+    pub(crate) comment_ts: Epoch,
+    /// CRINEX decompressor state
+    pub(crate) decompressor: Option<DecompressorExpert<5>>,
+    /// IONEX: [Epoch] of current map
+    pub(crate) ionex_epoch: Epoch,
+    /// IONEX: current scaling exponent
+    pub(crate) ionex_exponent: i8,
+}
+
+impl Rinex {
+    /// Parses [Rinex] content by consuming [BufReader], like [Self::parse],
+    /// but stops once `max_epochs` epochs have been parsed. The returned [ParsingCheckpoint]
+    /// allows resuming the parsing process later on, with [Self::resume_parsing].
+    /// No [ParsingCheckpoint] is returned once the end of the stream has been reached.
+    /// Each chunk is a valid [Rinex] on its own, that shares the [Header] of the stream.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::{BufReader, Cursor};
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// > 2020 01 01 00 01  0.0000000  0  1
+    /// G01  20832395.682
+    /// ";
+    ///
+    /// let mut reader = BufReader::new(Cursor::new(content.as_bytes()));
+    ///
+    /// // process 2 epochs at a time
+    /// let (chunk, checkpoint) = Rinex::parse_partial(&mut reader, 2)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chunk.epoch_iter().count(), 2);
+    /// let checkpoint = checkpoint.unwrap();
+    ///
+    /// // resume (possibly later on, with a new reader)
+    /// let mut reader = BufReader::new(Cursor::new(content.as_bytes()));
+    ///
+    /// let (chunk, checkpoint) = Rinex::resume_parsing(&mut reader, &checkpoint, 2)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chunk.epoch_iter().count(), 1);
+    /// assert!(checkpoint.is_none());
+    /// ```
+    pub fn parse_partial<R: Read + Seek>(
+        reader: &mut BufReader<R>,
+        max_epochs: usize,
+    ) -> Result<(Self, Option<ParsingCheckpoint>), ParsingError> {
+        let mut summary = ParsingSummary::default();
+        let header = Header::parse_summarized(reader, &mut summary)?;
+        let offset = reader.stream_position()?;
+        Self::parse_record_chunk(header, summary, reader, offset, None, max_epochs)
+    }
+
+    /// Resumes parsing [Rinex] content, from this [ParsingCheckpoint], until
+    /// `max_epochs` new epochs have been parsed. See [Self::parse_partial].
+    /// The [Header] is parsed again, then the [BufReader] is positioned where
+    /// parsing was interrupted: it must read the same stream.
+    pub fn resume_parsing<R: Read + Seek>(
+        reader: &mut BufReader<R>,
+        checkpoint: &ParsingCheckpoint,
+        max_epochs: usize,
+    ) -> Result<(Self, Option<ParsingCheckpoint>), ParsingError> {
+        reader.seek(SeekFrom::Start(0))?;
+
+        let mut summary = ParsingSummary::default();
+        let header = Header::parse_summarized(reader, &mut summary)?;
+
+        reader.seek(SeekFrom::Start(checkpoint.offset))?;
+
+        Self::parse_record_chunk(
+            header,
+            summary,
+            reader,
+            checkpoint.offset,
+            Some(checkpoint),
+            max_epochs,
+        )
+    }
+
+    fn parse_record_chunk<R: Read>(
+        mut header: Header,
+        mut summary: ParsingSummary,
+        reader: &mut BufReader<R>,
+        offset: u64,
+        checkpoint: Option<&ParsingCheckpoint>,
+        max_epochs: usize,
+    ) -> Result<(Self, Option<ParsingCheckpoint>), ParsingError> {
+        let (record, comments, checkpoint) = Record::parse_chunk(
+            &mut header,
+            reader,
+            offset,
+            checkpoint,
+            Some(max_epochs),
+            &ParsingOptions::default(),
+            &mut summary,
+        )?;
+
+        Ok((
+            Self {
+                header,
+                comments,
+                record,
+                production: Default::default(),
+                summary,
+            },
+            checkpoint,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::Rinex;
+    use std::io::{BufReader, Cursor};
+
+    #[test]
+    fn chunked_parsing() {
+        let mut content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     2    C1    L1                                          # / TYPES OF OBSERV
+                                                            END OF HEADER
+"
+            .to_string();
+
+        for i in 0..5 {
+            content.push_str(&format!(" 20  1  1  0 {:2}  0.0000000  0  2G01G07\n", i));
+            if i == 3 {
+                content.push_str(&format!("{:<60}COMMENT\n", "test comment"));
+            }
+            for sv in 0..2 {
+                content.push_str(&format!(
+                    "{:14.3}  {:14.3}  \n",
+                    20832393.682 + i as f64,
+                    109474991.854 + sv as f64
+                ));
+            }
+        }
+
+        let mut reader = BufReader::new(content.as_bytes());
+        let reference = Rinex::parse(&mut reader).unwrap();
+        assert_eq!(reference.epoch_iter().count(), 5);
+
+        let mut reader = BufReader::new(Cursor::new(content.as_bytes()));
+        let (mut merged, mut checkpoint) = Rinex::parse_partial(&mut reader, 2).unwrap();
+        assert_eq!(merged.epoch_iter().count(), 2);
+
+        let mut num_chunks = 1;
+
+        while let Some(resume) = checkpoint {
+            // new reader, like a new process would
+            let mut reader = BufReader::new(Cursor::new(content.as_bytes()));
+            let (chunk, next) = Rinex::resume_parsing(&mut reader, &resume, 2).unwrap();
+
+            let record = merged.record.as_mut_obs().unwrap();
+            for (k, v) in chunk.record.as_obs().unwrap() {
+                record.insert(*k, v.clone());
+            }
+
+            merged.comments.extend(chunk.comments);
+            checkpoint = next;
+            num_chunks += 1;
+        }
+
+        assert_eq!(num_chunks, 3);
+        assert_eq!(merged.record, reference.record);
+        assert_eq!(merged.comments, reference.comments);
+    }
+}
@@ -5,15 +5,29 @@ use crate::{
     navigation::format as format_navigation,
     prelude::{FormattingError, Header},
     record::Record,
+    rounding::RoundingMode,
 };
 
 use std::io::{BufWriter, Write};
 
 impl Record {
+    /// Formats this [Record] following the [Header] specifications,
+    /// with default [RoundingMode].
     pub fn format<W: Write>(
         &self,
         w: &mut BufWriter<W>,
         header: &Header,
+    ) -> Result<(), FormattingError> {
+        self.format_with_rounding(w, header, RoundingMode::default())
+    }
+
+    /// Formats this [Record] following the [Header] specifications,
+    /// fixed width numeric fields being rounded with desired [RoundingMode].
+    pub fn format_with_rounding<W: Write>(
+        &self,
+        w: &mut BufWriter<W>,
+        header: &Header,
+        rounding: RoundingMode,
     ) -> Result<(), FormattingError> {
         let version_major = header.version.major;
 
@@ -31,15 +45,15 @@ impl Record {
                 compressor.format(w, &rec, header)?;
             } else {
                 for (k, v) in rec.iter() {
-                    v.format(version_major == 2, k, &header, w)?;
+                    v.format(version_major < 3, k, &header, rounding, w)?;
                 }
             }
 
             Ok(())
         } else if let Some(rec) = self.as_meteo() {
-            format_meteo_observations(w, rec, header)
+            format_meteo_observations(w, rec, header, rounding)
         } else if let Some(rec) = self.as_doris() {
-            format_doris_observations(w, rec, header)
+            format_doris_observations(w, rec, header, rounding)
         } else if let Some(rec) = self.as_nav() {
             format_navigation(w, rec, header)
         } else {
@@ -1,7 +1,12 @@
 use crate::{
-    antex::Record as AntexRecord, clock::Record as ClockRecord, doris::Record as DorisRecord,
-    ionex::Record as IonexRecord, meteo::Record as MeteoRecord, navigation::Record as NavRecord,
-    observation::Record as ObservationRecord, prelude::Epoch,
+    antex::Record as AntexRecord,
+    clock::Record as ClockRecord,
+    doris::Record as DorisRecord,
+    ionex::Record as IonexRecord,
+    meteo::Record as MeteoRecord,
+    navigation::Record as NavRecord,
+    observation::Record as ObservationRecord,
+    prelude::{Duration, Epoch},
 };
 
 use std::collections::BTreeMap;
@@ -9,8 +14,13 @@ use std::collections::BTreeMap;
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+mod checkpoint;
 mod formatting;
 mod parsing;
+mod storage;
+
+pub use checkpoint::ParsingCheckpoint;
+pub use storage::{RecordStorage, SequenceStorage, StorageBackend};
 
 /// RINEX [Record] type, inner content is RINEX type dependent.
 #[derive(Clone, Debug, PartialEq)]
@@ -154,4 +164,159 @@ impl Record {
             _ => None,
         }
     }
+
+    /// Returns the [StorageBackend] of this [Record]
+    pub fn storage_backend(&self) -> StorageBackend {
+        match self {
+            Self::ObsRecord(r) => r.backend(),
+            Self::MeteoRecord(r) => r.backend(),
+            Self::NavRecord(r) => r.backend(),
+            Self::ClockRecord(r) => r.backend(),
+            Self::IonexRecord(r) => r.backend(),
+            Self::DorisRecord(r) => r.backend(),
+            Self::AntexRecord(_) => StorageBackend::Sequence,
+        }
+    }
+
+    /// Iterates this [Record] over a regular time grid, starting on the first [Epoch],
+    /// spaced by `interval`, and ending on the last [Epoch].
+    /// Each slot is described by its nominal [Epoch] and the [Record] content
+    /// that was sampled within half an interval of it, or None on data gaps.
+    /// This is convenient for algorithms that require regularly sampled input,
+    /// like spectral analysis or filters. ANTEX records, which are not indexed by
+    /// [Epoch], and null intervals, result in an empty iterator.
+    /// ```
+    /// use rinex::prelude::*;
+    /// let rinex = Rinex::from_file("data/MET/V2/abvi0010.15m")
+    ///     .unwrap();
+    /// let dt = Duration::from_seconds(60.0);
+    /// for (epoch, slot) in rinex.record.iter_continuous(dt) {
+    ///     if slot.is_none() {
+    ///         // data gap
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_continuous(
+        &self,
+        interval: Duration,
+    ) -> Box<dyn Iterator<Item = (Epoch, Option<Record>)> + '_> {
+        match self {
+            Self::ObsRecord(r) => continuous_iter(r, |k| k.epoch, Self::ObsRecord, interval),
+            Self::MeteoRecord(r) => continuous_iter(r, |k| k.epoch, Self::MeteoRecord, interval),
+            Self::NavRecord(r) => continuous_iter(r, |k| k.epoch, Self::NavRecord, interval),
+            Self::ClockRecord(r) => continuous_iter(r, |k| *k, Self::ClockRecord, interval),
+            Self::IonexRecord(r) => continuous_iter(r, |k| k.epoch, Self::IonexRecord, interval),
+            Self::DorisRecord(r) => continuous_iter(r, |k| k.epoch, Self::DorisRecord, interval),
+            Self::AntexRecord(_) => Box::new([].into_iter()),
+        }
+    }
+}
+
+/// Generic [Record::iter_continuous] implementation, for records sorted by [Epoch].
+fn continuous_iter<'a, K: Ord + Clone + 'a, V: Clone + 'a, S: RecordStorage<K, V>>(
+    record: &'a S,
+    epoch: fn(&K) -> Epoch,
+    wrap: fn(BTreeMap<K, V>) -> Record,
+    interval: Duration,
+) -> Box<dyn Iterator<Item = (Epoch, Option<Record>)> + 'a> {
+    let (first, last) = match (record.first_key(), record.last_key()) {
+        (Some(first), Some(last)) => (epoch(first), epoch(last)),
+        _ => return Box::new([].into_iter()),
+    };
+
+    if interval <= Duration::ZERO {
+        return Box::new([].into_iter());
+    }
+
+    let half = interval / 2;
+    let mut t = first;
+    let mut iter = record.entries().peekable();
+
+    Box::new(std::iter::from_fn(move || {
+        if t > last + half {
+            return None;
+        }
+
+        let mut slot = BTreeMap::new();
+
+        while let Some((k, v)) = iter.peek() {
+            let e = epoch(k);
+            if e >= t + half {
+                break;
+            }
+            if e >= t - half {
+                slot.insert((*k).clone(), (*v).clone());
+            }
+            iter.next();
+        }
+
+        let nominal = t;
+        t += interval;
+
+        if slot.is_empty() {
+            Some((nominal, None))
+        } else {
+            Some((nominal, Some(wrap(slot))))
+        }
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        observation::{ObsKey, Observations, SignalObservation},
+        prelude::{Observable, SV},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn continuous_iteration() {
+        let g01 = SV::from_str("G01").unwrap();
+        let c1c = Observable::from_str("C1C").unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let dt = Duration::from_seconds(30.0);
+
+        let mut record = ObservationRecord::new();
+
+        // 3rd epoch is missing, 4th is slightly late
+        for (i, delay) in [(0, 0.0), (1, 0.0), (3, 0.1), (4, 0.0)] {
+            let key = ObsKey {
+                epoch: t0 + i as f64 * dt + Duration::from_seconds(delay),
+                flag: Default::default(),
+            };
+            let mut obs = Observations::default();
+            obs.signals
+                .push(SignalObservation::new(g01, c1c.clone(), i as f64));
+            record.insert(key, obs);
+        }
+
+        let record = Record::ObsRecord(record);
+
+        let slots = record.iter_continuous(dt).collect::<Vec<_>>();
+        assert_eq!(slots.len(), 5);
+
+        for (i, (epoch, slot)) in slots.iter().enumerate() {
+            assert_eq!(*epoch, t0 + i as f64 * dt);
+            if i == 2 {
+                assert!(slot.is_none(), "slot #{} should be a gap", i);
+            } else {
+                let slot = slot.as_ref().unwrap().as_obs().unwrap();
+                assert_eq!(slot.len(), 1, "slot #{} should contain one epoch", i);
+                let (_, obs) = slot.iter().next().unwrap();
+                assert_eq!(obs.signals[0].value, i as f64);
+            }
+        }
+
+        assert_eq!(record.iter_continuous(Duration::ZERO).count(), 0);
+        assert_eq!(
+            Record::AntexRecord(Default::default())
+                .iter_continuous(dt)
+                .count(),
+            0
+        );
+
+        assert_eq!(record.storage_backend(), StorageBackend::Map);
+    }
 }
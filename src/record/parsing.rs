@@ -31,11 +31,13 @@ use crate::{
     },
     observation::Observations,
     observation::{
-        is_new_epoch as is_new_observation_epoch, parse_epoch as parse_observation_epoch,
-        Record as ObservationRecord,
+        event_records_count, is_new_epoch as is_new_observation_epoch,
+        parse_epoch as parse_observation_epoch, Record as ObservationRecord,
     },
-    prelude::{Epoch, Header, ParsingError, TimeScale},
-    record::{Comments, Record},
+    prelude::{Epoch, Header, ParsingError, ParsingOptions, TimeScale, SV},
+    record::{Comments, ParsingCheckpoint, Record},
+    summary::ParsingSummary,
+    sv::is_valid_prn,
     types::Type,
 };
 
@@ -55,6 +57,42 @@ impl Record {
         header: &mut Header,
         reader: &mut BufReader<R>,
     ) -> Result<(Self, Comments), ParsingError> {
+        let (record, comments, _) = Self::parse_chunk(
+            header,
+            reader,
+            0,
+            None,
+            None,
+            &ParsingOptions::default(),
+            &mut ParsingSummary::default(),
+        )?;
+        Ok((record, comments))
+    }
+
+    /// Parses [Record] section by consuming [Reader], until `max_epochs` have been parsed
+    /// (all of it if None), possibly resuming from a previous [ParsingCheckpoint].
+    /// ## Input
+    ///   - header: reference to [Header] that was parsed previously
+    ///   - reader: [Reader] positioned at `offset` (in bytes, from start of stream)
+    ///   - offset: current position in stream, in bytes
+    ///   - checkpoint: possible [ParsingCheckpoint] to resume from
+    ///   - max_epochs: maximal number of epochs to parse
+    ///   - opts: [ParsingOptions]
+    ///   - summary: [ParsingSummary] to report parsed and skipped epochs
+    /// ## Output
+    ///   - [ParsingCheckpoint] to resume from, if parsing was interrupted
+    pub(crate) fn parse_chunk<R: Read>(
+        header: &mut Header,
+        reader: &mut BufReader<R>,
+        mut offset: u64,
+        checkpoint: Option<&ParsingCheckpoint>,
+        max_epochs: Option<usize>,
+        opts: &ParsingOptions,
+        summary: &mut ParsingSummary,
+    ) -> Result<(Self, Comments, Option<ParsingCheckpoint>), ParsingError> {
+        // number of epochs parsed so far
+        let mut num_epochs = 0;
+
         // eos reached: process pending buffer & exit
         let mut eos = false;
 
@@ -81,6 +119,11 @@ impl Record {
         // OBS
         let mut obs_rec = ObservationRecord::new();
         let mut observations = Observations::default();
+        let mut obs_latest = Option::<Epoch>::None;
+        let mut obs_dropped = Vec::<SV>::with_capacity(4);
+
+        // special records still expected by pending event epoch (flag 2 to 5)
+        let mut event_records = 0;
 
         // CRINEX case
         const CRINEX_BUF_SIZE: usize = 1024;
@@ -106,6 +149,21 @@ impl Record {
             gnss_observables,
         );
 
+        if let Some(checkpoint) = checkpoint {
+            if is_crinex {
+                decompressor = checkpoint
+                    .decompressor
+                    .clone()
+                    .ok_or(ParsingError::CheckpointDecompressor)?;
+            }
+            epoch_buf.push_str(&checkpoint.pending);
+            comment_ts = checkpoint.comment_ts;
+
+            if header.rinex_type == Type::ObservationData {
+                event_records = event_records_count(&checkpoint.pending, header.version);
+            }
+        }
+
         // MET
         let mut met_rec = MeteoRecord::new();
 
@@ -118,16 +176,20 @@ impl Record {
         // OBSERVATION case: timescale is either defined by
         // [+] TIME OF FIRST header field
         // [+] TIME OF LAST header field (flexibility, actually invalid according to specs)
+        // [+] the constellation's own timescale, in mono constellation files
+        //     (this is the specified default when the time system is omitted)
         let mut obs_ts = TimeScale::default();
 
         if let Some(obs) = &header.obs {
             if let Some(t) = obs.timeof_first_obs {
                 obs_ts = t.time_scale;
-            } else {
-                let t = obs
-                    .timeof_last_obs
-                    .ok_or(ParsingError::BadObsBadTimescaleDefinition)?;
+            } else if let Some(t) = obs.timeof_last_obs {
                 obs_ts = t.time_scale;
+            } else {
+                obs_ts = header
+                    .constellation
+                    .and_then(|c| c.timescale())
+                    .unwrap_or(TimeScale::GPST);
             }
         }
 
@@ -170,8 +232,17 @@ impl Record {
             ionex_alt_exponent = IonexQuantized::find_exponent(ionex.grid.height.spacing);
         }
 
+        if let Some(checkpoint) = checkpoint {
+            ionex_t = checkpoint.ionex_epoch;
+            ionex_tec_exponent = checkpoint.ionex_exponent;
+        }
+
+        let mut next_checkpoint = Option::<ParsingCheckpoint>::None;
+
         // Iterate and consume, one line at a time
         while let Ok(size) = reader.read_line(&mut line_buf) {
+            offset += size as u64;
+
             if size == 0 {
                 // reached EOS
                 // we might still have something to process prior exiting
@@ -180,6 +251,14 @@ impl Record {
 
             // (special case) COMMENTS: store as is
             if is_rinex_comment(&line_buf) {
+                // unless this is a special record of the pending event epoch
+                if event_records > 0 {
+                    event_records -= 1;
+                    epoch_buf.push_str(&line_buf);
+                    line_buf.clear();
+                    continue;
+                }
+
                 let comment = line_buf.split_at(60).0.trim_end();
                 comment_content.push(comment.to_string());
 
@@ -269,12 +348,19 @@ impl Record {
                     //println!("***MATCH***");
 
                     match &header.rinex_type {
-                        Type::NavigationData => {
-                            if let Ok((k, v)) = parse_nav_epoch(&header, &epoch_buf) {
+                        Type::NavigationData => match parse_nav_epoch(&header, &epoch_buf) {
+                            Ok((k, _)) if !is_valid_prn(&k.sv) => {
+                                if opts.strict_prn {
+                                    return Err(ParsingError::InvalidPRN(k.sv));
+                                }
+                                summary.dropped_sv(k.epoch, k.sv);
+                            },
+                            Ok((k, v)) => {
                                 nav_rec.insert(k, v);
-                                // println!("nav_epoch={:?}", k); // DEBUG
+                                summary.epoch();
                                 comment_ts = k.epoch; // for comments storage
-                            }
+                            },
+                            Err(e) => summary.skip_epoch(&e),
                         },
                         Type::ObservationData => {
                             match parse_observation_epoch(
@@ -282,58 +368,87 @@ impl Record {
                                 &epoch_buf,
                                 obs_ts,
                                 &mut observations,
+                                &mut obs_dropped,
                             ) {
                                 Ok(key) => {
+                                    for sv in obs_dropped.drain(..) {
+                                        if opts.strict_prn {
+                                            return Err(ParsingError::InvalidPRN(sv));
+                                        }
+                                        summary.dropped_sv(key.epoch, sv);
+                                    }
+
                                     //println!("key={:?}", key);
-                                    obs_rec.insert(key, observations.clone());
+                                    if let Some(latest) = obs_latest {
+                                        if key.epoch < latest {
+                                            summary.unsorted_epoch(key.epoch);
+                                        }
+                                    }
+                                    obs_latest = Some(key.epoch);
+
+                                    if let Some(superseded) =
+                                        obs_rec.insert(key, observations.clone())
+                                    {
+                                        summary.duplicated_epoch(key, superseded);
+                                    }
+                                    summary.epoch();
                                     comment_ts = key.epoch; // for comments storage
                                 },
-                                #[cfg(feature = "log")]
                                 Err(e) => {
+                                    #[cfg(feature = "log")]
                                     error!("parsing: {}", e);
+                                    summary.skip_epoch(&e);
                                 },
-                                #[cfg(not(feature = "log"))]
-                                Err(_) => {},
                             }
 
+                            obs_dropped.clear();
                             observations.signals.clear(); // reset for next parsing (single alloc)
+                            observations.clock = None;
+                            observations.event = None;
                         },
 
-                        Type::DORIS => {
-                            if let Ok((k, observations)) = parse_doris_epoch(header, &epoch_buf) {
+                        Type::DORIS => match parse_doris_epoch(header, &epoch_buf) {
+                            Ok((k, observations)) => {
                                 comment_ts = k.epoch; // for comments storage
                                 dor_rec.insert(k, observations);
-                            }
+                                summary.epoch();
+                            },
+                            Err(e) => summary.skip_epoch(&e),
                         },
 
-                        Type::MeteoData => {
-                            if let Ok(items) = parse_meteo_epoch(header, &epoch_buf) {
+                        Type::MeteoData => match parse_meteo_epoch(header, &epoch_buf) {
+                            Ok(items) => {
                                 for (k, v) in items.iter() {
                                     met_rec.insert(k.clone(), *v);
                                     comment_ts = k.epoch; // for comments storage
                                 }
-                            }
+                                summary.epoch();
+                            },
+                            Err(e) => summary.skip_epoch(&e),
                         },
 
                         Type::ClockData => {
-                            if let Ok((epoch, key, profile)) =
-                                parse_clock_epoch(header.version, &epoch_buf, clk_ts)
-                            {
-                                if let Some(e) = clk_rec.get_mut(&epoch) {
-                                    e.insert(key, profile);
-                                } else {
-                                    let mut inner: BTreeMap<ClockKey, ClockProfile> =
-                                        BTreeMap::new();
-                                    inner.insert(key, profile);
-                                    clk_rec.insert(epoch, inner);
-                                }
-                                comment_ts = epoch; // for comments storage
+                            match parse_clock_epoch(header.version, &epoch_buf, clk_ts) {
+                                Ok((epoch, key, profile)) => {
+                                    if let Some(e) = clk_rec.get_mut(&epoch) {
+                                        e.insert(key, profile);
+                                    } else {
+                                        let mut inner: BTreeMap<ClockKey, ClockProfile> =
+                                            BTreeMap::new();
+                                        inner.insert(key, profile);
+                                        clk_rec.insert(epoch, inner);
+                                    }
+                                    comment_ts = epoch; // for comments storage
+                                    summary.epoch();
+                                },
+                                Err(e) => summary.skip_epoch(&e),
                             }
                         },
 
                         Type::AntennaData => {
                             let (antenna, content) = parse_antex_antenna(&epoch_buf).unwrap();
                             atx_rec.push((antenna, content));
+                            summary.epoch();
                         },
 
                         Type::IonosphereMaps => {
@@ -347,8 +462,8 @@ impl Record {
                                     ionex_t,
                                     &mut ionex_rec,
                                 ) {
-                                    Ok(()) => {},
-                                    Err(_) => {},
+                                    Ok(()) => summary.epoch(),
+                                    Err(e) => summary.skip_epoch(&e),
                                 }
                             } else if is_new_rms_map(&line_buf) {
                                 match parse_ionex_rms_map(
@@ -360,8 +475,8 @@ impl Record {
                                     ionex_t,
                                     &mut ionex_rec,
                                 ) {
-                                    Ok(()) => {},
-                                    Err(_) => {},
+                                    Ok(()) => summary.epoch(),
+                                    Err(e) => summary.skip_epoch(&e),
                                 }
                             } else {
                                 // match parse_ionex_height_map(
@@ -385,6 +500,29 @@ impl Record {
             // clear on new epoch detection
             if new_epoch {
                 epoch_buf.clear();
+                num_epochs += 1;
+
+                // interrupt: new epoch will be resumed from this point
+                if !eos && !crinex_error && max_epochs == Some(num_epochs) {
+                    next_checkpoint = Some(ParsingCheckpoint {
+                        offset,
+                        pending: line_buf.clone(),
+                        comment_ts,
+                        decompressor: if is_crinex { Some(decompressor) } else { None },
+                        ionex_epoch: ionex_t,
+                        ionex_exponent: ionex_tec_exponent,
+                    });
+                    break;
+                }
+            }
+
+            // keep track of the special records the new event epoch announces
+            if header.rinex_type == Type::ObservationData {
+                if new_epoch || epoch_buf.is_empty() {
+                    event_records = event_records_count(&line_buf, header.version);
+                } else {
+                    event_records = event_records.saturating_sub(1);
+                }
             }
 
             // always stack new content
@@ -407,7 +545,7 @@ impl Record {
             Type::ObservationData => Record::ObsRecord(obs_rec),
             Type::DORIS => Record::DorisRecord(dor_rec),
         };
-        Ok((record, comments))
+        Ok((record, comments, next_checkpoint))
     }
 
     fn is_new_epoch(line: &str, header: &Header) -> bool {
@@ -427,3 +565,26 @@ impl Record {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Rinex, TimeScale};
+    use std::io::BufReader;
+
+    #[test]
+    fn obs_timescale_from_constellation() {
+        let content =
+            "     3.04           OBSERVATION DATA    E                   RINEX VERSION / TYPE
+E    2 C1C L1C                                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2022 03 04 00 00  0.0000000  0  1
+E01  20243517.560   108353702.797
+";
+        let mut reader = BufReader::new(content.as_bytes());
+        let rinex = Rinex::parse(&mut reader).unwrap();
+
+        let epochs = rinex.epoch_iter().collect::<Vec<_>>();
+        assert_eq!(epochs.len(), 1);
+        assert_eq!(epochs[0].time_scale, TimeScale::GST);
+    }
+}
@@ -0,0 +1,278 @@
+//! Record storage abstraction
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [StorageBackend] describes how [RecordStorage] content is stored in memory.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[non_exhaustive]
+pub enum StorageBackend {
+    /// Sorted map: each entry is stored by its (sortable) key.
+    /// This is the backend of all parsed records.
+    #[default]
+    Map,
+    /// Entries are stored contiguously, in a vector: in key order for [SequenceStorage],
+    /// in order of appearance (without any index) for ANTEX records.
+    /// This favors iteration and memory footprint, over random insertion.
+    Sequence,
+}
+
+impl std::fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Map => write!(f, "Map"),
+            Self::Sequence => write!(f, "Sequence"),
+        }
+    }
+}
+
+/// [RecordStorage] is the access API of a RINEX record, independently
+/// of its [StorageBackend]. Entries are always iterated in key order,
+/// which is chronological order for all Epoch indexed records.
+/// Parsed records use the [StorageBackend::Map] backend, generic code should
+/// only rely on this API to remain compatible with the other backends
+/// (for example [SequenceStorage]).
+/// ```
+/// use rinex::prelude::Rinex;
+/// use rinex::record::{RecordStorage, StorageBackend};
+///
+/// let rinex = Rinex::basic_obs();
+///
+/// let record = rinex.record.as_obs()
+///     .unwrap();
+///
+/// assert_eq!(record.backend(), StorageBackend::Map);
+/// assert_eq!(record.entries().count(), RecordStorage::len(record));
+/// ```
+pub trait RecordStorage<K: Ord, V> {
+    /// Returns the [StorageBackend] being used
+    fn backend(&self) -> StorageBackend;
+
+    /// Returns total number of entries
+    fn len(&self) -> usize;
+
+    /// Returns true if this storage does not contain any entry
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns entry stored at this key, if it exists
+    fn entry_at(&self, key: &K) -> Option<&V>;
+
+    /// Stores a new entry, returns the previous entry stored at this key, if any
+    fn store(&mut self, key: K, value: V) -> Option<V>;
+
+    /// Removes and returns the entry stored at this key, if it exists
+    fn discard(&mut self, key: &K) -> Option<V>;
+
+    /// Iterates all entries, in key order
+    fn entries(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_>;
+
+    /// Retains only the entries for which `f` returns true
+    fn retain_entries(&mut self, f: &mut dyn FnMut(&K, &mut V) -> bool);
+
+    /// Returns first key
+    fn first_key(&self) -> Option<&K>;
+
+    /// Returns last key
+    fn last_key(&self) -> Option<&K>;
+}
+
+impl<K: Ord, V> RecordStorage<K, V> for BTreeMap<K, V> {
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::Map
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn entry_at(&self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+
+    fn store(&mut self, key: K, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+
+    fn discard(&mut self, key: &K) -> Option<V> {
+        BTreeMap::remove(self, key)
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(BTreeMap::iter(self))
+    }
+
+    fn retain_entries(&mut self, f: &mut dyn FnMut(&K, &mut V) -> bool) {
+        BTreeMap::retain(self, |k, v| f(k, v))
+    }
+
+    fn first_key(&self) -> Option<&K> {
+        BTreeMap::keys(self).next()
+    }
+
+    fn last_key(&self) -> Option<&K> {
+        BTreeMap::keys(self).next_back()
+    }
+}
+
+/// [SequenceStorage] is a [RecordStorage] backend ([StorageBackend::Sequence])
+/// that stores entries contiguously, sorted by key. Lookups are binary searches.
+/// It is well suited to records that are built once in chronological order, then
+/// mostly iterated, and to exchange entries with columnar formats.
+/// ```
+/// use rinex::record::{RecordStorage, SequenceStorage, StorageBackend};
+///
+/// let mut storage = SequenceStorage::<u32, f64>::default();
+/// storage.store(2, 2.0);
+/// storage.store(1, 1.0);
+///
+/// assert_eq!(storage.backend(), StorageBackend::Sequence);
+/// assert_eq!(storage.first_key(), Some(&1));
+/// assert_eq!(storage.entry_at(&2), Some(&2.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SequenceStorage<K: Ord, V> {
+    /// Entries, sorted by key
+    entries: Vec<(K, V)>,
+}
+
+impl<K: Ord, V> Default for SequenceStorage<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SequenceStorage<K, V> {
+    /// Builds [SequenceStorage] from these entries. Like for a map,
+    /// the last entry is retained when a key is found more than once.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut storage = Self::default();
+        for (key, value) in iter {
+            storage.store(key, value);
+        }
+        storage
+    }
+}
+
+impl<K: Ord, V> SequenceStorage<K, V> {
+    /// Builds new [SequenceStorage], with preallocated capacity
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn position(&self, key: &K) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(k, _)| k.cmp(key))
+    }
+}
+
+impl<K: Ord, V> RecordStorage<K, V> for SequenceStorage<K, V> {
+    fn backend(&self) -> StorageBackend {
+        StorageBackend::Sequence
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn entry_at(&self, key: &K) -> Option<&V> {
+        let index = self.position(key).ok()?;
+        Some(&self.entries[index].1)
+    }
+
+    fn store(&mut self, key: K, value: V) -> Option<V> {
+        // chronological construction: simple push
+        if self.entries.last().is_none_or(|(last, _)| *last < key) {
+            self.entries.push((key, value));
+            return None;
+        }
+
+        match self.position(&key) {
+            Ok(index) => Some(std::mem::replace(&mut self.entries[index].1, value)),
+            Err(index) => {
+                self.entries.insert(index, (key, value));
+                None
+            },
+        }
+    }
+
+    fn discard(&mut self, key: &K) -> Option<V> {
+        let index = self.position(key).ok()?;
+        Some(self.entries.remove(index).1)
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(self.entries.iter().map(|(k, v)| (k, v)))
+    }
+
+    fn retain_entries(&mut self, f: &mut dyn FnMut(&K, &mut V) -> bool) {
+        self.entries.retain_mut(|(k, v)| f(k, v))
+    }
+
+    fn first_key(&self) -> Option<&K> {
+        self.entries.first().map(|(k, _)| k)
+    }
+
+    fn last_key(&self) -> Option<&K> {
+        self.entries.last().map(|(k, _)| k)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RecordStorage, SequenceStorage, StorageBackend};
+    use std::collections::BTreeMap;
+
+    /// Exercises a [RecordStorage] through its generic API only
+    fn generic_access<S: RecordStorage<u32, f64>>(storage: &mut S) {
+        assert!(storage.is_empty());
+
+        for (k, v) in [(3, 3.0), (1, 1.0), (2, 2.0)] {
+            assert!(storage.store(k, v).is_none());
+        }
+
+        assert_eq!(storage.store(2, 20.0), Some(2.0));
+        assert_eq!(storage.len(), 3);
+        assert_eq!(storage.entry_at(&2), Some(&20.0));
+        assert_eq!(storage.first_key(), Some(&1));
+        assert_eq!(storage.last_key(), Some(&3));
+
+        let keys = storage.entries().map(|(k, _)| *k).collect::<Vec<_>>();
+        assert_eq!(keys, vec![1, 2, 3]);
+
+        storage.retain_entries(&mut |k, _| *k != 1);
+        assert_eq!(storage.discard(&3), Some(3.0));
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage.first_key(), Some(&2));
+    }
+
+    #[test]
+    fn map_storage() {
+        let mut map = BTreeMap::<u32, f64>::new();
+        assert_eq!(map.backend(), StorageBackend::Map);
+        generic_access(&mut map);
+    }
+
+    #[test]
+    fn sequence_storage() {
+        let mut sequence = SequenceStorage::<u32, f64>::default();
+        assert_eq!(sequence.backend(), StorageBackend::Sequence);
+        generic_access(&mut sequence);
+
+        // both backends expose the same content
+        let map = BTreeMap::from([(3, 3.0), (1, 1.0), (2, 2.0)]);
+        let sequence = map
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .collect::<SequenceStorage<_, _>>();
+
+        assert!(map.entries().eq(sequence.entries()));
+    }
+}
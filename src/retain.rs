@@ -0,0 +1,371 @@
+//! Constellation, satellite and observable selection
+use crate::prelude::{Constellation, Observable, Rinex, SV};
+
+/// Returns true if this [Constellation] is selected by this list.
+/// [Constellation::SBAS] selects all augmentation systems, like masking does.
+fn constellation_selected(constellation: Constellation, selection: &[Constellation]) -> bool {
+    selection.contains(&constellation)
+        || (constellation.is_sbas() && selection.contains(&Constellation::SBAS))
+}
+
+impl Rinex {
+    /// Retains [SV] dependent content that matches this predicate, whatever the record type.
+    /// Event epochs are preserved. The header satellite tables are updated accordingly.
+    fn retain_sv_with_mut<F: Fn(SV) -> bool>(&mut self, f: F) {
+        if let Some(rec) = self.record.as_mut_obs() {
+            rec.retain(|_, obs| {
+                obs.signals.retain(|sig| f(sig.sv));
+                obs.event.is_some() || !obs.signals.is_empty()
+            });
+        } else if let Some(rec) = self.record.as_mut_nav() {
+            rec.retain(|k, _| f(k.sv));
+        } else if let Some(rec) = self.record.as_mut_clock() {
+            rec.retain(|_, profiles| {
+                profiles.retain(|k, _| k.clock_type.as_sv().map(&f).unwrap_or(true));
+                !profiles.is_empty()
+            });
+        }
+
+        self.header.glo_channels.retain(|sv, _| f(*sv));
+
+        let sv_count = self.sv_count();
+
+        if let Some(obs) = &mut self.header.obs {
+            obs.prn_obs_counts.retain(|sv, _| f(*sv));
+            if obs.num_sv.is_some() {
+                obs.num_sv = Some(sv_count);
+            }
+        }
+    }
+
+    /// Retains only content of these [Constellation]s, in place.
+    /// See [Self::retain_constellations].
+    pub fn retain_constellations_mut(&mut self, constellations: &[Constellation]) {
+        self.retain_sv_with_mut(|sv| constellation_selected(sv.constellation, constellations));
+
+        if let Some(obs) = &mut self.header.obs {
+            obs.codes
+                .retain(|c, _| constellation_selected(*c, constellations));
+            obs.scaling
+                .retain(|(c, _), _| constellation_selected(*c, constellations));
+            obs.phase_shifts
+                .retain(|shift| constellation_selected(shift.constellation, constellations));
+        }
+
+        self.header
+            .ionod_corrections
+            .retain(|c, _| constellation_selected(*c, constellations));
+
+        if self.header.constellation == Some(Constellation::Mixed) {
+            let remaining = self.constellations_iter().collect::<Vec<_>>();
+            if remaining.len() == 1 {
+                self.header.constellation = Some(remaining[0]);
+            }
+        }
+    }
+
+    /// Copies and returns a [Rinex] that only contains these [Constellation]s,
+    /// for example to extract a GPS only file from a mixed file. The observables
+    /// and scaling header tables are pruned consistently, and a mixed [Rinex]
+    /// that ends up with a single [Constellation] is declared as such.
+    /// [Constellation::SBAS] selects all augmentation systems.
+    /// ```
+    /// use rinex::prelude::{Constellation, Rinex};
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    /// E    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682
+    /// E01  23832393.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let gps = rinex.retain_constellations(&[Constellation::GPS]);
+    ///
+    /// assert_eq!(gps.sv_count(), 1);
+    /// assert_eq!(gps.header.constellation, Some(Constellation::GPS));
+    ///
+    /// let obs = gps.header.obs.as_ref().unwrap();
+    /// assert!(obs.codes.get(&Constellation::Galileo).is_none());
+    /// ```
+    pub fn retain_constellations(&self, constellations: &[Constellation]) -> Self {
+        let mut s = self.clone();
+        s.retain_constellations_mut(constellations);
+        s
+    }
+
+    /// Retains only these [Observable]s, in place. See [Self::retain_observables].
+    pub fn retain_observables_mut(&mut self, observables: &[Observable]) {
+        if let Some(rec) = self.record.as_mut_obs() {
+            rec.retain(|_, obs| {
+                obs.signals
+                    .retain(|sig| observables.contains(&sig.observable));
+                obs.event.is_some() || !obs.signals.is_empty()
+            });
+        } else if let Some(rec) = self.record.as_mut_meteo() {
+            rec.retain(|k, _| observables.contains(&k.observable));
+        }
+
+        if let Some(obs) = &mut self.header.obs {
+            // "PRN / # OF OBS" follows the declared observables order
+            let mut prn_obs_counts = std::mem::take(&mut obs.prn_obs_counts);
+            for (sv, counts) in prn_obs_counts.iter_mut() {
+                if let Some(declared) = obs.sv_observables(sv) {
+                    *counts = declared
+                        .iter()
+                        .zip(counts.iter())
+                        .filter_map(|(ob, count)| {
+                            if observables.contains(ob) {
+                                Some(*count)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect();
+                }
+            }
+            prn_obs_counts.retain(|_, counts| !counts.is_empty());
+            obs.prn_obs_counts = prn_obs_counts;
+
+            obs.codes.retain(|_, codes| {
+                codes.retain(|ob| observables.contains(ob));
+                !codes.is_empty()
+            });
+            obs.scaling.retain(|(_, ob), _| observables.contains(ob));
+            obs.phase_shifts
+                .retain(|shift| observables.contains(&shift.observable));
+        }
+
+        if let Some(meteo) = &mut self.header.meteo {
+            meteo.codes.retain(|ob| observables.contains(ob));
+            meteo
+                .sensors
+                .retain(|sensor| observables.contains(&sensor.observable));
+        }
+
+        self.header
+            .glo_biases
+            .retain(|ob, _| observables.contains(ob));
+    }
+
+    /// Copies and returns a [Rinex] that only contains these [Observable]s,
+    /// for example to strip a file down to a few signals prior publication.
+    /// The "SYS / # / OBS TYPES" (or "# / TYPES OF OBSERV") header tables are rewritten
+    /// accordingly: a [Constellation] without remaining [Observable] is no longer declared.
+    /// ```
+    /// use rinex::prelude::{Constellation, Observable, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    3 C1C L1C S1C                                          SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682   109474991.854          40.000
+    /// ";
+    ///
+    /// let c1c = Observable::from_str("C1C").unwrap();
+    /// let l1c = Observable::from_str("L1C").unwrap();
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let rinex = rinex.retain_observables(&[c1c.clone(), l1c.clone()]);
+    ///
+    /// assert_eq!(rinex.signal_observations_iter().count(), 2);
+    ///
+    /// let obs = rinex.header.obs.as_ref().unwrap();
+    /// assert_eq!(obs.codes[&Constellation::GPS], vec![c1c, l1c]);
+    /// ```
+    pub fn retain_observables(&self, observables: &[Observable]) -> Self {
+        let mut s = self.clone();
+        s.retain_observables_mut(observables);
+        s
+    }
+
+    /// Retains only these [SV]s, in place. See [Self::retain_sv].
+    pub fn retain_sv_mut(&mut self, satellites: &[SV]) {
+        self.retain_sv_with_mut(|sv| satellites.contains(&sv));
+    }
+
+    /// Copies and returns a [Rinex] that only contains these [SV]s, whatever the record type.
+    /// Event epochs are preserved. The header satellite counts ("# OF SATELLITES",
+    /// "PRN / # OF OBS") and Glonass channels are updated accordingly.
+    pub fn retain_sv(&self, satellites: &[SV]) -> Self {
+        let mut s = self.clone();
+        s.retain_sv_mut(satellites);
+        s
+    }
+
+    /// Removes these [SV]s, in place. See [Self::exclude_sv].
+    pub fn exclude_sv_mut(&mut self, satellites: &[SV]) {
+        self.retain_sv_with_mut(|sv| !satellites.contains(&sv));
+    }
+
+    /// Copies and returns a [Rinex] where these [SV]s were removed, for example
+    /// unhealthy or test satellites. See [Self::retain_sv].
+    /// ```
+    /// use rinex::prelude::{Rinex, SV};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682
+    /// G02  21832393.682
+    /// ";
+    ///
+    /// let g02 = SV::from_str("G02").unwrap();
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let rinex = rinex.exclude_sv(&[g02]);
+    ///
+    /// assert_eq!(rinex.sv_count(), 1);
+    /// assert!(rinex.sv_iter().all(|sv| sv != g02));
+    /// ```
+    pub fn exclude_sv(&self, satellites: &[SV]) -> Self {
+        let mut s = self.clone();
+        s.exclude_sv_mut(satellites);
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        navigation::{Ephemeris, NavFrame, NavKey},
+        prelude::{Constellation, Epoch, Observable, Rinex, SV},
+    };
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    #[test]
+    fn retain_constellations() {
+        let content =
+            "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+E    1 C1C                                                  SYS / # / OBS TYPES
+R    1 C1C                                                  SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  3
+G01  20832393.682
+E01  23832393.682
+R01  21832393.682
+> 2020 01 01 00 00 30.0000000  0  1
+R01  21832394.682
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let masked = rinex.retain_constellations(&[Constellation::GPS, Constellation::Galileo]);
+        assert_eq!(masked.header.constellation, Some(Constellation::Mixed));
+        assert_eq!(masked.epoch_iter().count(), 1);
+        assert_eq!(
+            masked.sv_iter().collect::<Vec<_>>(),
+            vec![SV::from_str("G01").unwrap(), SV::from_str("E01").unwrap()]
+        );
+
+        let obs = masked.header.obs.as_ref().unwrap();
+        assert_eq!(obs.codes.len(), 2);
+        assert!(obs.codes.get(&Constellation::Glonass).is_none());
+
+        let glo = rinex.retain_constellations(&[Constellation::Glonass]);
+        assert_eq!(glo.header.constellation, Some(Constellation::Glonass));
+        assert_eq!(glo.epoch_iter().count(), 2);
+
+        let obs = glo.header.obs.as_ref().unwrap();
+        assert_eq!(
+            obs.codes[&Constellation::Glonass],
+            vec![Observable::from_str("C1C").unwrap()]
+        );
+
+        let empty = rinex.retain_constellations(&[]);
+        assert_eq!(empty.epoch_iter().count(), 0);
+    }
+
+    #[test]
+    fn retain_observables() {
+        let content =
+            "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+G    3 C1C L1C C2W                                          SYS / # / OBS TYPES
+E    2 C1C C5Q                                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  2
+G01  20832393.682   109474991.854    20832395.682
+E01                                  23832393.682
+";
+        let mut rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        rinex.header.obs.as_mut().unwrap().prn_obs_counts = rinex.prn_obs_counts();
+
+        let (c1c, c2w) = (
+            Observable::from_str("C1C").unwrap(),
+            Observable::from_str("C2W").unwrap(),
+        );
+
+        let masked = rinex.retain_observables(&[c1c.clone(), c2w.clone()]);
+        assert_eq!(masked.signal_observations_iter().count(), 2);
+
+        let obs = masked.header.obs.as_ref().unwrap();
+        assert_eq!(obs.codes[&Constellation::GPS], vec![c1c.clone(), c2w]);
+        assert_eq!(obs.codes[&Constellation::Galileo], vec![c1c.clone()]);
+
+        // counts follow the rewritten tables
+        assert_eq!(obs.prn_obs_counts, masked.prn_obs_counts());
+        assert_eq!(
+            obs.prn_obs_counts.keys().collect::<Vec<_>>(),
+            vec![&SV::from_str("G01").unwrap()]
+        );
+
+        // E01 no longer has data, Galileo is no longer declared
+        let masked = rinex.retain_observables(&[Observable::from_str("L1C").unwrap()]);
+        let obs = masked.header.obs.as_ref().unwrap();
+        assert_eq!(obs.codes.len(), 1);
+        assert_eq!(masked.sv_count(), 1);
+    }
+
+    #[test]
+    fn retain_and_exclude_sv() {
+        let content =
+            "     3.04           OBSERVATION DATA    R (GLONASS)         RINEX VERSION / TYPE
+R    1 C1C                                                  SYS / # / OBS TYPES
+  2 R01  1 R02 -4                                           GLONASS SLOT / FRQ #
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  2
+R01  20832393.682
+R02  21832393.682
+> 2020 01 01 00 00 30.0000000  0  1
+R02  21832394.682
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let (r01, r02) = (SV::from_str("R01").unwrap(), SV::from_str("R02").unwrap());
+
+        let masked = rinex.retain_sv(&[r01]);
+        assert_eq!(masked.epoch_iter().count(), 1);
+        assert_eq!(masked.sv_iter().collect::<Vec<_>>(), vec![r01]);
+        assert_eq!(masked.header.glo_channels.len(), 1);
+        assert_eq!(masked.header.glo_channels.get(&r01), Some(&1));
+
+        let masked = rinex.exclude_sv(&[r01]);
+        assert_eq!(masked.epoch_iter().count(), 2);
+        assert_eq!(masked.sv_iter().collect::<Vec<_>>(), vec![r02]);
+
+        // navigation frames
+        let mut nav = Rinex::basic_nav();
+        let t = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let record = nav.record.as_mut_nav().unwrap();
+        for sv in [r01, r02] {
+            let key = NavKey {
+                epoch: t,
+                sv,
+                msgtype: Default::default(),
+                frmtype: Default::default(),
+            };
+            record.insert(key, NavFrame::EPH(Ephemeris::default()));
+        }
+
+        let masked = nav.exclude_sv(&[r02]);
+        assert_eq!(masked.sv_iter().collect::<Vec<_>>(), vec![r01]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Rounding of fixed width numeric fields
+
+/// [RoundingMode] used when formatting fixed width numeric fields
+/// (for example, F14.3 observations).
+/// Rounding applies to the shortest decimal representation of the value,
+/// which is the value you would read back from a file. This makes the output
+/// predictable and lets us match the output of reference tools exactly.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Ties are rounded to the nearest even digit ("banker's rounding"),
+    /// for example 0.0125 is written 0.012 at 3 decimal places.
+    #[default]
+    HalfEven,
+    /// Ties are rounded away from zero,
+    /// for example 0.0125 is written 0.013 at 3 decimal places.
+    HalfAwayFromZero,
+}
+
+impl RoundingMode {
+    /// Rounds `value` to `precision` decimal places, returned as a readable string.
+    /// ```
+    /// use rinex::prelude::RoundingMode;
+    ///
+    /// assert_eq!(RoundingMode::HalfEven.round(0.0125, 3), "0.012");
+    /// assert_eq!(RoundingMode::HalfAwayFromZero.round(0.0125, 3), "0.013");
+    /// assert_eq!(RoundingMode::HalfEven.round(-2.5, 0), "-2");
+    /// assert_eq!(RoundingMode::HalfAwayFromZero.round(-2.5, 0), "-3");
+    /// ```
+    pub fn round(&self, value: f64, precision: usize) -> String {
+        if !value.is_finite() {
+            return format!("{:.precision$}", value, precision = precision);
+        }
+
+        // shortest (round trip) decimal representation
+        let repr = format!("{}", value.abs());
+
+        let (integer, fraction) = match repr.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (repr.as_str(), ""),
+        };
+
+        // retained digits
+        let mut digits = integer.bytes().collect::<Vec<_>>();
+        let kept = fraction.len().min(precision);
+        digits.extend(fraction[..kept].bytes());
+        digits.resize(digits.len() + precision - kept, b'0');
+
+        if fraction.len() > precision {
+            let first = fraction.as_bytes()[precision];
+            let tie = first == b'5' && fraction[precision + 1..].bytes().all(|b| b == b'0');
+
+            let round_up = if tie {
+                match self {
+                    Self::HalfAwayFromZero => true,
+                    Self::HalfEven => {
+                        let last = digits.last().copied().unwrap_or(b'0');
+                        (last - b'0') % 2 == 1
+                    },
+                }
+            } else {
+                first >= b'5'
+            };
+
+            if round_up {
+                // propagate carry
+                let mut i = digits.len();
+                loop {
+                    if i == 0 {
+                        digits.insert(0, b'1');
+                        break;
+                    }
+                    i -= 1;
+                    if digits[i] == b'9' {
+                        digits[i] = b'0';
+                    } else {
+                        digits[i] += 1;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let split = digits.len() - precision;
+        let (integer, fraction) = digits.split_at(split);
+
+        let is_zero = digits.iter().all(|b| *b == b'0');
+        let sign = if value.is_sign_negative() && !is_zero {
+            "-"
+        } else {
+            ""
+        };
+
+        let integer = String::from_utf8_lossy(integer);
+
+        if precision == 0 {
+            format!("{}{}", sign, integer)
+        } else {
+            format!("{}{}.{}", sign, integer, String::from_utf8_lossy(fraction))
+        }
+    }
+}
+
+/// [FixedFormatter] formats fixed width numeric fields (Fw.p),
+/// right aligned, following desired [RoundingMode].
+pub(crate) struct FixedFormatter {
+    value: f64,
+    width: usize,
+    precision: usize,
+    rounding: RoundingMode,
+}
+
+impl FixedFormatter {
+    pub fn new(value: f64, width: usize, precision: usize, rounding: RoundingMode) -> Self {
+        Self {
+            value,
+            width,
+            precision,
+            rounding,
+        }
+    }
+
+    /// F14.3 observation field
+    pub fn new_observation(value: f64, rounding: RoundingMode) -> Self {
+        Self::new(value, 14, 3, rounding)
+    }
+}
+
+impl std::fmt::Display for FixedFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>width$}",
+            self.rounding.round(self.value, self.precision),
+            width = self.width
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rounding_modes() {
+        for (value, precision, half_even, half_away) in [
+            (0.0125, 3, "0.012", "0.013"),
+            (0.0135, 3, "0.014", "0.014"),
+            (1.5, 0, "2", "2"),
+            (2.5, 0, "2", "3"),
+            (-2.5, 0, "-2", "-3"),
+            (-0.0005, 3, "0.000", "-0.001"),
+            (9.9995, 3, "10.000", "10.000"),
+            (999.9995, 3, "1000.000", "1000.000"),
+            (123.4, 3, "123.400", "123.400"),
+            (20832393.6825, 3, "20832393.682", "20832393.683"),
+            (20832393.68251, 3, "20832393.683", "20832393.683"),
+            (0.01251, 3, "0.013", "0.013"),
+            (0.01350001, 3, "0.014", "0.014"),
+            (-0.01251, 3, "-0.013", "-0.013"),
+            (0.0124999, 3, "0.012", "0.012"),
+            (0.0, 1, "0.0", "0.0"),
+            (-1.26, 1, "-1.3", "-1.3"),
+            (7.0, 0, "7", "7"),
+        ] {
+            assert_eq!(
+                RoundingMode::HalfEven.round(value, precision),
+                half_even,
+                "half-even({}, {})",
+                value,
+                precision
+            );
+            assert_eq!(
+                RoundingMode::HalfAwayFromZero.round(value, precision),
+                half_away,
+                "half-away({}, {})",
+                value,
+                precision
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_formatter() {
+        let formatted = FixedFormatter::new_observation(20832393.6825, RoundingMode::HalfEven);
+        assert_eq!(formatted.to_string(), "  20832393.682");
+
+        let formatted =
+            FixedFormatter::new_observation(20832393.6825, RoundingMode::HalfAwayFromZero);
+        assert_eq!(formatted.to_string(), "  20832393.683");
+
+        let formatted = FixedFormatter::new(-5.25, 7, 1, RoundingMode::HalfAwayFromZero);
+        assert_eq!(formatted.to_string(), "   -5.3");
+    }
+}
@@ -1,4 +1,40 @@
-use crate::prelude::{Duration, Epoch, Rinex, TimeSeries};
+use crate::prelude::{Duration, Epoch, Rinex, TimeSeries, SV};
+
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [SamplingReport] describes the actual epoch spacing of a [Rinex],
+/// see [Rinex::dominant_sample_rate].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SamplingReport {
+    /// Dominant (most frequent) epoch spacing
+    pub interval: Duration,
+    /// Histogram of epoch to epoch spacings, with their number of occurrences
+    pub histogram: BTreeMap<Duration, usize>,
+    /// [Epoch]s that are not spaced by the dominant interval from their
+    /// predecessor, with the actual spacing
+    pub outliers: Vec<(Epoch, Duration)>,
+}
+
+impl SamplingReport {
+    /// Returns the dominant sample rate, in Hertz
+    pub fn rate_hz(&self) -> f64 {
+        1.0 / self.interval.to_seconds()
+    }
+
+    /// Returns true if the "INTERVAL" header field is consistent with the
+    /// dominant epoch spacing (or simply missing)
+    pub fn matches_header(&self, rinex: &Rinex) -> bool {
+        match rinex.header.sampling_interval {
+            Some(dt) => dt == self.interval,
+            None => true,
+        }
+    }
+}
 
 impl Rinex {
     /// Returns first [Epoch] encountered in time
@@ -27,10 +63,36 @@ impl Rinex {
         Some(TimeSeries::inclusive(start, end, dt))
     }
 
-    /// Returns sample rate report by the GNSS receiver (if any).
-    /// NB: this is not actual data set analysis.
+    /// Returns the sampling interval of this [Rinex]: the "INTERVAL" header field
+    /// when it is declared, otherwise the dominant epoch spacing of the record
+    /// (see [Self::dominant_sampling_interval]), since many files omit this field.
+    /// ```
+    /// use rinex::prelude::{Duration, Rinex};
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 15.0000000  0  1
+    /// G01  20832394.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832395.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// assert_eq!(rinex.sampling_interval(), Some(Duration::from_seconds(15.0)));
+    ///
+    /// // declared value prevails
+    /// let rinex = rinex.with_header(
+    ///     rinex.header.with_sampling_interval(Duration::from_seconds(30.0)));
+    /// assert_eq!(rinex.sampling_interval(), Some(Duration::from_seconds(30.0)));
+    /// ```
     pub fn sampling_interval(&self) -> Option<Duration> {
-        self.header.sampling_interval
+        self.header
+            .sampling_interval
+            .or_else(|| self.dominant_sampling_interval())
     }
 
     /// Returns dominant sampling period, expressed as [Duration], by actual data analysis.
@@ -48,6 +110,73 @@ impl Rinex {
             .map(|dominant| dominant.0)
     }
 
+    /// Analyzes actual epoch spacing, by means of an histogram of epoch to epoch
+    /// spacings, and returns the dominant (most frequent) interval, along with the
+    /// epochs that do not follow it. This does not rely on the "INTERVAL" header field,
+    /// which is often wrong or absent. When several intervals are equally frequent,
+    /// the shortest one prevails. Returns None when there are less than two epochs.
+    /// ```
+    /// use rinex::prelude::{Duration, Rinex};
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    ///     60.000                                                  INTERVAL
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// > 2020 01 01 00 01  0.0000000  0  1
+    /// G01  20832395.682
+    /// > 2020 01 01 00 02  0.0000000  0  1
+    /// G01  20832396.682
+    /// > 2020 01 01 00 02 30.0000000  0  1
+    /// G01  20832397.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let report = rinex.dominant_sample_rate().unwrap();
+    /// assert_eq!(report.interval, Duration::from_seconds(30.0));
+    /// assert_eq!(report.outliers.len(), 1);
+    ///
+    /// // header is wrong
+    /// assert!(!report.matches_header(&rinex));
+    /// ```
+    pub fn dominant_sample_rate(&self) -> Option<SamplingReport> {
+        let histogram = self
+            .sampling_histogram()
+            .collect::<BTreeMap<Duration, usize>>();
+
+        // sorted by increasing interval: shortest prevails on equal population
+        let (interval, _) = histogram
+            .iter()
+            .rev()
+            .max_by(|(_, pop_i), (_, pop_j)| pop_i.cmp(pop_j))?;
+
+        let interval = *interval;
+
+        let outliers = self
+            .epoch_iter()
+            .zip(self.epoch_iter().skip(1))
+            .filter_map(|(ek, ekp1)| {
+                let dt = ekp1 - ek;
+                if dt != interval {
+                    Some((ekp1, dt))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Some(SamplingReport {
+            interval,
+            histogram,
+            outliers,
+        })
+    }
+
     /// Returns dominant sample rate (in Hertz) by actual data analysis.
     pub fn dominant_sampling_rate_hz(&self) -> Option<f64> {
         let interval = self.dominant_sampling_interval()?;
@@ -144,22 +273,12 @@ impl Rinex {
         &self,
         tolerance: Option<Duration>,
     ) -> Box<dyn Iterator<Item = (Epoch, Duration)> + '_> {
-        let sample_rate: Duration = match tolerance {
-            Some(dt) => dt, // user defined
+        let sample_rate = match self.data_gaps_threshold(tolerance) {
+            Some(dt) => dt,
             None => {
-                match self.dominant_sampling_interval() {
-                    Some(dt) => dt,
-                    None => {
-                        match self.sampling_interval() {
-                            Some(dt) => dt,
-                            None => {
-                                // not enough information
-                                // this is probably not an Epoch iterated RINEX
-                                return Box::new(Vec::<(Epoch, Duration)>::new().into_iter());
-                            },
-                        }
-                    },
-                }
+                // not enough information
+                // this is probably not an Epoch iterated RINEX
+                return Box::new(Vec::<(Epoch, Duration)>::new().into_iter());
             },
         };
         Box::new(
@@ -176,14 +295,221 @@ impl Rinex {
                 }),
         )
     }
+
+    /// Returns unexpected data gaps of each [SV], in the form ([Epoch], [Duration])
+    /// where epoch is the last sampling of this [SV] prior the gap, see [Self::data_gaps].
+    /// Gaps are detected between two consecutive observations of the same [SV],
+    /// so epochs where this [SV] is not tracked (out of sight for example) are gaps.
+    /// Only applies to Observation RINEX.
+    /// ```
+    /// use rinex::prelude::{Duration, Rinex, SV};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  2
+    /// G01  20832393.682
+    /// G02  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// > 2020 01 01 00 01  0.0000000  0  2
+    /// G01  20832395.682
+    /// G02  20832395.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// // file is complete
+    /// assert_eq!(rinex.data_gaps(None).count(), 0);
+    ///
+    /// let gaps = rinex.sv_data_gaps(None);
+    ///
+    /// let g01 = SV::from_str("G01").unwrap();
+    /// let g02 = SV::from_str("G02").unwrap();
+    ///
+    /// assert!(gaps.get(&g01).is_none());
+    /// assert_eq!(gaps[&g02].len(), 1);
+    /// assert_eq!(gaps[&g02][0].1, Duration::from_seconds(60.0));
+    /// ```
+    pub fn sv_data_gaps(
+        &self,
+        tolerance: Option<Duration>,
+    ) -> BTreeMap<SV, Vec<(Epoch, Duration)>> {
+        let mut ret = BTreeMap::<SV, Vec<(Epoch, Duration)>>::new();
+
+        let sample_rate = match self.data_gaps_threshold(tolerance) {
+            Some(dt) => dt,
+            None => return ret,
+        };
+
+        let mut past = HashMap::<SV, Epoch>::new();
+
+        for (k, v) in self.observations_iter() {
+            for sv in v.signals.iter().map(|sig| sig.sv).unique() {
+                if let Some(prev) = past.insert(sv, k.epoch) {
+                    let dt = k.epoch - prev;
+                    if dt > sample_rate {
+                        ret.entry(sv).or_default().push((prev, dt));
+                    }
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Returns the [Duration] above which epoch spacing is a data gap:
+    /// user defined tolerance, or nominal sampling interval
+    fn data_gaps_threshold(&self, tolerance: Option<Duration>) -> Option<Duration> {
+        tolerance
+            .or_else(|| self.dominant_sampling_interval())
+            .or(self.header.sampling_interval)
+    }
 }
 
 #[cfg(test)]
-#[cfg(feature = "flate2")]
 mod test {
-    use crate::prelude::Rinex;
+    use crate::prelude::{Duration, Epoch, Rinex, SV};
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    #[test]
+    fn sampling_interval_header_field() {
+        let content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     1.000                                                  INTERVAL
+     1    C1                                                # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  1G01
+  20832393.682
+ 20  1  1  0  0 30.0000000  0  1G01
+  20832394.682
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        assert_eq!(
+            rinex.header.sampling_interval,
+            Some(Duration::from_seconds(1.0))
+        );
+        assert_eq!(rinex.sampling_interval(), Some(Duration::from_seconds(1.0)));
+        assert_eq!(
+            rinex.dominant_sampling_interval(),
+            Some(Duration::from_seconds(30.0))
+        );
+    }
+
+    #[test]
+    fn dominant_sample_rate_outliers() {
+        let mut content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     1    C1                                                # / TYPES OF OBSERV
+                                                            END OF HEADER
+"
+            .to_string();
+
+        // 15s sampling, with one data gap and one irregular epoch
+        for seconds in [0, 15, 30, 45, 105, 120, 127, 135, 150] {
+            content.push_str(&format!(
+                " 20  1  1  0 {:2} {:2}.0000000  0  1G01\n  20832393.682\n",
+                seconds / 60,
+                seconds % 60
+            ));
+        }
+
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let report = rinex.dominant_sample_rate().unwrap();
+
+        assert_eq!(report.interval, Duration::from_seconds(15.0));
+        assert_eq!(report.rate_hz(), 1.0 / 15.0);
+        assert_eq!(report.histogram.values().sum::<usize>(), 8);
+        assert_eq!(
+            report.histogram.get(&Duration::from_seconds(15.0)),
+            Some(&5)
+        );
+        assert!(report.matches_header(&rinex));
+
+        let outliers = report
+            .outliers
+            .iter()
+            .map(|(_, dt)| dt.to_seconds())
+            .collect::<Vec<_>>();
+
+        assert_eq!(outliers, vec![60.0, 7.0, 8.0]);
+
+        // equal population: shortest interval prevails
+        let mut content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     1    C1                                                # / TYPES OF OBSERV
+                                                            END OF HEADER
+"
+            .to_string();
+
+        for seconds in [0, 30, 40] {
+            content.push_str(&format!(
+                " 20  1  1  0  0 {:2}.0000000  0  1G01\n  20832393.682\n",
+                seconds
+            ));
+        }
+
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let report = rinex.dominant_sample_rate().unwrap();
+        assert_eq!(report.interval, Duration::from_seconds(10.0));
+    }
+
+    #[test]
+    fn file_and_sv_data_gaps() {
+        let content =
+            "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  2
+G01  20832393.682
+G02  20832393.682
+> 2020 01 01 00 00 30.0000000  0  1
+G01  20832394.682
+> 2020 01 01 00 01  0.0000000  0  1
+G01  20832395.682
+> 2020 01 01 00 03  0.0000000  0  2
+G01  20832396.682
+G02  20832396.682
+> 2020 01 01 00 03 30.0000000  0  1
+G02  20832397.682
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let g01 = SV::from_str("G01").unwrap();
+        let g02 = SV::from_str("G02").unwrap();
+
+        let gaps = rinex.data_gaps(None).collect::<Vec<_>>();
+        assert_eq!(
+            gaps,
+            vec![(
+                t0 + Duration::from_seconds(60.0),
+                Duration::from_seconds(120.0)
+            )]
+        );
+
+        let gaps = rinex.sv_data_gaps(None);
+        assert_eq!(
+            gaps[&g01],
+            vec![(
+                t0 + Duration::from_seconds(60.0),
+                Duration::from_seconds(120.0)
+            )]
+        );
+        assert_eq!(gaps[&g02], vec![(t0, Duration::from_seconds(180.0))]);
+
+        // tolerated
+        let gaps = rinex.sv_data_gaps(Some(Duration::from_seconds(150.0)));
+        assert!(gaps.get(&g01).is_none());
+        assert_eq!(gaps[&g02].len(), 1);
+    }
 
     #[test]
+    #[cfg(feature = "flate2")]
     fn glacier_20240506_dominant_sample_rate() {
         let rnx = Rinex::from_gzip_file(format!(
             "{}/data/OBS/V3/240506_glacier_station.obs.gz",
@@ -0,0 +1,565 @@
+//! IGS site log support, to validate [Header] descriptions
+
+use crate::{
+    hardware::{Antenna, Receiver},
+    prelude::{Epoch, Header, ParsingError, Rinex},
+};
+
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Eccentricities are described with 0.1 mm resolution
+const ECCENTRICITY_TOLERANCE_M: f64 = 1.0E-4;
+
+/// [SiteLogReceiver] is one receiver installation described in the site log
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SiteLogReceiver {
+    /// [Receiver] description
+    pub receiver: Receiver,
+    /// Installation [Epoch]
+    pub installed: Option<Epoch>,
+    /// Removal [Epoch], None while still installed
+    pub removed: Option<Epoch>,
+}
+
+/// [SiteLogAntenna] is one antenna installation described in the site log
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SiteLogAntenna {
+    /// [Antenna] description: model (including radome)
+    /// and Marker->ARP eccentricities
+    pub antenna: Antenna,
+    /// Installation [Epoch]
+    pub installed: Option<Epoch>,
+    /// Removal [Epoch], None while still installed
+    pub removed: Option<Epoch>,
+}
+
+/// Returns true if [Epoch] lies within installation period
+fn installed_at(installed: Option<Epoch>, removed: Option<Epoch>, t: Epoch) -> bool {
+    installed.map(|i| t >= i).unwrap_or(true) && removed.map(|r| t < r).unwrap_or(true)
+}
+
+impl SiteLogReceiver {
+    /// Returns true if this [Receiver] was installed at this [Epoch]
+    pub fn is_valid(&self, t: Epoch) -> bool {
+        installed_at(self.installed, self.removed, t)
+    }
+}
+
+impl SiteLogAntenna {
+    /// Returns true if this [Antenna] was installed at this [Epoch]
+    pub fn is_valid(&self, t: Epoch) -> bool {
+        installed_at(self.installed, self.removed, t)
+    }
+}
+
+/// [SiteLog] describes the history of a permanent station, as published
+/// by IGS and data centers. It is parsed from the standard ASCII format,
+/// or deserialized from its JSON form (with the "serde" feature).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SiteLog {
+    /// Four character station ID
+    pub four_char_id: String,
+    /// IERS DOMES number, when defined
+    pub domes: Option<String>,
+    /// Receiver installations history
+    pub receivers: Vec<SiteLogReceiver>,
+    /// Antenna installations history
+    pub antennas: Vec<SiteLogAntenna>,
+}
+
+/// Parses "CCYY-MM-DDThh:mmZ" (or "CCYY-MM-DD") site log dates.
+/// Empty fields or templates return None.
+fn parse_date(content: &str) -> Option<Epoch> {
+    let content = content.trim().trim_end_matches('Z');
+
+    let (date, time) = content.split_once('T').unwrap_or((content, "00:00"));
+
+    let mut date = date.split('-');
+    let y = date.next()?.parse::<i32>().ok()?;
+    let m = date.next()?.parse::<u8>().ok()?;
+    let d = date.next()?.parse::<u8>().ok()?;
+
+    let (hh, mm) = time.split_once(':').unwrap_or((time, "00"));
+    let hh = hh.parse::<u8>().ok()?;
+    let mm = mm.parse::<u8>().ok()?;
+
+    Some(Epoch::from_gregorian_utc(y, m, d, hh, mm, 0, 0))
+}
+
+/// Site log section being parsed
+enum Section {
+    None,
+    Receiver,
+    Antenna,
+}
+
+impl FromStr for SiteLog {
+    type Err = ParsingError;
+    /// Parses [SiteLog] from standard ASCII content.
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut log = Self::default();
+        let mut section = Section::None;
+
+        for line in content.lines() {
+            // new section
+            let trimmed = line.trim_start();
+            if line.starts_with(|c: char| c.is_ascii_digit()) {
+                section = Section::None;
+
+                let index = trimmed.split_ascii_whitespace().next().unwrap_or("");
+                // template entries (3.x, 4.x) are not retained
+                let is_item = index.split('.').nth(1).is_some_and(|item| {
+                    !item.is_empty() && item.chars().all(|c| c.is_ascii_digit())
+                });
+
+                if is_item && index.starts_with("3.") && line.contains("Receiver Type") {
+                    section = Section::Receiver;
+                    log.receivers.push(Default::default());
+                } else if is_item && index.starts_with("4.") && line.contains("Antenna Type") {
+                    section = Section::Antenna;
+                    log.antennas.push(Default::default());
+                }
+            }
+
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+
+            // templates and empty fields
+            if value.is_empty() || value.starts_with('(') {
+                continue;
+            }
+
+            if key.contains("Four Character ID") || key.contains("Nine Character ID") {
+                log.four_char_id = value.chars().take(4).collect::<String>().to_uppercase();
+                continue;
+            } else if key.contains("IERS DOMES Number") {
+                log.domes = Some(value.to_string());
+                continue;
+            }
+
+            match section {
+                Section::Receiver => {
+                    let rcvr = log.receivers.last_mut().unwrap();
+                    if key.contains("Receiver Type") {
+                        rcvr.receiver = rcvr.receiver.with_model(value);
+                    } else if key.contains("Serial Number") {
+                        rcvr.receiver = rcvr.receiver.with_serial_number(value);
+                    } else if key.contains("Firmware Version") {
+                        rcvr.receiver = rcvr.receiver.with_firmware(value);
+                    } else if key.contains("Date Installed") {
+                        rcvr.installed = parse_date(value);
+                    } else if key.contains("Date Removed") {
+                        rcvr.removed = parse_date(value);
+                    }
+                },
+                Section::Antenna => {
+                    let ant = log.antennas.last_mut().unwrap();
+                    if key.contains("Antenna Type") {
+                        // model + radome, as in RINEX
+                        ant.antenna = ant.antenna.with_model(value);
+                    } else if key.contains("Serial Number") {
+                        ant.antenna = ant.antenna.with_serial_number(value);
+                    } else if key.contains("Up Ecc") {
+                        let h = value.parse::<f64>().map_err(|_| ParsingError::SiteLog)?;
+                        ant.antenna = ant.antenna.with_height(h);
+                    } else if key.contains("North Ecc") {
+                        let n = value.parse::<f64>().map_err(|_| ParsingError::SiteLog)?;
+                        ant.antenna = ant.antenna.with_northern_component(n);
+                    } else if key.contains("East Ecc") {
+                        let e = value.parse::<f64>().map_err(|_| ParsingError::SiteLog)?;
+                        ant.antenna = ant.antenna.with_eastern_component(e);
+                    } else if key.contains("Date Installed") {
+                        ant.installed = parse_date(value);
+                    } else if key.contains("Date Removed") {
+                        ant.removed = parse_date(value);
+                    }
+                },
+                Section::None => {},
+            }
+        }
+
+        if log.four_char_id.is_empty() {
+            return Err(ParsingError::SiteLog);
+        }
+
+        Ok(log)
+    }
+}
+
+impl SiteLog {
+    /// Returns [SiteLogReceiver] installed at this [Epoch]
+    pub fn receiver(&self, t: Epoch) -> Option<&SiteLogReceiver> {
+        self.receivers.iter().find(|rcvr| rcvr.is_valid(t))
+    }
+
+    /// Returns [SiteLogAntenna] installed at this [Epoch]
+    pub fn antenna(&self, t: Epoch) -> Option<&SiteLogAntenna> {
+        self.antennas.iter().find(|ant| ant.is_valid(t))
+    }
+}
+
+/// [SiteLogIssue] is one discrepancy between [Header] and [SiteLog]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SiteLogIssue {
+    /// Marker name does not match the station ID
+    MarkerName { header: String, log: String },
+    /// Marker number does not match the DOMES number
+    MarkerNumber { header: String, log: String },
+    /// Header does not describe the receiver
+    MissingReceiver,
+    /// Site log does not describe any receiver at this epoch
+    UndocumentedReceiver,
+    /// Receiver model mismatch
+    ReceiverModel { header: String, log: String },
+    /// Receiver serial number mismatch
+    ReceiverSerialNumber { header: String, log: String },
+    /// Receiver firmware mismatch
+    ReceiverFirmware { header: String, log: String },
+    /// Header does not describe the antenna
+    MissingAntenna,
+    /// Site log does not describe any antenna at this epoch
+    UndocumentedAntenna,
+    /// Antenna model (or radome) mismatch
+    AntennaModel { header: String, log: String },
+    /// Antenna serial number mismatch
+    AntennaSerialNumber { header: String, log: String },
+    /// Antenna eccentricity mismatch, in meters, for this component ('H', 'E' or 'N')
+    AntennaEccentricity {
+        component: char,
+        header: f64,
+        log: f64,
+    },
+}
+
+impl std::fmt::Display for SiteLogIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MarkerName { header, log } => {
+                write!(f, "marker name \"{}\" should be \"{}\"", header, log)
+            },
+            Self::MarkerNumber { header, log } => {
+                write!(f, "marker number \"{}\" should be \"{}\"", header, log)
+            },
+            Self::MissingReceiver => write!(f, "receiver is not described"),
+            Self::UndocumentedReceiver => write!(f, "site log does not document any receiver"),
+            Self::ReceiverModel { header, log } => {
+                write!(f, "receiver model \"{}\" should be \"{}\"", header, log)
+            },
+            Self::ReceiverSerialNumber { header, log } => {
+                write!(
+                    f,
+                    "receiver serial number \"{}\" should be \"{}\"",
+                    header, log
+                )
+            },
+            Self::ReceiverFirmware { header, log } => {
+                write!(f, "receiver firmware \"{}\" should be \"{}\"", header, log)
+            },
+            Self::MissingAntenna => write!(f, "antenna is not described"),
+            Self::UndocumentedAntenna => write!(f, "site log does not document any antenna"),
+            Self::AntennaModel { header, log } => {
+                write!(f, "antenna model \"{}\" should be \"{}\"", header, log)
+            },
+            Self::AntennaSerialNumber { header, log } => {
+                write!(
+                    f,
+                    "antenna serial number \"{}\" should be \"{}\"",
+                    header, log
+                )
+            },
+            Self::AntennaEccentricity {
+                component,
+                header,
+                log,
+            } => write!(
+                f,
+                "antenna {} eccentricity {:.4}m should be {:.4}m",
+                component, header, log
+            ),
+        }
+    }
+}
+
+/// [SiteLogReport] lists all discrepancies between [Header] and [SiteLog]
+/// at a given [Epoch]. Station operators should address all of them
+/// prior submitting data to data centers.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SiteLogReport {
+    /// [Epoch] of validation
+    pub epoch: Epoch,
+    /// [SiteLogIssue]s found
+    pub issues: Vec<SiteLogIssue>,
+}
+
+impl SiteLogReport {
+    /// Returns true if [Header] complies with the [SiteLog]
+    pub fn is_compliant(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Compares two descriptors, tolerating case and spacing differences
+fn same_descriptor(lhs: &str, rhs: &str) -> bool {
+    let lhs = lhs.split_ascii_whitespace().collect::<Vec<_>>();
+    let rhs = rhs.split_ascii_whitespace().collect::<Vec<_>>();
+    lhs.len() == rhs.len()
+        && lhs
+            .iter()
+            .zip(rhs.iter())
+            .all(|(lhs, rhs)| lhs.eq_ignore_ascii_case(rhs))
+}
+
+impl Header {
+    /// Validates this [Header] against the [SiteLog], at this [Epoch]:
+    /// marker name and number, receiver and antenna models, serial numbers
+    /// and antenna eccentricities must match the installation valid at that time.
+    pub fn site_log_compliance(&self, log: &SiteLog, t: Epoch) -> SiteLogReport {
+        let mut issues = Vec::new();
+
+        if let Some(marker) = &self.geodetic_marker {
+            let name = marker.name.chars().take(4).collect::<String>();
+            if !name.eq_ignore_ascii_case(&log.four_char_id) {
+                issues.push(SiteLogIssue::MarkerName {
+                    header: marker.name.clone(),
+                    log: log.four_char_id.clone(),
+                });
+            }
+
+            if let Some(domes) = &log.domes {
+                let number = marker.number().unwrap_or_default();
+                if !number.eq_ignore_ascii_case(domes) {
+                    issues.push(SiteLogIssue::MarkerNumber {
+                        header: number,
+                        log: domes.clone(),
+                    });
+                }
+            }
+        }
+
+        match (&self.rcvr, log.receiver(t)) {
+            (None, _) => issues.push(SiteLogIssue::MissingReceiver),
+            (Some(_), None) => issues.push(SiteLogIssue::UndocumentedReceiver),
+            (Some(rcvr), Some(installed)) => {
+                let expected = &installed.receiver;
+                if !same_descriptor(&rcvr.model, &expected.model) {
+                    issues.push(SiteLogIssue::ReceiverModel {
+                        header: rcvr.model.clone(),
+                        log: expected.model.clone(),
+                    });
+                }
+                if !same_descriptor(&rcvr.sn, &expected.sn) {
+                    issues.push(SiteLogIssue::ReceiverSerialNumber {
+                        header: rcvr.sn.clone(),
+                        log: expected.sn.clone(),
+                    });
+                }
+                if !expected.firmware.is_empty()
+                    && !same_descriptor(&rcvr.firmware, &expected.firmware)
+                {
+                    issues.push(SiteLogIssue::ReceiverFirmware {
+                        header: rcvr.firmware.clone(),
+                        log: expected.firmware.clone(),
+                    });
+                }
+            },
+        }
+
+        match (&self.rcvr_antenna, log.antenna(t)) {
+            (None, _) => issues.push(SiteLogIssue::MissingAntenna),
+            (Some(_), None) => issues.push(SiteLogIssue::UndocumentedAntenna),
+            (Some(ant), Some(installed)) => {
+                let expected = &installed.antenna;
+                if !same_descriptor(&ant.model, &expected.model) {
+                    issues.push(SiteLogIssue::AntennaModel {
+                        header: ant.model.clone(),
+                        log: expected.model.clone(),
+                    });
+                }
+                if !same_descriptor(&ant.sn, &expected.sn) {
+                    issues.push(SiteLogIssue::AntennaSerialNumber {
+                        header: ant.sn.clone(),
+                        log: expected.sn.clone(),
+                    });
+                }
+                for (component, header, log) in [
+                    ('H', ant.height_m(), expected.height_m()),
+                    ('E', ant.eastern_m(), expected.eastern_m()),
+                    ('N', ant.northern_m(), expected.northern_m()),
+                ] {
+                    let header = header.unwrap_or(0.0);
+                    let log = log.unwrap_or(0.0);
+                    if (header - log).abs() > ECCENTRICITY_TOLERANCE_M {
+                        issues.push(SiteLogIssue::AntennaEccentricity {
+                            component,
+                            header,
+                            log,
+                        });
+                    }
+                }
+            },
+        }
+
+        SiteLogReport { epoch: t, issues }
+    }
+}
+
+impl Rinex {
+    /// Validates [Header] against the [SiteLog], at the first [Epoch] of this [Rinex].
+    /// See [Header::site_log_compliance]. Returns None for empty records.
+    pub fn site_log_compliance(&self, log: &SiteLog) -> Option<SiteLogReport> {
+        let t = self.first_epoch()?;
+        Some(self.header.site_log_compliance(log, t))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::marker::GeodeticMarker;
+
+    const SITE_LOG: &str = "     ABMF Site Information Form (site log)
+     International GNSS Service
+
+0.   Form
+
+     Prepared by (full name)  : RGP TEAM
+     Date Prepared            : 2020-06-09
+
+1.   Site Identification of the GNSS Monument
+
+     Site Name                : Les Abymes - Raizet aeroport
+     Four Character ID        : ABMF
+     IERS DOMES Number        : 97103M001
+
+3.   GNSS Receiver Information
+
+3.1  Receiver Type            : TRIMBLE NETR9
+     Satellite System         : GPS+GLO+GAL
+     Serial Number            : 5036K69246
+     Firmware Version         : 4.85
+     Date Installed           : 2013-03-12T00:00Z
+     Date Removed             : 2019-06-01T10:30Z
+
+3.2  Receiver Type            : SEPT POLARX5
+     Satellite System         : GPS+GLO+GAL+BDS
+     Serial Number            : 3013312
+     Firmware Version         : 5.3.2
+     Date Installed           : 2019-06-01T10:30Z
+     Date Removed             : (CCYY-MM-DDThh:mmZ)
+
+3.x  Receiver Type            : (A20, from rcvr_ant.tab; see instr.)
+     Serial Number            : (A20, but note the first A5 is used in SINEX)
+     Date Installed           : (CCYY-MM-DDThh:mmZ)
+
+4.   GNSS Antenna Information
+
+4.1  Antenna Type             : TRM57971.00     NONE
+     Serial Number            : 1441112501
+     Marker->ARP Up Ecc. (m)  :   0.0000
+     Marker->ARP North Ecc(m) :   0.0000
+     Marker->ARP East Ecc(m)  :   0.0000
+     Date Installed           : 2012-01-10T00:00Z
+     Date Removed             : (CCYY-MM-DDThh:mmZ)
+
+5.   Surveyed Local Ties
+
+5.x  Tied Marker Name         :
+";
+
+    #[test]
+    fn site_log_parsing() {
+        let log = SiteLog::from_str(SITE_LOG).unwrap();
+        assert_eq!(log.four_char_id, "ABMF");
+        assert_eq!(log.domes.as_deref(), Some("97103M001"));
+
+        assert_eq!(log.receivers.len(), 2);
+        assert_eq!(log.receivers[0].receiver.model, "TRIMBLE NETR9");
+        assert_eq!(log.receivers[0].receiver.sn, "5036K69246");
+        assert_eq!(log.receivers[0].receiver.firmware, "4.85");
+        assert_eq!(
+            log.receivers[0].removed,
+            Some(Epoch::from_gregorian_utc(2019, 6, 1, 10, 30, 0, 0))
+        );
+        assert_eq!(log.receivers[1].receiver.model, "SEPT POLARX5");
+        assert_eq!(log.receivers[1].removed, None);
+
+        assert_eq!(log.antennas.len(), 1);
+        assert_eq!(log.antennas[0].antenna.model, "TRM57971.00     NONE");
+        assert_eq!(log.antennas[0].antenna.height_m(), Some(0.0));
+
+        assert!(SiteLog::from_str("empty").is_err());
+    }
+
+    #[test]
+    fn site_log_compliance() {
+        let log = SiteLog::from_str(SITE_LOG).unwrap();
+
+        let mut header = Header::basic_obs()
+            .with_receiver(
+                Receiver::default()
+                    .with_model("SEPT POLARX5")
+                    .with_serial_number("3013312")
+                    .with_firmware("5.3.2"),
+            )
+            .with_receiver_antenna(
+                Antenna::default()
+                    .with_model("TRM57971.00     NONE")
+                    .with_serial_number("1441112501")
+                    .with_height(0.0)
+                    .with_eastern_component(0.0)
+                    .with_northern_component(0.0),
+            );
+
+        header.geodetic_marker = Some(
+            GeodeticMarker::default()
+                .with_name("ABMF")
+                .with_number("97103M001"),
+        );
+
+        let t = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+        let report = header.site_log_compliance(&log, t);
+        assert!(report.is_compliant(), "{:?}", report.issues);
+
+        // previous receiver was installed back then
+        let t = Epoch::from_gregorian_utc_at_midnight(2015, 1, 1);
+        let report = header.site_log_compliance(&log, t);
+        assert_eq!(report.issues.len(), 3);
+        assert_eq!(
+            report.issues[0],
+            SiteLogIssue::ReceiverModel {
+                header: "SEPT POLARX5".to_string(),
+                log: "TRIMBLE NETR9".to_string(),
+            }
+        );
+
+        // bad eccentricity
+        let header =
+            header.with_receiver_antenna(header.rcvr_antenna.as_ref().unwrap().with_height(0.1));
+        let t = Epoch::from_gregorian_utc_at_midnight(2020, 1, 1);
+        let report = header.site_log_compliance(&log, t);
+        assert_eq!(
+            report.issues,
+            vec![SiteLogIssue::AntennaEccentricity {
+                component: 'H',
+                header: 0.1,
+                log: 0.0,
+            }]
+        );
+
+        // before any installation
+        let t = Epoch::from_gregorian_utc_at_midnight(2010, 1, 1);
+        let report = header.site_log_compliance(&log, t);
+        assert!(report.issues.contains(&SiteLogIssue::UndocumentedReceiver));
+        assert!(report.issues.contains(&SiteLogIssue::UndocumentedAntenna));
+    }
+}
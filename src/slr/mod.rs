@@ -0,0 +1,401 @@
+//! Satellite Laser Ranging (SLR) interoperability.
+//!
+//! Most GNSS constellations are equipped with laser retro reflectors,
+//! and are tracked by the ILRS network. This module converts the orbital
+//! states our Navigation [Rinex] resolves, into CPF-like (Consolidated Prediction Format)
+//! predictions, and describes SLR stations so these predictions can be
+//! expressed as predicted ranges.
+use std::io::{BufWriter, Write};
+
+use crate::{
+    constants::SPEED_OF_LIGHT_M_S,
+    error::FormattingError,
+    prelude::{Duration, Epoch, Rinex, COSPAR, SV},
+};
+
+/// Converts a [COSPAR] launch identifier into its ILRS satellite identifier.
+/// For example "2016-030B" is identified as 1603002.
+/// ```
+/// use std::str::FromStr;
+/// use rinex::prelude::COSPAR;
+/// use rinex::slr::ilrs_id;
+///
+/// let cospar = COSPAR::from_str("2016-030B").unwrap();
+/// assert_eq!(ilrs_id(&cospar), Some(1603002));
+/// ```
+pub fn ilrs_id(cospar: &COSPAR) -> Option<u32> {
+    let formatted = cospar.to_string();
+    let (year, rem) = formatted.split_once('-')?;
+
+    if year.len() != 4 || rem.len() < 4 {
+        return None;
+    }
+
+    let year = year[2..].parse::<u32>().ok()?;
+    let launch = rem[..3].parse::<u32>().ok()?;
+
+    let mut piece = 0;
+    for c in rem[3..].chars() {
+        if !c.is_ascii_uppercase() {
+            return None;
+        }
+        piece = piece * 26 + (c as u32 - 'A' as u32 + 1);
+    }
+
+    if piece > 99 {
+        return None;
+    }
+
+    Some(year * 100_000 + launch * 100 + piece)
+}
+
+/// [SlrStation] describes an SLR ground station.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlrStation {
+    /// Station name, as published by the ILRS
+    pub name: String,
+    /// Monument (pad) identifier
+    pub pad_id: u16,
+    /// Station reference position, ECEF (ITRF) coordinates in meters
+    pub position_ecef_m: (f64, f64, f64),
+}
+
+impl SlrStation {
+    /// Builds a new [SlrStation]
+    pub fn new(name: &str, pad_id: u16, position_ecef_m: (f64, f64, f64)) -> Self {
+        Self {
+            name: name.to_string(),
+            pad_id,
+            position_ecef_m,
+        }
+    }
+
+    /// Geometric range (in meters) from this station to
+    /// given ECEF (ITRF) position in meters.
+    pub fn range_m(&self, position_ecef_m: (f64, f64, f64)) -> f64 {
+        let (x, y, z) = self.position_ecef_m;
+        let (dx, dy, dz) = (
+            position_ecef_m.0 - x,
+            position_ecef_m.1 - y,
+            position_ecef_m.2 - z,
+        );
+        (dx.powi(2) + dy.powi(2) + dz.powi(2)).sqrt()
+    }
+
+    /// Two-way time of flight from this station to
+    /// given ECEF (ITRF) position in meters, ignoring all delays.
+    pub fn time_of_flight(&self, position_ecef_m: (f64, f64, f64)) -> Duration {
+        Duration::from_seconds(2.0 * self.range_m(position_ecef_m) / SPEED_OF_LIGHT_M_S)
+    }
+}
+
+/// [CpfHeader] describes the "H1" and "H2" CPF header records
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpfHeader {
+    /// Ephemeris source, 3 letter code
+    pub source: String,
+    /// Target name (lower case, no spaces)
+    pub target: String,
+    /// ILRS satellite identifier, see [ilrs_id]
+    pub ilrs_id: u32,
+    /// Optional NORAD identifier
+    pub norad_id: Option<u32>,
+    /// Optional SIC (satellite identification code) attributed by ILRS
+    pub sic: Option<u16>,
+    /// Production [Epoch]
+    pub production: Epoch,
+    /// Ephemeris sequence number
+    pub sequence: u16,
+}
+
+impl Default for CpfHeader {
+    fn default() -> Self {
+        Self {
+            source: "RSR".to_string(),
+            target: Default::default(),
+            ilrs_id: Default::default(),
+            norad_id: Default::default(),
+            sic: Default::default(),
+            production: Epoch::default(),
+            sequence: 1,
+        }
+    }
+}
+
+impl CpfHeader {
+    /// Copies and returns [CpfHeader] with desired source
+    pub fn with_source(&self, source: &str) -> Self {
+        let mut s = self.clone();
+        s.source = source.chars().take(3).collect();
+        s
+    }
+
+    /// Copies and returns [CpfHeader] with desired target name
+    pub fn with_target(&self, target: &str) -> Self {
+        let mut s = self.clone();
+        s.target = target.to_lowercase().replace(' ', "");
+        s
+    }
+
+    /// Copies and returns [CpfHeader] with desired ILRS identifier
+    pub fn with_ilrs_id(&self, ilrs_id: u32) -> Self {
+        let mut s = self.clone();
+        s.ilrs_id = ilrs_id;
+        s
+    }
+
+    /// Copies and returns [CpfHeader] with desired NORAD identifier
+    pub fn with_norad_id(&self, norad_id: u32) -> Self {
+        let mut s = self.clone();
+        s.norad_id = Some(norad_id);
+        s
+    }
+
+    /// Copies and returns [CpfHeader] with desired SIC
+    pub fn with_sic(&self, sic: u16) -> Self {
+        let mut s = self.clone();
+        s.sic = Some(sic);
+        s
+    }
+
+    /// Copies and returns [CpfHeader] with desired production [Epoch]
+    pub fn with_production_epoch(&self, production: Epoch) -> Self {
+        let mut s = self.clone();
+        s.production = production;
+        s
+    }
+
+    /// Copies and returns [CpfHeader] with desired sequence number
+    pub fn with_sequence(&self, sequence: u16) -> Self {
+        let mut s = self.clone();
+        s.sequence = sequence;
+        s
+    }
+}
+
+/// [CpfPosition] is one "10" CPF position record
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpfPosition {
+    /// [Epoch] of this prediction
+    pub epoch: Epoch,
+    /// Predicted ECEF (ITRF) position in meters
+    pub position_ecef_m: (f64, f64, f64),
+}
+
+/// [CpfPrediction] is a CPF-like prediction, for a single target
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpfPrediction {
+    /// [CpfHeader]
+    pub header: CpfHeader,
+    /// Sampling period
+    pub step: Duration,
+    /// Predicted [CpfPosition]s, in chronological order
+    pub positions: Vec<CpfPosition>,
+}
+
+/// (MJD, seconds of day) in UTC, as used by CPF
+fn mjd_seconds_of_day(t: Epoch) -> (u32, f64) {
+    let mjd = t.to_mjd_utc_days();
+    let day = mjd.floor();
+    let (_, _, _, hh, mm, ss, ns) = t.to_gregorian_utc();
+    let sod = (hh as f64) * 3600.0 + (mm as f64) * 60.0 + ss as f64 + ns as f64 * 1.0E-9;
+    (day as u32, sod)
+}
+
+/// Formats "YYYY MM DD HH MM SS" in UTC, as used by CPF
+fn format_datetime(t: Epoch) -> String {
+    let (y, m, d, hh, mm, ss, _) = t.to_gregorian_utc();
+    format!("{:04} {:02} {:02} {:02} {:02} {:02}", y, m, d, hh, mm, ss)
+}
+
+impl CpfPrediction {
+    /// First predicted [Epoch]
+    pub fn first_epoch(&self) -> Option<Epoch> {
+        self.positions.first().map(|p| p.epoch)
+    }
+
+    /// Last predicted [Epoch]
+    pub fn last_epoch(&self) -> Option<Epoch> {
+        self.positions.last().map(|p| p.epoch)
+    }
+
+    /// Returns predicted (geometric) range Iterator, in meters, from given [SlrStation].
+    pub fn station_range_iter<'a>(
+        &'a self,
+        station: &'a SlrStation,
+    ) -> Box<dyn Iterator<Item = (Epoch, f64)> + 'a> {
+        Box::new(
+            self.positions
+                .iter()
+                .map(|p| (p.epoch, station.range_m(p.position_ecef_m))),
+        )
+    }
+
+    /// Formats this [CpfPrediction] into writable I/O.
+    /// The header section follows the CPF V2 layout, using ITRF as reference frame
+    /// and an instantaneous (common epoch) position vector for each record.
+    pub fn format<W: Write>(&self, w: &mut BufWriter<W>) -> Result<(), FormattingError> {
+        let (y, m, d, hh, _, _, _) = self.header.production.to_gregorian_utc();
+
+        writeln!(
+            w,
+            "H1 CPF  2  {:<3} {:04} {:02} {:02} {:02}  {:04}{:02} {:<10}",
+            self.header.source, y, m, d, hh, self.header.sequence, 1, self.header.target
+        )?;
+
+        if let (Some(first), Some(last)) = (self.first_epoch(), self.last_epoch()) {
+            writeln!(
+                w,
+                "H2 {:08} {:04} {:08} {} {} {:5} 1 1  0 0 0",
+                self.header.ilrs_id,
+                self.header.sic.unwrap_or(9999),
+                self.header.norad_id.unwrap_or(0),
+                format_datetime(first),
+                format_datetime(last),
+                self.step.to_seconds().round() as u32,
+            )?;
+        }
+
+        writeln!(w, "H9")?;
+
+        for position in self.positions.iter() {
+            let (mjd, sod) = mjd_seconds_of_day(position.epoch);
+            let (x, y, z) = position.position_ecef_m;
+            writeln!(
+                w,
+                "10 0 {:5} {:13.6} 0 {:17.3} {:17.3} {:17.3}",
+                mjd, sod, x, y, z
+            )?;
+        }
+
+        writeln!(w, "99")?;
+        w.flush()?;
+        Ok(())
+    }
+}
+
+impl Rinex {
+    /// Converts the orbital states of given [SV], resolved from this Navigation [Rinex],
+    /// into a [CpfPrediction], with desired sampling period.
+    /// Epochs for which no orbital state could be resolved are omitted.
+    /// ## Inputs
+    /// - sv: target [SV]
+    /// - header: [CpfHeader] to describe this prediction
+    /// - start: first [Epoch] of the prediction
+    /// - end: last [Epoch] of the prediction
+    /// - step: sampling period
+    /// ## Returns
+    /// - [CpfPrediction], None if no orbital state could be resolved.
+    pub fn nav_cpf_prediction(
+        &self,
+        sv: SV,
+        header: CpfHeader,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+    ) -> Option<CpfPrediction> {
+        if step <= Duration::ZERO || end < start {
+            return None;
+        }
+
+        let mut positions = Vec::new();
+        let mut t = start;
+
+        while t <= end {
+            if let Some(orbit) = self.sv_orbit(sv, t) {
+                let state = orbit.to_cartesian_pos_vel();
+                positions.push(CpfPosition {
+                    epoch: t,
+                    position_ecef_m: (state[0] * 1.0E3, state[1] * 1.0E3, state[2] * 1.0E3),
+                });
+            }
+            t += step;
+        }
+
+        if positions.is_empty() {
+            None
+        } else {
+            Some(CpfPrediction {
+                header,
+                step,
+                positions,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn cospar_to_ilrs() {
+        for (cospar, expected) in [
+            ("2016-030B", Some(1603002)),
+            ("1992-079A", Some(9207901)),
+            ("2018-078AB", Some(1807828)),
+        ] {
+            let cospar = COSPAR::from_str(cospar).unwrap();
+            assert_eq!(ilrs_id(&cospar), expected, "failed for {}", cospar);
+        }
+    }
+
+    #[test]
+    fn station_range() {
+        let station = SlrStation::new("GRASSE", 7845, (4581691.0, 556159.0, 4389359.0));
+        let range = station.range_m((4581691.0, 556159.0, 4389359.0 + 20_000_000.0));
+        assert!((range - 20_000_000.0).abs() < 1.0E-6);
+
+        let tof = station.time_of_flight((4581691.0, 556159.0, 4389359.0 + 20_000_000.0));
+        assert!((tof.to_seconds() - 0.133425638).abs() < 1.0E-9);
+    }
+
+    #[test]
+    fn cpf_formatting() {
+        let t0 = Epoch::from_str("2021-01-01T00:00:00 UTC").unwrap();
+        let step = Duration::from_seconds(900.0);
+
+        let prediction = CpfPrediction {
+            header: CpfHeader::default()
+                .with_target("Galileo 101")
+                .with_ilrs_id(1107801)
+                .with_sic(7101)
+                .with_norad_id(37846)
+                .with_production_epoch(t0),
+            step,
+            positions: vec![
+                CpfPosition {
+                    epoch: t0,
+                    position_ecef_m: (1.0E7, 2.0E7, -1.5E7),
+                },
+                CpfPosition {
+                    epoch: t0 + step,
+                    position_ecef_m: (1.1E7, 2.1E7, -1.4E7),
+                },
+            ],
+        };
+
+        let mut w = BufWriter::new(Vec::<u8>::new());
+        prediction.format(&mut w).unwrap();
+        let content = String::from_utf8(w.into_inner().unwrap()).unwrap();
+
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "H1 CPF  2  RSR 2021 01 01 00  000101 galileo101");
+        assert_eq!(
+            lines[1],
+            "H2 01107801 7101 00037846 2021 01 01 00 00 00 2021 01 01 00 15 00   900 1 1  0 0 0"
+        );
+        assert_eq!(lines[2], "H9");
+        assert_eq!(
+            lines[3],
+            "10 0 59215      0.000000 0      10000000.000      20000000.000     -15000000.000"
+        );
+        assert_eq!(
+            lines[4],
+            "10 0 59215    900.000000 0      11000000.000      21000000.000     -14000000.000"
+        );
+        assert_eq!(lines[5], "99");
+    }
+}
@@ -0,0 +1,296 @@
+//! Parsing capability report
+use crate::{
+    observation::{ObsKey, Observations},
+    prelude::{Epoch, ParsingError, Rinex, SV},
+};
+
+use std::collections::BTreeMap;
+
+#[cfg(doc)]
+use crate::prelude::{Header, ParsingOptions};
+
+#[cfg(feature = "log")]
+use log::warn;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// [ParsingSummary] describes what was recognized and what was skipped
+/// while parsing a [Rinex], so silent feature gaps (non supported header fields,
+/// messages or revisions) are immediately visible. See [Rinex::parse_summary].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ParsingSummary {
+    /// Header fields that were interpreted, with their number of occurrences
+    pub header_fields: BTreeMap<String, usize>,
+    /// Header fields that are not supported and were skipped,
+    /// with their number of occurrences
+    pub skipped_header_fields: BTreeMap<String, usize>,
+    /// Number of header comments
+    pub header_comments: usize,
+    /// Header lines that could not be interpreted, either because their label is not
+    /// supported or because they are malformed. They were retained as is,
+    /// see [Header::unparsed].
+    pub header_warnings: Vec<String>,
+    /// Number of epochs that were parsed
+    pub epochs: usize,
+    /// Epochs that were skipped, sorted by cause, with their number of occurrences
+    pub skipped_epochs: BTreeMap<String, usize>,
+    /// Observation epochs that were found more than once (typically after a receiver reboot),
+    /// once per superseded occurrence. The latest occurrence is retained,
+    /// see [Rinex::dedup_epochs] to select another policy.
+    pub duplicated_epochs: Vec<Epoch>,
+    /// Observation epochs that were found prior their predecessor (out of chronological order).
+    /// The record is always chronologically sorted.
+    pub unsorted_epochs: Vec<Epoch>,
+    /// Satellites that were dropped, because their PRN is not possible for their
+    /// constellation (corrupted descriptors), with the epoch they were found at.
+    /// See [ParsingOptions::strict_prn] to reject them instead.
+    pub dropped_sv: Vec<(Epoch, SV)>,
+    /// Superseded occurrences of duplicated observation epochs, in order of appearance
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) superseded: Vec<(ObsKey, Observations)>,
+}
+
+impl ParsingSummary {
+    /// Declares a new header field, which is by default interpreted.
+    /// Comments are only counted.
+    pub(crate) fn header_field(&mut self, marker: &str) {
+        let marker = marker.trim();
+        if marker == "COMMENT" {
+            self.header_comments += 1;
+        } else {
+            *self.header_fields.entry(marker.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Declares that the latest header field was skipped
+    pub(crate) fn skip_header_field(&mut self, marker: &str) {
+        let marker = marker.trim();
+        if let Some(count) = self.header_fields.get_mut(marker) {
+            *count -= 1;
+            if *count == 0 {
+                self.header_fields.remove(marker);
+            }
+        }
+        *self
+            .skipped_header_fields
+            .entry(marker.to_string())
+            .or_default() += 1;
+    }
+
+    /// Declares that the latest header field could not be interpreted, because its
+    /// label is not supported or because of this [ParsingError], and was retained as is.
+    pub(crate) fn unparsed_header_field(&mut self, marker: &str, error: Option<&ParsingError>) {
+        self.skip_header_field(marker);
+
+        let warning = match error {
+            Some(error) => format!("\"{}\": {}, retained as is", marker.trim(), error),
+            None => format!("\"{}\": not supported, retained as is", marker.trim()),
+        };
+
+        #[cfg(feature = "log")]
+        warn!("{}", warning);
+
+        self.header_warnings.push(warning);
+    }
+
+    /// Declares that one epoch was parsed
+    pub(crate) fn epoch(&mut self) {
+        self.epochs += 1;
+    }
+
+    /// Declares that one epoch was skipped because of this [ParsingError]
+    pub(crate) fn skip_epoch(&mut self, error: &ParsingError) {
+        *self.skipped_epochs.entry(error.to_string()).or_default() += 1;
+    }
+
+    /// Declares that this observation epoch was already parsed:
+    /// its superseded content is retained for [Rinex::dedup_epochs]
+    pub(crate) fn duplicated_epoch(&mut self, key: ObsKey, superseded: Observations) {
+        #[cfg(feature = "log")]
+        warn!("{}: duplicated epoch", key.epoch);
+
+        self.duplicated_epochs.push(key.epoch);
+        self.superseded.push((key, superseded));
+    }
+
+    /// Declares that this epoch was found prior its predecessor
+    pub(crate) fn unsorted_epoch(&mut self, epoch: Epoch) {
+        #[cfg(feature = "log")]
+        warn!("{}: epoch out of chronological order", epoch);
+
+        self.unsorted_epochs.push(epoch);
+    }
+
+    /// Declares that this [SV] was dropped, because of its invalid PRN
+    pub(crate) fn dropped_sv(&mut self, epoch: Epoch, sv: SV) {
+        #[cfg(feature = "log")]
+        warn!("{}: {} dropped (invalid prn)", epoch, sv);
+
+        self.dropped_sv.push((epoch, sv));
+    }
+
+    /// Returns total number of epochs that were skipped
+    pub fn num_skipped_epochs(&self) -> usize {
+        self.skipped_epochs.values().sum()
+    }
+
+    /// Returns true if nothing was skipped: all content was interpreted
+    pub fn is_complete(&self) -> bool {
+        self.skipped_header_fields.is_empty()
+            && self.skipped_epochs.is_empty()
+            && self.dropped_sv.is_empty()
+    }
+}
+
+impl std::fmt::Display for ParsingSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(
+            f,
+            "header: {} field(s), {} comment(s)",
+            self.header_fields.values().sum::<usize>(),
+            self.header_comments
+        )?;
+        for (marker, count) in self.skipped_header_fields.iter() {
+            writeln!(f, "  skipped: \"{}\" (x{})", marker, count)?;
+        }
+        writeln!(f, "record: {} epoch(s)", self.epochs)?;
+        for (cause, count) in self.skipped_epochs.iter() {
+            writeln!(f, "  skipped: {} (x{})", cause, count)?;
+        }
+        if !self.duplicated_epochs.is_empty() {
+            writeln!(f, "  duplicated: {} epoch(s)", self.duplicated_epochs.len())?;
+        }
+        if !self.unsorted_epochs.is_empty() {
+            writeln!(f, "  unsorted: {} epoch(s)", self.unsorted_epochs.len())?;
+        }
+        if !self.dropped_sv.is_empty() {
+            writeln!(
+                f,
+                "  dropped: {} satellite(s) (invalid prn)",
+                self.dropped_sv.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Rinex {
+    /// Returns the [ParsingSummary] of this [Rinex], which lists the header fields
+    /// that were recognized or skipped, and the number of epochs that were parsed or skipped.
+    /// It is empty when this [Rinex] was not parsed from a file.
+    /// ```
+    /// use rinex::prelude::Rinex;
+    /// use std::io::BufReader;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///   0.1000        0.2000        0.3000                        ANTENNA: B.SIGHT XYZ
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    /// let summary = rinex.parse_summary();
+    ///
+    /// assert_eq!(summary.epochs, 1);
+    /// assert!(summary.header_fields.contains_key("SYS / # / OBS TYPES"));
+    /// assert_eq!(summary.skipped_header_fields.get("ANTENNA: B.SIGHT XYZ"), Some(&1));
+    /// assert!(!summary.is_complete());
+    /// ```
+    pub fn parse_summary(&self) -> &ParsingSummary {
+        &self.summary
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ParsingSummary;
+    use crate::{
+        observation::{ObsKey, Observations},
+        prelude::{Epoch, ParsingError, ParsingOptions, Rinex},
+    };
+    use std::io::BufReader;
+
+    #[test]
+    fn summary_bookkeeping() {
+        let mut summary = ParsingSummary::default();
+        summary.header_field("RINEX VERSION / TYPE   ");
+        summary.header_field("MERGED FILE");
+        summary.skip_header_field("MERGED FILE");
+        summary.epoch();
+        summary.skip_epoch(&ParsingError::EpochFlag);
+        summary.skip_epoch(&ParsingError::EpochFlag);
+
+        assert_eq!(summary.header_fields.len(), 1);
+        assert_eq!(summary.skipped_header_fields.get("MERGED FILE"), Some(&1));
+        assert_eq!(summary.num_skipped_epochs(), 2);
+        assert!(!summary.is_complete());
+
+        let report = summary.to_string();
+        assert!(report.contains("skipped: \"MERGED FILE\" (x1)"));
+        assert!(report.contains("record: 1 epoch(s)"));
+    }
+
+    #[test]
+    fn skipped_epochs() {
+        let content =
+            "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+header comment                                              COMMENT
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20832393.682
+> 2020 01 01 0a 00 30.0000000  0  1
+G01  20832394.682
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+        let summary = rinex.parse_summary();
+
+        assert_eq!(summary.header_comments, 1);
+        assert_eq!(summary.epochs, 1);
+        assert_eq!(summary.num_skipped_epochs(), 1);
+        assert!(summary.skipped_header_fields.is_empty());
+    }
+
+    #[test]
+    fn dropped_satellites() {
+        let obs = "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     1    C1                                                    # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  2G01G45
+  20832393.682
+  20832395.682
+";
+
+        let nav = "     3.04           N: GNSS NAV DATA    M (MIXED)           RINEX VERSION / TYPE
+                                                            END OF HEADER
+C70 2021 01 01 00 00 00 -.426337239332e-03 -.752518047875e-10  .000000000000e+00
+      .100000000000e+01  .118906250000e+02  .105325815814e-08 -.255139531119e+01
+      .169500708580e-06  .401772442274e-03  .292365439236e-04  .649346986580e+04
+      .432000000000e+06  .105705112219e-06 -.277512444499e+01 -.211410224438e-06
+      .607169709798e-01 -.897671875000e+03  .154887266488e+00 -.871464871438e-10
+     -.940753471872e-09  .000000000000e+00  .782000000000e+03  .000000000000e+00
+      .200000000000e+01  .000000000000e+00 -.599999994133e-09 -.900000000000e-08
+      .432000000000e+06  .000000000000e+00 0.000000000000e+00 0.000000000000e+00
+";
+
+        for (content, dropped) in [(obs, "G45"), (nav, "C70")] {
+            let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+            let summary = rinex.parse_summary();
+
+            assert_eq!(summary.dropped_sv.len(), 1);
+            assert_eq!(summary.dropped_sv[0].1.to_string(), dropped);
+            assert!(!summary.is_complete());
+            assert!(summary.to_string().contains("dropped: 1 satellite(s)"));
+
+            let opts = ParsingOptions::default().with_strict_prn();
+            match Rinex::parse_with_options(&mut BufReader::new(content.as_bytes()), &opts) {
+                Err(ParsingError::InvalidPRN(sv)) => assert_eq!(sv.to_string(), dropped),
+                _ => panic!("{} should have been rejected", dropped),
+            }
+        }
+    }
+}
@@ -0,0 +1,1040 @@
+//! Embedded satellite block (generation) table
+
+use crate::prelude::{Constellation, Epoch, ParsingError, SV};
+
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [SatelliteBlock] describes the generation of a satellite vehicle.
+/// Several models (attitude, yaw maneuvers, antenna offsets, biases)
+/// depend on the satellite block.
+/// Naming follows IGS conventions (as used in ANTEX).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SatelliteBlock {
+    /// GPS Block IIR
+    GpsIIR,
+    /// GPS Block IIR-M (modernized)
+    GpsIIRM,
+    /// GPS Block IIF
+    GpsIIF,
+    /// GPS Block IIIA
+    GpsIIIA,
+    /// GLONASS-M
+    GlonassM,
+    /// GLONASS-K1
+    GlonassK1,
+    /// Galileo In Orbit Validation
+    GalileoIOV,
+    /// Galileo Full Operational Capability
+    GalileoFOC,
+    /// BeiDou-2 Geostationary
+    BeiDou2G,
+    /// BeiDou-2 Inclined Geosynchronous
+    BeiDou2I,
+    /// BeiDou-2 Medium Earth Orbit
+    BeiDou2M,
+    /// BeiDou-3 Geostationary
+    BeiDou3G,
+    /// BeiDou-3 Inclined Geosynchronous
+    BeiDou3I,
+    /// BeiDou-3 Medium Earth Orbit
+    BeiDou3M,
+    /// QZSS-1
+    Qzss1,
+    /// QZSS-2 Inclined Geosynchronous (QZS-2, QZS-4, QZS-1R)
+    Qzss2I,
+    /// QZSS-2 Geostationary (QZS-3)
+    Qzss2G,
+}
+
+impl SatelliteBlock {
+    /// Returns [Constellation] this [SatelliteBlock] belongs to
+    pub fn constellation(&self) -> Constellation {
+        match self {
+            Self::GpsIIR | Self::GpsIIRM | Self::GpsIIF | Self::GpsIIIA => Constellation::GPS,
+            Self::GlonassM | Self::GlonassK1 => Constellation::Glonass,
+            Self::GalileoIOV | Self::GalileoFOC => Constellation::Galileo,
+            Self::BeiDou2G
+            | Self::BeiDou2I
+            | Self::BeiDou2M
+            | Self::BeiDou3G
+            | Self::BeiDou3I
+            | Self::BeiDou3M => Constellation::BeiDou,
+            Self::Qzss1 | Self::Qzss2I | Self::Qzss2G => Constellation::QZSS,
+        }
+    }
+}
+
+impl std::fmt::Display for SatelliteBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::GpsIIR => write!(f, "BLOCK IIR-A"),
+            Self::GpsIIRM => write!(f, "BLOCK IIR-M"),
+            Self::GpsIIF => write!(f, "BLOCK IIF"),
+            Self::GpsIIIA => write!(f, "BLOCK IIIA"),
+            Self::GlonassM => write!(f, "GLONASS-M"),
+            Self::GlonassK1 => write!(f, "GLONASS-K1"),
+            Self::GalileoIOV => write!(f, "GALILEO-1"),
+            Self::GalileoFOC => write!(f, "GALILEO-2"),
+            Self::BeiDou2G => write!(f, "BEIDOU-2G"),
+            Self::BeiDou2I => write!(f, "BEIDOU-2I"),
+            Self::BeiDou2M => write!(f, "BEIDOU-2M"),
+            Self::BeiDou3G => write!(f, "BEIDOU-3G"),
+            Self::BeiDou3I => write!(f, "BEIDOU-3I"),
+            Self::BeiDou3M => write!(f, "BEIDOU-3M"),
+            Self::Qzss1 => write!(f, "QZSS"),
+            Self::Qzss2I => write!(f, "QZSS-2I"),
+            Self::Qzss2G => write!(f, "QZSS-2G"),
+        }
+    }
+}
+
+impl FromStr for SatelliteBlock {
+    type Err = ParsingError;
+    /// Parses [SatelliteBlock] from IGS (ANTEX) satellite antenna type
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        match content.trim().to_uppercase().as_str() {
+            "BLOCK IIR-A" | "BLOCK IIR-B" | "BLOCK IIR" => Ok(Self::GpsIIR),
+            "BLOCK IIR-M" => Ok(Self::GpsIIRM),
+            "BLOCK IIF" => Ok(Self::GpsIIF),
+            "BLOCK IIIA" => Ok(Self::GpsIIIA),
+            "GLONASS-M" => Ok(Self::GlonassM),
+            "GLONASS-K1" => Ok(Self::GlonassK1),
+            "GALILEO-1" => Ok(Self::GalileoIOV),
+            "GALILEO-2" => Ok(Self::GalileoFOC),
+            "BEIDOU-2G" => Ok(Self::BeiDou2G),
+            "BEIDOU-2I" => Ok(Self::BeiDou2I),
+            "BEIDOU-2M" => Ok(Self::BeiDou2M),
+            "BEIDOU-3G" => Ok(Self::BeiDou3G),
+            "BEIDOU-3I" => Ok(Self::BeiDou3I),
+            "BEIDOU-3M" | "BEIDOU-3M-CAST" | "BEIDOU-3M-SECM" => Ok(Self::BeiDou3M),
+            "QZSS" => Ok(Self::Qzss1),
+            "QZSS-2I" => Ok(Self::Qzss2I),
+            "QZSS-2G" => Ok(Self::Qzss2G),
+            _ => Err(ParsingError::SatelliteBlock),
+        }
+    }
+}
+
+/// Compact satellite table: (constellation, PRN, (year, month, day), block).
+/// Each entry is valid from its date (launch) until the same PRN is assigned
+/// to another vehicle (next entry in time).
+const SATELLITE_TABLE: &[(Constellation, u8, (i32, u8, u8), SatelliteBlock)] = &[
+    // GPS IIR
+    (
+        Constellation::GPS,
+        13,
+        (1997, 7, 23),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        11,
+        (1999, 10, 7),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        20,
+        (2000, 5, 11),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        28,
+        (2000, 7, 16),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        14,
+        (2000, 11, 10),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        18,
+        (2001, 1, 30),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        16,
+        (2003, 1, 29),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        21,
+        (2003, 3, 31),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        22,
+        (2003, 12, 21),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        19,
+        (2004, 3, 20),
+        SatelliteBlock::GpsIIR,
+    ),
+    (
+        Constellation::GPS,
+        23,
+        (2004, 6, 23),
+        SatelliteBlock::GpsIIR,
+    ),
+    (Constellation::GPS, 2, (2004, 11, 6), SatelliteBlock::GpsIIR),
+    // GPS IIR-M
+    (
+        Constellation::GPS,
+        17,
+        (2005, 9, 26),
+        SatelliteBlock::GpsIIRM,
+    ),
+    (
+        Constellation::GPS,
+        31,
+        (2006, 9, 25),
+        SatelliteBlock::GpsIIRM,
+    ),
+    (
+        Constellation::GPS,
+        12,
+        (2006, 11, 17),
+        SatelliteBlock::GpsIIRM,
+    ),
+    (
+        Constellation::GPS,
+        15,
+        (2007, 10, 17),
+        SatelliteBlock::GpsIIRM,
+    ),
+    (
+        Constellation::GPS,
+        29,
+        (2007, 12, 20),
+        SatelliteBlock::GpsIIRM,
+    ),
+    (
+        Constellation::GPS,
+        7,
+        (2008, 3, 15),
+        SatelliteBlock::GpsIIRM,
+    ),
+    (
+        Constellation::GPS,
+        5,
+        (2009, 8, 17),
+        SatelliteBlock::GpsIIRM,
+    ),
+    // GPS IIF
+    (
+        Constellation::GPS,
+        25,
+        (2010, 5, 28),
+        SatelliteBlock::GpsIIF,
+    ),
+    (Constellation::GPS, 1, (2011, 7, 16), SatelliteBlock::GpsIIF),
+    (
+        Constellation::GPS,
+        24,
+        (2012, 10, 4),
+        SatelliteBlock::GpsIIF,
+    ),
+    (
+        Constellation::GPS,
+        27,
+        (2013, 5, 15),
+        SatelliteBlock::GpsIIF,
+    ),
+    (
+        Constellation::GPS,
+        30,
+        (2014, 2, 21),
+        SatelliteBlock::GpsIIF,
+    ),
+    (Constellation::GPS, 6, (2014, 5, 17), SatelliteBlock::GpsIIF),
+    (Constellation::GPS, 9, (2014, 8, 2), SatelliteBlock::GpsIIF),
+    (
+        Constellation::GPS,
+        3,
+        (2014, 10, 29),
+        SatelliteBlock::GpsIIF,
+    ),
+    (
+        Constellation::GPS,
+        26,
+        (2015, 3, 25),
+        SatelliteBlock::GpsIIF,
+    ),
+    (Constellation::GPS, 8, (2015, 7, 15), SatelliteBlock::GpsIIF),
+    (
+        Constellation::GPS,
+        10,
+        (2015, 10, 31),
+        SatelliteBlock::GpsIIF,
+    ),
+    (Constellation::GPS, 32, (2016, 2, 5), SatelliteBlock::GpsIIF),
+    // GPS III
+    (
+        Constellation::GPS,
+        4,
+        (2018, 12, 23),
+        SatelliteBlock::GpsIIIA,
+    ),
+    (
+        Constellation::GPS,
+        18,
+        (2019, 8, 22),
+        SatelliteBlock::GpsIIIA,
+    ),
+    (
+        Constellation::GPS,
+        23,
+        (2020, 6, 30),
+        SatelliteBlock::GpsIIIA,
+    ),
+    (
+        Constellation::GPS,
+        14,
+        (2020, 11, 5),
+        SatelliteBlock::GpsIIIA,
+    ),
+    (
+        Constellation::GPS,
+        11,
+        (2021, 6, 17),
+        SatelliteBlock::GpsIIIA,
+    ),
+    (
+        Constellation::GPS,
+        28,
+        (2023, 1, 18),
+        SatelliteBlock::GpsIIIA,
+    ),
+    // GLONASS: fully modernized constellation
+    (
+        Constellation::Glonass,
+        1,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        2,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        3,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        4,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        5,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        6,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        7,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        8,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        9,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        10,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        11,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        12,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        13,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        14,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        15,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        16,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        17,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        18,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        19,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        20,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        21,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        22,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        23,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    (
+        Constellation::Glonass,
+        24,
+        (2011, 1, 1),
+        SatelliteBlock::GlonassM,
+    ),
+    // GLONASS-K1
+    (
+        Constellation::Glonass,
+        9,
+        (2020, 10, 25),
+        SatelliteBlock::GlonassK1,
+    ),
+    // Galileo IOV
+    (
+        Constellation::Galileo,
+        11,
+        (2011, 10, 21),
+        SatelliteBlock::GalileoIOV,
+    ),
+    (
+        Constellation::Galileo,
+        12,
+        (2011, 10, 21),
+        SatelliteBlock::GalileoIOV,
+    ),
+    (
+        Constellation::Galileo,
+        19,
+        (2012, 10, 12),
+        SatelliteBlock::GalileoIOV,
+    ),
+    (
+        Constellation::Galileo,
+        20,
+        (2012, 10, 12),
+        SatelliteBlock::GalileoIOV,
+    ),
+    // Galileo FOC
+    (
+        Constellation::Galileo,
+        18,
+        (2014, 8, 22),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        14,
+        (2014, 8, 22),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        26,
+        (2015, 3, 27),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        22,
+        (2015, 3, 27),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        24,
+        (2015, 9, 11),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        30,
+        (2015, 9, 11),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        8,
+        (2015, 12, 17),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        9,
+        (2015, 12, 17),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        1,
+        (2016, 5, 24),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        2,
+        (2016, 5, 24),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        3,
+        (2016, 11, 17),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        4,
+        (2016, 11, 17),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        5,
+        (2016, 11, 17),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        7,
+        (2016, 11, 17),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        21,
+        (2017, 12, 12),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        25,
+        (2017, 12, 12),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        27,
+        (2017, 12, 12),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        31,
+        (2017, 12, 12),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        13,
+        (2018, 7, 25),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        15,
+        (2018, 7, 25),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        33,
+        (2018, 7, 25),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        36,
+        (2018, 7, 25),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        10,
+        (2021, 12, 5),
+        SatelliteBlock::GalileoFOC,
+    ),
+    (
+        Constellation::Galileo,
+        34,
+        (2021, 12, 5),
+        SatelliteBlock::GalileoFOC,
+    ),
+    // BeiDou-2
+    (
+        Constellation::BeiDou,
+        1,
+        (2010, 1, 17),
+        SatelliteBlock::BeiDou2G,
+    ),
+    (
+        Constellation::BeiDou,
+        2,
+        (2012, 10, 25),
+        SatelliteBlock::BeiDou2G,
+    ),
+    (
+        Constellation::BeiDou,
+        3,
+        (2010, 6, 2),
+        SatelliteBlock::BeiDou2G,
+    ),
+    (
+        Constellation::BeiDou,
+        4,
+        (2010, 11, 1),
+        SatelliteBlock::BeiDou2G,
+    ),
+    (
+        Constellation::BeiDou,
+        5,
+        (2012, 2, 25),
+        SatelliteBlock::BeiDou2G,
+    ),
+    (
+        Constellation::BeiDou,
+        6,
+        (2010, 8, 1),
+        SatelliteBlock::BeiDou2I,
+    ),
+    (
+        Constellation::BeiDou,
+        7,
+        (2010, 12, 18),
+        SatelliteBlock::BeiDou2I,
+    ),
+    (
+        Constellation::BeiDou,
+        8,
+        (2011, 4, 10),
+        SatelliteBlock::BeiDou2I,
+    ),
+    (
+        Constellation::BeiDou,
+        9,
+        (2011, 7, 27),
+        SatelliteBlock::BeiDou2I,
+    ),
+    (
+        Constellation::BeiDou,
+        10,
+        (2011, 12, 2),
+        SatelliteBlock::BeiDou2I,
+    ),
+    (
+        Constellation::BeiDou,
+        11,
+        (2012, 4, 30),
+        SatelliteBlock::BeiDou2M,
+    ),
+    (
+        Constellation::BeiDou,
+        12,
+        (2012, 4, 30),
+        SatelliteBlock::BeiDou2M,
+    ),
+    (
+        Constellation::BeiDou,
+        13,
+        (2016, 3, 30),
+        SatelliteBlock::BeiDou2I,
+    ),
+    (
+        Constellation::BeiDou,
+        14,
+        (2012, 9, 19),
+        SatelliteBlock::BeiDou2M,
+    ),
+    (
+        Constellation::BeiDou,
+        16,
+        (2018, 7, 10),
+        SatelliteBlock::BeiDou2I,
+    ),
+    // BeiDou-3
+    (
+        Constellation::BeiDou,
+        19,
+        (2017, 11, 5),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        20,
+        (2017, 11, 5),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        21,
+        (2018, 2, 12),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        22,
+        (2018, 2, 12),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        23,
+        (2018, 7, 29),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        24,
+        (2018, 7, 29),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        25,
+        (2018, 8, 24),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        26,
+        (2018, 8, 24),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        27,
+        (2018, 1, 11),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        28,
+        (2018, 1, 11),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        29,
+        (2018, 3, 29),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        30,
+        (2018, 3, 29),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        32,
+        (2018, 9, 19),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        33,
+        (2018, 9, 19),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        34,
+        (2018, 10, 15),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        35,
+        (2018, 10, 15),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        36,
+        (2018, 11, 18),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        37,
+        (2018, 11, 18),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        38,
+        (2019, 4, 20),
+        SatelliteBlock::BeiDou3I,
+    ),
+    (
+        Constellation::BeiDou,
+        39,
+        (2019, 6, 25),
+        SatelliteBlock::BeiDou3I,
+    ),
+    (
+        Constellation::BeiDou,
+        40,
+        (2019, 11, 4),
+        SatelliteBlock::BeiDou3I,
+    ),
+    (
+        Constellation::BeiDou,
+        41,
+        (2019, 12, 16),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        42,
+        (2019, 12, 16),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        43,
+        (2019, 11, 23),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        44,
+        (2019, 11, 23),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        45,
+        (2019, 9, 22),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        46,
+        (2019, 9, 22),
+        SatelliteBlock::BeiDou3M,
+    ),
+    (
+        Constellation::BeiDou,
+        59,
+        (2018, 11, 1),
+        SatelliteBlock::BeiDou3G,
+    ),
+    (
+        Constellation::BeiDou,
+        60,
+        (2020, 3, 9),
+        SatelliteBlock::BeiDou3G,
+    ),
+    (
+        Constellation::BeiDou,
+        61,
+        (2020, 6, 23),
+        SatelliteBlock::BeiDou3G,
+    ),
+    // QZSS
+    (Constellation::QZSS, 1, (2010, 9, 11), SatelliteBlock::Qzss1),
+    (Constellation::QZSS, 2, (2017, 6, 1), SatelliteBlock::Qzss2I),
+    (
+        Constellation::QZSS,
+        7,
+        (2017, 8, 19),
+        SatelliteBlock::Qzss2G,
+    ),
+    (
+        Constellation::QZSS,
+        3,
+        (2017, 10, 9),
+        SatelliteBlock::Qzss2I,
+    ),
+    (
+        Constellation::QZSS,
+        1,
+        (2021, 10, 26),
+        SatelliteBlock::Qzss2I,
+    ),
+];
+
+/// Returns the [SatelliteBlock] of this [SV] at this [Epoch], from our
+/// embedded table. PRN numbers get reassigned over time, which is why the
+/// sampling [Epoch] is required.
+/// Returns None for vehicles that are not described. This table is compact
+/// and focuses on vehicles active in the modern era, it is not exhaustive.
+/// ```
+/// use std::str::FromStr;
+/// use rinex::prelude::*;
+/// use rinex::sv::{satellite_block, SatelliteBlock};
+///
+/// let g18 = SV::from_str("G18").unwrap();
+///
+/// let t = Epoch::from_gregorian_utc_at_midnight(2015, 1, 1);
+/// assert_eq!(satellite_block(&g18, t), Some(SatelliteBlock::GpsIIR));
+///
+/// let t = Epoch::from_gregorian_utc_at_midnight(2022, 1, 1);
+/// assert_eq!(satellite_block(&g18, t), Some(SatelliteBlock::GpsIIIA));
+///
+/// let e11 = SV::from_str("E11").unwrap();
+/// assert_eq!(satellite_block(&e11, t), Some(SatelliteBlock::GalileoIOV));
+/// ```
+pub fn satellite_block(sv: &SV, t: Epoch) -> Option<SatelliteBlock> {
+    SATELLITE_TABLE
+        .iter()
+        .filter_map(|(constellation, prn, (y, m, d), block)| {
+            if *constellation == sv.constellation && *prn == sv.prn {
+                let start = Epoch::from_gregorian_utc_at_midnight(*y, *m, *d);
+                if start <= t {
+                    return Some((start, *block));
+                }
+            }
+            None
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, block)| block)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn satellite_table() {
+        // table is consistent
+        for (constellation, _, _, block) in SATELLITE_TABLE.iter() {
+            assert_eq!(block.constellation(), *constellation, "{}", block);
+        }
+
+        let t = Epoch::from_gregorian_utc_at_midnight(2022, 6, 1);
+
+        for (sv, expected) in [
+            ("G01", Some(SatelliteBlock::GpsIIF)),
+            ("G05", Some(SatelliteBlock::GpsIIRM)),
+            ("G11", Some(SatelliteBlock::GpsIIIA)),
+            ("G28", Some(SatelliteBlock::GpsIIR)),
+            ("R09", Some(SatelliteBlock::GlonassK1)),
+            ("R10", Some(SatelliteBlock::GlonassM)),
+            ("E19", Some(SatelliteBlock::GalileoIOV)),
+            ("E24", Some(SatelliteBlock::GalileoFOC)),
+            ("C01", Some(SatelliteBlock::BeiDou2G)),
+            ("C20", Some(SatelliteBlock::BeiDou3M)),
+            ("C59", Some(SatelliteBlock::BeiDou3G)),
+            ("J01", Some(SatelliteBlock::Qzss2I)),
+            ("J07", Some(SatelliteBlock::Qzss2G)),
+            ("E17", None),
+            ("S23", None),
+        ] {
+            let sv = SV::from_str(sv).unwrap();
+            assert_eq!(satellite_block(&sv, t), expected, "{}", sv);
+        }
+
+        // before launch
+        let t = Epoch::from_gregorian_utc_at_midnight(2005, 1, 1);
+        let j01 = SV::from_str("J01").unwrap();
+        assert_eq!(satellite_block(&j01, t), None);
+
+        let t = Epoch::from_gregorian_utc_at_midnight(2015, 1, 1);
+        assert_eq!(satellite_block(&j01, t), Some(SatelliteBlock::Qzss1));
+    }
+
+    #[test]
+    fn block_parsing() {
+        for block in [
+            SatelliteBlock::GpsIIR,
+            SatelliteBlock::GpsIIRM,
+            SatelliteBlock::GpsIIIA,
+            SatelliteBlock::GlonassK1,
+            SatelliteBlock::GalileoFOC,
+            SatelliteBlock::BeiDou3I,
+            SatelliteBlock::Qzss2G,
+        ] {
+            let parsed = SatelliteBlock::from_str(&block.to_string()).unwrap();
+            assert_eq!(parsed, block);
+        }
+
+        assert_eq!(
+            SatelliteBlock::from_str("BLOCK IIR-B").unwrap(),
+            SatelliteBlock::GpsIIR
+        );
+        assert!(SatelliteBlock::from_str("BLOCK I").is_err());
+    }
+}
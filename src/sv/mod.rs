@@ -0,0 +1,387 @@
+//! Satellite vehicle identification helpers
+
+use crate::prelude::{Constellation, ParsingError, SV};
+
+mod block;
+pub use block::{satellite_block, SatelliteBlock};
+
+use std::str::FromStr;
+
+#[cfg(feature = "log")]
+use log::warn;
+
+/// Returns the range of PRN numbers that may exist for this [Constellation].
+/// SBAS vehicles are identified either by their true PRN (120-158)
+/// or by their RINEX identifier (PRN-100).
+/// Returns None when we have no knowledge of this [Constellation].
+fn prn_ranges(constellation: Constellation) -> Option<&'static [(u8, u8)]> {
+    match constellation {
+        Constellation::GPS => Some(&[(1, 32)]),
+        Constellation::Glonass => Some(&[(1, 27)]),
+        Constellation::Galileo => Some(&[(1, 36)]),
+        Constellation::BeiDou => Some(&[(1, 63)]),
+        Constellation::QZSS => Some(&[(1, 10)]),
+        Constellation::IRNSS => Some(&[(1, 14)]),
+        c if c.is_sbas() => Some(&[(20, 58), (120, 158)]),
+        _ => None,
+    }
+}
+
+/// Returns true if this [SV] PRN number is possible for its [Constellation].
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::is_valid_prn;
+///
+/// assert!(is_valid_prn(&SV::new(Constellation::GPS, 32)));
+/// assert!(!is_valid_prn(&SV::new(Constellation::GPS, 45)));
+/// assert!(!is_valid_prn(&SV::new(Constellation::Glonass, 30)));
+/// assert!(!is_valid_prn(&SV::new(Constellation::Galileo, 40)));
+/// assert!(is_valid_prn(&SV::new(Constellation::SBAS, 123)));
+/// ```
+pub fn is_valid_prn(sv: &SV) -> bool {
+    match prn_ranges(sv.constellation) {
+        Some(ranges) => ranges
+            .iter()
+            .any(|(min, max)| sv.prn >= *min && sv.prn <= *max),
+        None => true,
+    }
+}
+
+/// Validates this [SV], returning [ParsingError::InvalidPRN]
+/// when its PRN number is not possible for its [Constellation].
+/// This is used to reject corrupted epoch descriptors.
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::validate;
+///
+/// let g01 = SV::new(Constellation::GPS, 1);
+/// assert_eq!(validate(g01).unwrap(), g01);
+/// assert!(validate(SV::new(Constellation::GPS, 45)).is_err());
+/// ```
+pub fn validate(sv: SV) -> Result<SV, ParsingError> {
+    if is_valid_prn(&sv) {
+        Ok(sv)
+    } else {
+        Err(ParsingError::InvalidPRN(sv))
+    }
+}
+
+/// Parses [SV] from a standardized "XYY" descriptor, in a tolerant manner.
+/// Real files (especially RINEX2 epoch descriptors) tend to space pad
+/// single digit PRNs, or use lower case constellation identifiers.
+/// Therefore this method accepts "G 1", "g01" or " R7".
+/// Bare PRN numbers are also accepted, when an assumed [Constellation] is provided.
+/// This is typically the case of old mono GNSS RINEX, that omit the constellation
+/// in their epoch descriptor.
+/// PRN numbers that are not possible for this [Constellation] are accepted,
+/// but a warning is emitted (see [validate] to reject them).
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::parse_lenient;
+///
+/// let g01 = SV::new(Constellation::GPS, 1);
+/// assert_eq!(parse_lenient("G 1", None).unwrap(), g01);
+/// assert_eq!(parse_lenient("g01", None).unwrap(), g01);
+/// assert_eq!(parse_lenient(" 1", Some(Constellation::GPS)).unwrap(), g01);
+/// assert!(parse_lenient(" 1", None).is_err());
+/// assert!(parse_lenient("G45", None).is_ok());
+/// ```
+pub fn parse_lenient(
+    content: &str,
+    constellation: Option<Constellation>,
+) -> Result<SV, ParsingError> {
+    let sv = parse_lenient_unchecked(content, constellation)?;
+
+    if !is_valid_prn(&sv) {
+        #[cfg(feature = "log")]
+        warn!("{}: PRN out of range for this constellation", sv);
+    }
+
+    Ok(sv)
+}
+
+fn parse_lenient_unchecked(
+    content: &str,
+    constellation: Option<Constellation>,
+) -> Result<SV, ParsingError> {
+    let content = content.trim();
+
+    let first = content.chars().next().ok_or(ParsingError::SVFormat)?;
+
+    if first.is_ascii_alphabetic() {
+        let prn = content[1..]
+            .trim()
+            .parse::<u8>()
+            .map_err(|_| ParsingError::SVFormat)?;
+
+        // standardized format handles the SBAS detailed identification
+        let sv = SV::from_str(&format!("{}{:02}", first.to_ascii_uppercase(), prn))?;
+        Ok(sv)
+    } else {
+        let prn = content.parse::<u8>().map_err(|_| ParsingError::SVFormat)?;
+
+        match constellation {
+            Some(Constellation::Mixed) | None => Err(ParsingError::UndefinedConstellation),
+            Some(constellation) => Ok(SV::new(constellation, prn)),
+        }
+    }
+}
+
+/// Parses [SV] from a standardized "XYY" descriptor, in a strict manner.
+/// The descriptor must be exactly three characters long: an upper case constellation
+/// identifier, followed by a zero padded 2 digit PRN number,
+/// that must be possible for this [Constellation] (see [validate]).
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::parse_strict;
+///
+/// assert_eq!(parse_strict("E05").unwrap(), SV::new(Constellation::Galileo, 5));
+/// assert!(parse_strict("E 5").is_err());
+/// assert!(parse_strict("e05").is_err());
+/// assert!(parse_strict("E5").is_err());
+/// assert!(parse_strict("E40").is_err());
+/// ```
+pub fn parse_strict(content: &str) -> Result<SV, ParsingError> {
+    let bytes = content.as_bytes();
+
+    if bytes.len() != 3
+        || !bytes[0].is_ascii_uppercase()
+        || !bytes[1].is_ascii_digit()
+        || !bytes[2].is_ascii_digit()
+    {
+        return Err(ParsingError::SVFormat);
+    }
+
+    let sv = SV::from_str(content)?;
+    validate(sv)
+}
+
+/// Formats [SV] as an SP3 satellite identifier (for example "G01").
+/// SBAS vehicles are identified by their PRN minus 100, like in RINEX.
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::to_sp3_id;
+///
+/// assert_eq!(to_sp3_id(&SV::new(Constellation::GPS, 1)), "G01");
+/// assert_eq!(to_sp3_id(&SV::new(Constellation::SBAS, 120)), "S20");
+/// ```
+pub fn to_sp3_id(sv: &SV) -> String {
+    format!("{:x}{:02}", sv.constellation, sv.prn % 100)
+}
+
+/// Parses [SV] from an SP3 satellite identifier.
+/// Early SP3 revisions only describe GPS vehicles, and may omit the
+/// constellation identifier: bare PRN numbers are therefore interpreted as GPS.
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::from_sp3_id;
+///
+/// assert_eq!(from_sp3_id("E05").unwrap(), SV::new(Constellation::Galileo, 5));
+/// assert_eq!(from_sp3_id("G 5").unwrap(), SV::new(Constellation::GPS, 5));
+/// assert_eq!(from_sp3_id(" 5").unwrap(), SV::new(Constellation::GPS, 5));
+/// ```
+pub fn from_sp3_id(content: &str) -> Result<SV, ParsingError> {
+    parse_lenient(content, Some(Constellation::GPS))
+}
+
+/// Formats [SV] as an ANTEX satellite identifier ("PRN" field of "TYPE / SERIAL NO").
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::to_antex_id;
+///
+/// assert_eq!(to_antex_id(&SV::new(Constellation::Glonass, 7)), "R07");
+/// ```
+pub fn to_antex_id(sv: &SV) -> String {
+    to_sp3_id(sv)
+}
+
+/// Parses [SV] from an ANTEX satellite identifier, which is always
+/// standardized (see [parse_strict]).
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::from_antex_id;
+///
+/// assert_eq!(from_antex_id("R07").unwrap(), SV::new(Constellation::Glonass, 7));
+/// assert!(from_antex_id("R 7").is_err());
+/// ```
+pub fn from_antex_id(content: &str) -> Result<SV, ParsingError> {
+    parse_strict(content.trim())
+}
+
+/// Formats an ANTEX space vehicle number (SVN) descriptor (for example "G032"),
+/// as found in the "TYPE / SERIAL NO" field. Unlike the PRN, the SVN
+/// uniquely identifies the spacecraft (and therefore its antenna) over time.
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::to_antex_svn;
+///
+/// assert_eq!(to_antex_svn(Constellation::GPS, 32), "G032");
+/// ```
+pub fn to_antex_svn(constellation: Constellation, svn: u16) -> String {
+    format!("{:x}{:03}", constellation, svn)
+}
+
+/// Parses an ANTEX space vehicle number (SVN) descriptor, like "G032".
+/// ```
+/// use rinex::prelude::*;
+/// use rinex::sv::from_antex_svn;
+///
+/// assert_eq!(from_antex_svn("G032").unwrap(), (Constellation::GPS, 32));
+/// assert!(from_antex_svn("G32").is_err());
+/// ```
+pub fn from_antex_svn(content: &str) -> Result<(Constellation, u16), ParsingError> {
+    let content = content.trim();
+
+    if content.len() != 4 || !content.is_char_boundary(1) {
+        return Err(ParsingError::SVFormat);
+    }
+
+    let constellation = Constellation::from_str(&content[..1])?;
+
+    let svn = content[1..]
+        .parse::<u16>()
+        .map_err(|_| ParsingError::SVFormat)?;
+
+    Ok((constellation, svn))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lenient_parsing() {
+        for (content, constellation, expected) in [
+            ("G01", None, SV::new(Constellation::GPS, 1)),
+            ("G 1", None, SV::new(Constellation::GPS, 1)),
+            ("g01", None, SV::new(Constellation::GPS, 1)),
+            (" R7", None, SV::new(Constellation::Glonass, 7)),
+            ("R 7 ", None, SV::new(Constellation::Glonass, 7)),
+            ("e12", None, SV::new(Constellation::Galileo, 12)),
+            ("C 19", None, SV::new(Constellation::BeiDou, 19)),
+            (
+                " 7",
+                Some(Constellation::GPS),
+                SV::new(Constellation::GPS, 7),
+            ),
+            (
+                "12",
+                Some(Constellation::Glonass),
+                SV::new(Constellation::Glonass, 12),
+            ),
+            (
+                "E01",
+                Some(Constellation::GPS),
+                SV::new(Constellation::Galileo, 1),
+            ),
+        ] {
+            let sv = parse_lenient(content, constellation)
+                .unwrap_or_else(|e| panic!("failed to parse \"{}\": {}", content, e));
+            assert_eq!(sv, expected, "badly identified \"{}\"", content);
+        }
+
+        for (content, constellation) in [
+            ("", None),
+            ("   ", None),
+            ("07", None),
+            ("07", Some(Constellation::Mixed)),
+            ("G", None),
+            ("Gxx", None),
+            ("X01", None),
+        ] {
+            assert!(
+                parse_lenient(content, constellation).is_err(),
+                "\"{}\" should not be accepted",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn strict_parsing() {
+        for (content, expected) in [
+            ("G01", SV::new(Constellation::GPS, 1)),
+            ("R24", SV::new(Constellation::Glonass, 24)),
+            ("J03", SV::new(Constellation::QZSS, 3)),
+        ] {
+            assert_eq!(parse_strict(content).unwrap(), expected);
+        }
+
+        for content in ["G 1", "g01", " R7", "G1", "G001", "07", "X01"] {
+            assert!(
+                parse_strict(content).is_err(),
+                "\"{}\" should not be accepted",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn prn_validation() {
+        for (sv, valid) in [
+            (SV::new(Constellation::GPS, 1), true),
+            (SV::new(Constellation::GPS, 32), true),
+            (SV::new(Constellation::GPS, 0), false),
+            (SV::new(Constellation::GPS, 45), false),
+            (SV::new(Constellation::Glonass, 24), true),
+            (SV::new(Constellation::Glonass, 30), false),
+            (SV::new(Constellation::Galileo, 36), true),
+            (SV::new(Constellation::Galileo, 40), false),
+            (SV::new(Constellation::BeiDou, 63), true),
+            (SV::new(Constellation::BeiDou, 64), false),
+            (SV::new(Constellation::QZSS, 11), false),
+            (SV::new(Constellation::IRNSS, 14), true),
+            (SV::new(Constellation::SBAS, 20), true),
+            (SV::new(Constellation::SBAS, 148), true),
+            (SV::new(Constellation::SBAS, 10), false),
+        ] {
+            assert_eq!(is_valid_prn(&sv), valid, "{} badly validated", sv);
+            assert_eq!(validate(sv).is_ok(), valid, "{} badly validated", sv);
+        }
+
+        for content in ["G45", "R30", "E40"] {
+            assert!(
+                parse_strict(content).is_err(),
+                "\"{}\" should not be accepted",
+                content
+            );
+            assert!(
+                parse_lenient(content, None).is_ok(),
+                "\"{}\" should be tolerated",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn sp3_antex_identifiers() {
+        for (sv, sp3) in [
+            (SV::new(Constellation::GPS, 1), "G01"),
+            (SV::new(Constellation::Glonass, 24), "R24"),
+            (SV::new(Constellation::BeiDou, 59), "C59"),
+            (SV::new(Constellation::QZSS, 3), "J03"),
+        ] {
+            assert_eq!(to_sp3_id(&sv), sp3);
+            assert_eq!(to_antex_id(&sv), sp3);
+            assert_eq!(from_sp3_id(sp3).unwrap(), sv);
+            assert_eq!(from_antex_id(sp3).unwrap(), sv);
+        }
+
+        for (constellation, svn, descriptor) in [
+            (Constellation::GPS, 32, "G032"),
+            (Constellation::Galileo, 101, "E101"),
+            (Constellation::Glonass, 7, "R007"),
+        ] {
+            assert_eq!(to_antex_svn(constellation, svn), descriptor);
+            assert_eq!(from_antex_svn(descriptor).unwrap(), (constellation, svn));
+        }
+
+        for descriptor in ["", "G32", "G0320", "Gxxx", "10032"] {
+            assert!(
+                from_antex_svn(descriptor).is_err(),
+                "\"{}\" should not be accepted",
+                descriptor
+            );
+        }
+    }
+}
@@ -1,11 +1,12 @@
 use crate::{
     hardware::{Antenna, Receiver},
     prelude::{Constellation, Header, Version},
+    summary::ParsingSummary,
     tests::formatting::{generic_formatted_lines_test, Utf8Buffer},
 };
 
 use std::collections::HashMap;
-use std::io::BufWriter;
+use std::io::{BufReader, BufWriter};
 
 #[test]
 fn obs_header_formatting() {
@@ -40,7 +41,7 @@ fn obs_header_formatting() {
         HashMap::from_iter([
             (
                 0,
-                "     3.05           OBSERVATION DATA    GPS                 RINEX VERSION / TYPE",
+                "     3.05           OBSERVATION DATA    G                   RINEX VERSION / TYPE",
             ),
             (
                 1,
@@ -109,3 +110,154 @@ fn crinex_mixed_header_formatting() {
         ]),
     );
 }
+
+/// Parses reference header, formats it back and compares byte for byte.
+fn header_round_trip(reference: &str) {
+    let mut reader = BufReader::new(reference.as_bytes());
+    let header = Header::parse(&mut reader).unwrap();
+
+    let mut buf = BufWriter::new(Utf8Buffer::new(4096));
+    header.format(&mut buf).unwrap();
+
+    let content = buf.into_inner().unwrap().to_ascii_utf8();
+
+    for (nth, (formatted, expected)) in content.lines().zip(reference.lines()).enumerate() {
+        assert!(
+            formatted.len() > 60 && formatted.len() <= 80,
+            "line #{}: invalid width",
+            nth
+        );
+        assert_eq!(formatted, expected, "line #{}", nth);
+    }
+
+    assert_eq!(content, reference);
+}
+
+#[test]
+fn obs_v2_header_round_trip() {
+    header_round_trip(
+        "     2.11           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+teqc  2019Feb25     IGS                 20210102 00:01:14UTCPGM / RUN BY / DATE
+GEODESY OBSERVER    ROYAL OBSERVATORY OF BELGIUM            OBSERVER / AGENCY
+reference header                                            COMMENT
+  2021     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+  2021     1     1    23    59   30.0000000     GPS         TIME OF LAST OBS
+    11    C1    C2    C5    L1    L2    L5    P1    P2    S1# / TYPES OF OBSERV
+          S2    S5                                          # / TYPES OF OBSERV
+5167R40101          TRIMBLE NETR9       5.45                REC # / TYPE / VERS
+5000115800          TRM59800.00     NONE                    ANT # / TYPE
+        0.0620        0.0000        0.0000                  ANTENNA: DELTA H/E/N
+DELF                                                        MARKER NAME
+13502M009                                                   MARKER NUMBER
+  3924698.1158   301124.8036  5001904.9952                  APPROX POSITION XYZ
+    30.000                                                  INTERVAL
+    18                                                      LEAP SECONDS
+                                                            END OF HEADER
+",
+    );
+}
+
+#[test]
+fn obs_v3_header_round_trip() {
+    header_round_trip(
+        "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+sbf2rin-13.4.3                          20200101 000000 UTC PGM / RUN BY / DATE
+  2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+G    9 C1C L1C D1C S1C C2W L2W D2W S2W C5Q                  SYS / # / OBS TYPES
+R   14 C1C L1C D1C S1C C1P L1P D1P S1P C2C L2C D2C S2C C2P  SYS / # / OBS TYPES
+       L2P                                                  SYS / # / OBS TYPES
+G L2W  0.00000                                              SYS / PHASE SHIFT
+R L2P  0.25000  02 R01 R02                                  SYS / PHASE SHIFT
+G   10   2 L1C L2W                                          SYS / SCALE FACTOR
+R  100  14 C1C C1P C2C C2P D1C D1P D2C D2P L1C L1P L2C L2P  SYS / SCALE FACTOR
+           S1C S1P                                          SYS / SCALE FACTOR
+     1                                                      RCV CLOCK OFFS APPL
+3047785             SEPT POLARX5        5.3.2               REC # / TYPE / VERS
+ESA                                                         MARKER NAME
+     1.000                                                  INTERVAL
+G CC2NONCC          ftp://igs.org/pub/dcb                   SYS / DCBS APPLIED
+E PCV_PGM           igs14.atx                               SYS / PCVS APPLIED
+    18    18  2185     7GPS                                 LEAP SECONDS
+ 10 R01  1 R02 -4 R03  5 R04  6 R05  1 R06 -4 R07  5 R08  6 GLONASS SLOT / FRQ #
+    R09 -2 R10 -7                                           GLONASS SLOT / FRQ #
+ C1C  -71.940 C1P  -71.940 C2C  -71.940 C2P  -71.940        GLONASS COD/PHS/BIS
+                                                            END OF HEADER
+",
+    );
+}
+
+#[test]
+fn obs_v4_header_round_trip() {
+    header_round_trip(
+        "     4.00           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+sbf2rin-15.0.0                          20220101 000000 UTC PGM / RUN BY / DATE
+https://doi.org/10.5880/GFZ.1.1.2022.001                    DOI
+This data is licensed under the Creative Commons AttributionLICENSE OF USE
+4.0 International license (CC BY 4.0)                       LICENSE OF USE
+https://network.igs.org/ESBC00DNK                           STATION INFORMATION
+  2022     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+ESBC                                                        MARKER NAME
+                                                            END OF HEADER
+",
+    );
+}
+
+#[test]
+fn nav_v3_ionospheric_corr_round_trip() {
+    let reference =
+        "     3.04           NAVIGATION DATA     MIXED               RINEX VERSION / TYPE
+gfzrnx-2.1.0                            20220101 000000 UTC PGM / RUN BY / DATE
+    18                                                      LEAP SECONDS
+GPSA   7.4506E-09  1.4901E-08 -5.9605E-08 -1.1921E-07       IONOSPHERIC CORR
+GPSB   9.2160E+04  1.3107E+05 -6.5536E+04 -5.2429E+05       IONOSPHERIC CORR
+BDSA   1.0245E-08  1.4901E-07 -1.0729E-06  1.3351E-06       IONOSPHERIC CORR
+BDSB   1.1878E+05 -1.3107E+05  1.9661E+05 -2.6214E+05       IONOSPHERIC CORR
+GAL    6.6250E+01 -1.6406E-01 -2.4719E-03  0.0000E+00       IONOSPHERIC CORR
+                                                            END OF HEADER
+";
+
+    header_round_trip(reference);
+
+    let header = Header::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+
+    let kb = header.klobuchar_model(Constellation::BeiDou).unwrap();
+    assert_eq!(kb.alpha, (1.0245E-08, 1.4901E-07, -1.0729E-06, 1.3351E-06));
+    assert_eq!(kb.beta, (1.1878E+05, -1.3107E+05, 1.9661E+05, -2.6214E+05));
+
+    let ng = header.nequick_g_model(Constellation::Galileo).unwrap();
+    assert_eq!(ng.a, (6.6250E+01, -1.6406E-01, -2.4719E-03));
+
+    assert!(header.klobuchar_model(Constellation::GPS).is_some());
+    assert!(header.klobuchar_model(Constellation::Glonass).is_none());
+}
+
+#[test]
+fn unparsed_header_lines_round_trip() {
+    let reference =
+        "     3.04           OBSERVATION DATA    G                   RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+G  abc  1 C1C                                               SYS / SCALE FACTOR
+vendor specific settings                                    SEPT RX SETTINGS
+                                                            END OF HEADER
+";
+
+    header_round_trip(reference);
+
+    let mut summary = ParsingSummary::default();
+    let header =
+        Header::parse_summarized(&mut BufReader::new(reference.as_bytes()), &mut summary).unwrap();
+
+    assert_eq!(header.unparsed.len(), 2);
+    assert!(header.obs.as_ref().unwrap().scaling.is_empty());
+
+    assert_eq!(summary.header_warnings.len(), 2);
+    assert_eq!(summary.skipped_header_fields.len(), 2);
+    assert!(summary.header_warnings[1].contains("\"SEPT RX SETTINGS\": not supported"));
+
+    // RINEX VERSION / TYPE remains mandatory
+    let content = "     X.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+                                                            END OF HEADER
+";
+    assert!(Header::parse(&mut BufReader::new(content.as_bytes())).is_err());
+}
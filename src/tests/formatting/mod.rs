@@ -1,4 +1,5 @@
 pub mod header;
+pub mod nav;
 pub mod obs;
 
 use std::collections::HashMap;
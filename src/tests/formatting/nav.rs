@@ -0,0 +1,58 @@
+use crate::prelude::Rinex;
+
+use std::io::{BufReader, BufWriter};
+
+/// Parses reference NAV content, formats it back and compares
+/// the record section byte for byte.
+fn nav_record_round_trip(reference: &str) {
+    let mut reader = BufReader::new(reference.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    let mut buf = BufWriter::new(Vec::new());
+    rinex.format(&mut buf).unwrap();
+
+    let content = String::from_utf8(buf.into_inner().unwrap()).unwrap();
+
+    let (_, formatted) = content.split_once("END OF HEADER\n").unwrap();
+    let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+
+    for (nth, (formatted, expected)) in formatted.lines().zip(expected.lines()).enumerate() {
+        assert_eq!(formatted, expected, "line #{}", nth);
+    }
+
+    assert_eq!(formatted, expected);
+}
+
+#[test]
+fn nav_v2_record_round_trip() {
+    nav_record_round_trip(
+        "     2.11           N: GPS NAV DATA                         RINEX VERSION / TYPE
+                                                            END OF HEADER
+ 1 20  6 25  0  0  0.0-3.240541554987D-04-6.252776074689D-12 0.000000000000D+00
+    5.900000000000D+01-1.184375000000D+02 4.022667267167D-09-2.101453627455D+00
+   -6.023049354553D-06 8.965924079530D-03 5.858391523361D-06 5.153708061218D+03
+    3.456000000000D+05 2.048909664154D-08-7.727970385488D-01-1.676380634308D-07
+    9.777128542054D-01 2.564062500000D+02 7.028787287921D-01-8.216413896601D-09
+    4.142707975542D-11 1.000000000000D+00 2.111000000000D+03 0.000000000000D+00
+    2.000000000000D+00 0.000000000000D+00 5.122274160385D-09 5.900000000000D+01
+    3.384180000000D+05 4.000000000000D+00
+",
+    );
+}
+
+#[test]
+fn nav_v3_record_round_trip() {
+    nav_record_round_trip(
+        "     3.04           N: GNSS NAV DATA    G: GPS              RINEX VERSION / TYPE
+                                                            END OF HEADER
+G01 2020 06 25 00 00 00-3.240541554987E-04-6.252776074689E-12 0.000000000000E+00
+     5.900000000000E+01-1.184375000000E+02 4.022667267167E-09-2.101453627455E+00
+    -6.023049354553E-06 8.965924079530E-03 5.858391523361E-06 5.153708061218E+03
+     3.456000000000E+05 2.048909664154E-08-7.727970385488E-01-1.676380634308E-07
+     9.777128542054E-01 2.564062500000E+02 7.028787287921E-01-8.216413896601E-09
+     4.142707975542E-11 1.000000000000E+00 2.111000000000E+03 0.000000000000E+00
+     2.000000000000E+00 0.000000000000E+00 5.122274160385E-09 5.900000000000E+01
+     3.384180000000E+05 4.000000000000E+00
+",
+    );
+}
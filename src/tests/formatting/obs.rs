@@ -2,12 +2,12 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::{
-    observation::HeaderFields,
-    prelude::{Constellation, Epoch, Observable},
+    observation::{EpochFlag, HeaderFields},
+    prelude::{Constellation, Epoch, Observable, Rinex},
     tests::formatting::{generic_formatted_lines_test, Utf8Buffer},
 };
 
-use std::io::BufWriter;
+use std::io::{BufReader, BufWriter};
 
 #[test]
 fn obs_v1_single_line_formatting() {
@@ -333,6 +333,7 @@ fn high_precision_obs() {
     let l5c = Observable::PhaseRange("L5C".to_string());
     let l1x = Observable::PhaseRange("L1X".to_string());
     let l5q = Observable::PhaseRange("L5Q".to_string());
+    let l2w = Observable::PhaseRange("L2W".to_string());
 
     let mut hd = HeaderFields::default();
 
@@ -340,6 +341,353 @@ fn high_precision_obs() {
     hd.with_scaling(gps, l5c, 20);
     hd.with_scaling(gal, l1x, 30);
     hd.with_scaling(gal, l5q, 40);
+    hd.with_scaling(gps, l2w, 10);
 
     hd.format(&mut buf, 3).unwrap();
+
+    let content = buf.into_inner().unwrap().to_ascii_utf8();
+
+    generic_formatted_lines_test(
+        &content,
+        HashMap::from_iter([
+            (
+                0,
+                "G   10   2 L1C L2W                                          SYS / SCALE FACTOR",
+            ),
+            (
+                1,
+                "G   20   1 L5C                                              SYS / SCALE FACTOR",
+            ),
+            (
+                2,
+                "E   30   1 L1X                                              SYS / SCALE FACTOR",
+            ),
+            (
+                3,
+                "E   40   1 L5Q                                              SYS / SCALE FACTOR",
+            ),
+        ]),
+    );
+}
+
+#[test]
+fn scaled_obs_record_round_trip() {
+    let reference =
+        "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+G   10   1 L1C                                              SYS / SCALE FACTOR
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20832393.682 7 109474991.854  
+";
+
+    let mut reader = BufReader::new(reference.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+
+    // stored values are scaled down when parsed
+    let values = rinex
+        .signal_observations_iter()
+        .map(|(_, sig)| sig.value)
+        .collect::<Vec<_>>();
+
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[0], 20832393.682);
+    assert!((values[1] - 10947499.1854).abs() < 1.0E-6);
+
+    // and scaled up when formatted
+    let mut buf = BufWriter::new(Vec::new());
+    rinex.format(&mut buf).unwrap();
+
+    let content = String::from_utf8(buf.into_inner().unwrap()).unwrap();
+    let (header, formatted) = content.split_once("END OF HEADER\n").unwrap();
+    let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+
+    assert!(header.contains(
+        "G   10   1 L1C                                              SYS / SCALE FACTOR"
+    ));
+    assert_eq!(formatted, expected);
+
+    // blank list: factor applies to all observables
+    let reference = reference.replace("G   10   1 L1C      ", "G   10              ");
+
+    let mut reader = BufReader::new(reference.as_bytes());
+    let rinex = Rinex::parse(&mut reader).unwrap();
+    let scaling = &rinex.header.obs.as_ref().unwrap().scaling;
+    assert_eq!(scaling.len(), 2);
+}
+
+#[test]
+fn obs_v2_record_round_trip() {
+    let mut reference =
+        "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     7    C1    P1    L1    D1    S1    P2    L2                # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0 14G01G02G03G04G05G06G07G08G09G10G11G12
+                                G13G14
+"
+        .to_string();
+
+    // 7 observables: continued on a second line
+    for prn in 1..=14 {
+        reference.push_str(&format!(
+            "{:14.3}7 {:14.3}  {:14.3}18{:14.3}  {:14.3}  \n{:14.3}  {:14.3}  \n",
+            20832393.682,
+            20832394.682,
+            109474991.854,
+            -256.789,
+            44.0,
+            20832396.421,
+            85305781.010 + prn as f64
+        ));
+    }
+
+    let rinex = Rinex::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+    assert_eq!(rinex.sv_count(), 14);
+
+    let mut formatted = Vec::<u8>::new();
+    rinex.to_writer(&mut formatted).unwrap();
+
+    let formatted = String::from_utf8(formatted).unwrap();
+    let (_, record) = formatted.split_once("END OF HEADER\n").unwrap();
+    let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+
+    for (nth, (line, expected)) in record.lines().zip(expected.lines()).enumerate() {
+        assert!(line.len() <= 80, "line #{}: too wide", nth);
+        assert_eq!(line.trim_end(), expected.trim_end(), "line #{}", nth);
+    }
+
+    assert_eq!(record.lines().count(), expected.lines().count());
+
+    let parsed = Rinex::parse(&mut BufReader::new(formatted.as_bytes())).unwrap();
+    assert_eq!(parsed.record, rinex.record);
+}
+
+#[test]
+fn obs_v3_record_round_trip() {
+    let reference =
+        "     3.04           OBSERVATION DATA    M (MIXED)           RINEX VERSION / TYPE
+G    4 C1C L1C D1C S1C                                      SYS / # / OBS TYPES
+E    2 C1C L1C                                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  3
+G01  20832393.682 7 109474991.85418      -256.789          44.000  
+G07  21832393.682   114274991.854                          41.000  
+E05  23832393.682   125474991.854  
+> 2020 01 01 00 00 30.0000000  0  1
+E05  23832394.682   125474992.854  
+";
+
+    let rinex = Rinex::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+
+    let mut formatted = Vec::<u8>::new();
+    rinex.to_writer(&mut formatted).unwrap();
+
+    let formatted = String::from_utf8(formatted).unwrap();
+    let (_, record) = formatted.split_once("END OF HEADER\n").unwrap();
+    let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+
+    assert_eq!(record, expected);
+
+    let parsed = Rinex::parse(&mut BufReader::new(formatted.as_bytes())).unwrap();
+    assert_eq!(parsed.record, rinex.record);
+}
+
+#[test]
+fn obs_event_epochs_round_trip() {
+    let reference =
+        "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     2    C1    L1                                          # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  1G01
+  20832393.682   109474991.854  
+ 20  1  1  0  0 30.0000000  4  2
+ESBC01DNK                                                   MARKER NAME
+        0.1000        0.0000        0.0000                  ANTENNA: DELTA H/E/N
+ 20  1  1  0  0 45.0000000  5  0
+ 20  1  1  0  1  0.0000000  0  1G01
+  20832395.682   109474993.854  
+";
+
+    let rinex = Rinex::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+
+    assert_eq!(rinex.epoch_iter().count(), 4);
+    assert_eq!(rinex.signal_observations_iter().count(), 4);
+
+    let events = rinex.events_iter().collect::<Vec<_>>();
+    assert_eq!(events.len(), 2);
+
+    let (key, event) = events[0];
+    assert_eq!(key.flag, EpochFlag::HeaderInformationFollows);
+    assert_eq!(
+        event.header_records(),
+        vec![
+            ("MARKER NAME", "ESBC01DNK"),
+            (
+                "ANTENNA: DELTA H/E/N",
+                "        0.1000        0.0000        0.0000"
+            ),
+        ]
+    );
+
+    let (key, event) = events[1];
+    assert_eq!(key.flag, EpochFlag::ExternalEvent);
+    assert!(event.records.is_empty());
+
+    for (nth, (major, reference)) in [
+        (2, reference.to_string()),
+        (
+            3,
+            reference
+                .replace(
+                    "     2.11           OBSERVATION DATA",
+                    "     3.04           OBSERVATION DATA",
+                )
+                .replace(
+                    "     2    C1    L1                                          # / TYPES OF OBSERV",
+                    "G    2 C1C L1C                                              SYS / # / OBS TYPES",
+                )
+                .replace(" 20  1  1  0  0  0.0000000  0  1G01\n  ", "> 2020 01 01 00 00  0.0000000  0  1\nG01  ")
+                .replace(" 20  1  1  0  0 30.0000000  4  2", "> 2020 01 01 00 00 30.0000000  4  2")
+                .replace(" 20  1  1  0  0 45.0000000  5  0", "> 2020 01 01 00 00 45.0000000  5  0")
+                .replace(" 20  1  1  0  1  0.0000000  0  1G01\n  ", "> 2020 01 01 00 01  0.0000000  0  1\nG01  "),
+        ),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let rinex = Rinex::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+        assert_eq!(rinex.header.version.major, *major);
+        assert_eq!(rinex.events_iter().count(), 2, "test #{}", nth);
+
+        let mut formatted = Vec::<u8>::new();
+        rinex.to_writer(&mut formatted).unwrap();
+
+        let formatted = String::from_utf8(formatted).unwrap();
+        let (_, record) = formatted.split_once("END OF HEADER\n").unwrap();
+        let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+        assert_eq!(record, expected, "test #{}", nth);
+    }
+}
+
+#[test]
+fn obs_clock_offsets_round_trip() {
+    for (major, reference) in [
+        (
+            2,
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     2    C1    L1                                          # / TYPES OF OBSERV
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  1G01                                 -0.000123456
+  20832393.682   109474991.854  
+ 20  1  1  0  0 30.0000000  0  1G01
+  20832395.682   109474993.854  
+",
+        ),
+        (
+            3,
+            "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    2 C1C L1C                                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1      -0.000123456789
+G01  20832393.682   109474991.854  
+> 2020 01 01 00 00 30.0000000  0  1
+G01  20832395.682   109474993.854  
+",
+        ),
+    ] {
+        let rinex = Rinex::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+        assert_eq!(rinex.clock_offsets_iter().count(), 1, "V{}", major);
+
+        let (_, offset_s) = rinex.clock_offsets_iter().next().unwrap();
+        assert!((offset_s + 0.000123456789).abs() < 1.0E-9, "V{}", major);
+
+        let mut formatted = Vec::<u8>::new();
+        rinex.to_writer(&mut formatted).unwrap();
+
+        let formatted = String::from_utf8(formatted).unwrap();
+        let (_, record) = formatted.split_once("END OF HEADER\n").unwrap();
+        let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+        assert_eq!(record, expected, "V{}", major);
+    }
+}
+
+#[test]
+fn obs_event_comment_records_round_trip() {
+    let reference =
+        "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20832393.682  
+> 2020 01 01 00 00 30.0000000  5  2
+EXTERNAL TRIGGER                                            COMMENT
+RECEIVER RESET                                              COMMENT
+> 2020 01 01 00 01  0.0000000  0  1
+G01  20832395.682  
+";
+
+    let rinex = Rinex::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+
+    let events = rinex.events_iter().collect::<Vec<_>>();
+    assert_eq!(events.len(), 1);
+
+    let (key, event) = events[0];
+    assert_eq!(key.flag, EpochFlag::ExternalEvent);
+    assert_eq!(
+        event.records,
+        vec![
+            format!("{:<60}COMMENT", "EXTERNAL TRIGGER"),
+            format!("{:<60}COMMENT", "RECEIVER RESET"),
+        ]
+    );
+
+    assert_eq!(rinex.signal_observations_iter().count(), 2);
+
+    let mut formatted = Vec::<u8>::new();
+    rinex.to_writer(&mut formatted).unwrap();
+
+    let formatted = String::from_utf8(formatted).unwrap();
+    let (_, record) = formatted.split_once("END OF HEADER\n").unwrap();
+    let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+    assert_eq!(record, expected);
+}
+
+#[test]
+fn obs_v4_receiver_antenna_changes_round_trip() {
+    let reference =
+        "     4.00           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2022 01 01 00 00  0.0000000  0  1
+G01  20832393.682  
+> 2022 01 01 00 00 30.0000000  4  2
+3001376             SEPT POLARX5        5.4.0               REC # / TYPE / VERS
+5012                TRM59800.00     NONE                    ANT # / TYPE
+> 2022 01 01 00 01  0.0000000  0  1
+G01  20832395.682  
+";
+
+    let rinex = Rinex::parse(&mut BufReader::new(reference.as_bytes())).unwrap();
+    let t = Epoch::from_str("2022-01-01T00:00:30 GPST").unwrap();
+
+    let receivers = rinex.receiver_changes_iter().collect::<Vec<_>>();
+    assert_eq!(receivers.len(), 1);
+    assert_eq!(receivers[0].0, t);
+    assert_eq!(receivers[0].1.sn, "3001376");
+    assert_eq!(receivers[0].1.model, "SEPT POLARX5");
+    assert_eq!(receivers[0].1.firmware, "5.4.0");
+
+    let antennas = rinex.antenna_changes_iter().collect::<Vec<_>>();
+    assert_eq!(antennas.len(), 1);
+    assert_eq!(antennas[0].0, t);
+    assert_eq!(antennas[0].1.sn, "5012");
+    assert_eq!(antennas[0].1.model, "TRM59800.00     NONE");
+
+    let mut formatted = Vec::<u8>::new();
+    rinex.to_writer(&mut formatted).unwrap();
+
+    let formatted = String::from_utf8(formatted).unwrap();
+    let (_, record) = formatted.split_once("END OF HEADER\n").unwrap();
+    let (_, expected) = reference.split_once("END OF HEADER\n").unwrap();
+    assert_eq!(record, expected);
 }
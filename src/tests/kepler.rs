@@ -1,6 +1,6 @@
 use crate::{
-    navigation::{NavFrameType, NavMessageType},
-    prelude::{Constellation, Epoch, Rinex, TimeScale, SV},
+    navigation::{Ephemeris, Kepler, NavFrameType, NavMessageType, OrbitItem, Perturbations},
+    prelude::{Constellation, Duration, Epoch, Rinex, TimeScale, SV},
     tests::init_logger,
     tests::toolkit::{generic_navigation_test, TimeFrame},
 };
@@ -193,3 +193,215 @@ fn v3_kepler() {
         );
     }
 }
+
+/// Builds a Keplerian [Ephemeris] from (sqrta, e, i0, omega0, omega, m0, toe)
+/// and (dn, omega_dot, idot, cuc, cus, crc, crs, cic, cis).
+fn kepler_ephemeris(week: u32, kepler: [f64; 7], perturbations: [f64; 9]) -> Ephemeris {
+    Ephemeris::default()
+        .with_week(week)
+        .with_kepler(Kepler {
+            a: kepler[0].powi(2),
+            e: kepler[1],
+            i_0: kepler[2],
+            omega_0: kepler[3],
+            omega: kepler[4],
+            m_0: kepler[5],
+            toe: kepler[6],
+        })
+        .with_perturbations(Perturbations {
+            dn: perturbations[0],
+            omega_dot: perturbations[1],
+            i_dot: perturbations[2],
+            cuc: perturbations[3],
+            cus: perturbations[4],
+            crc: perturbations[5],
+            crs: perturbations[6],
+            cic: perturbations[7],
+            cis: perturbations[8],
+        })
+}
+
+#[test]
+fn broadcast_kepler_solver() {
+    let g01 = SV::from_str("G01").unwrap();
+    let j01 = SV::from_str("J01").unwrap();
+    let c01 = SV::from_str("C01").unwrap();
+    let r01 = SV::from_str("R01").unwrap();
+
+    let eph = kepler_ephemeris(
+        2138,
+        [5153.6, 0.0112, 0.9761, -2.2, 0.85, 1.1, 432000.0],
+        [
+            4.5E-9, -8.0E-9, 1.0E-10, -1.5E-6, 8.0E-6, 220.0, -30.0, 1.0E-7, -5.0E-8,
+        ],
+    );
+
+    let toe = Epoch::from_time_of_week(2138, 432_000 * 1_000_000_000, TimeScale::GPST);
+
+    // reference vectors
+    for (dt_s, x_km, y_km, z_km) in [
+        (0.0, 17035.993972, -1189.408278, 20168.216743),
+        (1800.0, 19977.328256, 2058.804166, 17291.184517),
+        (-3600.0, 11605.418633, -9540.958213, 21599.500619),
+    ] {
+        let t = toe + dt_s * Unit::Second;
+
+        // expressed in other timescale
+        let t_gst = t.to_time_scale(TimeScale::GST);
+
+        for t in [t, t_gst] {
+            let (x, y, z) = eph.sv_position(g01, t).unwrap();
+            let err_km = ((x - x_km).powi(2) + (y - y_km).powi(2) + (z - z_km).powi(2)).sqrt();
+            assert!(err_km < 1.0E-6, "G01 {} err={} km", t, err_km);
+        }
+
+        // same ICD
+        let (x, y, z) = eph.sv_position(j01, t).unwrap();
+        assert!((x - x_km).abs() < 1.0E-6);
+        assert!((y - y_km).abs() < 1.0E-6);
+        assert!((z - z_km).abs() < 1.0E-6);
+    }
+
+    // not Keplerian
+    assert!(eph.sv_position(r01, toe).is_none());
+
+    // BeiDou GEO
+    let eph = kepler_ephemeris(
+        850,
+        [6493.4, 0.0005, 0.08, 2.9, -1.3, 0.4, 345600.0],
+        [
+            1.0E-9, 5.0E-10, -2.0E-10, 1.0E-6, -2.0E-6, -100.0, 50.0, 2.0E-8, -3.0E-8,
+        ],
+    );
+
+    let toe = Epoch::from_time_of_week(850, 345_600 * 1_000_000_000, TimeScale::BDT);
+
+    for (dt_s, x_km, y_km, z_km) in [
+        (0.0, -14909.798013, 39411.182756, 800.539613),
+        (1800.0, -14914.509744, 39408.597673, 898.073074),
+    ] {
+        let t = toe + dt_s * Unit::Second;
+        let (x, y, z) = eph.sv_position(c01, t).unwrap();
+        let err_km = ((x - x_km).powi(2) + (y - y_km).powi(2) + (z - z_km).powi(2)).sqrt();
+        assert!(err_km < 1.0E-6, "C01 {} err={} km", t, err_km);
+    }
+}
+
+#[test]
+fn broadcast_kepler_velocity() {
+    let g01 = SV::from_str("G01").unwrap();
+    let c01 = SV::from_str("C01").unwrap();
+    let c06 = SV::from_str("C06").unwrap();
+
+    let meo = kepler_ephemeris(
+        2138,
+        [5153.6, 0.0112, 0.9761, -2.2, 0.85, 1.1, 432000.0],
+        [
+            4.5E-9, -8.0E-9, 1.0E-10, -1.5E-6, 8.0E-6, 220.0, -30.0, 1.0E-7, -5.0E-8,
+        ],
+    );
+
+    let geo = kepler_ephemeris(
+        850,
+        [6493.4, 0.0005, 0.08, 2.9, -1.3, 0.4, 345600.0],
+        [
+            1.0E-9, 5.0E-10, -2.0E-10, 1.0E-6, -2.0E-6, -100.0, 50.0, 2.0E-8, -3.0E-8,
+        ],
+    );
+
+    let gps_toe = Epoch::from_time_of_week(2138, 432_000 * 1_000_000_000, TimeScale::GPST);
+    let bds_toe = Epoch::from_time_of_week(850, 345_600 * 1_000_000_000, TimeScale::BDT);
+
+    let dt = 0.5 * Unit::Second;
+
+    for (sv, eph, toe) in [
+        (g01, &meo, gps_toe),
+        (c01, &geo, bds_toe),
+        (c06, &geo, bds_toe),
+    ] {
+        for dt_s in [-3600.0, 0.0, 900.0, 5400.0] {
+            let t = toe + dt_s * Unit::Second;
+
+            let (vx, vy, vz) = eph.sv_velocity(sv, t).unwrap();
+
+            // central differences
+            let (x0, y0, z0) = eph.sv_position(sv, t - dt).unwrap();
+            let (x1, y1, z1) = eph.sv_position(sv, t + dt).unwrap();
+
+            for (v, v_ref) in [(vx, x1 - x0), (vy, y1 - y0), (vz, z1 - z0)] {
+                assert!((v - v_ref).abs() < 1.0E-6, "{} {}: {} {}", sv, t, v, v_ref);
+            }
+        }
+    }
+}
+
+#[test]
+fn broadcast_clock_correction() {
+    let g01 = SV::from_str("G01").unwrap();
+    let r01 = SV::from_str("R01").unwrap();
+
+    let mut eph = kepler_ephemeris(
+        2138,
+        [5153.6, 0.0112, 0.9761, -2.2, 0.85, 1.1, 432000.0],
+        [
+            4.5E-9, -8.0E-9, 1.0E-10, -1.5E-6, 8.0E-6, 220.0, -30.0, 1.0E-7, -5.0E-8,
+        ],
+    );
+
+    eph.clock_bias = -2.5E-4;
+    eph.clock_drift = 3.0E-12;
+    eph.clock_drift_rate = 1.0E-18;
+
+    let toc = Epoch::from_time_of_week(2138, 432_000 * 1_000_000_000, TimeScale::GPST);
+
+    for dt_s in [-1800.0, 0.0, 3600.0] {
+        let t = toc + dt_s * Unit::Second;
+
+        let polynomial = -2.5E-4 + 3.0E-12 * dt_s + 1.0E-18 * dt_s.powi(2);
+
+        // -2 r.v / c², which differs by the harmonic corrections of the orbit radius
+        let (x, y, z) = eph.sv_position(g01, t).unwrap();
+        let (vx, vy, vz) = eph.sv_velocity(g01, t).unwrap();
+        let dtr = -2.0 * (x * vx + y * vy + z * vz) * 1.0E6 / 299_792_458.0_f64.powi(2);
+
+        let correction = eph.sv_clock_correction(g01, toc, t).unwrap();
+        let correction_s = correction.to_seconds();
+
+        // Duration has a 1 ns resolution
+        assert!(
+            (correction_s - polynomial - dtr).abs() < 2.0E-9,
+            "{}: dtr={} err={}",
+            t,
+            dtr,
+            correction_s - polynomial - dtr
+        );
+
+        // TGD is not broadcast
+        assert_eq!(
+            eph.sv_single_frequency_clock_correction(g01, toc, t),
+            Some(correction)
+        );
+    }
+
+    let with_tgd = eph.with_orbit("tgd", OrbitItem::from(-1.0E-8));
+    let t = toc + 900.0 * Unit::Second;
+
+    let dual_freq = with_tgd.sv_clock_correction(g01, toc, t).unwrap();
+    let single_freq = with_tgd
+        .sv_single_frequency_clock_correction(g01, toc, t)
+        .unwrap();
+
+    assert!(((single_freq - dual_freq).to_seconds() - 1.0E-8).abs() < 2.0E-9);
+
+    // Glonass linear model
+    let mut glo = Ephemeris::default();
+    glo.clock_bias = 1.0E-5;
+    glo.clock_drift = 1.0E-12;
+    glo.clock_drift_rate = 86400.0;
+
+    let toc = Epoch::from_str("2021-01-01T00:15:00 UTC").unwrap();
+    let t = toc + 600.0 * Unit::Second;
+
+    let correction = glo.sv_clock_correction(r01, toc, t).unwrap();
+    assert_eq!(correction, Duration::from_seconds(1.0E-5 + 600.0E-12));
+}
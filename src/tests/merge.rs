@@ -9,6 +9,56 @@ mod test {
         path::PathBuf,
     };
 
+    #[test]
+    fn merge_obs_headers() {
+        use crate::prelude::{Constellation, Epoch, Header, Observable, SV};
+        use std::str::FromStr;
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let l1c = Observable::from_str("L1C").unwrap();
+        let c2w = Observable::from_str("C2W").unwrap();
+        let r01 = SV::from_str("R01").unwrap();
+        let r02 = SV::from_str("R02").unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let t1 = Epoch::from_str("2020-01-01T12:00:00 GPST").unwrap();
+        let t2 = Epoch::from_str("2020-01-02T00:00:00 GPST").unwrap();
+
+        let a = Header::basic_obs()
+            .with_comment("common comment")
+            .with_timeof_first_obs(t1)
+            .with_timeof_last_obs(t1)
+            .with_observables(Constellation::GPS, &[c1c.clone(), l1c.clone()])
+            .with_glonass_channel(r01, 1);
+
+        let b = Header::basic_obs()
+            .with_comment("common comment")
+            .with_timeof_first_obs(t0)
+            .with_timeof_last_obs(t2)
+            .with_observables(Constellation::GPS, &[c1c.clone(), c2w.clone()])
+            .with_glonass_channel(r01, -1)
+            .with_glonass_channel(r02, -4);
+
+        let merged = a.merge(&b).unwrap();
+
+        let obs = merged.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, Some(t0));
+        assert_eq!(obs.timeof_last_obs, Some(t2));
+        assert_eq!(
+            obs.codes.get(&Constellation::GPS),
+            Some(&vec![c1c, l1c, c2w])
+        );
+
+        assert_eq!(merged.glonass_channel(r01), Some(1));
+        assert_eq!(merged.glonass_channel(r02), Some(-4));
+
+        let comments = merged.comments();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0], "common comment");
+        assert!(comments[1].contains("FILE MERGE"));
+        assert!(comments[1].len() <= 60);
+    }
+
     #[test]
     fn fail_on_type_mismatch() {
         let data = PathBuf::new().join(env!("CARGO_MANIFEST_DIR")).join("data");
@@ -226,6 +276,81 @@ mod test {
         // let _ = fs_remove_file("ajac-merged.txt");
     }
 
+    #[test]
+    fn merge_obs_policies() {
+        use crate::prelude::{
+            qc::{MergePolicy, ObsMergeOptions},
+            Constellation, Duration, Observable,
+        };
+        use std::{io::BufReader, str::FromStr};
+
+        let a = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    1 C1C                                                  SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.0000000  0  1
+G01  20000000.000
+> 2020 01 01 00 00 30.0000000  0  1
+G01  20000030.000
+";
+
+        // B time tags are 100ms late and it observes a different signal
+        let b = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+G    2 C1C C2W                                              SYS / # / OBS TYPES
+                                                            END OF HEADER
+> 2020 01 01 00 00  0.1000000  0  1
+G01  20000002.000    20000004.000
+> 2020 01 01 00 01  0.1000000  0  1
+G01  20000060.000    20000062.000
+";
+
+        let a = Rinex::parse(&mut BufReader::new(a.as_bytes())).unwrap();
+        let b = Rinex::parse(&mut BufReader::new(b.as_bytes())).unwrap();
+
+        let c1c = Observable::from_str("C1C").unwrap();
+        let c2w = Observable::from_str("C2W").unwrap();
+
+        // strict alignment: epochs are not combined
+        let merged = a
+            .merge_observations(&b, &ObsMergeOptions::default())
+            .unwrap();
+        assert_eq!(merged.epoch_iter().count(), 4);
+
+        let obs = merged.header.obs.as_ref().unwrap();
+        assert_eq!(obs.codes[&Constellation::GPS], vec![c1c.clone(), c2w]);
+
+        let opts = ObsMergeOptions::default().with_alignment(Duration::from_milliseconds(200.0));
+
+        for (policy, expected) in [
+            (MergePolicy::Preserve, 20000000.0),
+            (MergePolicy::Overwrite, 20000002.0),
+            (MergePolicy::Average, 20000001.0),
+        ] {
+            let merged = a.merge_observations(&b, &opts.with_policy(policy)).unwrap();
+
+            let epochs = merged.epoch_iter().collect::<Vec<_>>();
+            assert_eq!(epochs.len(), 3);
+            assert_eq!(epochs[0], a.first_epoch().unwrap());
+
+            let first = merged.observations_iter().next().unwrap().1;
+            assert_eq!(first.signals.len(), 2);
+
+            let value = first
+                .signals
+                .iter()
+                .find(|sig| sig.observable == c1c)
+                .unwrap()
+                .value;
+
+            assert_eq!(value, expected, "{:?}", policy);
+        }
+
+        // incompatible types
+        let nav = Rinex::basic_nav();
+        assert!(a
+            .merge_observations(&nav, &ObsMergeOptions::default())
+            .is_err());
+    }
+
     // #[cfg(feature = "antex")]
     // use crate::antex::antenna::AntennaMatcher;
     // #[cfg(feature = "antex")]
@@ -1,5 +1,5 @@
 use crate::{
-    prelude::Rinex,
+    prelude::{FormattingOptions, Rinex},
     tests::toolkit::{generic_meteo_rinex_test, generic_rinex_comparison, TimeFrame},
 };
 
@@ -94,7 +94,11 @@ fn v2_abvi0010_15m() {
         ),
     );
 
-    dut.to_file("v2_abvi0010_15m.txt").unwrap();
+    dut.to_file_with_options(
+        "v2_abvi0010_15m.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
     let parsed = Rinex::from_file("v2_abvi0010_15m.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
     let _ = remove_file("v2_abvi0010_15m.txt");
@@ -115,7 +119,11 @@ fn v3_pots00deu() {
         TimeFrame::from_inclusive_csv("2023-09-11T00:00:00 UTC, 2023-09-11T23:55:00 UTC, 300 s"),
     );
 
-    dut.to_file("v3_pots00deu.txt").unwrap();
+    dut.to_file_with_options(
+        "v3_pots00deu.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
     let parsed = Rinex::from_file("v3_pots00deu.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
     let _ = remove_file("v3_pots00deu.txt");
@@ -134,7 +142,11 @@ fn v4_example_1() {
         TimeFrame::from_inclusive_csv("2021-01-07T00:00:00 UTC, 2021-01-07T00:02:00 UTC, 30 s"),
     );
 
-    dut.to_file("v4_example_1.txt").unwrap();
+    dut.to_file_with_options(
+        "v4_example_1.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
     let parsed = Rinex::from_file("v4_example_1.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
     let _ = remove_file("v4_example_1.txt");
@@ -1098,6 +1098,7 @@ fn nav_v3_ionospheric_corr() {
 
 #[test]
 #[cfg(feature = "flate2")]
+#[allow(deprecated)]
 fn nav_v4_messages() {
     for fp in [
         "KMS300DNK_R_20221591000_01H_MN.rnx.gz",
@@ -1,13 +1,13 @@
 use crate::{
     observation::{EpochFlag, LliFlags, ObsKey, SignalObservation, SNR},
-    prelude::{Epoch, GeodeticMarker, Observable, Rinex, SV},
+    prelude::{Carrier, Epoch, FormattingOptions, GeodeticMarker, Observable, Rinex, Version, SV},
     tests::toolkit::{
         generic_null_rinex_test, generic_observation_rinex_test, generic_rinex_comparison,
         SignalDataPoint, TimeFrame,
     },
 };
 
-use std::{fs::remove_file, path::Path, str::FromStr};
+use std::{fs::remove_file, io::BufReader, path::Path, str::FromStr};
 
 #[test]
 fn v2_aopr0010_17o() {
@@ -42,7 +42,11 @@ fn v2_aopr0010_17o() {
             vec![],
         );
 
-    dut.to_file("v2_aopr0010_17o.txt").unwrap();
+    dut.to_file_with_options(
+        "v2_aopr0010_17o.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
 
     let parsed = Rinex::from_file("v2_aopr0010_17o.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
@@ -82,7 +86,11 @@ fn v2_npaz3550_21o() {
         vec![],
     );
 
-    dut.to_file("v2_npaz3550_21o.txt").unwrap();
+    dut.to_file_with_options(
+        "v2_npaz3550_21o.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
 
     let parsed = Rinex::from_file("v2_npaz3550_21o.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
@@ -136,7 +144,11 @@ fn v2_rovn0010_21o() {
     let agency = dut.header.agency.as_ref().unwrap();
     assert_eq!(agency, "TU Delft for Deltares");
 
-    dut.to_file("v2_rovn0010_21o.txt").unwrap();
+    dut.to_file_with_options(
+        "v2_rovn0010_21o.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
 
     let parsed = Rinex::from_file("v2_rovn0010_21o.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
@@ -192,7 +204,11 @@ fn v3_duth0630() {
             vec![],
         );
 
-    dut.to_file("v3_duth0630.txt").unwrap();
+    dut.to_file_with_options(
+        "v3_duth0630.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
 
     let parsed = Rinex::from_file("v3_duth0630.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
@@ -239,6 +255,84 @@ fn v4_kms300dnk_r_2022_v3crx() {
         );
 }
 
+#[test]
+fn v4_01_inline_observations() {
+    let content = "     4.01           OBSERVATION DATA    M                   RINEX VERSION / TYPE
+sbf2rin-15.4.0                          20230101 000000 UTC PGM / RUN BY / DATE
+ESBC00DNK                                                   MARKER NAME
+GEODETIC                                                    MARKER TYPE
+10.57931/example                                            DOI
+CC BY 4.0                                                   LICENSE OF USE
+https://network.igs.org/ESBC00DNK                           STATION INFORMATION
+G    4 C1C L1C D1C S1C                                      SYS / # / OBS TYPES
+C    4 C1P L1P C5P L5P                                      SYS / # / OBS TYPES
+E    4 C1C L1C C6C L6C                                      SYS / # / OBS TYPES
+J    2 C1L L1L                                              SYS / # / OBS TYPES
+    30.000                                                  INTERVAL
+  2023     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+    18    18  2185     7                                    LEAP SECONDS
+                                                            END OF HEADER
+> 2023 01 01 00 00  0.0000000  0  4
+G01  20832393.682 7 109474991.85418      -256.789          44.000  
+C19  21832393.682   114274991.854    21832399.682    85274991.854  
+E05  23832393.682   125474991.854    23832399.682   101274991.854  
+J02  38832393.682   208474991.854  
+> 2023 01 01 00 00 30.0000000  0  1
+E05  23832394.682   125474992.854  
+";
+
+    let dut = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+    assert_eq!(dut.header.version, Version::new(4, 1));
+    assert_eq!(dut.header.doi.as_deref(), Some("10.57931/example"));
+    assert_eq!(dut.header.license.as_deref(), Some("CC BY 4.0"));
+    assert!(dut.parse_summary().is_complete());
+
+    assert_eq!(dut.epoch_iter().count(), 2);
+    assert_eq!(dut.signal_observations_iter().count(), 16);
+
+    // modern signals
+    for (sv, code, carrier) in [
+        ("C19", "C1P", Carrier::B1c),
+        ("C19", "L5P", Carrier::B2a),
+        ("E05", "L6C", Carrier::E6),
+        ("J02", "C1L", Carrier::L1),
+    ] {
+        let sv = SV::from_str(sv).unwrap();
+        let observable = Observable::from_str(code).unwrap();
+
+        let signal = dut
+            .signal_observations_iter()
+            .find(|(_, sig)| sig.sv == sv && sig.observable == observable)
+            .map(|(_, sig)| sig)
+            .unwrap_or_else(|| panic!("missing {} {}", sv, code));
+
+        assert_eq!(
+            signal.observable.to_carrier(sv.constellation).ok(),
+            Some(carrier)
+        );
+    }
+
+    // round trip
+    let mut formatted = Vec::<u8>::new();
+    dut.to_writer(&mut formatted).unwrap();
+
+    let parsed = Rinex::parse(&mut BufReader::new(formatted.as_slice())).unwrap();
+    assert_eq!(parsed.header.version, Version::new(4, 1));
+
+    // signals are sorted by constellation when formatted
+    let sorted = |rinex: &Rinex| {
+        let mut signals = rinex
+            .signal_observations_iter()
+            .map(|(k, sig)| (k.epoch, sig.clone()))
+            .collect::<Vec<_>>();
+        signals.sort_by_key(|(t, sig)| (*t, sig.sv, sig.observable.to_string()));
+        signals
+    };
+
+    assert_eq!(sorted(&parsed), sorted(&dut));
+}
+
 #[test]
 #[ignore] // TODO
 fn v2_kosg0010_95o() {
@@ -280,7 +374,11 @@ fn v2_kosg0010_95o() {
         vec![],
     );
 
-    dut.to_file("v2_kosg0010_95o.txt").unwrap();
+    dut.to_file_with_options(
+        "v2_kosg0010_95o.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
 
     // TODO
     let _parsed = Rinex::from_file("v2_kosg0010_95o.txt").unwrap();
@@ -533,7 +631,11 @@ fn v2_ajac3550() {
             vec![],
         );
 
-    dut.to_file("v2_ajac3550.txt").unwrap();
+    dut.to_file_with_options(
+        "v2_ajac3550.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
 
     let parsed = Rinex::from_file("v2_ajac3550.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
@@ -574,7 +676,11 @@ fn v3_noa10630() {
         vec![],
     );
 
-    dut.to_file("v3_noa10630.txt").unwrap();
+    dut.to_file_with_options(
+        "v3_noa10630.txt",
+        &FormattingOptions::default().without_stamp(),
+    )
+    .unwrap();
 
     let parsed = Rinex::from_file("v3_noa10630.txt").unwrap();
     generic_rinex_comparison(&parsed, &dut);
@@ -94,4 +94,57 @@ mod decimation {
         let count = rinex.epoch_iter().count();
         assert_eq!(count, 1013, "decimate(1'+1s): error",);
     }
+    #[test]
+    fn decimation_interval_consistency() {
+        let mut content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    30.000                                                  INTERVAL
+     1    C1                                                # / TYPES OF OBSERV
+                                                            END OF HEADER
+"
+            .to_string();
+
+        for i in 0..10 {
+            content.push_str(&format!(
+                " 20  1  1  0 {:2} {:2}.0000000  0  1G01\n  20832393.682\n",
+                i * 30 / 60,
+                i * 30 % 60
+            ));
+        }
+
+        let rinex = Rinex::parse(&mut std::io::BufReader::new(content.as_bytes())).unwrap();
+
+        let decimated = rinex.decimate_by_ratio(3);
+        assert_eq!(decimated.epoch_iter().count(), 4);
+        assert_eq!(
+            decimated.header.sampling_interval,
+            Some(Duration::from_seconds(90.0))
+        );
+
+        let decimated = rinex.decimate_by_interval(Duration::from_seconds(120.0));
+        assert_eq!(decimated.epoch_iter().count(), 3);
+        assert_eq!(
+            decimated.header.sampling_interval,
+            Some(Duration::from_seconds(120.0))
+        );
+
+        // finer interval: untouched
+        let mut decimated = rinex.clone();
+        decimated.decimate_by_interval_mut(Duration::from_seconds(10.0));
+        assert_eq!(decimated.record, rinex.record);
+        assert_eq!(
+            decimated.header.sampling_interval,
+            Some(Duration::from_seconds(30.0))
+        );
+
+        // null ratio: untouched
+        let decimated = rinex.decimate_by_ratio(0);
+        assert_eq!(decimated.record, rinex.record);
+
+        // undeclared INTERVAL remains undeclared
+        let mut rinex = rinex;
+        rinex.header.sampling_interval = None;
+        let decimated = rinex.decimate_by_ratio(2);
+        assert_eq!(decimated.header.sampling_interval, None);
+    }
 }
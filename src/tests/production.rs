@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod test {
+    use crate::prelude::FormattingOptions;
     use crate::tests::toolkit::{generic_rinex_comparison, random_name};
     use crate::*;
     use std::path::Path;
@@ -16,7 +17,9 @@ mod test {
         let model = model.unwrap();
 
         let tmp_path = format!("test-{}.rnx", random_name(5));
-        model.to_file(&tmp_path).unwrap(); // test writer
+        model
+            .to_file_with_options(&tmp_path, &FormattingOptions::default().without_stamp())
+            .unwrap(); // test writer
 
         let dut = Rinex::from_file(&tmp_path).unwrap();
 
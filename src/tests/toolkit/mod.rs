@@ -87,18 +87,11 @@ fn rcvr_antenna_comparison(dut: &Option<Antenna>, model: &Option<Antenna>) {
             assert_eq!(dut.model, model.model);
             assert_eq!(dut.sn, model.sn);
 
-            let model_h = model.height.unwrap_or(0.0);
-            let dut_h = dut.height.unwrap_or(0.0);
-            assert_eq!(dut_h, model_h);
-
-            assert_eq!(dut.coords, model.coords);
-
-            let (model_east, model_north) =
-                (model.eastern.unwrap_or(0.0), model.northern.unwrap_or(0.0));
-            let (dut_east, dut_north) = (dut.eastern.unwrap_or(0.0), dut.northern.unwrap_or(0.0));
-
-            assert_eq!(dut_east, model_east);
-            assert_eq!(dut_north, model_north);
+            assert_eq!(dut.delta_xyz, model.delta_xyz);
+            assert_eq!(
+                dut.delta_hen.unwrap_or_default(),
+                model.delta_hen.unwrap_or_default()
+            );
         } else {
             panic!("found unexpected rx-antenna infos!");
         }
@@ -173,6 +166,7 @@ pub fn generic_header_comparison(dut: &Header, model: &Header) {
     assert_eq!(dut.agency, model.agency);
     assert_eq!(dut.geodetic_marker, model.geodetic_marker);
     assert_eq!(dut.glo_channels, model.glo_channels);
+    assert_eq!(dut.glo_biases, model.glo_biases);
     assert_eq!(dut.cospar, model.cospar);
     assert_eq!(dut.leap, model.leap);
     if let Some((dut_x, dut_y, dut_z)) = dut.rx_position {
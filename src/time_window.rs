@@ -0,0 +1,320 @@
+//! Time window cropping and splitting
+use crate::prelude::{Duration, Epoch, Rinex};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// [TimeWindow] is a time frame, both ends included, see [Rinex::crop].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimeWindow {
+    /// First [Epoch] of this [TimeWindow]
+    pub start: Epoch,
+    /// Last [Epoch] of this [TimeWindow]
+    pub end: Epoch,
+}
+
+impl TimeWindow {
+    /// Builds a new [TimeWindow], both ends included
+    pub fn new(start: Epoch, end: Epoch) -> Self {
+        Self { start, end }
+    }
+
+    /// Builds a new [TimeWindow] starting at this [Epoch] and lasting this [Duration]
+    pub fn from_duration(start: Epoch, duration: Duration) -> Self {
+        Self {
+            start,
+            end: start + duration,
+        }
+    }
+
+    /// Returns [Duration] of this [TimeWindow]
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Returns true if this [Epoch] lies within this [TimeWindow]
+    pub fn contains(&self, t: Epoch) -> bool {
+        t >= self.start && t <= self.end
+    }
+}
+
+impl std::fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} - {}", self.start, self.end)
+    }
+}
+
+impl Rinex {
+    /// Retains epochs that match this predicate, whatever the record type,
+    /// and updates the header time frame accordingly.
+    fn retain_epochs_mut<F: Fn(Epoch) -> bool>(&mut self, f: F) {
+        if let Some(rec) = self.record.as_mut_obs() {
+            rec.retain(|k, _| f(k.epoch));
+        } else if let Some(rec) = self.record.as_mut_nav() {
+            rec.retain(|k, _| f(k.epoch));
+        } else if let Some(rec) = self.record.as_mut_clock() {
+            rec.retain(|t, _| f(*t));
+        } else if let Some(rec) = self.record.as_mut_meteo() {
+            rec.retain(|k, _| f(k.epoch));
+        } else if let Some(rec) = self.record.as_mut_doris() {
+            rec.retain(|k, _| f(k.epoch));
+        } else if let Some(rec) = self.record.as_mut_ionex() {
+            rec.retain(|k, _| f(k.epoch));
+        }
+
+        self.comments.retain(|t, _| f(*t));
+
+        let first = self.first_epoch();
+        let last = self.last_epoch();
+
+        if let Some(obs) = &mut self.header.obs {
+            if first.is_some() {
+                obs.timeof_first_obs = first;
+            }
+            if obs.timeof_last_obs.is_some() && last.is_some() {
+                obs.timeof_last_obs = last;
+            }
+        }
+
+        if let Some(doris) = &mut self.header.doris {
+            if first.is_some() {
+                doris.timeof_first_obs = first;
+            }
+            if doris.timeof_last_obs.is_some() && last.is_some() {
+                doris.timeof_last_obs = last;
+            }
+        }
+
+        if let Some(ionex) = &mut self.header.ionex {
+            if let (Some(first), Some(last)) = (first, last) {
+                ionex.epoch_of_first_map = first;
+                ionex.epoch_of_last_map = last;
+            }
+        }
+    }
+
+    /// Retains only epochs within this [TimeWindow], in place. See [Self::crop].
+    pub fn crop_mut(&mut self, window: &TimeWindow) {
+        self.retain_epochs_mut(|t| window.contains(t));
+    }
+
+    /// Copies and returns a [Rinex] that only retains epochs within this [TimeWindow],
+    /// whatever the record type. The header "TIME OF FIRST OBS" and "TIME OF LAST OBS"
+    /// fields (or first and last IONEX maps) are updated accordingly.
+    /// ```
+    /// use rinex::prelude::{Epoch, Rinex, TimeWindow};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///   2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// > 2020 01 01 00 01  0.0000000  0  1
+    /// G01  20832395.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let t1 = Epoch::from_str("2020-01-01T00:00:30 GPST").unwrap();
+    /// let t2 = Epoch::from_str("2020-01-01T00:05:00 GPST").unwrap();
+    ///
+    /// let cropped = rinex.crop(&TimeWindow::new(t1, t2));
+    /// assert_eq!(cropped.epoch_iter().count(), 2);
+    ///
+    /// let obs = cropped.header.obs.as_ref().unwrap();
+    /// assert_eq!(obs.timeof_first_obs, Some(t1));
+    /// ```
+    pub fn crop(&self, window: &TimeWindow) -> Self {
+        let mut s = self.clone();
+        s.crop_mut(window);
+        s
+    }
+
+    /// Splits this [Rinex] at this [Epoch]: the first [Rinex] contains all epochs
+    /// prior `t`, the second [Rinex] contains `t` and all following epochs.
+    /// Both headers describe their own time frame, like [Self::crop].
+    /// ```
+    /// use rinex::prelude::{Epoch, Rinex};
+    /// use std::io::BufReader;
+    /// use std::str::FromStr;
+    ///
+    /// let content = "     3.04           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+    /// G    1 C1C                                                  SYS / # / OBS TYPES
+    ///   2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+    ///                                                             END OF HEADER
+    /// > 2020 01 01 00 00  0.0000000  0  1
+    /// G01  20832393.682
+    /// > 2020 01 01 00 00 30.0000000  0  1
+    /// G01  20832394.682
+    /// > 2020 01 01 00 01  0.0000000  0  1
+    /// G01  20832395.682
+    /// ";
+    ///
+    /// let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+    ///
+    /// let t = Epoch::from_str("2020-01-01T00:00:30 GPST").unwrap();
+    /// let (a, b) = rinex.split_at(t);
+    ///
+    /// assert_eq!(a.epoch_iter().count(), 1);
+    /// assert_eq!(b.epoch_iter().count(), 2);
+    /// assert_eq!(b.header.obs.as_ref().unwrap().timeof_first_obs, Some(t));
+    /// ```
+    pub fn split_at(&self, t: Epoch) -> (Self, Self) {
+        let (mut a, mut b) = (self.clone(), self.clone());
+        a.retain_epochs_mut(|e| e < t);
+        b.retain_epochs_mut(|e| e >= t);
+        (a, b)
+    }
+
+    /// Splits this [Rinex] into consecutive [Rinex]s that span this [Duration] each,
+    /// for example hourly files from a daily file. Chunks are aligned to
+    /// integral multiples of [Duration] (in the timescale of the first epoch)
+    /// and empty chunks are not returned. Each header describes its own time frame,
+    /// like [Self::crop]. Returns an empty list on null [Duration].
+    pub fn split_every(&self, dt: Duration) -> Vec<Self> {
+        let mut ret = Vec::new();
+
+        if dt.total_nanoseconds() <= 0 {
+            return ret;
+        }
+
+        let (first, last) = match (self.first_epoch(), self.last_epoch()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return ret,
+        };
+
+        let mut start = first.floor(dt);
+
+        while start <= last {
+            let end = start + dt;
+
+            let mut chunk = self.clone();
+            chunk.retain_epochs_mut(|t| t >= start && t < end);
+
+            if chunk.first_epoch().is_some() {
+                ret.push(chunk);
+            }
+
+            start = end;
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeWindow;
+    use crate::prelude::{Duration, Epoch, Rinex};
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    #[test]
+    fn crop_observations() {
+        let content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     1    C1                                                # / TYPES OF OBSERV
+  2020     1     1     0     0    0.0000000     GPS         TIME OF FIRST OBS
+  2020     1     1     0     2    0.0000000     GPS         TIME OF LAST OBS
+                                                            END OF HEADER
+ 20  1  1  0  0  0.0000000  0  1G01
+  20832393.682
+ 20  1  1  0  1  0.0000000  0  1G01
+  20832394.682
+ 20  1  1  0  1 30.0000000  0  1G01
+  20832395.682
+ 20  1  1  0  2  0.0000000  0  1G01
+  20832396.682
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let window = TimeWindow::from_duration(
+            t0 + Duration::from_seconds(30.0),
+            Duration::from_seconds(60.0),
+        );
+
+        assert!(window.contains(t0 + Duration::from_seconds(90.0)));
+        assert!(!window.contains(t0 + Duration::from_seconds(91.0)));
+
+        let cropped = rinex.crop(&window);
+
+        let epochs = cropped.epoch_iter().collect::<Vec<_>>();
+        assert_eq!(
+            epochs,
+            vec![
+                t0 + Duration::from_seconds(60.0),
+                t0 + Duration::from_seconds(90.0),
+            ]
+        );
+
+        let obs = cropped.header.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, Some(epochs[0]));
+        assert_eq!(obs.timeof_last_obs, Some(epochs[1]));
+
+        // no overlap: header is preserved
+        let mut cropped = rinex.clone();
+        cropped.crop_mut(&TimeWindow::new(
+            t0 - Duration::from_seconds(60.0),
+            t0 - Duration::from_seconds(1.0),
+        ));
+
+        assert_eq!(cropped.epoch_iter().count(), 0);
+        assert_eq!(cropped.header.obs, rinex.header.obs);
+    }
+
+    #[test]
+    fn split_observations() {
+        let content =
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE
+     1    C1                                                # / TYPES OF OBSERV
+  2020     1     1     0    30    0.0000000     GPS         TIME OF FIRST OBS
+  2020     1     1     3    30    0.0000000     GPS         TIME OF LAST OBS
+                                                            END OF HEADER
+ 20  1  1  0 30  0.0000000  0  1G01
+  20832393.682
+ 20  1  1  0 59 30.0000000  0  1G01
+  20832394.682
+ 20  1  1  1  0  0.0000000  0  1G01
+  20832395.682
+ 20  1  1  3 30  0.0000000  0  1G01
+  20832396.682
+";
+        let rinex = Rinex::parse(&mut BufReader::new(content.as_bytes())).unwrap();
+
+        let t0 = Epoch::from_str("2020-01-01T00:00:00 GPST").unwrap();
+        let t1 = t0 + Duration::from_hours(1.0);
+
+        let (a, b) = rinex.split_at(t1);
+        assert_eq!(a.epoch_iter().count(), 2);
+        assert_eq!(b.epoch_iter().count(), 2);
+
+        let obs = a.header.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, rinex.first_epoch());
+        assert_eq!(
+            obs.timeof_last_obs,
+            Some(t0 + Duration::from_seconds(3570.0))
+        );
+
+        let obs = b.header.obs.as_ref().unwrap();
+        assert_eq!(obs.timeof_first_obs, Some(t1));
+        assert_eq!(obs.timeof_last_obs, rinex.last_epoch());
+
+        // hourly chunks: 02:00-03:00 is empty
+        let chunks = rinex.split_every(Duration::from_hours(1.0));
+        let sizes = chunks
+            .iter()
+            .map(|chunk| chunk.epoch_iter().count())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![2, 1, 1]);
+        assert_eq!(chunks[1].first_epoch(), Some(t1));
+
+        assert!(rinex.split_every(Duration::ZERO).is_empty());
+    }
+}
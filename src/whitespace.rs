@@ -0,0 +1,106 @@
+//! Whitespace repair, for tolerant parsing
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Tabulation stops, as displayed by most text editors
+const TAB_WIDTH: usize = 8;
+
+/// [WhitespaceReport] lists the lines that were repaired prior parsing,
+/// in tolerant mode (see [crate::Rinex::parse_tolerant]).
+/// RINEX is a fixed column format: files edited by hand or produced by broken
+/// converters may contain tabulations, that would otherwise shift all following fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct WhitespaceReport {
+    /// Line numbers (starting at 1) that contained tabulations,
+    /// expanded to spaces.
+    pub repaired_lines: Vec<usize>,
+}
+
+impl WhitespaceReport {
+    /// Returns true if no line had to be repaired
+    pub fn is_empty(&self) -> bool {
+        self.repaired_lines.is_empty()
+    }
+}
+
+/// Expands tabulations to spaces, up to the next tabulation stop,
+/// which is how the line is displayed, and most likely how it was meant to be read.
+/// Returns None if this line does not contain any tabulation.
+pub(crate) fn expand_tabs(line: &str) -> Option<String> {
+    if !line.contains('\t') {
+        return None;
+    }
+
+    let mut expanded = String::with_capacity(line.len() + TAB_WIDTH);
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = TAB_WIDTH - expanded.chars().count() % TAB_WIDTH;
+            expanded.push_str(&" ".repeat(spaces));
+        } else {
+            expanded.push(c);
+        }
+    }
+
+    Some(expanded)
+}
+
+/// Repairs all lines of this content, returns repaired content and [WhitespaceReport].
+pub(crate) fn repair(content: &str) -> (String, WhitespaceReport) {
+    let mut report = WhitespaceReport::default();
+    let mut repaired = String::with_capacity(content.len());
+
+    for (nth, line) in content.split_inclusive('\n').enumerate() {
+        match expand_tabs(line) {
+            Some(expanded) => {
+                report.repaired_lines.push(nth + 1);
+                repaired.push_str(&expanded);
+            },
+            None => repaired.push_str(line),
+        }
+    }
+
+    (repaired, report)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{expand_tabs, repair};
+    use crate::prelude::Rinex;
+    use std::io::BufReader;
+
+    #[test]
+    fn tab_expansion() {
+        assert_eq!(expand_tabs("no tabs"), None);
+        assert_eq!(expand_tabs("\t1").unwrap(), "        1");
+        assert_eq!(expand_tabs("abc\t1").unwrap(), "abc     1");
+        assert_eq!(expand_tabs("abcdefgh\t1").unwrap(), "abcdefgh        1");
+        assert_eq!(expand_tabs("a\t\tb").unwrap(), "a               b");
+
+        let (repaired, report) = repair("line 1\nline\t2\nline 3\n\tline 4");
+        assert_eq!(repaired, "line 1\nline    2\nline 3\n        line 4");
+        assert_eq!(report.repaired_lines, vec![2, 4]);
+        assert!(!report.is_empty());
+    }
+
+    #[test]
+    fn tolerant_parsing() {
+        // Y coordinate starts at column 15 and is padded with one tabulation
+        let content = format!(
+            "{}\n{}\n{}\n",
+            "     2.11           OBSERVATION DATA    G (GPS)             RINEX VERSION / TYPE",
+            "  4696989.6880\t 723994.2090  4239678.3040                  APPROX POSITION XYZ",
+            "                                                            END OF HEADER",
+        );
+
+        let mut reader = BufReader::new(content.as_bytes());
+        let (rinex, report) = Rinex::parse_tolerant(&mut reader).unwrap();
+
+        assert_eq!(report.repaired_lines, vec![2]);
+        assert_eq!(
+            rinex.header.rx_position,
+            Some((4696989.6880, 723994.2090, 4239678.3040))
+        );
+    }
+}